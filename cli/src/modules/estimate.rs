@@ -12,18 +12,18 @@ impl Estimate {
         let account = ctx.wallet().account()?;
 
         if argv.is_empty() {
-            tprintln!(ctx, "usage: estimate <amount> [<priority fee>]");
+            tprintln!(ctx, "usage: estimate <amount> [<priority fee> | low | normal | priority]");
             return Ok(());
         }
 
         let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.first())?;
-        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(1))?.unwrap_or(0);
+        let (priority_fee_sompi, fee_priority) = try_parse_optional_priority_fee(argv.get(1))?;
         let abortable = Abortable::default();
 
         // just use any address for an estimate (change address)
         let change_address = account.change_address()?;
         let destination = PaymentDestination::PaymentOutputs(PaymentOutputs::from((change_address.clone(), amount_sompi)));
-        let estimate = account.estimate(destination, priority_fee_sompi.into(), None, &abortable).await?;
+        let estimate = account.estimate(destination, priority_fee_sompi.into(), fee_priority, None, &abortable).await?;
 
         tprintln!(ctx, "Estimate - {estimate}");
 
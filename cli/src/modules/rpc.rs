@@ -49,6 +49,10 @@ impl Rpc {
                 let result = rpc.get_sync_status_call(GetSyncStatusRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetFeeEstimate => {
+                let result = rpc.get_fee_estimate_call(GetFeeEstimateRequest {}).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetCurrentNetwork => {
                 let result = rpc.get_current_network_call(GetCurrentNetworkRequest {}).await?;
                 self.println(&ctx, result);
@@ -84,6 +88,10 @@ impl Rpc {
                 let result = rpc.get_connected_peer_info_call(GetConnectedPeerInfoRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetBannedPeers => {
+                let result = rpc.get_banned_peers_call(GetBannedPeersRequest {}).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::AddPeer => {
                 if argv.is_empty() {
                     return Err(Error::custom("Usage: rpc addpeer <ip:port> [true|false for 'is_permanent']"));
@@ -229,6 +237,63 @@ impl Rpc {
                     }
                 }
             }
+            RpcApiOps::GetTransactionAcceptance => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify a transaction id"));
+                }
+                let transaction_ids =
+                    argv.iter().map(|s| RpcHash::from_hex(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc.get_transaction_acceptance_call(GetTransactionAcceptanceRequest { transaction_ids }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionInclusionProof => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify a transaction id"));
+                }
+                let transaction_id = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let result = rpc.get_transaction_inclusion_proof_call(GetTransactionInclusionProofRequest { transaction_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetReorgHistory => {
+                let min_depth = if argv.is_empty() {
+                    0
+                } else {
+                    argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse min_depth to u64"))?
+                };
+                let result = rpc.get_reorg_history_call(GetReorgHistoryRequest { min_depth }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetBlocksAcceptanceData => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify a chain block hash"));
+                }
+                let chain_block_hashes =
+                    argv.iter().map(|s| RpcHash::from_hex(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc.get_blocks_acceptance_data_call(GetBlocksAcceptanceDataRequest { chain_block_hashes }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetMempoolEntryAncestry => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify a transaction id"));
+                }
+                let transaction_id = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let result = rpc.get_mempool_entry_ancestry_call(GetMempoolEntryAncestryRequest { transaction_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionsByAddresses => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify at least one address"));
+                }
+                let addresses = argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc
+                    .get_transactions_by_addresses_call(GetTransactionsByAddressesRequest {
+                        addresses,
+                        start_daa_score: 0,
+                        limit: 100,
+                    })
+                    .await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
@@ -12,13 +12,13 @@ impl Send {
         let account = ctx.wallet().account()?;
 
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
+            tprintln!(ctx, "usage: send <address> <amount> [<priority fee> | low | normal | priority]");
             return Ok(());
         }
 
         let address = Address::try_from(argv.first().unwrap().as_str())?;
         let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
-        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        let (priority_fee_sompi, fee_priority) = try_parse_optional_priority_fee(argv.get(2))?;
         let outputs = PaymentOutputs::from((address.clone(), amount_sompi));
         let abortable = Abortable::default();
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
@@ -28,6 +28,7 @@ impl Send {
             .send(
                 outputs.into(),
                 priority_fee_sompi.into(),
+                fee_priority,
                 None,
                 wallet_secret,
                 payment_secret,
@@ -11,7 +11,7 @@ impl Transfer {
         let account = ctx.wallet().account()?;
 
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: transfer <account> <amount> <priority fee>");
+            tprintln!(ctx, "usage: transfer <account> <amount> [<priority fee> | low | normal | priority]");
             return Ok(());
         }
 
@@ -21,7 +21,7 @@ impl Transfer {
             return Err("Cannot transfer to the same account".into());
         }
         let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
-        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        let (priority_fee_sompi, fee_priority) = try_parse_optional_priority_fee(argv.get(2))?;
         let target_address = target_account.receive_address()?;
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
 
@@ -33,6 +33,7 @@ impl Transfer {
             .send(
                 outputs.into(),
                 priority_fee_sompi.into(),
+                fee_priority,
                 None,
                 wallet_secret,
                 payment_secret,
@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::result::Result;
 use kaspa_consensus_core::constants::SOMPI_PER_RUSTWEAVE;
+use kaspa_wallet_core::tx::FeePriority;
 use std::fmt::Display;
 
 pub fn try_parse_required_nonzero_kaspa_as_sompi_u64<S: ToString + Display>(kaspa_amount: Option<S>) -> Result<u64> {
@@ -58,3 +59,13 @@ pub fn try_parse_optional_kaspa_as_sompi_i64<S: ToString + Display>(kaspa_amount
         Ok(None)
     }
 }
+
+/// Parses an optional priority fee argument that is either a Rustweave amount
+/// or a fee priority level (`low`, `normal` or `priority`).
+pub fn try_parse_optional_priority_fee<S: ToString + Display>(priority_fee: Option<S>) -> Result<(i64, Option<FeePriority>)> {
+    if let Some(Ok(fee_priority)) = priority_fee.as_ref().map(|fee| FeePriority::try_from(fee.to_string().as_str())) {
+        Ok((0, Some(fee_priority)))
+    } else {
+        Ok((try_parse_optional_kaspa_as_sompi_i64(priority_fee)?.unwrap_or(0), None))
+    }
+}
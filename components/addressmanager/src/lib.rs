@@ -1,3 +1,4 @@
+pub mod netgroup;
 mod port_mapping_extender;
 mod stores;
 extern crate self as address_manager;
@@ -18,15 +19,22 @@ use kaspa_core::{debug, info, task::tick::TickService, time::unix_now, warn};
 use kaspa_database::prelude::{CachePolicy, StoreResultExtensions, DB};
 use kaspa_utils::networking::IpAddress;
 use local_ip_address::list_afinet_netifas;
+use netgroup::{AsnMap, NetGroup};
 use parking_lot::Mutex;
-use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
+use stores::{
+    anchor_store::{AnchorAddressesStore, AnchorAddressesStoreReader, DbAnchorAddressesStore},
+    banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, DbBannedAddressesStore},
+};
 use thiserror::Error;
 
-pub use stores::NetAddress;
+pub use stores::{banned_address_store::ConnectionBan, NetAddress};
 
 const MAX_ADDRESSES: usize = 4096;
 const MAX_CONNECTION_FAILED_COUNT: u64 = 3;
 
+/// Duration of bans which do not specify one explicitly
+pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
 const UPNP_DEADLINE_SEC: u64 = 2 * 60;
 const UPNP_EXTEND_PERIOD: u64 = UPNP_DEADLINE_SEC / 2;
 
@@ -53,18 +61,22 @@ pub enum UpnpError {
 
 pub struct AddressManager {
     banned_address_store: DbBannedAddressesStore,
+    anchor_address_store: DbAnchorAddressesStore,
     address_store: address_store_with_cache::Store,
     config: Arc<Config>,
     local_net_addresses: Vec<NetAddress>,
+    asn_map: Option<AsnMap>,
 }
 
 impl AddressManager {
     pub fn new(config: Arc<Config>, db: Arc<DB>, tick_service: Arc<TickService>) -> (Arc<Mutex<Self>>, Option<Extender>) {
         let mut instance = Self {
             banned_address_store: DbBannedAddressesStore::new(db.clone(), CachePolicy::Count(MAX_ADDRESSES)),
+            anchor_address_store: DbAnchorAddressesStore::new(db.clone()),
             address_store: address_store_with_cache::new(db),
             local_net_addresses: Vec::new(),
             config,
+            asn_map: None,
         };
 
         let extender = instance.init_local_addresses(tick_service);
@@ -115,7 +127,7 @@ impl AddressManager {
     fn local_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
         match self.config.externalip {
             // An external IP was passed, we will try to bind that if it's valid
-            Some(local_net_address) if local_net_address.ip.is_publicly_routable() => {
+            Some(local_net_address) if local_net_address.is_onion() || local_net_address.ip.is_publicly_routable() => {
                 info!("External address is publicly routable {}", local_net_address);
                 return Left(iter::once(local_net_address));
             }
@@ -210,7 +222,7 @@ impl AddressManager {
             let port =
                 gateway.add_any_port(igd::PortMappingProtocol::TCP, local_addr, UPNP_DEADLINE_SEC as u32, UPNP_REGISTRATION_NAME)?;
             info!("[UPnP] Added port mapping to random external port: {ip}:{port}");
-            return Ok(Some((NetAddress { ip, port }, ExtendHelper { gateway, local_addr, external_port: port })));
+            return Ok(Some((NetAddress::new(ip, port), ExtendHelper { gateway, local_addr, external_port: port })));
         }
 
         match gateway.add_port(
@@ -223,7 +235,7 @@ impl AddressManager {
             Ok(_) => {
                 info!("[UPnP] Added port mapping to default external port: {ip}:{desired_external_port}");
                 Ok(Some((
-                    NetAddress { ip, port: desired_external_port },
+                    NetAddress::new(ip, desired_external_port),
                     ExtendHelper { gateway, local_addr, external_port: desired_external_port },
                 )))
             }
@@ -235,7 +247,7 @@ impl AddressManager {
                     UPNP_REGISTRATION_NAME,
                 )?;
                 info!("[UPnP] Added port mapping to random external port: {ip}:{port}");
-                Ok(Some((NetAddress { ip, port }, ExtendHelper { gateway, local_addr, external_port: port })))
+                Ok(Some((NetAddress::new(ip, port), ExtendHelper { gateway, local_addr, external_port: port })))
             }
             Err(err) => Err(err.into()),
         }
@@ -257,6 +269,11 @@ impl AddressManager {
             return;
         }
 
+        if address.ip.is_onion() && address.onion.is_none() {
+            debug!("[Address manager] skipping onion virtual address {} lacking its onion address", address.ip);
+            return;
+        }
+
         if self.address_store.has(address) {
             return;
         }
@@ -290,12 +307,43 @@ impl AddressManager {
         self.address_store.iterate_addresses()
     }
 
+    /// Returns the stored version of `address`, which also holds the onion address if `address` is its virtual IP
+    pub fn lookup_address(&self, address: NetAddress) -> NetAddress {
+        self.address_store.lookup(address).unwrap_or(address)
+    }
+
     pub fn iterate_prioritized_random_addresses(&self, exceptions: HashSet<NetAddress>) -> impl ExactSizeIterator<Item = NetAddress> {
-        self.address_store.iterate_prioritized_random_addresses(exceptions)
+        self.address_store.iterate_prioritized_random_addresses(exceptions, self.asn_map.as_ref())
+    }
+
+    /// Sets the ASN map used for grouping addresses by their autonomous system
+    pub fn set_asn_map(&mut self, asn_map: AsnMap) {
+        info!("Loaded ASN map with {} prefixes", asn_map.len());
+        self.asn_map = Some(asn_map);
+    }
+
+    /// Returns the network group of `ip`. The node makes at most one automatic outbound connection per routable group
+    pub fn net_group(&self, ip: IpAddress) -> NetGroup {
+        NetGroup::new(ip, self.asn_map.as_ref())
+    }
+
+    /// Returns the anchor addresses persisted by the last [`Self::set_anchors`] call
+    pub fn get_anchors(&self) -> Vec<NetAddress> {
+        self.anchor_address_store.get().unwrap_option().unwrap_or_default()
+    }
+
+    /// Persists the outbound peers which should be reconnected to first after a node restart
+    pub fn set_anchors(&mut self, anchors: Vec<NetAddress>) {
+        if self.get_anchors() != anchors {
+            self.anchor_address_store.set(anchors).unwrap();
+        }
     }
 
-    pub fn ban(&mut self, ip: IpAddress) {
-        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
+    /// Bans the given IP for `duration`. The ban expiry is persisted so it survives node restarts
+    pub fn ban_for(&mut self, ip: IpAddress, duration: Duration, reason: String) {
+        let now = unix_now();
+        let ban = ConnectionBan::new(now, now.saturating_add(duration.as_millis() as u64), reason);
+        self.banned_address_store.set(ip.into(), ban).unwrap();
         self.address_store.remove_by_ip(ip.into());
     }
 
@@ -304,10 +352,9 @@ impl AddressManager {
     }
 
     pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
         match self.banned_address_store.get(ip.into()).unwrap_option() {
-            Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
+            Some(ban) => {
+                if unix_now() >= ban.expiry {
                     self.unban(ip);
                     false
                 } else {
@@ -325,6 +372,17 @@ impl AddressManager {
     pub fn get_all_banned_addresses(&self) -> Vec<IpAddress> {
         self.banned_address_store.iterator().map(|x| IpAddress::from(x.unwrap().0)).collect_vec()
     }
+
+    /// Returns all bans which have not expired yet, along with their banned IPs
+    pub fn get_all_bans(&self) -> Vec<(IpAddress, ConnectionBan)> {
+        let now = unix_now();
+        self.banned_address_store
+            .iterator()
+            .map(|x| x.unwrap())
+            .filter(|(_, ban)| ban.expiry > now)
+            .map(|(ip, ban)| (IpAddress::from(ip), ban))
+            .collect_vec()
+    }
 }
 
 mod address_store_with_cache {
@@ -338,13 +396,13 @@ mod address_store_with_cache {
 
     use itertools::Itertools;
     use kaspa_database::prelude::{CachePolicy, DB};
-    use kaspa_utils::networking::PrefixBucket;
     use rand::{
         distributions::{WeightedError, WeightedIndex},
         prelude::Distribution,
     };
 
     use crate::{
+        netgroup::{AsnMap, NetGroup},
         stores::{
             address_store::{AddressesStore, DbAddressesStore, Entry},
             AddressKey,
@@ -395,6 +453,10 @@ mod address_store_with_cache {
             *self.addresses.get(&address.into()).unwrap()
         }
 
+        pub fn lookup(&self, address: NetAddress) -> Option<NetAddress> {
+            self.addresses.get(&address.into()).map(|entry| entry.address)
+        }
+
         pub fn remove(&mut self, address: NetAddress) {
             self.remove_by_key(address.into())
         }
@@ -411,7 +473,8 @@ mod address_store_with_cache {
         /// This iterator functions as the node's ip routing selection algo.
         /// It first adjusts in respect to the number of connection failures of each ip address,
         /// whereby each connection failure (up to [`MAX_CONNECTION_FAILED_COUNT`]) reduces an ip's selection weight by a factor of 64,
-        /// Afterwards the weights are normalized uniformly over the ip's [`PrefixBucket`] size.
+        /// Afterwards the weights are normalized uniformly over the ip's [`NetGroup`] size, where the group
+        /// is the ip's autonomous system if covered by `asn_map`, and its network prefix otherwise.
         ///
         /// This ensures a distributed selection across the global network, while respecting
         /// weight reductions due to ip connection failures.
@@ -423,28 +486,29 @@ mod address_store_with_cache {
         ///             whereby:
         ///                 x: max allowed connection failures.
         ///                 y: connection failures of the ip.
-        ///                 n: number of ips with the same prefix bytes.
+        ///                 n: number of ips within the same network group.
         ///```
         pub fn iterate_prioritized_random_addresses(
             &self,
             exceptions: HashSet<NetAddress>,
+            asn_map: Option<&AsnMap>,
         ) -> impl ExactSizeIterator<Item = NetAddress> {
             let exceptions: HashSet<AddressKey> = exceptions.into_iter().map(|addr| addr.into()).collect();
-            let mut prefix_counter: HashMap<PrefixBucket, usize> = HashMap::new();
-            let (mut weights, filtered_addresses): (Vec<f64>, Vec<NetAddress>) = self
+            let mut group_counter: HashMap<NetGroup, usize> = HashMap::new();
+            let (mut weights, groups, filtered_addresses): (Vec<f64>, Vec<NetGroup>, Vec<NetAddress>) = self
                 .addresses
                 .iter()
                 .filter(|(addr_key, _)| !exceptions.contains(addr_key))
                 .map(|(_, e)| {
-                    let count = prefix_counter.entry(e.address.prefix_bucket()).or_insert(0);
-                    *count += 1;
-                    (64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - e.connection_failed_count) as f64), e.address)
+                    let group = NetGroup::new(e.address.ip, asn_map);
+                    *group_counter.entry(group).or_insert(0) += 1;
+                    (64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - e.connection_failed_count) as f64), group, e.address)
                 })
-                .unzip();
+                .multiunzip();
 
-            // Divide weights by size of bucket of the prefix bytes, to partially uniform the distribution over prefix buckets.
-            for (i, address) in filtered_addresses.iter().enumerate() {
-                *weights.get_mut(i).unwrap() /= *prefix_counter.get(&address.prefix_bucket()).unwrap() as f64;
+            // Divide weights by size of the network group, to partially uniform the distribution over network groups.
+            for (weight, group) in weights.iter_mut().zip(groups.iter()) {
+                *weight /= *group_counter.get(group).unwrap() as f64;
             }
 
             RandomWeightedIterator::new(weights, filtered_addresses)
@@ -536,6 +600,49 @@ mod address_store_with_cache {
             assert_eq!(iter.count(), 0);
         }
 
+        #[test]
+        fn test_timed_bans() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            let banned = IpAddress::from_str("1.2.3.4").unwrap();
+            let expired = IpAddress::from_str("5.6.7.8").unwrap();
+            am.ban_for(banned, std::time::Duration::from_secs(60), "invalid block".to_owned());
+            am.ban_for(expired, std::time::Duration::ZERO, "invalid transaction".to_owned());
+
+            let bans = am.get_all_bans();
+            assert_eq!(bans.len(), 1);
+            assert_eq!(bans[0].0, banned);
+            assert_eq!(bans[0].1.reason, "invalid block");
+            assert_eq!(bans[0].1.expiry, bans[0].1.timestamp + 60_000);
+
+            assert!(am.is_banned(banned));
+            assert!(!am.is_banned(expired));
+            // Expired bans are removed once checked
+            assert_eq!(am.get_all_banned_addresses(), vec![banned]);
+        }
+
+        #[test]
+        fn test_anchors() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Arc::new(Config::new(SIMNET_PARAMS));
+            let (am, _) = AddressManager::new(config.clone(), db.1.clone(), Arc::new(TickService::default()));
+            assert!(am.lock().get_anchors().is_empty());
+
+            let anchors = vec![
+                NetAddress::new(IpAddress::from_str("1.2.3.4").unwrap(), 16111),
+                NetAddress::new(IpAddress::from_str("5.6.7.8").unwrap(), 16111),
+            ];
+            am.lock().set_anchors(anchors.clone());
+            drop(am);
+
+            // Anchors survive a restart of the address manager
+            let (am, _) = AddressManager::new(config, db.1, Arc::new(TickService::default()));
+            assert_eq!(am.lock().get_anchors(), anchors);
+        }
+
         #[test]
         fn test_network_distribution_weighting() {
             kaspa_core::log::try_init_logger("info");
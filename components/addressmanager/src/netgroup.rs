@@ -0,0 +1,174 @@
+//!
+//! Network groups are used for diversifying the outbound peers of the node, so that an attacker controlling a few
+//! subnets or a single hosting provider cannot occupy all of its outbound connections.
+//!
+//! By default publicly routable addresses are grouped by their IPv4 /16 or IPv6 /32 prefix. When an ASN map is
+//! supplied, addresses it covers are grouped by their autonomous system instead.
+//!
+
+use ipnet::IpNet;
+use kaspa_utils::networking::{IpAddress, PrefixBucket};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AsnMapError {
+    #[error("failed reading ASN map {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("invalid ASN map entry at line {0}: {1}")]
+    InvalidEntry(usize, String),
+}
+
+/// A mapping of IP prefixes to the autonomous systems announcing them
+#[derive(Debug, Default)]
+pub struct AsnMap {
+    /// Prefixes by prefix length, ordered from the most to the least specific
+    prefixes: Vec<(u8, HashMap<IpNet, u32>)>,
+}
+
+impl AsnMap {
+    /// Loads an ASN map file. Each non-empty line holds a CIDR prefix followed by an AS number, optionally
+    /// prefixed with `AS` (e.g. `1.2.0.0/16 AS13335`). Lines starting with `#` are ignored
+    pub fn load(path: &Path) -> Result<Self, AsnMapError> {
+        fs::read_to_string(path).map_err(|err| AsnMapError::Io(path.to_owned(), err))?.parse()
+    }
+
+    /// Returns the AS number of the most specific prefix containing `ip`
+    pub fn lookup(&self, ip: IpAddr) -> Option<u32> {
+        let ip = normalize(ip);
+        self.prefixes.iter().find_map(|(len, map)| IpNet::new(ip, *len).ok().and_then(|net| map.get(&net.trunc())).copied())
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefixes.iter().map(|(_, map)| map.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl FromStr for AsnMap {
+    type Err = AsnMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut by_len: HashMap<u8, HashMap<IpNet, u32>> = HashMap::new();
+        for (i, line) in s.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || AsnMapError::InvalidEntry(i, line.to_owned());
+            let mut parts = line.split_whitespace();
+            let (Some(net), Some(asn), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(invalid());
+            };
+            let net = IpNet::from_str(net).map_err(|_| invalid())?.trunc();
+            let asn = asn.strip_prefix("AS").unwrap_or(asn).parse::<u32>().map_err(|_| invalid())?;
+            by_len.entry(net.prefix_len()).or_default().insert(net, asn);
+        }
+        let mut prefixes: Vec<_> = by_len.into_iter().collect();
+        prefixes.sort_by_key(|(len, _)| Reverse(*len));
+        Ok(Self { prefixes })
+    }
+}
+
+/// The network group of an IP address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetGroup {
+    /// An autonomous system of the ASN map
+    Asn(u32),
+    Ipv4Prefix([u8; 2]),
+    Ipv6Prefix([u8; 4]),
+    /// Onion addresses are grouped by the top 4 bits of their public key, which cannot be tied to any network
+    /// location but still spread the outbound peers over a few groups
+    Onion(u8),
+    /// Addresses which are not publicly routable (local networks, loopback etc.) are grouped by their
+    /// prefix bucket and are exempt from outbound diversity rules
+    Unroutable(PrefixBucket),
+}
+
+impl NetGroup {
+    pub fn new(ip: IpAddress, asn_map: Option<&AsnMap>) -> Self {
+        if let IpAddr::V6(ipv6) = ip.0 {
+            if ip.is_onion() {
+                return Self::Onion(ipv6.octets()[6] >> 4);
+            }
+        }
+        if !ip.is_publicly_routable() {
+            return Self::Unroutable(ip.prefix_bucket());
+        }
+        if let Some(asn) = asn_map.and_then(|map| map.lookup(ip.0)) {
+            return Self::Asn(asn);
+        }
+        match normalize(ip.0) {
+            IpAddr::V4(ip) => {
+                let octets = ip.octets();
+                Self::Ipv4Prefix([octets[0], octets[1]])
+            }
+            IpAddr::V6(ip) => {
+                let octets = ip.octets();
+                Self::Ipv6Prefix([octets[0], octets[1], octets[2], octets[3]])
+            }
+        }
+    }
+
+    /// Indicates whether at most one outbound connection should be made to this group
+    pub fn is_routable(&self) -> bool {
+        !matches!(self, Self::Unroutable(_))
+    }
+}
+
+fn normalize(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        ip => ip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddress {
+        IpAddress::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_net_groups() {
+        assert_eq!(NetGroup::new(ip("8.8.4.4"), None), NetGroup::new(ip("8.8.8.8"), None));
+        assert_eq!(NetGroup::new(ip("::ffff:8.8.8.8"), None), NetGroup::Ipv4Prefix([8, 8]));
+        assert_ne!(NetGroup::new(ip("8.8.8.8"), None), NetGroup::new(ip("8.9.8.8"), None));
+        assert_eq!(NetGroup::new(ip("2a01:4f8:1::1"), None), NetGroup::new(ip("2a01:4f8:ffff::1"), None));
+        assert!(!NetGroup::new(ip("127.0.0.1"), None).is_routable());
+        assert!(!NetGroup::new(ip("192.168.1.1"), None).is_routable());
+        assert_eq!(NetGroup::new(ip("fd87:d87e:eb43:a1b2::1"), None), NetGroup::Onion(0xa));
+        assert!(NetGroup::new(ip("fd87:d87e:eb43:a1b2::1"), None).is_routable());
+    }
+
+    #[test]
+    fn test_asn_map() {
+        let map: AsnMap = "# comment\n8.8.0.0/16 AS15169\n8.8.8.0/24 13335\n\n2a01:4f8::/32 AS24940\n".parse().unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.lookup("8.8.4.4".parse().unwrap()), Some(15169));
+        // The most specific prefix wins
+        assert_eq!(map.lookup("8.8.8.8".parse().unwrap()), Some(13335));
+        assert_eq!(map.lookup("::ffff:8.8.4.4".parse().unwrap()), Some(15169));
+        assert_eq!(map.lookup("9.9.9.9".parse().unwrap()), None);
+
+        // Groups of the same AS span unrelated prefixes
+        let map: AsnMap = "8.8.0.0/16 AS15169\n34.64.0.0/10 AS15169\n".parse().unwrap();
+        assert_eq!(NetGroup::new(ip("8.8.8.8"), Some(&map)), NetGroup::new(ip("34.80.1.1"), Some(&map)));
+        assert_eq!(NetGroup::new(ip("9.9.9.9"), Some(&map)), NetGroup::Ipv4Prefix([9, 9]));
+
+        assert!(matches!("8.8.0.0/16".parse::<AsnMap>(), Err(AsnMapError::InvalidEntry(1, _))));
+        assert!(matches!("8.8.0.0/16 ASX".parse::<AsnMap>(), Err(AsnMapError::InvalidEntry(1, _))));
+    }
+}
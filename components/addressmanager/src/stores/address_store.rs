@@ -4,7 +4,7 @@ use kaspa_database::{
     prelude::{CachedDbAccess, DirectDbWriter},
     registry::DatabaseStorePrefixes,
 };
-use kaspa_utils::mem_size::MemSizeEstimator;
+use kaspa_utils::{mem_size::MemSizeEstimator, networking::IpAddress};
 use serde::{Deserialize, Serialize};
 use std::net::Ipv6Addr;
 use std::{error::Error, fmt::Display, sync::Arc};
@@ -20,6 +20,28 @@ pub struct Entry {
 
 impl MemSizeEstimator for Entry {}
 
+/// The address layout persisted by versions preceding onion address support
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct LegacyNetAddress {
+    ip: IpAddress,
+    port: u16,
+}
+
+/// The entry layout persisted by versions preceding onion address support
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct LegacyEntry {
+    connection_failed_count: u64,
+    address: LegacyNetAddress,
+}
+
+impl MemSizeEstimator for LegacyEntry {}
+
+impl From<LegacyEntry> for Entry {
+    fn from(entry: LegacyEntry) -> Self {
+        Self { connection_failed_count: entry.connection_failed_count, address: NetAddress::new(entry.address.ip, entry.address.port) }
+    }
+}
+
 pub trait AddressesStoreReader {
     fn get(&self, key: AddressKey) -> Result<Entry, StoreError>;
 }
@@ -79,7 +101,30 @@ pub struct DbAddressesStore {
 
 impl DbAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::Addresses.into()) }
+        let store =
+            Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::Addresses.into()) };
+        store.migrate_legacy_entries().unwrap();
+        store
+    }
+
+    /// Re-encodes the entries persisted by previous versions, which lack the onion address field, in the current layout.
+    /// Note that current entries decode as legacy ones as well (the trailing field is ignored), hence an entry is migrated
+    /// only if it cannot be decoded in the current layout
+    fn migrate_legacy_entries(&self) -> StoreResult<()> {
+        let legacy_access: CachedDbAccess<DbAddressKey, LegacyEntry> =
+            CachedDbAccess::new(self.db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into());
+        let legacy_entries: Vec<(DbAddressKey, LegacyEntry)> = legacy_access
+            .iterator()
+            .filter_map(|res| {
+                let (key_bytes, entry) = res.ok()?;
+                let key = DbAddressKey(<[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]).ok()?);
+                self.access.read(key).is_err().then_some((key, entry))
+            })
+            .collect();
+        for (key, entry) in legacy_entries {
+            self.access.write(DirectDbWriter::new(&self.db), key, entry.into())?;
+        }
+        Ok(())
     }
 
     pub fn iterator(&self) -> impl Iterator<Item = Result<(AddressKey, Entry), Box<dyn Error>>> + '_ {
@@ -117,3 +162,35 @@ impl AddressesStore for DbAddressesStore {
         self.set(key, Entry { connection_failed_count, address: entry.address })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_legacy_entries_migration() {
+        let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let legacy_address = NetAddress::from_str("1.2.3.4:16111").unwrap();
+        let legacy_access: CachedDbAccess<DbAddressKey, LegacyEntry> =
+            CachedDbAccess::new(db.1.clone(), CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into());
+        let legacy_entry =
+            LegacyEntry { connection_failed_count: 3, address: LegacyNetAddress { ip: legacy_address.ip, port: legacy_address.port } };
+        let legacy_key = AddressKey::from(legacy_address);
+        legacy_access.write(DirectDbWriter::new(&db.1), legacy_key.into(), legacy_entry).unwrap();
+
+        let mut store = DbAddressesStore::new(db.1.clone(), CachePolicy::Empty);
+        let onion_address = NetAddress::from_str("pg6mmjiyjmcrsslvykfwnntlaru7p5svn6y2ymmju6nubxndf4pscryd.onion:16111").unwrap();
+        store.set(onion_address.into(), Entry { connection_failed_count: 0, address: onion_address }).unwrap();
+
+        // Legacy entries are migrated while current entries are kept as is, also across restarts
+        for store in [store.clone(), DbAddressesStore::new(db.1.clone(), CachePolicy::Empty)] {
+            let entry = store.get(legacy_key).unwrap();
+            assert_eq!(entry.connection_failed_count, 3);
+            assert_eq!(entry.address, legacy_address);
+            assert_eq!(store.get(onion_address.into()).unwrap().address, onion_address);
+            assert_eq!(store.iterator().filter(Result::is_ok).count(), 2);
+        }
+    }
+}
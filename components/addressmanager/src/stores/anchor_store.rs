@@ -0,0 +1,41 @@
+use kaspa_database::{
+    prelude::DB,
+    prelude::{CachedDbItem, DirectDbWriter, StoreResult},
+    registry::DatabaseStorePrefixes,
+};
+use std::sync::Arc;
+
+use crate::NetAddress;
+
+pub trait AnchorAddressesStoreReader {
+    fn get(&self) -> StoreResult<Vec<NetAddress>>;
+}
+
+pub trait AnchorAddressesStore: AnchorAddressesStoreReader {
+    fn set(&mut self, anchors: Vec<NetAddress>) -> StoreResult<()>;
+}
+
+/// Persists the outbound peers the node reconnects to first after a restart
+#[derive(Clone)]
+pub struct DbAnchorAddressesStore {
+    db: Arc<DB>,
+    access: CachedDbItem<Vec<NetAddress>>,
+}
+
+impl DbAnchorAddressesStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::AnchorAddresses.into()) }
+    }
+}
+
+impl AnchorAddressesStoreReader for DbAnchorAddressesStore {
+    fn get(&self) -> StoreResult<Vec<NetAddress>> {
+        self.access.read()
+    }
+}
+
+impl AnchorAddressesStore for DbAnchorAddressesStore {
+    fn set(&mut self, anchors: Vec<NetAddress>) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), &anchors)
+    }
+}
@@ -8,17 +8,29 @@ use serde::{Deserialize, Serialize};
 use std::net::{IpAddr, Ipv6Addr};
 use std::{error::Error, fmt::Display, sync::Arc};
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
-pub struct ConnectionBanTimestamp(pub u64);
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionBan {
+    /// Unix time in milliseconds at which the ban was applied
+    pub timestamp: u64,
+    /// Unix time in milliseconds at which the ban expires
+    pub expiry: u64,
+    pub reason: String,
+}
 
-impl MemSizeEstimator for ConnectionBanTimestamp {}
+impl ConnectionBan {
+    pub fn new(timestamp: u64, expiry: u64, reason: String) -> Self {
+        Self { timestamp, expiry, reason }
+    }
+}
+
+impl MemSizeEstimator for ConnectionBan {}
 
 pub trait BannedAddressesStoreReader {
-    fn get(&self, address: IpAddr) -> Result<ConnectionBanTimestamp, StoreError>;
+    fn get(&self, address: IpAddr) -> Result<ConnectionBan, StoreError>;
 }
 
 pub trait BannedAddressesStore: BannedAddressesStoreReader {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()>;
+    fn set(&mut self, ip: IpAddr, ban: ConnectionBan) -> StoreResult<()>;
     fn remove(&mut self, ip: IpAddr) -> StoreResult<()>;
 }
 
@@ -69,21 +81,28 @@ impl From<AddressKey> for IpAddr {
 #[derive(Clone)]
 pub struct DbBannedAddressesStore {
     db: Arc<DB>,
-    access: CachedDbAccess<AddressKey, ConnectionBanTimestamp>,
+    access: CachedDbAccess<AddressKey, ConnectionBan>,
 }
 
 impl DbBannedAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedAddresses.into()) }
+        let store =
+            Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedAddresses.into()) };
+        // Bans persisted by previous versions only hold a timestamp and cannot be decoded anymore. Since such bans
+        // were limited to a day anyway, we simply drop them
+        if store.access.iterator().any(|res| res.is_err()) {
+            store.access.delete_all(DirectDbWriter::new(&store.db)).unwrap();
+        }
+        store
     }
 
-    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBanTimestamp), Box<dyn Error>>> + '_ {
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBan), Box<dyn Error>>> + '_ {
         self.access.iterator().map(|iter_result| match iter_result {
-            Ok((key_bytes, connection_ban_timestamp)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
+            Ok((key_bytes, connection_ban)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
                 Ok(address_key_slice) => {
                     let addr_key = AddressKey(address_key_slice);
                     let address: IpAddr = addr_key.into();
-                    Ok((address, connection_ban_timestamp))
+                    Ok((address, connection_ban))
                 }
                 Err(e) => Err(e.into()),
             },
@@ -93,14 +112,14 @@ impl DbBannedAddressesStore {
 }
 
 impl BannedAddressesStoreReader for DbBannedAddressesStore {
-    fn get(&self, ip: IpAddr) -> Result<ConnectionBanTimestamp, StoreError> {
+    fn get(&self, ip: IpAddr) -> Result<ConnectionBan, StoreError> {
         self.access.read(ip.into())
     }
 }
 
 impl BannedAddressesStore for DbBannedAddressesStore {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), ip.into(), timestamp)
+    fn set(&mut self, ip: IpAddr, ban: ConnectionBan) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), ip.into(), ban)
     }
 
     fn remove(&mut self, ip: IpAddr) -> StoreResult<()> {
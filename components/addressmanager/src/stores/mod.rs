@@ -3,6 +3,7 @@ use std::net::{IpAddr, Ipv6Addr};
 pub use kaspa_utils::networking::NetAddress;
 
 pub(super) mod address_store;
+pub(super) mod anchor_store;
 pub(super) mod banned_address_store;
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
@@ -1,5 +1,5 @@
 use std::{
-    cmp::min,
+    cmp::{min, Reverse},
     collections::{HashMap, HashSet},
     net::{IpAddr, SocketAddr, ToSocketAddrs},
     sync::Arc,
@@ -8,8 +8,9 @@ use std::{
 
 use duration_string::DurationString;
 use futures_util::future::join_all;
+use ipnet::IpNet;
 use itertools::Itertools;
-use kaspa_addressmanager::{AddressManager, NetAddress};
+use kaspa_addressmanager::{netgroup::NetGroup, AddressManager, NetAddress};
 use kaspa_core::{debug, info, warn};
 use kaspa_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
 use kaspa_utils::triggers::SingleTrigger;
@@ -24,28 +25,39 @@ use tokio::{
     time::{interval, MissedTickBehavior},
 };
 
+/// The number of long-lived outbound peers which are persisted and reconnected to first after a restart
+const MAX_ANCHORS: usize = 2;
+
 pub struct ConnectionManager {
     p2p_adaptor: Arc<kaspa_p2p_lib::Adaptor>,
     outbound_target: usize,
     inbound_limit: usize,
+    /// Networks of trusted peers, which are exempt from the inbound limit and are never banned
+    whitelist: Vec<IpNet>,
+    /// Duration of bans of misbehaving peers
+    ban_duration: Duration,
     dns_seeders: &'static [&'static str],
     default_port: u16,
     address_manager: Arc<ParkingLotMutex<AddressManager>>,
     connection_requests: TokioMutex<HashMap<SocketAddr, ConnectionRequest>>,
+    /// Anchors persisted by the previous run which were not tried yet
+    pending_anchors: ParkingLotMutex<Vec<NetAddress>>,
     force_next_iteration: UnboundedSender<()>,
     shutdown_signal: SingleTrigger,
 }
 
 #[derive(Clone, Debug)]
 struct ConnectionRequest {
+    /// The requested address, which unlike the socket address keying the request might be an onion address
+    address: NetAddress,
     next_attempt: SystemTime,
     is_permanent: bool,
     attempts: u32,
 }
 
 impl ConnectionRequest {
-    fn new(is_permanent: bool) -> Self {
-        Self { next_attempt: SystemTime::now(), is_permanent, attempts: 0 }
+    fn new(address: NetAddress, is_permanent: bool) -> Self {
+        Self { address, next_attempt: SystemTime::now(), is_permanent, attempts: 0 }
     }
 }
 
@@ -54,17 +66,23 @@ impl ConnectionManager {
         p2p_adaptor: Arc<kaspa_p2p_lib::Adaptor>,
         outbound_target: usize,
         inbound_limit: usize,
+        whitelist: Vec<IpNet>,
+        ban_duration: Duration,
         dns_seeders: &'static [&'static str],
         default_port: u16,
         address_manager: Arc<ParkingLotMutex<AddressManager>>,
     ) -> Arc<Self> {
         let (tx, rx) = unbounded_channel::<()>();
+        let pending_anchors = ParkingLotMutex::new(address_manager.lock().get_anchors());
         let manager = Arc::new(Self {
             p2p_adaptor,
             outbound_target,
             inbound_limit,
+            whitelist,
+            ban_duration,
             address_manager,
             connection_requests: Default::default(),
+            pending_anchors,
             force_next_iteration: tx,
             shutdown_signal: SingleTrigger::new(),
             dns_seeders,
@@ -103,9 +121,9 @@ impl ConnectionManager {
         self.handle_inbound_connections(&peer_by_address).await;
     }
 
-    pub async fn add_connection_request(&self, address: SocketAddr, is_permanent: bool) {
+    pub async fn add_connection_request(&self, address: NetAddress, is_permanent: bool) {
         // If the request already exists, it resets the attempts count and overrides the `is_permanent` setting.
-        self.connection_requests.lock().await.insert(address, ConnectionRequest::new(is_permanent));
+        self.connection_requests.lock().await.insert(address.into(), ConnectionRequest::new(address, is_permanent));
         self.force_next_iteration.send(()).unwrap(); // We force the next iteration of the connection loop.
     }
 
@@ -126,8 +144,8 @@ impl ConnectionManager {
             }
 
             if !is_connected && request.next_attempt <= SystemTime::now() {
-                debug!("Connecting to peer request {}", address);
-                match self.p2p_adaptor.connect_peer(address.to_string()).await {
+                debug!("Connecting to peer request {}", request.address);
+                match self.p2p_adaptor.connect_peer(request.address.to_string()).await {
                     Err(err) => {
                         debug!("Failed connecting to peer request: {}, {}", address, err);
                         if request.is_permanent {
@@ -138,6 +156,7 @@ impl ConnectionManager {
                             new_requests.insert(
                                 address,
                                 ConnectionRequest {
+                                    address: request.address,
                                     next_attempt: SystemTime::now() + retry_duration,
                                     attempts: request.attempts + 1,
                                     is_permanent: true,
@@ -147,7 +166,7 @@ impl ConnectionManager {
                     }
                     Ok(_) if request.is_permanent => {
                         // Permanent requests are kept forever
-                        new_requests.insert(address, ConnectionRequest::new(true));
+                        new_requests.insert(address, ConnectionRequest::new(request.address, true));
                     }
                     Ok(_) => {}
                 }
@@ -159,7 +178,15 @@ impl ConnectionManager {
         *requests = new_requests;
     }
 
+    /// Connects to new outbound peers until the outbound target is reached. Automatic outbound peers, i.e. peers
+    /// which were not explicitly requested, are limited to one per routable network group, so that an attacker
+    /// controlling a few subnets cannot eclipse the node. Anchors of the previous run are tried first.
     async fn handle_outbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
+        let requested: HashSet<SocketAddr> = self.connection_requests.lock().await.keys().copied().collect();
+        let automatic_outbound =
+            peer_by_address.values().filter(|peer| peer.is_outbound() && !requested.contains(&peer.net_address())).collect_vec();
+        self.persist_anchors(&automatic_outbound);
+
         let active_outbound: HashSet<kaspa_addressmanager::NetAddress> =
             peer_by_address.values().filter(|peer| peer.is_outbound()).map(|peer| peer.net_address().into()).collect();
         if active_outbound.len() >= self.outbound_target {
@@ -167,7 +194,25 @@ impl ConnectionManager {
         }
 
         let mut missing_connections = self.outbound_target - active_outbound.len();
-        let mut addr_iter = self.address_manager.lock().iterate_prioritized_random_addresses(active_outbound);
+        let (mut used_groups, anchors, addr_iter) = {
+            let mut amgr_lock = self.address_manager.lock();
+            let used_groups: HashSet<NetGroup> = automatic_outbound
+                .iter()
+                .map(|peer| amgr_lock.net_group(peer.net_address().ip().into()))
+                .filter(NetGroup::is_routable)
+                .collect();
+            let anchors = std::mem::take(&mut *self.pending_anchors.lock())
+                .into_iter()
+                .filter(|anchor| {
+                    !active_outbound.contains(&SocketAddr::from(*anchor).into())
+                        && !amgr_lock.is_banned(anchor.ip)
+                        && self.p2p_adaptor.can_connect(anchor)
+                })
+                .collect_vec();
+            let exceptions = active_outbound.into_iter().chain(anchors.iter().copied()).collect();
+            (used_groups, anchors, amgr_lock.iterate_prioritized_random_addresses(exceptions))
+        };
+        let mut addr_iter = anchors.into_iter().chain(addr_iter);
 
         let mut progressing = true;
         let mut connecting = true;
@@ -178,14 +223,18 @@ impl ConnectionManager {
             let mut addrs_to_connect = Vec::with_capacity(missing_connections);
             let mut jobs = Vec::with_capacity(missing_connections);
             for _ in 0..missing_connections {
-                let Some(net_addr) = addr_iter.next() else {
+                let next = addr_iter.by_ref().filter(|net_addr| self.p2p_adaptor.can_connect(net_addr)).find_map(|net_addr| {
+                    // Skip addresses of network groups we already have an outbound peer in
+                    let group = self.address_manager.lock().net_group(net_addr.ip);
+                    (!group.is_routable() || used_groups.insert(group)).then_some((net_addr, group))
+                });
+                let Some((net_addr, group)) = next else {
                     connecting = false;
                     break;
                 };
-                let socket_addr = SocketAddr::new(net_addr.ip.into(), net_addr.port).to_string();
-                debug!("Connecting to {}", &socket_addr);
-                addrs_to_connect.push(net_addr);
-                jobs.push(self.p2p_adaptor.connect_peer(socket_addr.clone()));
+                debug!("Connecting to {}", net_addr);
+                addrs_to_connect.push((net_addr, group));
+                jobs.push(self.p2p_adaptor.connect_peer(net_addr.to_string()));
             }
 
             if progressing && !jobs.is_empty() {
@@ -203,16 +252,17 @@ impl ConnectionManager {
                     self.outbound_target - missing_connections,
                     self.outbound_target,
                     jobs.len(),
-                    addr_iter.len(),
+                    addr_iter.size_hint().0,
                 );
             }
 
-            for (res, net_addr) in (join_all(jobs).await).into_iter().zip(addrs_to_connect) {
+            for (res, (net_addr, group)) in (join_all(jobs).await).into_iter().zip(addrs_to_connect) {
                 match res {
                     Ok(_) => {
                         self.address_manager.lock().mark_connection_success(net_addr);
                         missing_connections -= 1;
                         progressing = true;
+                        continue;
                     }
                     Err(ConnectionError::ProtocolError(ProtocolError::PeerAlreadyExists(_))) => {
                         // We avoid marking the existing connection as connection failure
@@ -223,6 +273,8 @@ impl ConnectionManager {
                         self.address_manager.lock().mark_connection_failure(net_addr);
                     }
                 }
+                // The group is free for other candidates
+                used_groups.remove(&group);
             }
         }
 
@@ -236,8 +288,27 @@ impl ConnectionManager {
         }
     }
 
+    /// Persists the longest-lived automatic outbound peers as anchors for the next run. Nothing is persisted
+    /// while there are no such peers (e.g. right after startup), so that previous anchors are not discarded
+    fn persist_anchors(&self, automatic_outbound: &[&Peer]) {
+        if automatic_outbound.is_empty() {
+            return;
+        }
+        let mut amgr_lock = self.address_manager.lock();
+        let anchors = automatic_outbound
+            .iter()
+            .sorted_by_key(|peer| Reverse(peer.time_connected()))
+            .take(MAX_ANCHORS)
+            .map(|peer| amgr_lock.lookup_address(peer.net_address().into()))
+            .collect_vec();
+        amgr_lock.set_anchors(anchors);
+    }
+
+    /// Disconnects from random inbound peers until the inbound limit is respected. Whitelisted peers are not
+    /// accounted for and are never disconnected
     async fn handle_inbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
-        let active_inbound = peer_by_address.values().filter(|peer| !peer.is_outbound()).collect_vec();
+        let active_inbound =
+            peer_by_address.values().filter(|peer| !peer.is_outbound() && !self.is_whitelisted(peer.net_address().ip())).collect_vec();
         let active_inbound_len = active_inbound.len();
         if self.inbound_limit >= active_inbound_len {
             return;
@@ -283,7 +354,18 @@ impl ConnectionManager {
     ///
     /// _GO-RUSTWEAVED: BanByIP_
     pub async fn ban(&self, ip: IpAddr) {
-        if self.ip_has_permanent_connection(ip).await {
+        self.ban_for(ip, self.ban_duration, "manual ban".to_owned()).await;
+    }
+
+    /// The duration of bans of misbehaving peers
+    pub fn ban_duration(&self) -> Duration {
+        self.ban_duration
+    }
+
+    /// Bans the given IP for `duration` and disconnects from all the peers with that IP.
+    /// Whitelisted IPs and IPs with a permanent connection request are never banned
+    pub async fn ban_for(&self, ip: IpAddr, duration: Duration, reason: String) {
+        if self.is_whitelisted(ip) || self.ip_has_permanent_connection(ip).await {
             return;
         }
         for peer in self.p2p_adaptor.active_peers() {
@@ -291,12 +373,14 @@ impl ConnectionManager {
                 self.p2p_adaptor.terminate(peer.key()).await;
             }
         }
-        self.address_manager.lock().ban(ip.into());
+        self.address_manager.lock().ban_for(ip.into(), duration, reason);
     }
 
     /// Returns whether the given address is banned.
     pub async fn is_banned(&self, address: &SocketAddr) -> bool {
-        !self.is_permanent(address).await && self.address_manager.lock().is_banned(address.ip().into())
+        !self.is_whitelisted(address.ip())
+            && !self.is_permanent(address).await
+            && self.address_manager.lock().is_banned(address.ip().into())
     }
 
     /// Returns whether the given address is a permanent request.
@@ -308,4 +392,11 @@ impl ConnectionManager {
     pub async fn ip_has_permanent_connection(&self, ip: IpAddr) -> bool {
         self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
     }
+
+    /// Returns whether the given IP belongs to a whitelisted network.
+    pub fn is_whitelisted(&self, ip: IpAddr) -> bool {
+        // Inbound IPv4 peers are seen as IPv4-mapped IPv6 addresses when listening on a dual-stack socket
+        let ip = ip.to_canonical();
+        self.whitelist.iter().any(|net| net.contains(&ip))
+    }
 }
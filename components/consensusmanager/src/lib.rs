@@ -6,6 +6,7 @@ use std::{collections::VecDeque, ops::Deref, sync::Arc, thread::JoinHandle};
 
 mod batch;
 mod session;
+pub mod snapshot;
 
 pub use batch::BlockProcessingBatch;
 pub use session::{
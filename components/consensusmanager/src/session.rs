@@ -11,8 +11,9 @@ use kaspa_consensus_core::{
     errors::consensus::ConsensusResult,
     header::Header,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
+    reorg::ReorgRecord,
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
     BlockHashSet, BlueWorkType, ChainPath, Hash,
 };
 use kaspa_utils::sync::rwlock::*;
@@ -224,6 +225,11 @@ impl ConsensusSessionOwned {
         self.consensus.get_virtual_parents_len()
     }
 
+    pub fn get_reorg_history(&self, min_depth: u64) -> Vec<ReorgRecord> {
+        // The reorg history is held in memory and does not require spawn_blocking
+        self.consensus.get_reorg_history(min_depth)
+    }
+
     pub async fn async_get_stats(&self) -> ConsensusStats {
         self.clone().spawn_blocking(|c| c.get_stats()).await
     }
@@ -267,6 +273,10 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_virtual_chain_from_block(hash)).await
     }
 
+    pub async fn async_get_chain_from_block(&self, hash: Hash, max_blocks: usize) -> ConsensusResult<Vec<Hash>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_from_block(hash, max_blocks)).await
+    }
+
     pub async fn async_get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -380,6 +390,11 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_blocks_acceptance_data(&hashes)).await
     }
 
+    /// Returns, for each of the given blocks, the ids of its transactions in block order
+    pub async fn async_get_blocks_transaction_ids(&self, hashes: Vec<Hash>) -> ConsensusResult<Vec<Vec<TransactionId>>> {
+        self.clone().spawn_blocking(move |c| c.get_blocks_transaction_ids(&hashes)).await
+    }
+
     pub async fn async_is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.clone().spawn_blocking(move |c| c.is_chain_block(hash)).await
     }
@@ -0,0 +1,238 @@
+//!
+//! Local pruning point snapshots, allowing a node to bootstrap its consensus from a file instead of
+//! downloading the pruning point proof, trusted data and UTXO set from a peer during IBD.
+//!
+//! Snapshots are built on the [`kaspa_utils::snapshot_file`] format, with values laid out as follows:
+//! 1) the pruning point proof and the headers of all past pruning points
+//! 2) the trusted data: the DAA and GHOSTDAG windows and the blocks of the pruning point anticone, the pruning point first
+//! 3) the pruning point UTXO set, as a chunked collection
+//!
+
+use crate::{ConsensusManager, StagingConsensus};
+use kaspa_consensus_core::{
+    block::Block,
+    config::Config,
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    header::Header,
+    pruning::{PruningPointProof, PruningPointsList},
+    trusted::{build_trusted_subdag, ExternalGhostdagData, TrustedBlock, TrustedGhostdagData, TrustedHeader},
+    tx::{Transaction, TransactionOutpoint, UtxoEntry},
+};
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use kaspa_utils::snapshot_file::{SnapshotFileError, SnapshotFileReader, SnapshotFileWriter, MAGIC_SIZE};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::BufReader, path::Path, sync::Arc};
+use thiserror::Error;
+
+/// Magic bytes opening every pruning point snapshot file
+pub const SNAPSHOT_MAGIC: [u8; MAGIC_SIZE] = *b"RWPPSNAP";
+
+/// Current version of the snapshot file format
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// Number of utxos queried from consensus and written per snapshot chunk
+const SNAPSHOT_CHUNK_SIZE: usize = 1000;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("{0}")]
+    SnapshotFileError(#[from] SnapshotFileError),
+
+    #[error("the pruning point is the genesis block, there is nothing to snapshot")]
+    GenesisPruningPoint,
+
+    #[error("invalid snapshot: {0}")]
+    InvalidSnapshot(&'static str),
+
+    #[error("{0}")]
+    RuleError(#[from] RuleError),
+
+    #[error("{0}")]
+    PruningImportError(#[from] PruningImportError),
+
+    #[error("{0}")]
+    ConsensusError(#[from] ConsensusError),
+}
+
+pub type SnapshotResult<T> = Result<T, SnapshotError>;
+
+/// The trusted data of a snapshot, equivalent to the data sent by a peer following a pruning point and anticone request
+#[derive(Serialize, Deserialize)]
+struct SnapshotTrustedData {
+    daa_window: Vec<(Arc<Header>, ExternalGhostdagData)>,
+    ghostdag_window: Vec<(Hash, ExternalGhostdagData)>,
+    /// The pruning point block followed by the blocks of its anticone
+    blocks: Vec<(Arc<Header>, Vec<Transaction>)>,
+}
+
+impl SnapshotTrustedData {
+    /// Returns the hash of the first block, which is expected to be the pruning point
+    fn pruning_point(&self) -> Option<Hash> {
+        self.blocks.first().map(|(header, _)| header.hash)
+    }
+
+    fn into_trusted_set(self) -> SnapshotResult<Vec<TrustedBlock>> {
+        build_trusted_subdag(
+            self.daa_window.into_iter().map(|(header, ghostdag)| TrustedHeader::new(header, ghostdag)).collect(),
+            self.ghostdag_window.into_iter().map(|(hash, ghostdag)| TrustedGhostdagData::new(hash, ghostdag)).collect(),
+            self.blocks.into_iter().map(|(header, transactions)| Block::from_arcs(header, Arc::new(transactions))),
+        )
+        .ok_or(SnapshotError::InvalidSnapshot("missing ghostdag data for some trusted blocks"))
+    }
+}
+
+/// Exports the pruning point of the current consensus, along with its proof, trusted data and UTXO set, into a snapshot file
+pub fn export_pruning_snapshot(consensus_manager: &ConsensusManager, config: &Config, path: &Path) -> SnapshotResult<()> {
+    let consensus = consensus_manager.consensus();
+    let session = futures::executor::block_on(consensus.session_blocking());
+
+    let pruning_point = session.pruning_point();
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::GenesisPruningPoint);
+    }
+    info!("Exporting a snapshot of pruning point {} to {}", pruning_point, path.display());
+
+    let trusted_data = session.get_pruning_point_anticone_and_trusted_data()?;
+    let trusted_data = SnapshotTrustedData {
+        daa_window: trusted_data.daa_window_blocks.iter().map(|th| (th.header.clone(), th.ghostdag.clone())).collect(),
+        ghostdag_window: trusted_data.ghostdag_blocks.iter().map(|tgd| (tgd.hash, tgd.ghostdag.clone())).collect(),
+        blocks: trusted_data
+            .anticone
+            .iter()
+            .map(|&hash| session.get_block(hash).map(|block| (block.header, block.transactions.to_vec())))
+            .collect::<Result<_, _>>()?,
+    };
+
+    let mut writer = SnapshotFileWriter::create(path, &SNAPSHOT_MAGIC, SNAPSHOT_VERSION)?;
+    writer.write(&session.get_pruning_point_proof())?;
+    writer.write(&session.pruning_point_headers())?;
+    writer.write(&trusted_data)?;
+
+    let mut count = 0u64;
+    let mut from_outpoint = None;
+    loop {
+        let chunk = session.get_pruning_point_utxos(pruning_point, from_outpoint, SNAPSHOT_CHUNK_SIZE, from_outpoint.is_some())?;
+        writer.write_chunk(&chunk)?;
+        count += chunk.len() as u64;
+        if chunk.len() < SNAPSHOT_CHUNK_SIZE {
+            break;
+        }
+        from_outpoint = chunk.last().map(|(outpoint, _)| *outpoint);
+    }
+    writer.end_chunks()?;
+    writer.finish()?;
+
+    info!("Exported the pruning point snapshot {} with {} utxos", path.display(), count);
+    Ok(())
+}
+
+/// Seeds a new staging consensus from a snapshot file and commits it, replacing current consensus. Returns whether the
+/// snapshot was imported.
+///
+/// The snapshot is validated the same way the data received during an IBD with headers proof is: the proof is validated
+/// in the context of current consensus, the trusted blocks are checked by consensus and the multiset of the imported UTXO
+/// set must match the UTXO commitment of the pruning point header. The import is skipped if the node already synced
+/// beyond the snapshot pruning point.
+///
+/// Note: this must not be called while an IBD is running
+pub fn import_pruning_snapshot(consensus_manager: &Arc<ConsensusManager>, config: &Config, path: &Path) -> SnapshotResult<bool> {
+    info!("Importing the pruning point snapshot {}...", path.display());
+
+    let mut reader = SnapshotFileReader::open(path, &SNAPSHOT_MAGIC, SNAPSHOT_VERSION)?;
+    let proof: PruningPointProof = reader.read()?;
+    let pruning_points: PruningPointsList = reader.read()?;
+    let trusted_data: SnapshotTrustedData = reader.read()?;
+
+    {
+        let consensus = consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        // The proof is validated in the context of current consensus
+        session.validate_pruning_proof(&proof)?;
+        let pruning_point_header = proof[0].last().expect("was just ensured by validation").clone();
+
+        if pruning_point_header.hash == config.genesis.hash {
+            return Err(SnapshotError::InvalidSnapshot("the proof pruning point is the genesis block"));
+        }
+
+        let headers_selected_tip = session.get_header(session.get_headers_selected_tip())?;
+        if pruning_point_header.blue_work <= headers_selected_tip.blue_work {
+            info!(
+                "Skipping the import of the pruning point snapshot since the node is already synced beyond its pruning point {}",
+                pruning_point_header.hash
+            );
+            return Ok(false);
+        }
+
+        if pruning_points.is_empty() || pruning_points.last().unwrap().hash != pruning_point_header.hash {
+            return Err(SnapshotError::InvalidSnapshot("the proof pruning point is not equal to the last pruning point in the list"));
+        }
+
+        if pruning_points.first().unwrap().hash != config.genesis.hash {
+            return Err(SnapshotError::InvalidSnapshot("the first pruning point in the list is expected to be genesis"));
+        }
+
+        if session.are_pruning_points_violating_finality(pruning_points.clone()) {
+            return Err(SnapshotError::InvalidSnapshot("pruning points are violating finality"));
+        }
+
+        if trusted_data.pruning_point() != Some(pruning_point_header.hash) {
+            return Err(SnapshotError::InvalidSnapshot("the proof pruning point is not equal to the first trusted block"));
+        }
+    }
+
+    let pruning_point = pruning_points.last().unwrap().hash;
+    let trusted_set = trusted_data.into_trusted_set()?;
+    let staging = consensus_manager.new_staging_consensus();
+    match import_into_staging(&staging, reader, proof, pruning_points, trusted_set, config.is_light) {
+        Ok(()) => {
+            staging.commit();
+            info!(
+                "Imported the pruning point snapshot {}. Committed staging consensus at pruning point {}",
+                path.display(),
+                pruning_point
+            );
+            Ok(true)
+        }
+        Err(err) => {
+            staging.cancel();
+            Err(err)
+        }
+    }
+}
+
+fn import_into_staging(
+    staging: &StagingConsensus,
+    mut reader: SnapshotFileReader<BufReader<File>>,
+    proof: PruningPointProof,
+    pruning_points: PruningPointsList,
+    trusted_set: Vec<TrustedBlock>,
+    is_light: bool,
+) -> SnapshotResult<()> {
+    let session = staging.unguarded_session_blocking();
+    let pruning_point = pruning_points.last().expect("was checked by the caller").hash;
+
+    session.apply_pruning_proof(proof, &trusted_set)?;
+    session.import_pruning_points(pruning_points);
+
+    info!("Starting to process {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        futures::executor::block_on(session.validate_and_insert_trusted_block(tb).virtual_state_task)?;
+    }
+    info!("Done processing trusted blocks");
+
+    // Light nodes keep no UTXO set, the chunks are still read through in order to verify the snapshot checksum
+    let mut multiset = MuHash::new();
+    while let Some(chunk) = reader.next_chunk::<(TransactionOutpoint, UtxoEntry)>()? {
+        if !is_light {
+            session.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+        }
+    }
+    reader.finish()?;
+    if !is_light {
+        session.import_pruning_point_utxo_set(pruning_point, multiset)?;
+    }
+    Ok(())
+}
@@ -17,8 +17,10 @@ use crate::{
     },
     header::Header,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
+    reorg::ReorgRecord,
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath,
 };
 use kaspa_hashes::Hash;
@@ -151,6 +153,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the selected chain blocks walking up from `hash` (included) toward the selected chain tip,
+    /// up to `max_blocks` of them. Fails with `ConsensusError::NotChainBlock` if `hash` is not on the selected chain.
+    fn get_chain_from_block(&self, hash: Hash, max_blocks: usize) -> ConsensusResult<Vec<Hash>> {
+        unimplemented!()
+    }
+
     fn get_chain_block_samples(&self) -> Vec<DaaScoreTimestamp> {
         unimplemented!()
     }
@@ -163,6 +171,11 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the MuHash multiset of the virtual UTXO set
+    fn get_virtual_utxo_multiset(&self) -> MuHash {
+        unimplemented!()
+    }
+
     fn get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -287,10 +300,26 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns, for each of the given blocks, the ids of its transactions in block order
+    fn get_blocks_transaction_ids(&self, hashes: &[Hash]) -> ConsensusResult<Vec<Vec<TransactionId>>> {
+        unimplemented!()
+    }
+
+    /// Returns the UTXO diff of a chain block relative to its selected parent, i.e., the UTXOs
+    /// created and spent by the transactions it accepted from its mergeset.
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        unimplemented!()
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         unimplemented!()
     }
 
+    /// Returns the most recent reorgs of the selected chain with a depth of at least `min_depth`, most recent first
+    fn get_reorg_history(&self, min_depth: u64) -> Vec<ReorgRecord> {
+        unimplemented!()
+    }
+
     fn get_pruning_point_utxos(
         &self,
         expected_pruning_point: Hash,
@@ -35,6 +35,17 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
+    /// If set, full block data (bodies, acceptance data and UTXO diffs) is retained for blocks up to this DAA score
+    /// depth below the pruning point, and only older data is pruned (partial archival)
+    pub retention_depth: Option<u64>,
+
+    /// Indicates whether this node is a light node, which syncs headers only and keeps neither block bodies nor a UTXO set
+    pub is_light: bool,
+
+    /// If set, reorgs of the selected chain removing at least this number of chain blocks are logged as warnings
+    /// and emitted as `ReorgAlert` notifications
+    pub reorg_alert_depth: Option<u64>,
+
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
@@ -42,6 +53,12 @@ pub struct Config {
     /// Enable the UTXO index
     pub utxoindex: bool,
 
+    /// Enable the transaction index
+    pub txindex: bool,
+
+    /// Enable the address transaction history index
+    pub addressindex: bool,
+
     /// Enable RPC commands which affect the state of the node
     pub unsafe_rpc: bool,
 
@@ -66,6 +83,9 @@ pub struct Config {
 
     pub disable_upnp: bool,
 
+    /// Relay new transactions through a Dandelion++ stem phase before broadcasting them
+    pub enable_dandelion: bool,
+
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
 }
@@ -81,8 +101,13 @@ impl Config {
             perf,
             process_genesis: true,
             is_archival: false,
+            retention_depth: None,
+            is_light: false,
+            reorg_alert_depth: None,
             enable_sanity_checks: false,
             utxoindex: false,
+            txindex: false,
+            addressindex: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
             enable_mainnet_mining: false,
@@ -94,6 +119,7 @@ impl Config {
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
             disable_upnp: false,
+            enable_dandelion: false,
             ram_scale: 1.0,
         }
     }
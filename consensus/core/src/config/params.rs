@@ -87,6 +87,12 @@ pub struct Params {
     /// DAA score from which storage mass calculation and transaction mass field are activated as a consensus rule
     pub storage_mass_activation_daa_score: u64,
 
+    /// DAA score from which the transaction introspection opcodes are activated as a consensus rule
+    pub introspection_activation_daa_score: u64,
+
+    /// DAA score from which the string and bitwise opcodes are activated as a consensus rule
+    pub string_opcodes_activation_daa_score: u64,
+
     /// DAA score after which the pre-deflationary period switches to the deflationary period
     pub deflationary_phase_daa_score: u64,
 
@@ -339,6 +345,8 @@ pub const MAINNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: u64::MAX,
+    introspection_activation_daa_score: u64::MAX,
+    string_opcodes_activation_daa_score: u64::MAX,
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -400,6 +408,8 @@ pub const TESTNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: u64::MAX,
+    introspection_activation_daa_score: u64::MAX,
+    string_opcodes_activation_daa_score: u64::MAX,
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -468,6 +478,8 @@ pub const TESTNET11_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: 0,
+    introspection_activation_daa_score: u64::MAX,
+    string_opcodes_activation_daa_score: u64::MAX,
 
     skip_proof_of_work: false,
     max_block_level: 250,
@@ -522,6 +534,8 @@ pub const SIMNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: 0,
+    introspection_activation_daa_score: 0,
+    string_opcodes_activation_daa_score: 0,
 
     skip_proof_of_work: true, // For simnet only, PoW can be simulated by default
     max_block_level: 250,
@@ -570,6 +584,8 @@ pub const DEVNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: u64::MAX,
+    introspection_activation_daa_score: u64::MAX,
+    string_opcodes_activation_daa_score: 0,
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -17,6 +17,36 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --import-utxoindex-snapshot and --export-utxoindex-snapshot require --utxoindex")]
+    UtxoIndexSnapshotWithoutUtxoIndex,
+
+    #[error("Configuration: --light cannot be used together with --archival, --utxoindex, --txindex or --addressindex")]
+    LightWithFullNodeData,
+
+    #[error("Configuration: --retention-period-days cannot be used together with --archival or --light")]
+    RetentionPeriodWithArchivalOrLight,
+
+    #[error("Configuration: --retention-period-days must be positive")]
+    RetentionPeriodNotPositive,
+
+    #[error("Configuration: --export-pruning-snapshot cannot be used together with --light")]
+    PruningSnapshotExportWithLight,
+
+    #[error("Configuration: --repair-db requires --verify-db")]
+    RepairDbWithoutVerifyDb,
+
+    #[error("Configuration: --reorg-alert-depth must be positive")]
+    ReorgAlertDepthNotPositive,
+
+    #[error("Configuration: --ban-duration-sec must be positive")]
+    BanDurationNotPositive,
+
+    #[error("Configuration: --p2p-allowlist contains an invalid public key {0}")]
+    InvalidP2pAllowlistKey(String),
+
+    #[error("Configuration: --whitelist contains an invalid CIDR network {0}")]
+    InvalidWhitelistNetwork(String),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -17,6 +17,9 @@ pub enum ConsensusError {
     #[error("some data is missing for block {0}")]
     MissingData(Hash),
 
+    #[error("block {0} is not on the selected chain")]
+    NotChainBlock(Hash),
+
     #[error("got unexpected pruning point")]
     UnexpectedPruningPoint,
 
@@ -24,6 +24,7 @@ pub mod merkle;
 pub mod muhash;
 pub mod network;
 pub mod pruning;
+pub mod reorg;
 pub mod sign;
 pub mod subnets;
 pub mod trusted;
@@ -2,6 +2,8 @@ use crate::{hashing, tx::Transaction};
 use kaspa_hashes::Hash;
 use kaspa_merkle::calc_merkle_root;
 
+pub use kaspa_merkle::{create_merkle_witness, verify_merkle_witness, MerkleWitness};
+
 pub fn calc_hash_merkle_root_with_options<'a>(txs: impl ExactSizeIterator<Item = &'a Transaction>, include_mass_field: bool) -> Hash {
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, include_mass_field)))
 }
@@ -10,9 +12,29 @@ pub fn calc_hash_merkle_root<'a>(txs: impl ExactSizeIterator<Item = &'a Transact
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, false)))
 }
 
+/// Builds the inclusion proof of the transaction at position `index` within the hash merkle tree of `txs`
+pub fn create_hash_merkle_witness<'a>(
+    txs: impl ExactSizeIterator<Item = &'a Transaction>,
+    index: usize,
+    include_mass_field: bool,
+) -> Option<MerkleWitness> {
+    create_merkle_witness(txs.map(|tx| hashing::tx::hash(tx, include_mass_field)), index)
+}
+
+/// Verifies that `witness` proves the inclusion of `tx` within a block whose header commits to `hash_merkle_root`.
+/// `include_mass_field` must match the hashing rules applied to the block, see [`calc_hash_merkle_root_with_options`].
+pub fn verify_hash_merkle_witness(
+    tx: &Transaction,
+    witness: &MerkleWitness,
+    hash_merkle_root: Hash,
+    include_mass_field: bool,
+) -> bool {
+    verify_merkle_witness(hashing::tx::hash(tx, include_mass_field), witness, hash_merkle_root)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::merkle::calc_hash_merkle_root;
+    use crate::merkle::{calc_hash_merkle_root, create_hash_merkle_witness, verify_hash_merkle_witness};
     use crate::{
         subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
         tx::{scriptvec, ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
@@ -248,5 +270,12 @@ mod tests {
                 0x38, 0x97, 0x4d, 0xa5, 0x0f, 0xd6, 0xef, 0xb4, 0xd2, 0x66, 0xbc, 0x8d, 0x21,
             ])
         );
+
+        let root = calc_hash_merkle_root(txs.iter());
+        for (index, tx) in txs.iter().enumerate() {
+            let witness = create_hash_merkle_witness(txs.iter(), index, false).unwrap();
+            assert!(verify_hash_merkle_witness(tx, &witness, root, false));
+            assert!(!verify_hash_merkle_witness(&txs[(index + 1) % txs.len()], &witness, root, false));
+        }
     }
 }
@@ -0,0 +1,16 @@
+use kaspa_hashes::Hash;
+
+/// A change of the virtual selected chain which removed chain blocks (a reorg)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgRecord {
+    /// Unix time in milliseconds at which the reorg was processed
+    pub timestamp: u64,
+    /// The number of chain blocks removed from the selected chain
+    pub depth: u64,
+    /// The removed chain blocks, ordered from the previous sink downwards
+    pub removed_chain_block_hashes: Vec<Hash>,
+    /// The number of chain blocks added to the selected chain
+    pub added_chain_block_count: u64,
+    /// The number of transactions which were accepted by the removed chain blocks and are not accepted by the added ones
+    pub lost_accepted_transaction_count: u64,
+}
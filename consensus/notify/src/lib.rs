@@ -2,6 +2,7 @@ pub mod collector;
 pub mod connection;
 pub mod notification;
 pub mod notifier;
+pub mod reorg;
 pub mod root;
 
 cfg_if::cfg_if! {
@@ -1,5 +1,5 @@
 use derive_more::Display;
-use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, utxo::utxo_diff::UtxoDiff};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, reorg::ReorgRecord, utxo::utxo_diff::UtxoDiff};
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     events::EventType,
@@ -42,6 +42,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "ReorgAlert notification: {} removed chain blocks", "_0.reorg.depth")]
+    ReorgAlert(ReorgAlertNotification),
 }
 }
 
@@ -181,3 +184,15 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+/// Sent whenever a reorg of the virtual selected chain reaches the configured alert depth
+#[derive(Debug, Clone)]
+pub struct ReorgAlertNotification {
+    pub reorg: Arc<ReorgRecord>,
+}
+
+impl ReorgAlertNotification {
+    pub fn new(reorg: Arc<ReorgRecord>) -> Self {
+        Self { reorg }
+    }
+}
@@ -0,0 +1,107 @@
+use kaspa_consensus_core::reorg::ReorgRecord;
+use std::{collections::VecDeque, sync::Mutex};
+
+/// The maximum number of most recent reorgs kept by [`ReorgTracker`]
+pub const REORG_HISTORY_CAPACITY: usize = 1_000;
+
+/// Shallow reorgs are routine at high block rates, hence reorgs removing less chain blocks than this are not
+/// tracked, unless the alert depth is lower
+pub const MIN_TRACKED_REORG_DEPTH: u64 = 5;
+
+/// Keeps a bounded history of the reorgs of the virtual selected chain and decides which of them
+/// are deep enough to raise an alert
+#[derive(Debug)]
+pub struct ReorgTracker {
+    alert_depth: Option<u64>,
+    min_depth: u64,
+    history: Mutex<VecDeque<ReorgRecord>>,
+}
+
+impl ReorgTracker {
+    pub fn new(alert_depth: Option<u64>) -> Self {
+        let min_depth = alert_depth.map_or(MIN_TRACKED_REORG_DEPTH, |alert_depth| alert_depth.min(MIN_TRACKED_REORG_DEPTH));
+        Self { alert_depth, min_depth, history: Mutex::new(VecDeque::with_capacity(REORG_HISTORY_CAPACITY)) }
+    }
+
+    /// The minimal reorg depth raising an alert, if alerts are enabled
+    pub fn alert_depth(&self) -> Option<u64> {
+        self.alert_depth
+    }
+
+    /// The minimal depth of tracked reorgs. Shallower reorgs should not even be built into a record
+    pub fn min_depth(&self) -> u64 {
+        self.min_depth
+    }
+
+    /// Records `reorg`, evicting the oldest record if the history is full, and returns whether the reorg
+    /// reaches the alert depth. Reorgs shallower than [`Self::min_depth`] are ignored
+    pub fn record(&self, reorg: ReorgRecord) -> bool {
+        if reorg.depth < self.min_depth {
+            return false;
+        }
+        let alert = self.alert_depth.is_some_and(|alert_depth| reorg.depth >= alert_depth);
+        let mut history = self.history.lock().unwrap();
+        if history.len() == REORG_HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(reorg);
+        alert
+    }
+
+    /// Returns the recorded reorgs of at least `min_depth`, most recent first
+    pub fn history(&self, min_depth: u64) -> Vec<ReorgRecord> {
+        self.history.lock().unwrap().iter().rev().filter(|reorg| reorg.depth >= min_depth).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_hashes::Hash;
+
+    fn reorg(depth: u64) -> ReorgRecord {
+        ReorgRecord {
+            timestamp: depth,
+            depth,
+            removed_chain_block_hashes: (0..depth).map(Hash::from_u64_word).collect(),
+            added_chain_block_count: depth + 1,
+            lost_accepted_transaction_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_reorg_tracker() {
+        let tracker = ReorgTracker::new(Some(MIN_TRACKED_REORG_DEPTH + 2));
+        assert_eq!(tracker.min_depth(), MIN_TRACKED_REORG_DEPTH);
+        let min_depth = MIN_TRACKED_REORG_DEPTH;
+        assert!(!tracker.record(reorg(min_depth - 1)));
+        assert!(tracker.record(reorg(min_depth + 2)));
+        assert!(!tracker.record(reorg(min_depth + 1)));
+        assert!(tracker.record(reorg(min_depth + 4)));
+        assert!(!tracker.record(reorg(min_depth)));
+
+        // Shallow reorgs are not tracked
+        let depths = |reorgs: Vec<ReorgRecord>| reorgs.iter().map(|reorg| reorg.depth).collect::<Vec<_>>();
+        assert_eq!(depths(tracker.history(0)), vec![min_depth, min_depth + 4, min_depth + 1, min_depth + 2]);
+        assert_eq!(depths(tracker.history(min_depth + 2)), vec![min_depth + 4, min_depth + 2]);
+
+        // The oldest records are evicted once the history is full, while shallow reorgs never evict any
+        for _ in 0..REORG_HISTORY_CAPACITY {
+            tracker.record(reorg(1));
+        }
+        assert_eq!(tracker.history(0).len(), 4);
+        for _ in 0..REORG_HISTORY_CAPACITY {
+            tracker.record(reorg(min_depth));
+        }
+        assert_eq!(tracker.history(0).len(), REORG_HISTORY_CAPACITY);
+        assert!(tracker.history(min_depth + 1).is_empty());
+
+        // A lower alert depth lowers the tracking depth as well
+        let tracker = ReorgTracker::new(Some(2));
+        assert_eq!(tracker.min_depth(), 2);
+        assert!(tracker.record(reorg(2)));
+
+        // Alerts are disabled without an alert depth
+        assert!(!ReorgTracker::new(None).record(reorg(100)));
+    }
+}
@@ -14,10 +14,10 @@ use kaspa_database::{
     registry::DatabaseStorePrefixes,
 };
 
+use kaspa_database::prelude::WriteBatch;
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use parking_lot::RwLock;
-use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, error::Error, fs, path::PathBuf, sync::Arc};
 
@@ -4,6 +4,7 @@ pub mod factory;
 pub mod services;
 pub mod storage;
 pub mod test_consensus;
+pub mod verifier;
 
 #[cfg(feature = "devnet-prealloc")]
 mod utxo_set_override;
@@ -24,6 +25,7 @@ use crate::{
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
+            utxo_diffs::UtxoDiffsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
             DB,
         },
@@ -32,7 +34,7 @@ use crate::{
         body_processor::BlockBodyProcessor,
         deps_manager::{BlockProcessingMessage, BlockResultSender, BlockTask, VirtualStateProcessingMessage},
         header_processor::HeaderProcessor,
-        pruning_processor::processor::{PruningProcessingMessage, PruningProcessor},
+        pruning_processor::processor::{LightPruningUpdates, PruningProcessingMessage, PruningProcessor},
         virtual_processor::{errors::PruningImportResult, VirtualStateProcessor},
         ProcessingCounters,
     },
@@ -56,11 +58,13 @@ use kaspa_consensus_core::{
     muhash::MuHashExtensions,
     network::NetworkType,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
+    reorg::ReorgRecord,
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath,
 };
-use kaspa_consensus_notify::root::ConsensusNotificationRoot;
+use kaspa_consensus_notify::{reorg::ReorgTracker, root::ConsensusNotificationRoot};
 
 use crossbeam_channel::{
     bounded as bounded_crossbeam, unbounded as unbounded_crossbeam, Receiver as CrossbeamReceiver, Sender as CrossbeamSender,
@@ -115,6 +119,7 @@ pub struct Consensus {
 
     // Notification management
     notification_root: Arc<ConsensusNotificationRoot>,
+    reorg_tracker: Arc<ReorgTracker>,
 
     // Counters
     counters: Arc<ProcessingCounters>,
@@ -213,9 +218,11 @@ impl Consensus {
         // Pipeline processors
         //
 
+        let light_pruning_updates = config.is_light.then(|| Arc::new(LightPruningUpdates::new(pruning_sender.clone())));
         let header_processor = Arc::new(HeaderProcessor::new(
             receiver,
             body_sender,
+            light_pruning_updates.clone(),
             block_processors_pool.clone(),
             params,
             db.clone(),
@@ -248,6 +255,7 @@ impl Consensus {
             params.storage_mass_activation_daa_score,
         ));
 
+        let reorg_tracker = Arc::new(ReorgTracker::new(config.reorg_alert_depth));
         let virtual_processor = Arc::new(VirtualStateProcessor::new(
             virtual_receiver,
             pruning_sender,
@@ -259,11 +267,13 @@ impl Consensus {
             &services,
             pruning_lock.clone(),
             notification_root.clone(),
+            reorg_tracker.clone(),
             counters.clone(),
         ));
 
         let pruning_processor = Arc::new(PruningProcessor::new(
             pruning_receiver,
+            light_pruning_updates,
             db.clone(),
             &storage,
             &services,
@@ -295,6 +305,7 @@ impl Consensus {
             services,
             pruning_lock,
             notification_root,
+            reorg_tracker,
             counters,
             config,
             creation_timestamp,
@@ -378,6 +389,16 @@ impl Consensus {
         }
     }
 
+    /// Returns the tip of the selected chain exposed to clients. This is the sink, except for light nodes
+    /// which never resolve virtual and hence follow the headers selected tip
+    fn chain_tip(&self) -> Hash {
+        if self.config.is_light {
+            self.headers_selected_tip_store.read().get().unwrap().hash
+        } else {
+            self.get_sink()
+        }
+    }
+
     fn estimate_network_hashes_per_second_impl(&self, ghostdag_data: &GhostdagData, window_size: usize) -> ConsensusResult<u64> {
         let window = match self.services.window_manager.block_window(ghostdag_data, WindowType::VaryingWindow(window_size)) {
             Ok(w) => w,
@@ -500,8 +521,8 @@ impl ConsensusApi for Consensus {
     }
 
     fn get_source(&self) -> Hash {
-        if self.config.is_archival {
-            // we use the history root in archival cases.
+        if self.config.is_archival || self.config.retention_depth.is_some() {
+            // we use the history root in archival and partial archival cases.
             return self.pruning_point_store.read().history_root().unwrap();
         }
         self.pruning_point_store.read().pruning_point().unwrap()
@@ -512,8 +533,15 @@ impl ConsensusApi for Consensus {
     /// This is an estimation based on the daa score difference between the node's `source` and `sink`'s daa score,
     /// as such, it does not include non-daa blocks, and does not include headers stored as part of the pruning proof.  
     fn estimate_block_count(&self) -> BlockCount {
-        // PRUNE SAFETY: node is either archival or source is the pruning point which its header is kept permanently
-        let source_score = self.headers_store.get_compact_header_data(self.get_source()).unwrap().daa_score;
+        // PRUNE SAFETY: the source is either the pruning point, whose header is kept permanently, or the history root. In
+        // partial archival, the header of a former history root is pruned on the pruning round following its replacement,
+        // and since no lock is held here, we fall back to the pruning point header if the source was pruned meanwhile
+        let source_score = self
+            .headers_store
+            .get_compact_header_data(self.get_source())
+            .unwrap_option()
+            .unwrap_or_else(|| self.headers_store.get_compact_header_data(self.pruning_point()).unwrap())
+            .daa_score;
         let virtual_score = self.get_virtual_daa_score();
         let header_count = self
             .headers_store
@@ -529,8 +557,7 @@ impl ConsensusApi for Consensus {
 
     fn is_nearly_synced(&self) -> bool {
         // See comment within `config.is_nearly_synced`
-        let sink = self.get_sink();
-        let compact = self.headers_store.get_compact_header_data(sink).unwrap();
+        let compact = self.headers_store.get_compact_header_data(self.chain_tip()).unwrap();
         self.config.is_nearly_synced(compact.timestamp, compact.daa_score)
     }
 
@@ -541,7 +568,18 @@ impl ConsensusApi for Consensus {
         // won't later need to remove it from the result.
         let _guard = self.pruning_lock.blocking_read();
         self.validate_block_exists(hash)?;
-        Ok(self.services.dag_traversal_manager.calculate_chain_path(hash, self.get_sink()))
+        Ok(self.services.dag_traversal_manager.calculate_chain_path(hash, self.chain_tip()))
+    }
+
+    fn get_chain_from_block(&self, hash: Hash, max_blocks: usize) -> ConsensusResult<Vec<Hash>> {
+        let _guard = self.pruning_lock.blocking_read();
+        self.validate_block_exists(hash)?;
+        let chain_tip = self.chain_tip();
+        if !self.services.reachability_service.is_chain_ancestor_of(hash, chain_tip) {
+            return Err(ConsensusError::NotChainBlock(hash));
+        }
+        // Walk forward from `hash` so that the cost is bounded by `max_blocks` rather than by the distance to the tip
+        Ok(self.services.reachability_service.forward_chain_iterator(hash, chain_tip, true).take(max_blocks).collect())
     }
 
     /// Returns a Vec of header samples since genesis
@@ -619,6 +657,10 @@ impl ConsensusApi for Consensus {
         self.lkg_virtual_state.load().parents.len()
     }
 
+    fn get_virtual_utxo_multiset(&self) -> MuHash {
+        self.lkg_virtual_state.load().multiset.clone()
+    }
+
     fn get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -844,10 +886,30 @@ impl ConsensusApi for Consensus {
             .collect::<ConsensusResult<Vec<_>>>()
     }
 
+    fn get_blocks_transaction_ids(&self, hashes: &[Hash]) -> ConsensusResult<Vec<Vec<TransactionId>>> {
+        hashes
+            .iter()
+            .copied()
+            .map(|hash| {
+                let transactions =
+                    self.block_transactions_store.get(hash).unwrap_option().ok_or(ConsensusError::BlockNotFound(hash))?;
+                Ok(transactions.iter().map(|tx| tx.id()).collect())
+            })
+            .collect::<ConsensusResult<Vec<_>>>()
+    }
+
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.utxo_diffs_store.get(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.is_chain_ancestor_of(hash, self.get_sink())
     }
 
+    fn get_reorg_history(&self, min_depth: u64) -> Vec<ReorgRecord> {
+        self.reorg_tracker.history(min_depth)
+    }
+
     fn get_missing_block_body_hashes(&self, high: Hash) -> ConsensusResult<Vec<Hash>> {
         let _guard = self.pruning_lock.blocking_read();
         self.validate_block_exists(high)?;
@@ -160,6 +160,8 @@ impl ConsensusServices {
             tx_script_cache_counters,
             mass_calculator.clone(),
             params.storage_mass_activation_daa_score,
+            params.introspection_activation_daa_score,
+            params.string_opcodes_activation_daa_score,
         );
 
         let pruning_point_manager = PruningPointManager::new(
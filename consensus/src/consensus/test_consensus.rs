@@ -13,8 +13,8 @@ use kaspa_hashes::Hash;
 use kaspa_notify::subscription::context::SubscriptionContext;
 use parking_lot::RwLock;
 
-use kaspa_database::create_temp_db;
 use kaspa_database::prelude::ConnBuilder;
+use kaspa_database::{create_memory_db, create_temp_db};
 use std::future::Future;
 use std::{sync::Arc, thread::JoinHandle};
 
@@ -89,6 +89,17 @@ impl TestConsensus {
     /// Creates a test consensus instance based on `config` with a temp DB and no notifier
     pub fn new(config: &Config) -> Self {
         let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        Self::with_db_lifetime(db_lifetime, db, config)
+    }
+
+    /// Creates a test consensus instance based on `config` with an in-memory DB and no notifier. Aimed for tests
+    /// which do not depend on the behavior of the RocksDB backend (e.g. its iteration snapshots)
+    pub fn with_memory_db(config: &Config) -> Self {
+        let (db_lifetime, db) = create_memory_db!();
+        Self::with_db_lifetime(db_lifetime, db, config)
+    }
+
+    fn with_db_lifetime(db_lifetime: DbLifetime, db: Arc<DB>, config: &Config) -> Self {
         let (dummy_notification_sender, _) = async_channel::unbounded();
         let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
         let counters = Default::default();
@@ -149,6 +160,22 @@ impl TestConsensus {
             .virtual_state_task
     }
 
+    /// Adds a chain of UTXO valid blocks with the given hashes on top of `root`, all paying to `miner_data`
+    pub async fn add_utxo_valid_chain(
+        &self,
+        root: Hash,
+        hashes: impl IntoIterator<Item = Hash>,
+        miner_data: MinerData,
+    ) -> BlockProcessResult<()> {
+        let mut parent = root;
+        for hash in hashes {
+            let block = self.build_utxo_valid_block_with_parents(hash, vec![parent], miner_data.clone(), vec![]);
+            self.validate_and_insert_block(block.to_immutable()).virtual_state_task.await?;
+            parent = hash;
+        }
+        Ok(())
+    }
+
     pub fn build_utxo_valid_block_with_parents(
         &self,
         hash: Hash,
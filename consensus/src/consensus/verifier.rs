@@ -0,0 +1,413 @@
+//!
+//! Offline consistency checks over the consensus stores. Aimed to be run on a consensus DB which is not
+//! concurrently processed (e.g. on startup, before the consensus processors are started) in order to detect
+//! data corrupted by a crash or a faulty disk.
+//!
+
+use super::storage::ConsensusStorage;
+use crate::{
+    config::Config,
+    model::stores::{
+        acceptance_data::AcceptanceDataStoreReader, block_transactions::BlockTransactionsStoreReader, ghostdag::GhostdagStoreReader,
+        headers::HeaderStoreReader, pruning::PruningStoreReader, reachability::ReachabilityStoreReader,
+        relations::RelationsStoreReader, selected_chain::SelectedChainStoreReader, statuses::StatusesStoreReader,
+        utxo_diffs::UtxoDiffsStoreReader, utxo_multisets::UtxoMultisetsStoreReader, virtual_state::VirtualStateStoreReader, DB,
+    },
+    processes::reachability::inquirer as reachability,
+};
+use kaspa_consensus_core::{
+    blockhash::ORIGIN,
+    hashing,
+    muhash::MuHashExtensions,
+    tx::{TransactionOutpoint, UtxoEntry},
+};
+use kaspa_core::info;
+use kaspa_database::{
+    prelude::{StoreError, StoreResult, WriteBatch},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use std::{fmt::Display, sync::Arc};
+
+/// An inconsistency found in a consensus store, identified by the store prefix and the block it relates to (if any)
+#[derive(Debug, Clone)]
+pub struct DbInconsistency {
+    pub prefix: DatabaseStorePrefixes,
+    pub hash: Option<Hash>,
+    pub reason: String,
+}
+
+impl DbInconsistency {
+    fn new(prefix: DatabaseStorePrefixes, hash: Option<Hash>, reason: impl Into<String>) -> Self {
+        Self { prefix, hash, reason: reason.into() }
+    }
+
+    /// Indicates whether the inconsistency is in a store which is fully derived from other stores and hence
+    /// can be repaired by resyncing it locally (see [`ConsensusDbVerifier::repair_selected_chain`])
+    pub fn is_derived(&self) -> bool {
+        matches!(
+            self.prefix,
+            DatabaseStorePrefixes::ChainHashByIndex
+                | DatabaseStorePrefixes::ChainIndexByHash
+                | DatabaseStorePrefixes::ChainHighestIndex
+        )
+    }
+}
+
+impl Display for DbInconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.hash {
+            Some(hash) => write!(f, "[{:?} ({})] {}: {}", self.prefix, self.prefix as u8, hash, self.reason),
+            None => write!(f, "[{:?} ({})] {}", self.prefix, self.prefix as u8, self.reason),
+        }
+    }
+}
+
+/// Walks the consensus stores and checks their cross-store invariants
+pub struct ConsensusDbVerifier {
+    db: Arc<DB>,
+    config: Arc<Config>,
+    storage: Arc<ConsensusStorage>,
+}
+
+impl ConsensusDbVerifier {
+    pub fn new(db: Arc<DB>, config: Arc<Config>) -> Self {
+        let storage = ConsensusStorage::new(db.clone(), config.clone());
+        Self { db, config, storage }
+    }
+
+    /// Runs all checks and returns the inconsistencies found
+    pub fn verify(&self) -> Vec<DbInconsistency> {
+        let mut findings = Vec::new();
+        let Some(pruning_point) =
+            self.read(&mut findings, DatabaseStorePrefixes::PruningPoint, None, |s| s.pruning_point_store.read().pruning_point())
+        else {
+            return findings;
+        };
+
+        info!("Verifying block data of the consensus stores");
+        self.verify_blocks(&mut findings, pruning_point);
+
+        // Light nodes never resolve virtual and hold no UTXO data
+        if !self.config.is_light {
+            info!("Verifying the selected chain and its UTXO data");
+            self.verify_selected_chain(&mut findings, pruning_point);
+            info!("Verifying the virtual and pruning point UTXO sets");
+            self.verify_utxo_sets(&mut findings);
+        }
+        findings
+    }
+
+    /// Checks the per-block invariants between the statuses, headers, ghostdag, relations and reachability stores
+    fn verify_blocks(&self, findings: &mut Vec<DbInconsistency>, pruning_point: Hash) {
+        use DatabaseStorePrefixes::*;
+        let s = &self.storage;
+        let statuses = s.statuses_store.read();
+        let relations_read = s.relations_stores.read();
+        let relations = &relations_read[0];
+        let reachability_store = s.reachability_store.read();
+        let is_known = |hash: Hash| statuses.get(hash).is_ok_and(|status| status.is_valid());
+
+        for item in statuses.iterator() {
+            let (hash, status) = match item {
+                Ok(item) => item,
+                Err(err) => {
+                    findings.push(DbInconsistency::new(Statuses, None, format!("unreadable entry: {err}")));
+                    continue;
+                }
+            };
+            if status.is_invalid() {
+                // Invalid blocks are only kept as a status
+                continue;
+            }
+
+            let Some(header) = self.read(findings, Headers, Some(hash), |s| s.headers_store.get_header(hash)) else { continue };
+            if hashing::header::hash(&header) != hash {
+                findings.push(DbInconsistency::new(Headers, Some(hash), "header content does not match the block hash"));
+            }
+
+            if let Some(ghostdag) = self.read(findings, Ghostdag, Some(hash), |s| s.ghostdag_primary_store.get_data(hash)) {
+                let selected_parent = ghostdag.selected_parent;
+                if selected_parent != ORIGIN && !header.direct_parents().contains(&selected_parent) {
+                    findings.push(DbInconsistency::new(
+                        Ghostdag,
+                        Some(hash),
+                        format!("selected parent {selected_parent} is not a parent"),
+                    ));
+                }
+                // Ghostdag data of blocks at the pruning boundary is truncated, so we only check blocks in the future of the pruning point
+                let in_pruning_point_future = hash != pruning_point
+                    && reachability::is_dag_ancestor_of(&*reachability_store, pruning_point, hash).unwrap_or(false);
+                if in_pruning_point_future {
+                    if let Ok(selected_parent_blue_score) = s.ghostdag_primary_store.get_blue_score(selected_parent) {
+                        let expected = selected_parent_blue_score + ghostdag.mergeset_blues.len() as u64;
+                        if ghostdag.blue_score != expected {
+                            findings.push(DbInconsistency::new(
+                                Ghostdag,
+                                Some(hash),
+                                format!("blue score {} does not match the expected {}", ghostdag.blue_score, expected),
+                            ));
+                        }
+                    }
+                }
+            }
+
+            if let Some(parents) = self.read(findings, RelationsParents, Some(hash), |_| relations.get_parents(hash)) {
+                for parent in parents.iter().copied().filter(|&parent| is_known(parent)) {
+                    if !relations.get_children(parent).is_ok_and(|children| children.read().contains(&hash)) {
+                        findings.push(DbInconsistency::new(RelationsChildren, Some(parent), format!("child {hash} is missing")));
+                    }
+                    if reachability_store.has(parent).unwrap_or(false)
+                        && !reachability::is_dag_ancestor_of(&*reachability_store, parent, hash).unwrap_or(false)
+                    {
+                        findings.push(DbInconsistency::new(
+                            Reachability,
+                            Some(hash),
+                            format!("parent {parent} is not in the past of the block"),
+                        ));
+                    }
+                }
+            }
+            if let Some(children) = self.read(findings, RelationsChildren, Some(hash), |_| relations.get_children(hash)) {
+                for child in children.read().iter().copied().filter(|&child| is_known(child)) {
+                    if !relations.get_parents(child).is_ok_and(|parents| parents.contains(&hash)) {
+                        findings.push(DbInconsistency::new(RelationsParents, Some(child), format!("parent {hash} is missing")));
+                    }
+                }
+            }
+
+            if let Some(interval) = self.read(findings, Reachability, Some(hash), |_| reachability_store.get_interval(hash)) {
+                let tree_parent = reachability_store.get_parent(hash).unwrap_or(ORIGIN);
+                if tree_parent != ORIGIN
+                    && !reachability_store
+                        .get_interval(tree_parent)
+                        .is_ok_and(|parent_interval| parent_interval.strictly_contains(interval))
+                {
+                    findings.push(DbInconsistency::new(
+                        Reachability,
+                        Some(hash),
+                        format!("interval is not contained in the interval of its tree parent {tree_parent}"),
+                    ));
+                }
+            }
+
+            if status.has_block_body() {
+                self.read(findings, BlockTransactions, Some(hash), |s| s.block_transactions_store.get(hash));
+            }
+        }
+    }
+
+    /// Checks that the selected chain index matches the selected parent chain of the sink, and that chain
+    /// blocks above the pruning point hold UTXO data which matches the UTXO commitment of their header
+    fn verify_selected_chain(&self, findings: &mut Vec<DbInconsistency>, pruning_point: Hash) {
+        use DatabaseStorePrefixes::*;
+        let s = &self.storage;
+        let selected_chain = s.selected_chain_store.read();
+        let Some((tip_index, tip)) = self.read(findings, ChainHighestIndex, None, |_| selected_chain.get_tip()) else { return };
+        if let Some(state) = self.read(findings, VirtualState, None, |s| s.virtual_stores.read().state.get()) {
+            let sink = state.ghostdag_data.selected_parent;
+            if tip != sink {
+                findings.push(DbInconsistency::new(ChainHighestIndex, Some(tip), format!("chain tip does not match the sink {sink}")));
+            }
+        }
+        let pruning_point_index = selected_chain.get_by_hash(pruning_point).ok();
+        if pruning_point_index.is_none() {
+            findings.push(DbInconsistency::new(ChainIndexByHash, Some(pruning_point), "the pruning point is not indexed"));
+        }
+
+        let mut current = tip;
+        for index in (0..=tip_index).rev() {
+            if selected_chain.get_by_hash(current).ok() != Some(index) {
+                findings.push(DbInconsistency::new(ChainIndexByHash, Some(current), format!("expected chain index {index}")));
+            }
+
+            if pruning_point_index.is_some_and(|pruning_point_index| index > pruning_point_index) {
+                if let Some(multiset) = self.read(findings, UtxoMultisets, Some(current), |s| s.utxo_multisets_store.get(current)) {
+                    let header = s.headers_store.get_header(current);
+                    if header.is_ok_and(|header| header.utxo_commitment != multiset.clone().finalize()) {
+                        findings.push(DbInconsistency::new(
+                            UtxoMultisets,
+                            Some(current),
+                            "multiset does not match the header UTXO commitment",
+                        ));
+                    }
+                }
+                self.read(findings, UtxoDiffs, Some(current), |s| s.utxo_diffs_store.get(current));
+                self.read(findings, AcceptanceData, Some(current), |s| s.acceptance_data_store.get(current));
+            }
+
+            if index == 0 {
+                break;
+            }
+            match selected_chain.get_by_index(index - 1) {
+                // This signals that data below this point was pruned
+                Err(StoreError::KeyNotFound(_)) => break,
+                Ok(below) => {
+                    if s.ghostdag_primary_store.get_selected_parent(current).is_ok_and(|selected_parent| selected_parent != below) {
+                        findings.push(DbInconsistency::new(
+                            ChainHashByIndex,
+                            Some(below),
+                            format!("chain block at index {} is not the selected parent of {current}", index - 1),
+                        ));
+                    }
+                    current = below;
+                }
+                Err(err) => {
+                    findings.push(DbInconsistency::new(
+                        ChainHashByIndex,
+                        None,
+                        format!("unreadable entry at index {}: {err}", index - 1),
+                    ));
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Checks that the virtual and pruning point UTXO sets match their multiset commitments
+    fn verify_utxo_sets(&self, findings: &mut Vec<DbInconsistency>) {
+        use DatabaseStorePrefixes::*;
+        let s = &self.storage;
+
+        if let Some(state) = self.read(findings, VirtualState, None, |s| s.virtual_stores.read().state.get()) {
+            let virtual_stores = s.virtual_stores.read();
+            if let Some(mut multiset) = Self::utxoset_multiset(findings, VirtualUtxoset, virtual_stores.utxo_set.iterator()) {
+                if multiset.finalize() != state.multiset.clone().finalize() {
+                    findings.push(DbInconsistency::new(VirtualUtxoset, None, "UTXO set does not match the virtual multiset"));
+                }
+            }
+        }
+
+        let pruning_utxoset = s.pruning_utxoset_stores.read();
+        let Some(position) = self.read(findings, PruningUtxosetPosition, None, |_| pruning_utxoset.utxoset_position()) else { return };
+        if let Some(header) = self.read(findings, Headers, Some(position), |s| s.headers_store.get_header(position)) {
+            if let Some(mut multiset) = Self::utxoset_multiset(findings, PruningUtxoset, pruning_utxoset.utxo_set.iterator()) {
+                if multiset.finalize() != header.utxo_commitment {
+                    findings.push(DbInconsistency::new(
+                        PruningUtxoset,
+                        Some(position),
+                        "UTXO set does not match the header UTXO commitment",
+                    ));
+                }
+            }
+        }
+    }
+
+    fn utxoset_multiset<E: Display>(
+        findings: &mut Vec<DbInconsistency>,
+        prefix: DatabaseStorePrefixes,
+        utxos: impl Iterator<Item = Result<(TransactionOutpoint, Arc<UtxoEntry>), E>>,
+    ) -> Option<MuHash> {
+        let mut multiset = MuHash::new();
+        for item in utxos {
+            match item {
+                Ok((outpoint, entry)) => multiset.add_utxo(&outpoint, &entry),
+                Err(err) => {
+                    findings.push(DbInconsistency::new(prefix, None, format!("unreadable entry: {err}")));
+                    return None;
+                }
+            }
+        }
+        Some(multiset)
+    }
+
+    /// Reads a store entry, recording a missing or unreadable entry as an inconsistency of the store with `prefix`
+    fn read<T>(
+        &self,
+        findings: &mut Vec<DbInconsistency>,
+        prefix: DatabaseStorePrefixes,
+        hash: Option<Hash>,
+        op: impl FnOnce(&ConsensusStorage) -> StoreResult<T>,
+    ) -> Option<T> {
+        match op(&self.storage) {
+            Ok(value) => Some(value),
+            Err(StoreError::KeyNotFound(_)) => {
+                findings.push(DbInconsistency::new(prefix, hash, "missing entry"));
+                None
+            }
+            Err(err) => {
+                findings.push(DbInconsistency::new(prefix, hash, format!("unreadable entry: {err}")));
+                None
+            }
+        }
+    }
+
+    /// Resyncs the selected chain index from the selected parent chain of the sink down to the history root
+    /// (or to the lowest block holding ghostdag data). Returns the number of indexed chain blocks
+    pub fn repair_selected_chain(&self) -> StoreResult<usize> {
+        let s = &self.storage;
+        let sink = s.virtual_stores.read().state.get()?.ghostdag_data.selected_parent;
+        let history_root = s.pruning_point_store.read().history_root().ok();
+
+        let mut chain = vec![sink];
+        let mut current = sink;
+        while Some(current) != history_root {
+            match s.ghostdag_primary_store.get_selected_parent(current) {
+                Ok(selected_parent) if selected_parent != ORIGIN && s.ghostdag_primary_store.has(selected_parent)? => {
+                    chain.push(selected_parent);
+                    current = selected_parent;
+                }
+                _ => break,
+            }
+        }
+        chain.reverse();
+
+        // Preserve the current index of the lowest chain block if it is still readable
+        let mut selected_chain = s.selected_chain_store.write();
+        let base_index = selected_chain.get_by_hash(chain[0]).unwrap_or_default();
+        let mut batch = WriteBatch::default();
+        selected_chain.reset_with_chain(&mut batch, base_index, &chain)?;
+        self.db.write(batch)?;
+        Ok(chain.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::ConfigBuilder, consensus::test_consensus::TestConsensus, model::stores::selected_chain::DbSelectedChainStore,
+        params::DEVNET_PARAMS,
+    };
+    use kaspa_consensus_core::{api::ConsensusApi, coinbase::MinerData, tx::ScriptPublicKey};
+    use kaspa_database::{create_memory_db, prelude::CachePolicy};
+
+    #[tokio::test]
+    async fn test_verify_and_repair_selected_chain() {
+        let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+        let (_lifetime, db) = create_memory_db!();
+        let consensus = TestConsensus::with_db(db.clone(), &config, async_channel::unbounded().0);
+        let wait_handles = consensus.init();
+
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+        let mut tip = config.genesis.hash;
+        for _ in 0..10 {
+            let mut block = consensus.build_utxo_valid_block_with_parents(Hash::default(), vec![tip], miner_data.clone(), vec![]);
+            // Use the actual header hash so that header content checks pass
+            block.header.finalize();
+            tip = block.header.hash;
+            consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+        }
+        consensus.shutdown(wait_handles);
+
+        let config = Arc::new(config);
+        let findings = ConsensusDbVerifier::new(db.clone(), config.clone()).verify();
+        assert!(findings.is_empty(), "unexpected inconsistencies: {findings:?}");
+
+        // Corrupt the selected chain index by truncating it to genesis
+        let mut selected_chain = DbSelectedChainStore::new(db.clone(), CachePolicy::Empty);
+        let mut batch = WriteBatch::default();
+        selected_chain.reset_with_chain(&mut batch, 0, &[config.genesis.hash]).unwrap();
+        db.write(batch).unwrap();
+
+        let verifier = ConsensusDbVerifier::new(db.clone(), config.clone());
+        let findings = verifier.verify();
+        assert!(!findings.is_empty());
+        assert!(findings.iter().all(|finding| finding.is_derived()), "unexpected inconsistencies: {findings:?}");
+
+        assert_eq!(verifier.repair_selected_chain().unwrap(), 11);
+        let findings = ConsensusDbVerifier::new(db, config).verify();
+        assert!(findings.is_empty(), "unexpected inconsistencies after repair: {findings:?}");
+    }
+}
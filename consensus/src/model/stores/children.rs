@@ -8,10 +8,10 @@ use kaspa_database::prelude::DbWriter;
 use kaspa_database::prelude::ReadLock;
 use kaspa_database::prelude::StoreError;
 use kaspa_database::prelude::StoreResult;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
-use rocksdb::WriteBatch;
 use std::sync::Arc;
 
 pub trait ChildrenStoreReader {
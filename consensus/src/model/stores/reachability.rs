@@ -12,9 +12,9 @@ use kaspa_database::{
 use kaspa_hashes::Hash;
 
 use itertools::Itertools;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
-use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::hash_map::Entry::{Occupied, Vacant},
@@ -1,8 +1,8 @@
 use kaspa_consensus_core::blockstatus::BlockStatus;
 use kaspa_consensus_core::ChainPath;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::registry::DatabaseStorePrefixes;
 use parking_lot::RwLockWriteGuard;
-use rocksdb::WriteBatch;
 
 use std::sync::Arc;
 
@@ -50,6 +50,19 @@ impl DbSelectedChainStore {
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
         Self::new(Arc::clone(&self.db), cache_policy)
     }
+
+    /// Replaces the whole index with `chain`, where `chain[0]` is assigned `base_index` and the
+    /// last block becomes the tip. Used for resyncing the index from the ghostdag store
+    pub fn reset_with_chain(&mut self, batch: &mut WriteBatch, base_index: u64, chain: &[Hash]) -> StoreResult<()> {
+        self.access_hash_by_index.delete_all(BatchDbWriter::new(batch))?;
+        self.access_index_by_hash.delete_all(BatchDbWriter::new(batch))?;
+        for (i, hash) in chain.iter().copied().enumerate() {
+            let index = base_index + i as u64;
+            self.access_index_by_hash.write(BatchDbWriter::new(batch), hash, index)?;
+            self.access_hash_by_index.write(BatchDbWriter::new(batch), index.into(), hash)?;
+        }
+        self.access_highest_index.write(BatchDbWriter::new(batch), &(base_index + chain.len() as u64 - 1))
+    }
 }
 
 pub trait SelectedChainStoreBatchExtensions {
@@ -7,11 +7,11 @@ use kaspa_database::prelude::DbWriter;
 use kaspa_database::prelude::ReadLock;
 use kaspa_database::prelude::StoreResult;
 use kaspa_database::prelude::StoreResultExtensions;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, DirectDbWriter};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
-use rocksdb::WriteBatch;
 
 /// Reader API for `TipsStore`.
 pub trait TipsStoreReader {
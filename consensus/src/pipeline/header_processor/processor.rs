@@ -24,7 +24,10 @@ use crate::{
         },
     },
     params::Params,
-    pipeline::deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
+    pipeline::{
+        deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
+        pruning_processor::processor::LightPruningUpdates,
+    },
     processes::{ghostdag::ordering::SortableBlock, reachability::inquirer as reachability, relations::RelationsStoreExtensions},
 };
 use crossbeam_channel::{Receiver, Sender};
@@ -37,12 +40,12 @@ use kaspa_consensus_core::{
     BlockHashSet, BlockLevel,
 };
 use kaspa_consensusmanager::SessionLock;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::{StoreResultEmptyTuple, StoreResultExtensions};
 use kaspa_hashes::Hash;
 use kaspa_utils::vec::VecExtensions;
 use parking_lot::RwLock;
 use rayon::ThreadPool;
-use rocksdb::WriteBatch;
 use std::sync::{atomic::Ordering, Arc};
 
 use super::super::ProcessingCounters;
@@ -108,6 +111,9 @@ pub struct HeaderProcessor {
     receiver: Receiver<BlockProcessingMessage>,
     body_sender: Sender<BlockProcessingMessage>,
 
+    // Light nodes never resolve virtual, so the pruning processor is driven by headers selected tip changes instead
+    light_pruning_updates: Option<Arc<LightPruningUpdates>>,
+
     // Thread pool
     pub(super) thread_pool: Arc<ThreadPool>,
 
@@ -160,6 +166,7 @@ impl HeaderProcessor {
     pub fn new(
         receiver: Receiver<BlockProcessingMessage>,
         body_sender: Sender<BlockProcessingMessage>,
+        light_pruning_updates: Option<Arc<LightPruningUpdates>>,
         thread_pool: Arc<ThreadPool>,
         params: &Params,
         db: Arc<DB>,
@@ -171,6 +178,7 @@ impl HeaderProcessor {
         Self {
             receiver,
             body_sender,
+            light_pruning_updates,
             thread_pool,
             genesis: params.genesis.clone(),
             db,
@@ -403,9 +411,9 @@ impl HeaderProcessor {
         // Note we need to keep the lock write guards until the batch is written.
         let mut hst_write = self.headers_selected_tip_store.write();
         let prev_hst = hst_write.get().unwrap();
-        if SortableBlock::new(ctx.hash, header.blue_work) > prev_hst
-            && reachability::is_chain_ancestor_of(&staging, pp, ctx.hash).unwrap()
-        {
+        let is_new_hst = SortableBlock::new(ctx.hash, header.blue_work) > prev_hst
+            && reachability::is_chain_ancestor_of(&staging, pp, ctx.hash).unwrap();
+        if is_new_hst {
             // Hint reachability about the new tip.
             // TODO: identify a disqualified hst and make sure to use sink instead
             reachability::hint_virtual_selected_parent(&mut staging, ctx.hash).unwrap();
@@ -443,6 +451,12 @@ impl HeaderProcessor {
         drop(reachability_relations_write);
         drop(relations_write);
         drop(hst_write);
+
+        if is_new_hst {
+            if let Some(light_pruning_updates) = self.light_pruning_updates.as_ref() {
+                light_pruning_updates.update(ghostdag_data[0].to_compact());
+            }
+        }
     }
 
     fn commit_trusted_header(&self, ctx: HeaderProcessingContext, _header: &Header) {
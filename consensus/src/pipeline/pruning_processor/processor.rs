@@ -10,11 +10,12 @@ use crate::{
         stores::{
             ghostdag::{CompactGhostdagData, GhostdagStoreReader},
             headers::HeaderStoreReader,
+            headers_selected_tip::HeadersSelectedTipStoreReader,
             past_pruning_points::PastPruningPointsStoreReader,
             pruning::{PruningStore, PruningStoreReader},
             reachability::{DbReachabilityStore, ReachabilityStoreReader, StagingReachabilityStore},
             relations::StagingRelationsStore,
-            selected_chain::SelectedChainStore,
+            selected_chain::{SelectedChainStore, SelectedChainStoreReader},
             statuses::StatusesStoreReader,
             tips::{TipsStore, TipsStoreReader},
             utxo_diffs::UtxoDiffsStoreReader,
@@ -22,7 +23,7 @@ use crate::{
     },
     processes::{pruning_proof::PruningProofManager, reachability::inquirer as reachability, relations},
 };
-use crossbeam_channel::Receiver as CrossbeamReceiver;
+use crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use itertools::Itertools;
 use kaspa_consensus_core::{
     blockhash::ORIGIN,
@@ -35,12 +36,12 @@ use kaspa_consensus_core::{
 };
 use kaspa_consensusmanager::SessionLock;
 use kaspa_core::{debug, info, warn};
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::{BatchDbWriter, MemoryWriter, StoreResultExtensions, DB};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_utils::iter::IterExtensions;
-use parking_lot::RwLockUpgradableReadGuard;
-use rocksdb::WriteBatch;
+use parking_lot::{Mutex, RwLockUpgradableReadGuard};
 use std::{
     collections::VecDeque,
     ops::Deref,
@@ -53,7 +54,45 @@ use std::{
 
 pub enum PruningProcessingMessage {
     Exit,
-    Process { sink_ghostdag_data: CompactGhostdagData },
+    /// Carries the ghostdag data of the sink, or of the headers selected tip on light nodes
+    Process {
+        sink_ghostdag_data: CompactGhostdagData,
+    },
+}
+
+/// Coalesces the headers selected tip updates which drive the pruning processor of light nodes.
+///
+/// Header workers run concurrently and cannot block on a full channel, so the latest tip is kept in a slot which
+/// the pruning processor drains whenever it handles a message. A failed send thus never loses an update, since
+/// a full channel means the pruning processor still has pending messages to handle.
+pub struct LightPruningUpdates {
+    sender: CrossbeamSender<PruningProcessingMessage>,
+    latest_tip: Mutex<Option<CompactGhostdagData>>,
+}
+
+impl LightPruningUpdates {
+    pub fn new(sender: CrossbeamSender<PruningProcessingMessage>) -> Self {
+        Self { sender, latest_tip: Mutex::new(None) }
+    }
+
+    /// Records a new headers selected tip and notifies the pruning processor
+    pub fn update(&self, tip_ghostdag_data: CompactGhostdagData) {
+        {
+            let mut latest_tip = self.latest_tip.lock();
+            if latest_tip.as_ref().map_or(true, |latest| latest.blue_work < tip_ghostdag_data.blue_work) {
+                *latest_tip = Some(tip_ghostdag_data);
+            }
+        }
+        let _ = self.sender.try_send(PruningProcessingMessage::Process { sink_ghostdag_data: tip_ghostdag_data });
+    }
+
+    /// Returns the latest recorded headers selected tip, if it has more blue work than `sink_ghostdag_data`
+    fn take_latest(&self, sink_ghostdag_data: CompactGhostdagData) -> CompactGhostdagData {
+        match self.latest_tip.lock().take() {
+            Some(latest) if latest.blue_work > sink_ghostdag_data.blue_work => latest,
+            _ => sink_ghostdag_data,
+        }
+    }
 }
 
 /// A processor dedicated for moving the pruning point and pruning any possible data in its past
@@ -61,6 +100,9 @@ pub struct PruningProcessor {
     // Channels
     receiver: CrossbeamReceiver<PruningProcessingMessage>,
 
+    // Pending headers selected tip updates, on light nodes only
+    light_pruning_updates: Option<Arc<LightPruningUpdates>>,
+
     // DB
     db: Arc<DB>,
 
@@ -94,6 +136,7 @@ impl Deref for PruningProcessor {
 impl PruningProcessor {
     pub fn new(
         receiver: CrossbeamReceiver<PruningProcessingMessage>,
+        light_pruning_updates: Option<Arc<LightPruningUpdates>>,
         db: Arc<DB>,
         storage: &Arc<ConsensusStorage>,
         services: &Arc<ConsensusServices>,
@@ -103,6 +146,7 @@ impl PruningProcessor {
     ) -> Self {
         Self {
             receiver,
+            light_pruning_updates,
             db,
             storage: storage.clone(),
             reachability_service: services.reachability_service.clone(),
@@ -142,7 +186,7 @@ impl PruningProcessor {
             pruning_point, history_root, pruning_utxoset_position
         );
 
-        if let Some(pruning_utxoset_position) = pruning_utxoset_position {
+        if let Some(pruning_utxoset_position) = pruning_utxoset_position.filter(|_| !self.config.is_light) {
             // This indicates the node crashed during a former pruning point move and we need to recover
             if pruning_utxoset_position != pruning_point {
                 info!("Recovering pruning utxo-set from {} to the pruning point {}", pruning_utxoset_position, pruning_point);
@@ -156,7 +200,7 @@ impl PruningProcessor {
         if let Some(history_root) = history_root {
             // This indicates the node crashed or was forced to stop during a former data prune operation hence
             // we need to complete it
-            if history_root != pruning_point {
+            if history_root != self.retention_root(pruning_point) {
                 self.prune(pruning_point);
             }
         }
@@ -165,6 +209,10 @@ impl PruningProcessor {
     }
 
     fn advance_pruning_point_and_candidate_if_possible(&self, sink_ghostdag_data: CompactGhostdagData) {
+        let sink_ghostdag_data = match self.light_pruning_updates.as_ref() {
+            Some(updates) => updates.take_latest(sink_ghostdag_data),
+            None => sink_ghostdag_data,
+        };
         let pruning_point_read = self.pruning_point_store.upgradable_read();
         let current_pruning_info = pruning_point_read.get().unwrap();
         let (new_pruning_points, new_candidate) = self.pruning_point_manager.next_pruning_points_and_candidate_by_ghostdag_data(
@@ -190,12 +238,15 @@ impl PruningProcessor {
             // Inform the user
             info!("Periodic pruning point movement: advancing from {} to {}", current_pruning_info.pruning_point, new_pruning_point);
 
-            // Advance the pruning point utxoset to the state of the new pruning point using chain-block UTXO diffs
-            if !self.advance_pruning_utxoset(current_pruning_info.pruning_point, new_pruning_point) {
-                info!("Interrupted while advancing the pruning point UTXO set: Process is exiting");
-                return;
+            // Advance the pruning point utxoset to the state of the new pruning point using chain-block UTXO diffs.
+            // Light nodes keep no UTXO state
+            if !self.config.is_light {
+                if !self.advance_pruning_utxoset(current_pruning_info.pruning_point, new_pruning_point) {
+                    info!("Interrupted while advancing the pruning point UTXO set: Process is exiting");
+                    return;
+                }
+                info!("Updated the pruning point UTXO set");
             }
-            info!("Updated the pruning point UTXO set");
 
             // Finally, prune data in the new pruning point past
             self.prune(new_pruning_point);
@@ -247,10 +298,14 @@ impl PruningProcessor {
         info!("Header and Block pruning: preparing proof and anticone data...");
 
         let proof = self.pruning_proof_manager.get_pruning_point_proof();
-        let data = self
-            .pruning_proof_manager
-            .get_pruning_point_anticone_and_trusted_data()
-            .expect("insufficient depth error is unexpected here");
+        let data = if self.config.is_light {
+            // Light nodes never resolve virtual, hence the anticone is computed relative to the headers selected tip
+            Arc::new(self.pruning_proof_manager.calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, self.dag_tips()))
+        } else {
+            self.pruning_proof_manager
+                .get_pruning_point_anticone_and_trusted_data()
+                .expect("insufficient depth error is unexpected here")
+        };
 
         let genesis = self.past_pruning_points_store.get(0).unwrap();
 
@@ -259,6 +314,16 @@ impl PruningProcessor {
         assert_eq!(genesis, self.config.genesis.hash);
         assert_eq!(genesis, proof.last().unwrap().last().unwrap().hash);
 
+        // With a retention period, all blocks outside the past of the retention root are kept in full
+        let retention_root = self.retention_root(new_pruning_point);
+        let is_retained = |reachability: &DbReachabilityStore, hash: Hash| {
+            retention_root != new_pruning_point
+                && (hash == retention_root || !reachability.is_dag_ancestor_of_result(hash, retention_root).unwrap_or(true))
+        };
+        if retention_root != new_pruning_point {
+            info!("Header and Block pruning: retaining full block data in the future of {}", retention_root);
+        }
+
         // We keep full data for pruning point and its anticone, relations for DAA/GD
         // windows and pruning proof, and only headers for past pruning points
         let keep_blocks: BlockHashSet = data.anticone.iter().copied().collect();
@@ -268,7 +333,12 @@ impl PruningProcessor {
             .chain(data.ghostdag_blocks.iter().map(|gd| gd.hash))
             .chain(proof.iter().flatten().map(|h| h.hash))
             .collect();
-        let keep_headers: BlockHashSet = self.past_pruning_points();
+        let mut keep_headers: BlockHashSet = self.past_pruning_points();
+        if self.config.retention_depth.is_some() {
+            // The header of the previous history root is kept since it might be queried as the consensus source until
+            // the history root is updated below
+            keep_headers.extend(self.pruning_point_store.read().history_root().unwrap_option());
+        }
 
         info!("Header and Block pruning: waiting for consensus write permissions...");
 
@@ -285,12 +355,13 @@ impl PruningProcessor {
                 let Some(ghostdag) = self.ghostdag_primary_store.get_data(kept).unwrap_option() else {
                     continue;
                 };
-                if ghostdag.unordered_mergeset().any(|h| !keep_relations.contains(&h)) {
+                let is_kept = |h: &Hash| keep_relations.contains(h) || is_retained(&reachability_read, *h);
+                if ghostdag.unordered_mergeset().any(|h| !is_kept(&h)) {
                     let mut mutable_ghostdag: ExternalGhostdagData = ghostdag.as_ref().into();
-                    mutable_ghostdag.mergeset_blues.retain(|h| keep_relations.contains(h));
-                    mutable_ghostdag.mergeset_reds.retain(|h| keep_relations.contains(h));
-                    mutable_ghostdag.blues_anticone_sizes.retain(|k, _| keep_relations.contains(k));
-                    if !keep_relations.contains(&mutable_ghostdag.selected_parent) {
+                    mutable_ghostdag.mergeset_blues.retain(is_kept);
+                    mutable_ghostdag.mergeset_reds.retain(is_kept);
+                    mutable_ghostdag.blues_anticone_sizes.retain(|k, _| is_kept(k));
+                    if !is_kept(&mutable_ghostdag.selected_parent) {
                         mutable_ghostdag.selected_parent = ORIGIN;
                     }
                     counter += 1;
@@ -301,7 +372,8 @@ impl PruningProcessor {
             info!("Header and Block pruning: updated ghostdag data for {} blocks", counter);
         }
 
-        {
+        // Light nodes hold neither body tips nor a selected chain index beyond their initial state
+        if !self.config.is_light {
             // Start with a batch for pruning body tips and selected chain stores
             let mut batch = WriteBatch::default();
 
@@ -327,9 +399,9 @@ impl PruningProcessor {
                 )
             }
 
-            // Prune the selected chain index below the pruning point
+            // Prune the selected chain index below the retention root (which is the pruning point when no retention is configured)
             let mut selected_chain_write = self.selected_chain_store.write();
-            selected_chain_write.prune_below_pruning_point(BatchDbWriter::new(&mut batch), new_pruning_point).unwrap();
+            selected_chain_write.prune_below_pruning_point(BatchDbWriter::new(&mut batch), retention_root).unwrap();
 
             // Flush the batch to the DB
             self.db.write(batch).unwrap();
@@ -348,6 +420,11 @@ impl PruningProcessor {
             if reachability_read.is_dag_ancestor_of_result(new_pruning_point, current).unwrap() {
                 continue;
             }
+            // Retained blocks are skipped along with their reachability subtree, since tree children
+            // of a block outside the past of the retention root are outside of it as well
+            if is_retained(&reachability_read, current) {
+                continue;
+            }
             traversed += 1;
             // Obtain the tree children of `current` and push them to the queue before possibly being deleted below
             queue.extend(reachability_read.get_children(current).unwrap().iter());
@@ -462,15 +539,54 @@ impl PruningProcessor {
         }
 
         {
-            // Set the history root to the new pruning point only after we successfully pruned its past
+            // Set the history root to the new retention root (usually the pruning point) only after we successfully pruned its past
             let mut pruning_point_write = self.pruning_point_store.write();
             let mut batch = WriteBatch::default();
-            pruning_point_write.set_history_root(&mut batch, new_pruning_point).unwrap();
+            pruning_point_write.set_history_root(&mut batch, retention_root).unwrap();
             self.db.write(batch).unwrap();
             drop(pruning_point_write);
         }
     }
 
+    /// Returns the root of the retained history for `pruning_point`: the highest selected chain block which is at least
+    /// `retention_depth` DAA scores below the pruning point. Returns the pruning point itself if no retention period is
+    /// configured. The root never moves below the current history root since data in its past was already pruned.
+    fn retention_root(&self, pruning_point: Hash) -> Hash {
+        let Some(retention_depth) = self.config.retention_depth else {
+            return pruning_point;
+        };
+        let target_daa_score = self.headers_store.get_daa_score(pruning_point).unwrap().saturating_sub(retention_depth);
+        let history_root = self.pruning_point_store.read().history_root().unwrap_option();
+        let selected_chain_read = self.selected_chain_store.read();
+        let daa_score_at = |index: u64| self.headers_store.get_daa_score(selected_chain_read.get_by_index(index).unwrap()).unwrap();
+
+        let mut high = selected_chain_read.get_by_hash(pruning_point).unwrap();
+        let mut low = history_root.and_then(|root| selected_chain_read.get_by_hash(root).unwrap_option()).unwrap_or(high);
+        if daa_score_at(low) > target_daa_score {
+            // The history held is shorter than the retention period, so it is retained as is
+            return selected_chain_read.get_by_index(low).unwrap();
+        }
+        // Binary search for the highest chain index whose block DAA score does not exceed the target
+        while low < high {
+            let mid = (low + high + 1) / 2;
+            if daa_score_at(mid) <= target_daa_score {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        selected_chain_read.get_by_index(low).unwrap()
+    }
+
+    /// Returns the tips bounding the DAG from above: the virtual parents, or the headers selected tip on light nodes
+    fn dag_tips(&self) -> impl Iterator<Item = Hash> {
+        if self.config.is_light {
+            vec![self.headers_selected_tip_store.read().get().unwrap().hash].into_iter()
+        } else {
+            self.lkg_virtual_state.load().parents.clone().into_iter()
+        }
+    }
+
     fn past_pruning_points(&self) -> BlockHashSet {
         (0..self.pruning_point_store.read().get().unwrap().index)
             .map(|index| self.past_pruning_points_store.get(index).unwrap())
@@ -493,10 +609,8 @@ impl PruningProcessor {
 
     fn assert_data_rebuilding(&self, ref_data: Arc<PruningPointTrustedData>, new_pruning_point: Hash) {
         info!("Rebuilding pruning point trusted data (sanity test)");
-        let virtual_state = self.lkg_virtual_state.load();
-        let built_data = self
-            .pruning_proof_manager
-            .calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, virtual_state.parents.iter().copied());
+        let built_data =
+            self.pruning_proof_manager.calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, self.dag_tips());
         assert_eq!(
             ref_data.anticone.iter().copied().collect::<BlockHashSet>(),
             built_data.anticone.iter().copied().collect::<BlockHashSet>()
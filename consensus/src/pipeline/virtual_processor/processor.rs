@@ -55,7 +55,8 @@ use kaspa_consensus_core::{
     header::Header,
     merkle::calc_hash_merkle_root_with_options,
     pruning::PruningPointsList,
-    tx::{MutableTransaction, ScriptPublicKey, Transaction},
+    reorg::ReorgRecord,
+    tx::{MutableTransaction, ScriptPublicKey, Transaction, TransactionId},
     utxo::{
         utxo_diff::UtxoDiff,
         utxo_view::{UtxoView, UtxoViewComposition},
@@ -64,9 +65,10 @@ use kaspa_consensus_core::{
 };
 use kaspa_consensus_notify::{
     notification::{
-        NewBlockTemplateNotification, Notification, SinkBlueScoreChangedNotification, UtxosChangedNotification,
-        VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+        NewBlockTemplateNotification, Notification, ReorgAlertNotification, SinkBlueScoreChangedNotification,
+        UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
     },
+    reorg::ReorgTracker,
     root::ConsensusNotificationRoot,
 };
 use kaspa_consensusmanager::SessionLock;
@@ -78,6 +80,7 @@ use kaspa_notify::{events::EventType, notifier::Notify};
 
 use crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use itertools::Itertools;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_utils::binary_heap::BinaryHeapExtensions;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use rand::{seq::SliceRandom, Rng};
@@ -85,10 +88,9 @@ use rayon::{
     prelude::{IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator},
     ThreadPool,
 };
-use rocksdb::WriteBatch;
 use std::{
     cmp::min,
-    collections::{BinaryHeap, HashMap, VecDeque},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     ops::Deref,
     sync::{atomic::Ordering, Arc},
 };
@@ -156,6 +158,9 @@ pub struct VirtualStateProcessor {
     // Notifier
     notification_root: Arc<ConsensusNotificationRoot>,
 
+    // Reorg history
+    reorg_tracker: Arc<ReorgTracker>,
+
     // Counters
     counters: Arc<ProcessingCounters>,
 
@@ -176,6 +181,7 @@ impl VirtualStateProcessor {
         services: &Arc<ConsensusServices>,
         pruning_lock: SessionLock,
         notification_root: Arc<ConsensusNotificationRoot>,
+        reorg_tracker: Arc<ReorgTracker>,
         counters: Arc<ProcessingCounters>,
     ) -> Self {
         Self {
@@ -222,6 +228,7 @@ impl VirtualStateProcessor {
 
             pruning_lock,
             notification_root,
+            reorg_tracker,
             counters,
             storage_mass_activation_daa_score: params.storage_mass_activation_daa_score,
         }
@@ -327,6 +334,10 @@ impl VirtualStateProcessor {
         self.notification_root
             .notify(Notification::VirtualDaaScoreChanged(VirtualDaaScoreChangedNotification::new(new_virtual_state.daa_score)))
             .expect("expecting an open unbounded channel");
+        // Shallow reorgs are skipped right away, so that their acceptance data is not read on the critical path
+        if chain_path.removed.len() as u64 >= self.reorg_tracker.min_depth() {
+            self.track_reorg(&chain_path);
+        }
         if self.notification_root.has_subscription(EventType::VirtualChainChanged) {
             // check for subscriptions before the heavy lifting
             let added_chain_blocks_acceptance_data =
@@ -341,6 +352,43 @@ impl VirtualStateProcessor {
         }
     }
 
+    /// Records a reorg of the selected chain along with the number of transactions which lost their acceptance,
+    /// and raises an alert if the reorg reaches the configured alert depth
+    fn track_reorg(&self, chain_path: &ChainPath) {
+        let accepted_transaction_ids = |chain_blocks: &[Hash]| {
+            let mut transaction_ids = HashSet::<TransactionId>::new();
+            for acceptance_data in chain_blocks.iter().filter_map(|&hash| self.acceptance_data_store.get(hash).ok()) {
+                transaction_ids.extend(
+                    acceptance_data
+                        .iter()
+                        .flat_map(|mergeset_block| mergeset_block.accepted_transactions.iter().map(|entry| entry.transaction_id)),
+                );
+            }
+            transaction_ids
+        };
+        let still_accepted = accepted_transaction_ids(&chain_path.added);
+        let lost_accepted_transaction_count = accepted_transaction_ids(&chain_path.removed).difference(&still_accepted).count() as u64;
+
+        let reorg = ReorgRecord {
+            timestamp: unix_now(),
+            depth: chain_path.removed.len() as u64,
+            removed_chain_block_hashes: chain_path.removed.clone(),
+            added_chain_block_count: chain_path.added.len() as u64,
+            lost_accepted_transaction_count,
+        };
+        if self.reorg_tracker.record(reorg.clone()) {
+            warn!(
+                "Deep reorg of the selected chain: {} chain blocks were removed (alert depth: {}), {} transactions lost their acceptance",
+                reorg.depth,
+                self.reorg_tracker.alert_depth().unwrap_or_default(),
+                reorg.lost_accepted_transaction_count
+            );
+            self.notification_root
+                .notify(Notification::ReorgAlert(ReorgAlertNotification::new(Arc::new(reorg))))
+                .expect("expecting an open unbounded channel");
+        }
+    }
+
     pub(crate) fn virtual_finality_point(&self, virtual_ghostdag_data: &GhostdagData, pruning_point: Hash) -> Hash {
         let finality_point = self.depth_manager.calc_finality_point(virtual_ghostdag_data, pruning_point);
         if self.reachability_service.is_chain_ancestor_of(pruning_point, finality_point) {
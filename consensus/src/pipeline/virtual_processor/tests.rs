@@ -155,7 +155,7 @@ impl TestContext {
 #[tokio::test]
 async fn template_mining_sanity_test() {
     let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().build();
-    let mut ctx = TestContext::new(TestConsensus::new(&config));
+    let mut ctx = TestContext::new(TestConsensus::with_memory_db(&config));
     let rounds = 10;
     let width = 3;
     for _ in 0..rounds {
@@ -179,7 +179,7 @@ async fn antichain_merge_test() {
         })
         .build();
 
-    let mut ctx = TestContext::new(TestConsensus::new(&config));
+    let mut ctx = TestContext::new(TestConsensus::with_memory_db(&config));
 
     // Build a large 32-wide antichain
     ctx.build_block_template_row(0..32)
@@ -207,7 +207,7 @@ async fn basic_utxo_disqualified_test() {
         })
         .build();
 
-    let mut ctx = TestContext::new(TestConsensus::new(&config));
+    let mut ctx = TestContext::new(TestConsensus::with_memory_db(&config));
 
     // Mine a valid chain
     for _ in 0..10 {
@@ -239,7 +239,7 @@ async fn double_search_disqualified_test() {
             p.min_difficulty_window_len = p.legacy_difficulty_window_size;
         })
         .build();
-    let mut ctx = TestContext::new(TestConsensus::new(&config));
+    let mut ctx = TestContext::new(TestConsensus::with_memory_db(&config));
 
     // Mine 3 valid blocks over genesis
     ctx.build_block_template_row(0..3)
@@ -10,9 +10,9 @@ use std::{
 };
 
 use itertools::Itertools;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_math::int::SignedInteger;
 use parking_lot::{Mutex, RwLock};
-use rocksdb::WriteBatch;
 
 use kaspa_consensus_core::{
     blockhash::{self, BlockHashExtensions, BlockHashes, ORIGIN},
@@ -268,10 +268,10 @@ mod tests {
     use itertools::Itertools;
     use kaspa_consensus_core::blockhash::ORIGIN;
     use kaspa_database::prelude::ConnBuilder;
+    use kaspa_database::prelude::WriteBatch;
     use kaspa_database::{create_temp_db, prelude::CachePolicy};
     use parking_lot::RwLock;
     use rand::seq::IteratorRandom;
-    use rocksdb::WriteBatch;
     use std::{iter::once, ops::Deref};
 
     #[test]
@@ -28,9 +28,16 @@ pub struct TransactionValidator {
 
     /// Storage mass hardfork DAA score
     storage_mass_activation_daa_score: u64,
+
+    /// Transaction introspection opcodes hardfork DAA score
+    introspection_activation_daa_score: u64,
+
+    /// String and bitwise opcodes hardfork DAA score
+    string_opcodes_activation_daa_score: u64,
 }
 
 impl TransactionValidator {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         max_tx_inputs: usize,
         max_tx_outputs: usize,
@@ -42,6 +49,8 @@ impl TransactionValidator {
         counters: Arc<TxScriptCacheCounters>,
         mass_calculator: MassCalculator,
         storage_mass_activation_daa_score: u64,
+        introspection_activation_daa_score: u64,
+        string_opcodes_activation_daa_score: u64,
     ) -> Self {
         Self {
             max_tx_inputs,
@@ -54,6 +63,8 @@ impl TransactionValidator {
             sig_cache: Cache::with_counters(10_000, counters),
             mass_calculator,
             storage_mass_activation_daa_score,
+            introspection_activation_daa_score,
+            string_opcodes_activation_daa_score,
         }
     }
 
@@ -78,6 +89,8 @@ impl TransactionValidator {
             sig_cache: Cache::with_counters(10_000, counters),
             mass_calculator: MassCalculator::new(0, 0, 0, 0),
             storage_mass_activation_daa_score: u64::MAX,
+            introspection_activation_daa_score: u64::MAX,
+            string_opcodes_activation_daa_score: u64::MAX,
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::constants::{MAX_SOMPI, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK};
 use kaspa_consensus_core::{hashing::sighash::SigHashReusedValues, tx::VerifiableTransaction};
 use kaspa_core::warn;
-use kaspa_txscript::{get_sig_op_count, TxScriptEngine};
+use kaspa_txscript::{get_sig_op_count, EngineFlags, TxScriptEngine};
 
 use super::{
     errors::{TxResult, TxRuleError},
@@ -43,7 +43,7 @@ impl TransactionValidator {
         match flags {
             TxValidationFlags::Full | TxValidationFlags::SkipMassCheck => {
                 Self::check_sig_op_counts(tx)?;
-                self.check_scripts(tx)?;
+                self.check_scripts(tx, pov_daa_score)?;
             }
             TxValidationFlags::SkipScriptChecks => {}
         }
@@ -144,10 +144,14 @@ impl TransactionValidator {
         Ok(())
     }
 
-    pub fn check_scripts(&self, tx: &impl VerifiableTransaction) -> TxResult<()> {
+    pub fn check_scripts(&self, tx: &impl VerifiableTransaction, pov_daa_score: u64) -> TxResult<()> {
+        let flags = EngineFlags {
+            introspection_enabled: pov_daa_score > self.introspection_activation_daa_score,
+            string_opcodes_enabled: pov_daa_score > self.string_opcodes_activation_daa_score,
+        };
         let mut reused_values = SigHashReusedValues::new();
         for (i, (input, entry)) in tx.populated_inputs().enumerate() {
-            let mut engine = TxScriptEngine::from_transaction_input(tx, input, i, entry, &mut reused_values, &self.sig_cache)
+            let mut engine = TxScriptEngine::from_transaction_input(tx, input, i, entry, &mut reused_values, &self.sig_cache, flags)
                 .map_err(TxRuleError::SignatureInvalid)?;
             engine.execute().map_err(TxRuleError::SignatureInvalid)?;
         }
@@ -230,7 +234,7 @@ mod tests {
             }],
         );
 
-        tv.check_scripts(&populated_tx).expect("Signature check failed");
+        tv.check_scripts(&populated_tx, 0).expect("Signature check failed");
     }
 
     #[test]
@@ -292,7 +296,7 @@ mod tests {
             }],
         );
 
-        assert!(tv.check_scripts(&populated_tx).is_err(), "Failing Signature Test Failed");
+        assert!(tv.check_scripts(&populated_tx, 0).is_err(), "Failing Signature Test Failed");
     }
 
     #[test]
@@ -354,7 +358,7 @@ mod tests {
                 is_coinbase: false,
             }],
         );
-        tv.check_scripts(&populated_tx).expect("Signature check failed");
+        tv.check_scripts(&populated_tx, 0).expect("Signature check failed");
     }
 
     #[test]
@@ -417,7 +421,7 @@ mod tests {
             }],
         );
 
-        assert!(tv.check_scripts(&populated_tx) == Err(TxRuleError::SignatureInvalid(TxScriptError::NullFail)));
+        assert!(tv.check_scripts(&populated_tx, 0) == Err(TxRuleError::SignatureInvalid(TxScriptError::NullFail)));
     }
 
     #[test]
@@ -480,7 +484,7 @@ mod tests {
             }],
         );
 
-        assert!(tv.check_scripts(&populated_tx) == Err(TxRuleError::SignatureInvalid(TxScriptError::NullFail)));
+        assert!(tv.check_scripts(&populated_tx, 0) == Err(TxRuleError::SignatureInvalid(TxScriptError::NullFail)));
     }
 
     #[test]
@@ -543,7 +547,7 @@ mod tests {
             }],
         );
 
-        let result = tv.check_scripts(&populated_tx);
+        let result = tv.check_scripts(&populated_tx, 0);
         assert!(result == Err(TxRuleError::SignatureInvalid(TxScriptError::EvalFalse)));
     }
 
@@ -598,7 +602,7 @@ mod tests {
             }],
         );
 
-        let result = tv.check_scripts(&populated_tx);
+        let result = tv.check_scripts(&populated_tx, 0);
         assert!(result == Err(TxRuleError::SignatureInvalid(TxScriptError::SignatureScriptNotPushOnly)));
     }
 
@@ -678,7 +682,7 @@ mod tests {
         let schnorr_key = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &secret_key.secret_bytes()).unwrap();
         let signed_tx = sign(MutableTransaction::with_entries(unsigned_tx, entries), schnorr_key);
         let populated_tx = signed_tx.as_verifiable();
-        assert_eq!(tv.check_scripts(&populated_tx), Ok(()));
+        assert_eq!(tv.check_scripts(&populated_tx, 0), Ok(()));
         assert_eq!(TransactionValidator::check_sig_op_counts(&populated_tx), Ok(()));
     }
 }
@@ -28,6 +28,7 @@ blake3_hasher! {
     struct ProofOfWorkHash => b"ProofOfWorkHash",
     struct MerkleBranchHash => b"MerkleBranchHash",
     struct PersonalMessageSigningHash => b"PersonalMessageSigningHash",
+    struct ShortTransactionIdHash => b"ShortTransactionIdHash",
 }
 
 blake2b_hasher! {
@@ -22,8 +22,78 @@ pub fn calc_merkle_root(hashes: impl ExactSizeIterator<Item = Hash>) -> Hash {
     merkles.last().unwrap().unwrap()
 }
 
+/// Inclusion proof of a leaf within a merkle tree built by [`calc_merkle_root`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleWitness {
+    /// The position of the leaf among the hashes the tree was built from
+    pub index: u32,
+    /// The sibling hashes along the path from the leaf to the root, ordered from the leaf level upwards
+    pub siblings: Vec<Hash>,
+}
+
+/// Builds the inclusion proof of the hash at position `index` within the merkle tree of `hashes`.
+/// Returns `None` if `index` is out of range.
+pub fn create_merkle_witness(hashes: impl ExactSizeIterator<Item = Hash>, index: usize) -> Option<MerkleWitness> {
+    if index >= hashes.len() {
+        return None;
+    }
+    let mut level = hashes.collect::<Vec<_>>();
+    let mut position = index;
+    let mut siblings = Vec::new();
+    // Missing right nodes are hashed as zero, which is equivalent to the padding applied by `calc_merkle_root`
+    while level.len() > 1 {
+        siblings.push(level.get(position ^ 1).copied().unwrap_or(ZERO_HASH));
+        level = level.chunks(2).map(|pair| merkle_hash(pair[0], pair.get(1).copied().unwrap_or(ZERO_HASH))).collect();
+        position /= 2;
+    }
+    Some(MerkleWitness { index: index as u32, siblings })
+}
+
+/// Verifies that `witness` proves the inclusion of `leaf` within the merkle tree committed to by `root`
+pub fn verify_merkle_witness(leaf: Hash, witness: &MerkleWitness, root: Hash) -> bool {
+    let mut position = witness.index as u64;
+    let mut hash = leaf;
+    for sibling in witness.siblings.iter().copied() {
+        hash = if position % 2 == 0 { merkle_hash(hash, sibling) } else { merkle_hash(sibling, hash) };
+        position /= 2;
+    }
+    // A non-zero remainder means the index points outside of the tree
+    position == 0 && hash == root
+}
+
 fn merkle_hash(left: Hash, right: Hash) -> Hash {
     let mut hasher = MerkleBranchHash::new();
     hasher.update(left).update(right);
     hasher.finalize()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_witness() {
+        for len in 1..=17u64 {
+            let hashes = (0..len).map(Hash::from_u64_word).collect::<Vec<_>>();
+            let root = calc_merkle_root(hashes.iter().copied());
+            for (index, leaf) in hashes.iter().copied().enumerate() {
+                let witness = create_merkle_witness(hashes.iter().copied(), index).unwrap();
+                assert_eq!(witness.siblings.len(), len.next_power_of_two().trailing_zeros() as usize);
+                assert!(verify_merkle_witness(leaf, &witness, root), "len {len}, index {index}");
+
+                // Proofs must not hold for other leaves, positions or roots
+                assert!(!verify_merkle_witness(Hash::from_u64_word(len), &witness, root));
+                assert!(
+                    !verify_merkle_witness(leaf, &MerkleWitness { index: witness.index ^ 1, ..witness.clone() }, root) || len == 1
+                );
+                assert!(!verify_merkle_witness(
+                    leaf,
+                    &MerkleWitness { index: witness.index + (1 << witness.siblings.len()), ..witness.clone() },
+                    root
+                ));
+                assert!(!verify_merkle_witness(leaf, &witness, ZERO_HASH));
+            }
+            assert_eq!(create_merkle_witness(hashes.iter().copied(), len as usize), None);
+        }
+    }
+}
@@ -8,6 +8,14 @@ pub enum TxScriptError {
     MalformedPush(usize, usize),
     #[error("transaction input index {0} >= {1}")]
     InvalidIndex(usize, usize),
+    #[error("transaction input index {0} out of range [0, {1})")]
+    InvalidInputIndex(i32, usize),
+    #[error("transaction output index {0} out of range [0, {1})")]
+    InvalidOutputIndex(i32, usize),
+    #[error("range of {1} bytes starting at {0} is out of bounds of an element of {2} bytes")]
+    OutOfBoundsRange(i32, i32, usize),
+    #[error("operands have mismatched sizes {0} and {1}")]
+    MismatchedOperandSizes(usize, usize),
     #[error("combined stack size {0} > max allowed {1}")]
     StackSizeExceeded(usize, usize),
     #[error("attempt to execute invalid opcode {0}")]
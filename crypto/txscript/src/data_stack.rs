@@ -143,7 +143,7 @@ impl<const LEN: usize> OpcodeData<SizedEncodeInt<LEN>> for Vec<u8> {
                 "numeric value encoded as {:x?} is {} bytes which exceeds the max allowed of {}",
                 self,
                 self.len(),
-                DEFAULT_SCRIPT_NUM_LEN
+                LEN
             ))),
             false => deserialize_i64(self).map(SizedEncodeInt::<LEN>),
         }
@@ -155,6 +155,12 @@ impl<const LEN: usize> OpcodeData<SizedEncodeInt<LEN>> for Vec<u8> {
     }
 }
 
+impl<const LEN: usize> From<SizedEncodeInt<LEN>> for i64 {
+    fn from(value: SizedEncodeInt<LEN>) -> Self {
+        value.0
+    }
+}
+
 impl OpcodeData<bool> for Vec<u8> {
     #[inline]
     fn deserialize(&self) -> Result<bool, TxScriptError> {
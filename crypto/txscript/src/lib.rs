@@ -41,6 +41,19 @@ pub const MAX_PUB_KEYS_PER_MUTLTISIG: i32 = 20;
 // Note that this includes OP_RESERVED which counts as a push operation.
 pub const NO_COST_OPCODE: u8 = 0x60;
 
+// Number of bytes a string or bitwise opcode may produce per operation it counts toward
+// the script operation limit, on top of the operation of the opcode itself.
+pub const STRING_OPCODE_BYTES_PER_OP: usize = 32;
+
+/// Script engine rules activated by consensus hardforks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EngineFlags {
+    /// Whether the transaction introspection opcodes are active
+    pub introspection_enabled: bool,
+    /// Whether the string and bitwise opcodes (OpCat, OpSubStr, OpLeft, OpRight, OpInvert, OpAnd, OpOr and OpXor) are active
+    pub string_opcodes_enabled: bool,
+}
+
 #[derive(Clone, Hash, PartialEq, Eq)]
 enum Signature {
     Secp256k1(secp256k1::schnorr::Signature),
@@ -80,6 +93,8 @@ pub struct TxScriptEngine<'a, T: VerifiableTransaction> {
     cond_stack: Vec<OpCond>, // Following if stacks, and whether it is running
 
     num_ops: i32,
+
+    flags: EngineFlags,
 }
 
 fn parse_script<T: VerifiableTransaction>(
@@ -152,6 +167,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             sig_cache,
             cond_stack: vec![],
             num_ops: 0,
+            flags: Default::default(),
         }
     }
 
@@ -162,6 +178,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
         utxo_entry: &'a UtxoEntry,
         reused_values: &'a mut SigHashReusedValues,
         sig_cache: &'a Cache<SigCacheKey, bool>,
+        flags: EngineFlags,
     ) -> Result<Self, TxScriptError> {
         let script_public_key = utxo_entry.script_public_key.script();
         // The script_public_key in P2SH is just validating the hash on the OpMultiSig script
@@ -176,6 +193,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 sig_cache,
                 cond_stack: Default::default(),
                 num_ops: 0,
+                flags,
             }),
             false => Err(TxScriptError::InvalidIndex(input_idx, tx.tx().inputs.len())),
         }
@@ -190,6 +208,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             sig_cache,
             cond_stack: Default::default(),
             num_ops: 0,
+            flags: Default::default(),
         }
     }
 
@@ -220,10 +239,19 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
         }
     }
 
+    /// Counts the extra operations of an opcode processing `len` bytes toward the script operation limit
+    fn count_data_ops(&mut self, len: usize) -> Result<(), TxScriptError> {
+        self.num_ops += (len / STRING_OPCODE_BYTES_PER_OP) as i32;
+        if self.num_ops > MAX_OPS_PER_SCRIPT {
+            return Err(TxScriptError::TooManyOperations(MAX_OPS_PER_SCRIPT));
+        }
+        Ok(())
+    }
+
     fn execute_script(&mut self, script: &[u8], verify_only_push: bool) -> Result<(), TxScriptError> {
         let script_result = parse_script(script).try_for_each(|opcode| {
             let opcode = opcode?;
-            if opcode.is_disabled() {
+            if opcode.is_disabled(&self.flags) {
                 return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
             }
 
@@ -561,8 +589,16 @@ mod tests {
 
             let populated_tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
 
-            let mut vm = TxScriptEngine::from_transaction_input(&populated_tx, &input, 0, &utxo_entry, &mut reused_values, &sig_cache)
-                .expect("Script creation failed");
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &populated_tx,
+                &input,
+                0,
+                &utxo_entry,
+                &mut reused_values,
+                &sig_cache,
+                Default::default(),
+            )
+            .expect("Script creation failed");
             assert_eq!(vm.execute(), test.expected_result);
         }
     }
@@ -912,6 +948,45 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_introspection_amount_covenant() {
+        use crate::opcodes::codes::{OpFalse, OpGreaterThanOrEqual, OpSub, OpTxInputAmount, OpTxInputIndex, OpTxOutputAmount};
+        use crate::script_builder::ScriptBuilder;
+        use kaspa_consensus_core::constants::SOMPI_PER_RUSTWEAVE;
+
+        // The first output must carry the spent amount minus a fee of at most 1 RWE
+        let mut builder = ScriptBuilder::new();
+        builder
+            .add_ops(&[OpFalse, OpTxOutputAmount, OpTxInputIndex, OpTxInputAmount])
+            .unwrap()
+            .add_i64(SOMPI_PER_RUSTWEAVE as i64)
+            .unwrap()
+            .add_ops(&[OpSub, OpGreaterThanOrEqual])
+            .unwrap();
+        let script_public_key = ScriptPublicKey::new(0, builder.drain().into());
+
+        let sig_cache = Cache::new(10_000);
+        let mut reused_values = SigHashReusedValues::new();
+        let mut run = |output_amount: u64, introspection_enabled: bool| {
+            let input = TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(1), 0), vec![], 0, 0);
+            let output = TransactionOutput::new(output_amount, ScriptPublicKey::default());
+            let tx = Transaction::new(1, vec![input.clone()], vec![output], 0, Default::default(), 0, vec![]);
+            // Amounts well above the 4 bytes of a default script number
+            let utxo_entry = UtxoEntry::new(50 * SOMPI_PER_RUSTWEAVE, script_public_key.clone(), 0, false);
+            let populated_tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+            let flags = EngineFlags { introspection_enabled, ..Default::default() };
+            let mut vm =
+                TxScriptEngine::from_transaction_input(&populated_tx, &input, 0, &utxo_entry, &mut reused_values, &sig_cache, flags)
+                    .expect("Script creation failed");
+            vm.execute()
+        };
+
+        assert_eq!(run(49 * SOMPI_PER_RUSTWEAVE, true), Ok(()));
+        assert_eq!(run(49 * SOMPI_PER_RUSTWEAVE + 1, true), Ok(()));
+        assert_eq!(run(49 * SOMPI_PER_RUSTWEAVE - 1, true), Err(TxScriptError::EvalFalse));
+        assert!(matches!(run(49 * SOMPI_PER_RUSTWEAVE, false), Err(TxScriptError::InvalidOpcode(_))));
+    }
 }
 
 #[cfg(test)]
@@ -1023,6 +1098,7 @@ mod bitcoind_tests {
                 &populated_tx.entries[0],
                 &mut reused_values,
                 &sig_cache,
+                Default::default(),
             )
             .map_err(UnifiedError::TxScriptError)?;
             vm.execute().map_err(UnifiedError::TxScriptError)
@@ -3,15 +3,16 @@ use core::mem::size_of;
 #[macro_use]
 mod macros;
 
-use crate::data_stack::{DataStack, OpcodeData};
+use crate::data_stack::{DataStack, OpcodeData, SizedEncodeInt};
 use crate::{
-    ScriptSource, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD, MAX_TX_IN_SEQUENCE_NUM, NO_COST_OPCODE,
-    SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK,
+    EngineFlags, ScriptSource, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD, MAX_SCRIPT_ELEMENT_SIZE, MAX_TX_IN_SEQUENCE_NUM,
+    NO_COST_OPCODE, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK,
 };
 use blake2b_simd::Params;
 use core::cmp::{max, min};
+use core::ops::Range;
 use kaspa_consensus_core::hashing::sighash_type::SigHashType;
-use kaspa_consensus_core::tx::VerifiableTransaction;
+use kaspa_consensus_core::tx::{ScriptPublicKey, VerifiableTransaction};
 use sha2::{Digest, Sha256};
 use std::fmt::{Debug, Formatter};
 
@@ -65,7 +66,7 @@ pub trait OpCodeMetadata: Debug {
     // For push data- check if we can use shorter encoding
     fn check_minimal_data_push(&self) -> Result<(), TxScriptError>;
 
-    fn is_disabled(&self) -> bool;
+    fn is_disabled(&self, flags: &EngineFlags) -> bool;
     fn always_illegal(&self) -> bool;
     fn is_push_opcode(&self) -> bool;
     fn get_data(&self) -> &[u8];
@@ -103,25 +104,19 @@ impl<const CODE: u8> OpCodeMetadata for OpCode<CODE> {
         CODE
     }
 
-    fn is_disabled(&self) -> bool {
-        matches!(
-            CODE,
+    fn is_disabled(&self, flags: &EngineFlags) -> bool {
+        match CODE {
             codes::OpCat
-                | codes::OpSubStr
-                | codes::OpLeft
-                | codes::OpRight
-                | codes::OpInvert
-                | codes::OpAnd
-                | codes::OpOr
-                | codes::OpXor
-                | codes::Op2Mul
-                | codes::Op2Div
-                | codes::OpMul
-                | codes::OpDiv
-                | codes::OpMod
-                | codes::OpLShift
-                | codes::OpRShift
-        )
+            | codes::OpSubStr
+            | codes::OpLeft
+            | codes::OpRight
+            | codes::OpInvert
+            | codes::OpAnd
+            | codes::OpOr
+            | codes::OpXor => !flags.string_opcodes_enabled,
+            codes::Op2Mul | codes::Op2Div | codes::OpMul | codes::OpDiv | codes::OpMod | codes::OpLShift | codes::OpRShift => true,
+            _ => false,
+        }
     }
 
     fn always_illegal(&self) -> bool {
@@ -206,6 +201,86 @@ fn push_number<T: VerifiableTransaction>(number: i64, vm: &mut TxScriptEngine<T>
     Ok(())
 }
 
+/// Pops the operands of a numeric opcode. Once transaction introspection is active, operands may be up to 8 bytes
+/// long so that amounts in sompi can be compared and combined; before that, they are limited to the default 4 bytes.
+#[inline]
+fn pop_numbers<const SIZE: usize, T: VerifiableTransaction>(vm: &mut TxScriptEngine<T>) -> Result<[i64; SIZE], TxScriptError> {
+    if vm.flags.introspection_enabled {
+        let numbers: [SizedEncodeInt<8>; SIZE] = vm.dstack.pop_items()?;
+        Ok(numbers.map(i64::from))
+    } else {
+        vm.dstack.pop_items()
+    }
+}
+
+/// Returns the transaction being verified and the index of the input being executed, provided that
+/// transaction introspection is active. Until then, introspection opcodes are invalid, as they used to be.
+#[inline]
+fn introspected_tx<'a, T: VerifiableTransaction>(
+    opcode: &impl Debug,
+    vm: &TxScriptEngine<'a, T>,
+) -> Result<(&'a T, usize), TxScriptError> {
+    if !vm.flags.introspection_enabled {
+        return Err(TxScriptError::InvalidOpcode(format!("{opcode:?}")));
+    }
+    match vm.script_source {
+        ScriptSource::TxInput { tx, id, .. } => Ok((tx, id)),
+        _ => Err(TxScriptError::InvalidSource(format!("{opcode:?} only applies to transaction inputs"))),
+    }
+}
+
+#[inline]
+fn pop_input_index<T: VerifiableTransaction>(tx: &T, vm: &mut TxScriptEngine<T>) -> Result<usize, TxScriptError> {
+    let [index]: [i32; 1] = vm.dstack.pop_items()?;
+    usize::try_from(index)
+        .ok()
+        .filter(|&index| index < tx.inputs().len())
+        .ok_or(TxScriptError::InvalidInputIndex(index, tx.inputs().len()))
+}
+
+#[inline]
+fn pop_output_index<T: VerifiableTransaction>(tx: &T, vm: &mut TxScriptEngine<T>) -> Result<usize, TxScriptError> {
+    let [index]: [i32; 1] = vm.dstack.pop_items()?;
+    usize::try_from(index)
+        .ok()
+        .filter(|&index| index < tx.outputs().len())
+        .ok_or(TxScriptError::InvalidOutputIndex(index, tx.outputs().len()))
+}
+
+#[inline]
+fn check_string_opcodes_enabled<T: VerifiableTransaction>(opcode: &impl Debug, vm: &TxScriptEngine<T>) -> OpCodeResult {
+    match vm.flags.string_opcodes_enabled {
+        true => Ok(()),
+        false => Err(TxScriptError::OpcodeDisabled(format!("{opcode:?}"))),
+    }
+}
+
+/// Returns the range of `size` bytes starting at `begin`, provided it lies within an element of `len` bytes
+#[inline]
+fn substring_range(begin: i32, size: i32, len: usize) -> Result<Range<usize>, TxScriptError> {
+    match (usize::try_from(begin), usize::try_from(size)) {
+        (Ok(start), Ok(size)) if start.checked_add(size).is_some_and(|end| end <= len) => Ok(start..start + size),
+        _ => Err(TxScriptError::OutOfBoundsRange(begin, size, len)),
+    }
+}
+
+/// Pops two elements of equal size and pushes the result of applying `op` to each pair of their bytes
+#[inline]
+fn bitwise_op<T: VerifiableTransaction>(vm: &mut TxScriptEngine<T>, op: impl Fn(u8, u8) -> u8) -> OpCodeResult {
+    let [first, second] = vm.dstack.pop_raw()?;
+    if first.len() != second.len() {
+        return Err(TxScriptError::MismatchedOperandSizes(first.len(), second.len()));
+    }
+    vm.count_data_ops(first.len())?;
+    push_data(first.into_iter().zip(second).map(|(a, b)| op(a, b)).collect(), vm)
+}
+
+/// Serializes a script public key as its big endian version followed by its script
+#[inline]
+fn script_public_key_bytes(script_public_key: &ScriptPublicKey) -> Vec<u8> {
+    [script_public_key.version().to_be_bytes().as_slice(), script_public_key.script()].concat()
+}
+
 /*
 The following is the implementation and metadata of all opcodes. Each opcode has unique
 number (and template system makes it impossible to use two opcodes), length specification,
@@ -505,10 +580,43 @@ opcode_list! {
     }
 
     // Splice opcodes.
-    opcode OpCat<0x7e, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpSubStr<0x7f, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpLeft<0x80, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpRight<0x81, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+    opcode OpCat<0x7e, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        let [first, second] = vm.dstack.pop_raw()?;
+        let len = first.len() + second.len();
+        if len > MAX_SCRIPT_ELEMENT_SIZE {
+            return Err(TxScriptError::ElementTooBig(len, MAX_SCRIPT_ELEMENT_SIZE));
+        }
+        vm.count_data_ops(len)?;
+        push_data([first, second].concat(), vm)
+    }
+
+    opcode OpSubStr<0x7f, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        let [begin, size]: [i32; 2] = vm.dstack.pop_items()?;
+        let [data] = vm.dstack.pop_raw()?;
+        let range = substring_range(begin, size, data.len())?;
+        vm.count_data_ops(range.len())?;
+        push_data(data[range].to_vec(), vm)
+    }
+
+    opcode OpLeft<0x80, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        let [size]: [i32; 1] = vm.dstack.pop_items()?;
+        let [data] = vm.dstack.pop_raw()?;
+        let range = substring_range(0, size, data.len())?;
+        vm.count_data_ops(range.len())?;
+        push_data(data[range].to_vec(), vm)
+    }
+
+    opcode OpRight<0x81, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        let [size]: [i32; 1] = vm.dstack.pop_items()?;
+        let [data] = vm.dstack.pop_raw()?;
+        let size = substring_range(0, size, data.len())?.len();
+        vm.count_data_ops(size)?;
+        push_data(data[data.len() - size..].to_vec(), vm)
+    }
 
     opcode OpSize<0x82, 1>(self, vm) {
         match vm.dstack.last() {
@@ -521,10 +629,27 @@ opcode_list! {
     }
 
     // Bitwise logic opcodes.
-    opcode OpInvert<0x83, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpAnd<0x84, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpOr<0x85, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpXor<0x86, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+    opcode OpInvert<0x83, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        let [data] = vm.dstack.pop_raw()?;
+        vm.count_data_ops(data.len())?;
+        push_data(data.into_iter().map(|byte| !byte).collect(), vm)
+    }
+
+    opcode OpAnd<0x84, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        bitwise_op(vm, |a, b| a & b)
+    }
+
+    opcode OpOr<0x85, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        bitwise_op(vm, |a, b| a | b)
+    }
+
+    opcode OpXor<0x86, 1>(self, vm) {
+        check_string_opcodes_enabled(self, vm)?;
+        bitwise_op(vm, |a, b| a ^ b)
+    }
 
     opcode OpEqual<0x87, 1>(self, vm) {
         match vm.dstack.len() >= 2 {
@@ -558,14 +683,14 @@ opcode_list! {
 
     // Numeric related opcodes.
     opcode Op1Add<0x8b, 1>(self, vm) {
-        let [value]: [i64; 1] = vm.dstack.pop_items()?;
-        vm.dstack.push_item(value + 1);
+        let [value]: [i64; 1] = pop_numbers(vm)?;
+        vm.dstack.push_item(value.checked_add(1).ok_or_else(|| TxScriptError::NumberTooBig(format!("{self:?} overflowed")))?);
         Ok(())
     }
 
     opcode Op1Sub<0x8c, 1>(self, vm) {
-        let [value]: [i64; 1] = vm.dstack.pop_items()?;
-        vm.dstack.push_item(value - 1);
+        let [value]: [i64; 1] = pop_numbers(vm)?;
+        vm.dstack.push_item(value.checked_sub(1).ok_or_else(|| TxScriptError::NumberTooBig(format!("{self:?} overflowed")))?);
         Ok(())
     }
 
@@ -573,38 +698,38 @@ opcode_list! {
     opcode Op2Div<0x8e, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
 
     opcode OpNegate<0x8f, 1>(self, vm) {
-        let [value]: [i64; 1] = vm.dstack.pop_items()?;
+        let [value]: [i64; 1] = pop_numbers(vm)?;
         vm.dstack.push_item(-value);
         Ok(())
     }
 
     opcode OpAbs<0x90, 1>(self, vm) {
-        let [m]: [i64; 1] = vm.dstack.pop_items()?;
+        let [m]: [i64; 1] = pop_numbers(vm)?;
         vm.dstack.push_item(m.abs());
         Ok(())
     }
 
     opcode OpNot<0x91, 1>(self, vm) {
-        let [m]: [i64; 1] = vm.dstack.pop_items()?;
+        let [m]: [i64; 1] = pop_numbers(vm)?;
         vm.dstack.push_item((m == 0) as i64);
         Ok(())
     }
 
     opcode Op0NotEqual<0x92, 1>(self, vm) {
-        let [m]: [i64; 1] = vm.dstack.pop_items()?;
+        let [m]: [i64; 1] = pop_numbers(vm)?;
         vm.dstack.push_item((m != 0) as i64 );
         Ok(())
     }
 
     opcode OpAdd<0x93, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
-        vm.dstack.push_item(a+b);
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
+        vm.dstack.push_item(a.checked_add(b).ok_or_else(|| TxScriptError::NumberTooBig(format!("{self:?} overflowed")))?);
         Ok(())
     }
 
     opcode OpSub<0x94, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
-        vm.dstack.push_item(a-b);
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
+        vm.dstack.push_item(a.checked_sub(b).ok_or_else(|| TxScriptError::NumberTooBig(format!("{self:?} overflowed")))?);
         Ok(())
     }
 
@@ -615,25 +740,25 @@ opcode_list! {
     opcode OpRShift<0x99, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
 
     opcode OpBoolAnd<0x9a, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item(((a != 0) && (b != 0)) as i64);
         Ok(())
     }
 
     opcode OpBoolOr<0x9b, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item(((a != 0) || (b != 0)) as i64);
         Ok(())
     }
 
     opcode OpNumEqual<0x9c, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item((a == b) as i64);
         Ok(())
     }
 
     opcode OpNumEqualVerify<0x9d, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         match a == b {
             true => Ok(()),
             false => Err(TxScriptError::VerifyError)
@@ -641,49 +766,49 @@ opcode_list! {
     }
 
     opcode OpNumNotEqual<0x9e, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item((a != b) as i64);
         Ok(())
     }
 
     opcode OpLessThan<0x9f, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item((a < b) as i64);
         Ok(())
     }
 
     opcode OpGreaterThan<0xa0, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item((a > b) as i64);
         Ok(())
     }
 
     opcode OpLessThanOrEqual<0xa1, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item((a <= b) as i64);
         Ok(())
     }
 
     opcode OpGreaterThanOrEqual<0xa2, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item((a >= b) as i64);
         Ok(())
     }
 
     opcode OpMin<0xa3, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item(min(a,b));
         Ok(())
     }
 
     opcode OpMax<0xa4, 1>(self, vm) {
-        let [a,b]: [i64; 2] = vm.dstack.pop_items()?;
+        let [a,b]: [i64; 2] = pop_numbers(vm)?;
         vm.dstack.push_item(max(a,b));
         Ok(())
     }
 
     opcode OpWithin<0xa5, 1>(self, vm) {
-        let [x,l,u]: [i64; 3] = vm.dstack.pop_items()?;
+        let [x,l,u]: [i64; 3] = pop_numbers(vm)?;
         vm.dstack.push_item((x >= l && x < u) as i64);
         Ok(())
     }
@@ -876,25 +1001,81 @@ opcode_list! {
         }
     }
 
-    // Undefined opcodes.
-    opcode OpUnknown178<0xb2, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown179<0xb3, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown180<0xb4, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown181<0xb5, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
+    // Transaction introspection opcodes, active from the introspection activation DAA score.
+    opcode OpTxVersion<0xb2, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        push_number(tx.tx().version as i64, vm)
+    }
+
+    opcode OpTxInputCount<0xb3, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        push_number(tx.inputs().len() as i64, vm)
+    }
+
+    opcode OpTxOutputCount<0xb4, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        push_number(tx.outputs().len() as i64, vm)
+    }
+
+    opcode OpTxLockTime<0xb5, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        let lock_time = i64::try_from(tx.tx().lock_time)
+            .map_err(|_| TxScriptError::NumberTooBig(format!("lock time {} does not fit a script number", tx.tx().lock_time)))?;
+        push_number(lock_time, vm)
+    }
+
     opcode OpUnknown182<0xb6, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
     opcode OpUnknown183<0xb7, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
     opcode OpUnknown184<0xb8, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown185<0xb9, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown186<0xba, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown187<0xbb, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
+
+    opcode OpTxInputIndex<0xb9, 1>(self, vm) {
+        let (_, id) = introspected_tx(self, vm)?;
+        push_number(id as i64, vm)
+    }
+
+    opcode OpOutpointTxId<0xba, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        let index = pop_input_index(tx, vm)?;
+        push_data(tx.inputs()[index].previous_outpoint.transaction_id.as_bytes().to_vec(), vm)
+    }
+
+    opcode OpOutpointIndex<0xbb, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        let index = pop_input_index(tx, vm)?;
+        push_number(tx.inputs()[index].previous_outpoint.index as i64, vm)
+    }
+
     opcode OpUnknown188<0xbc, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
     opcode OpUnknown189<0xbd, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown190<0xbe, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown191<0xbf, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
+
+    opcode OpTxInputAmount<0xbe, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        let index = pop_input_index(tx, vm)?;
+        push_number(tx.populated_input(index).1.amount as i64, vm)
+    }
+
+    opcode OpTxInputSpk<0xbf, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        let index = pop_input_index(tx, vm)?;
+        push_data(script_public_key_bytes(&tx.populated_input(index).1.script_public_key), vm)
+    }
+
     opcode OpUnknown192<0xc0, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
     opcode OpUnknown193<0xc1, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown194<0xc2, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
-    opcode OpUnknown195<0xc3, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
+
+    opcode OpTxOutputAmount<0xc2, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        let index = pop_output_index(tx, vm)?;
+        push_number(tx.outputs()[index].value as i64, vm)
+    }
+
+    opcode OpTxOutputSpk<0xc3, 1>(self, vm) {
+        let (tx, _) = introspected_tx(self, vm)?;
+        let index = pop_output_index(tx, vm)?;
+        push_data(script_public_key_bytes(&tx.outputs()[index].script_public_key), vm)
+    }
+
+    // Undefined opcodes.
     opcode OpUnknown196<0xc4, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
     opcode OpUnknown197<0xc5, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
     opcode OpUnknown198<0xc6, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
@@ -975,7 +1156,10 @@ mod test {
     use crate::caches::Cache;
     use crate::data_stack::Stack;
     use crate::opcodes::{OpCodeExecution, OpCodeImplementation};
-    use crate::{opcodes, pay_to_address_script, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD};
+    use crate::{
+        opcodes, pay_to_address_script, EngineFlags, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD, MAX_SCRIPT_ELEMENT_SIZE,
+        STRING_OPCODE_BYTES_PER_OP,
+    };
     use kaspa_addresses::{Address, Prefix, Version};
     use kaspa_consensus_core::constants::{SOMPI_PER_RUSTWEAVE, TX_VERSION};
     use kaspa_consensus_core::hashing::sighash::SigHashReusedValues;
@@ -1085,24 +1269,24 @@ mod test {
         let tests: Vec<Box<dyn OpCodeImplementation<PopulatedTransaction>>> = vec![
             opcodes::OpUnknown166::empty().expect("Should accept empty"),
             opcodes::OpUnknown167::empty().expect("Should accept empty"),
-            opcodes::OpUnknown178::empty().expect("Should accept empty"),
-            opcodes::OpUnknown179::empty().expect("Should accept empty"),
-            opcodes::OpUnknown180::empty().expect("Should accept empty"),
-            opcodes::OpUnknown181::empty().expect("Should accept empty"),
+            opcodes::OpTxVersion::empty().expect("Should accept empty"),
+            opcodes::OpTxInputCount::empty().expect("Should accept empty"),
+            opcodes::OpTxOutputCount::empty().expect("Should accept empty"),
+            opcodes::OpTxLockTime::empty().expect("Should accept empty"),
             opcodes::OpUnknown182::empty().expect("Should accept empty"),
             opcodes::OpUnknown183::empty().expect("Should accept empty"),
             opcodes::OpUnknown184::empty().expect("Should accept empty"),
-            opcodes::OpUnknown185::empty().expect("Should accept empty"),
-            opcodes::OpUnknown186::empty().expect("Should accept empty"),
-            opcodes::OpUnknown187::empty().expect("Should accept empty"),
+            opcodes::OpTxInputIndex::empty().expect("Should accept empty"),
+            opcodes::OpOutpointTxId::empty().expect("Should accept empty"),
+            opcodes::OpOutpointIndex::empty().expect("Should accept empty"),
             opcodes::OpUnknown188::empty().expect("Should accept empty"),
             opcodes::OpUnknown189::empty().expect("Should accept empty"),
-            opcodes::OpUnknown190::empty().expect("Should accept empty"),
-            opcodes::OpUnknown191::empty().expect("Should accept empty"),
+            opcodes::OpTxInputAmount::empty().expect("Should accept empty"),
+            opcodes::OpTxInputSpk::empty().expect("Should accept empty"),
             opcodes::OpUnknown192::empty().expect("Should accept empty"),
             opcodes::OpUnknown193::empty().expect("Should accept empty"),
-            opcodes::OpUnknown194::empty().expect("Should accept empty"),
-            opcodes::OpUnknown195::empty().expect("Should accept empty"),
+            opcodes::OpTxOutputAmount::empty().expect("Should accept empty"),
+            opcodes::OpTxOutputSpk::empty().expect("Should accept empty"),
             opcodes::OpUnknown196::empty().expect("Should accept empty"),
             opcodes::OpUnknown197::empty().expect("Should accept empty"),
             opcodes::OpUnknown198::empty().expect("Should accept empty"),
@@ -2753,8 +2937,16 @@ mod test {
         ] {
             let mut tx = base_tx.clone();
             tx.0.lock_time = tx_lock_time;
-            let mut vm = TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &mut reused_values, &sig_cache)
-                .expect("Shouldn't fail");
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &input,
+                0,
+                &utxo_entry,
+                &mut reused_values,
+                &sig_cache,
+                Default::default(),
+            )
+            .expect("Shouldn't fail");
             vm.dstack = vec![lock_time.clone()];
             match code.execute(&mut vm) {
                 // Message is based on the should_fail values
@@ -2796,8 +2988,16 @@ mod test {
         ] {
             let mut input = base_input.clone();
             input.sequence = tx_sequence;
-            let mut vm = TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &mut reused_values, &sig_cache)
-                .expect("Shouldn't fail");
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &input,
+                0,
+                &utxo_entry,
+                &mut reused_values,
+                &sig_cache,
+                Default::default(),
+            )
+            .expect("Shouldn't fail");
             vm.dstack = vec![sequence.clone()];
             match code.execute(&mut vm) {
                 // Message is based on the should_fail values
@@ -2816,6 +3016,174 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_string_opcodes() {
+        let cache = Cache::new(10_000);
+        let mut reused_values = SigHashReusedValues::new();
+        let run = |code: Box<dyn OpCodeImplementation<PopulatedTransaction>>, init: Stack, reused_values: &mut SigHashReusedValues| {
+            let mut vm = TxScriptEngine::new(reused_values, &cache);
+            vm.flags.string_opcodes_enabled = true;
+            vm.dstack = init;
+            code.execute(&mut vm).map(|_| (vm.dstack.clone(), vm.num_ops))
+        };
+
+        let big = vec![0xa5u8; MAX_SCRIPT_ELEMENT_SIZE];
+        type StringOpcodeTestCase =
+            (Box<dyn OpCodeImplementation<PopulatedTransaction<'static>>>, Stack, Result<Stack, TxScriptError>);
+        let tests: Vec<StringOpcodeTestCase> = vec![
+            (opcodes::OpCat::empty().unwrap(), vec![vec![1, 2], vec![3]], Ok(vec![vec![1, 2, 3]])),
+            (opcodes::OpCat::empty().unwrap(), vec![vec![], vec![]], Ok(vec![vec![]])),
+            (opcodes::OpCat::empty().unwrap(), vec![big.clone(), vec![1]], Err(TxScriptError::ElementTooBig(521, 520))),
+            (opcodes::OpCat::empty().unwrap(), vec![vec![1]], Err(TxScriptError::InvalidStackOperation(2, 1))),
+            (opcodes::OpSubStr::empty().unwrap(), vec![vec![1, 2, 3, 4], vec![1], vec![2]], Ok(vec![vec![2, 3]])),
+            (opcodes::OpSubStr::empty().unwrap(), vec![vec![1, 2, 3, 4], vec![4], vec![]], Ok(vec![vec![]])),
+            (
+                opcodes::OpSubStr::empty().unwrap(),
+                vec![vec![1, 2, 3, 4], vec![3], vec![2]],
+                Err(TxScriptError::OutOfBoundsRange(3, 2, 4)),
+            ),
+            (
+                opcodes::OpSubStr::empty().unwrap(),
+                vec![vec![1, 2], vec![0x81], vec![1]],
+                Err(TxScriptError::OutOfBoundsRange(-1, 1, 2)),
+            ),
+            (opcodes::OpLeft::empty().unwrap(), vec![vec![1, 2, 3], vec![2]], Ok(vec![vec![1, 2]])),
+            (opcodes::OpLeft::empty().unwrap(), vec![vec![1, 2, 3], vec![4]], Err(TxScriptError::OutOfBoundsRange(0, 4, 3))),
+            (opcodes::OpRight::empty().unwrap(), vec![vec![1, 2, 3], vec![2]], Ok(vec![vec![2, 3]])),
+            (opcodes::OpRight::empty().unwrap(), vec![vec![1, 2, 3], vec![0x81]], Err(TxScriptError::OutOfBoundsRange(0, -1, 3))),
+            (opcodes::OpInvert::empty().unwrap(), vec![vec![0x0f, 0xff]], Ok(vec![vec![0xf0, 0x00]])),
+            (opcodes::OpAnd::empty().unwrap(), vec![vec![0x0f, 0x33], vec![0xff, 0x0f]], Ok(vec![vec![0x0f, 0x03]])),
+            (opcodes::OpOr::empty().unwrap(), vec![vec![0x0f, 0x30], vec![0xf0, 0x03]], Ok(vec![vec![0xff, 0x33]])),
+            (opcodes::OpXor::empty().unwrap(), vec![vec![0xff, 0x0f], vec![0x0f, 0x0f]], Ok(vec![vec![0xf0, 0x00]])),
+            (opcodes::OpXor::empty().unwrap(), vec![vec![0xff], vec![0x0f, 0x0f]], Err(TxScriptError::MismatchedOperandSizes(1, 2))),
+        ];
+        for (code, init, expected) in tests {
+            let value = code.value();
+            assert_eq!(
+                run(code, init, &mut reused_values).map(|(dstack, _)| dstack),
+                expected,
+                "Opcode {value} returned an unexpected result"
+            );
+        }
+
+        // Large elements count extra operations toward the script limit
+        let (_, num_ops) = run(opcodes::OpInvert::empty().unwrap(), vec![big], &mut reused_values).unwrap();
+        assert_eq!(num_ops, (MAX_SCRIPT_ELEMENT_SIZE / STRING_OPCODE_BYTES_PER_OP) as i32);
+    }
+
+    #[test]
+    fn test_introspection_opcodes() {
+        let spk = |script: &[u8]| ScriptPublicKey::new(0, script.iter().copied().collect());
+        let outpoint = TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(7), 3);
+        let inputs = vec![
+            TransactionInput::new(TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(1), 0), vec![], 0, 0),
+            TransactionInput::new(outpoint, vec![], 0, 0),
+        ];
+        let outputs = vec![TransactionOutput::new(1000, spk(&[0x51])), TransactionOutput::new(2000, spk(&[0x52, 0x53]))];
+        let tx = Transaction::new(1, inputs, outputs, 42, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let entries = vec![UtxoEntry::new(3000, spk(&[0x54]), 0, false), UtxoEntry::new(500, spk(&[0x55]), 0, false)];
+        let populated_tx = PopulatedTransaction::new(&tx, entries);
+
+        let sig_cache = Cache::new(10_000);
+        let mut reused_values = SigHashReusedValues::new();
+
+        type Op<'a> = Box<dyn OpCodeImplementation<PopulatedTransaction<'a>>>;
+        let tests: Vec<(Op, Stack, Result<Stack, TxScriptError>)> = vec![
+            (opcodes::OpTxVersion::empty().unwrap(), vec![], Ok(vec![vec![1]])),
+            (opcodes::OpTxInputCount::empty().unwrap(), vec![], Ok(vec![vec![2]])),
+            (opcodes::OpTxOutputCount::empty().unwrap(), vec![], Ok(vec![vec![2]])),
+            (opcodes::OpTxLockTime::empty().unwrap(), vec![], Ok(vec![vec![42]])),
+            (opcodes::OpTxInputIndex::empty().unwrap(), vec![], Ok(vec![vec![1]])),
+            (opcodes::OpOutpointTxId::empty().unwrap(), vec![vec![1]], Ok(vec![outpoint.transaction_id.as_bytes().to_vec()])),
+            (opcodes::OpOutpointIndex::empty().unwrap(), vec![vec![1]], Ok(vec![vec![3]])),
+            (opcodes::OpTxInputAmount::empty().unwrap(), vec![vec![]], Ok(vec![vec![0xb8, 0x0b]])),
+            (opcodes::OpTxInputSpk::empty().unwrap(), vec![vec![1]], Ok(vec![vec![0, 0, 0x55]])),
+            (opcodes::OpTxOutputAmount::empty().unwrap(), vec![vec![1]], Ok(vec![vec![0xd0, 0x07]])),
+            (opcodes::OpTxOutputSpk::empty().unwrap(), vec![vec![1]], Ok(vec![vec![0, 0, 0x52, 0x53]])),
+            (opcodes::OpTxInputAmount::empty().unwrap(), vec![vec![2]], Err(TxScriptError::InvalidInputIndex(2, 2))),
+            (opcodes::OpOutpointIndex::empty().unwrap(), vec![vec![0x81]], Err(TxScriptError::InvalidInputIndex(-1, 2))),
+            (opcodes::OpTxOutputSpk::empty().unwrap(), vec![vec![2]], Err(TxScriptError::InvalidOutputIndex(2, 2))),
+            (opcodes::OpTxOutputAmount::empty().unwrap(), vec![], Err(TxScriptError::InvalidStackOperation(1, 0))),
+        ];
+
+        for (code, init, expected) in tests {
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &populated_tx,
+                &populated_tx.tx().inputs[1],
+                1,
+                &populated_tx.entries[1],
+                &mut reused_values,
+                &sig_cache,
+                EngineFlags { introspection_enabled: true, ..Default::default() },
+            )
+            .expect("Shouldn't fail");
+            vm.dstack = init;
+            let result = code.execute(&mut vm).map(|_| vm.dstack.clone());
+            assert_eq!(result, expected, "Opcode {} returned an unexpected result", code.value());
+        }
+    }
+
+    #[test]
+    fn test_numeric_opcodes_operand_size() {
+        let sig_cache = Cache::new(10_000);
+        let mut reused_values = SigHashReusedValues::new();
+        // 5_000_000_000 and 4_000_000_000, both exceeding the default 4 bytes of a script number
+        let five = vec![0x00, 0xf2, 0x05, 0x2a, 0x01];
+        let four = vec![0x00, 0x28, 0x6b, 0xee, 0x00];
+        let max = vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f];
+
+        type Op = Box<dyn OpCodeImplementation<PopulatedTransaction<'static>>>;
+        let tests: Vec<(Op, Stack, bool, Result<Stack, TxScriptError>)> = vec![
+            (opcodes::OpGreaterThanOrEqual::empty().unwrap(), vec![five.clone(), four.clone()], true, Ok(vec![vec![1]])),
+            (opcodes::OpSub::empty().unwrap(), vec![five.clone(), four.clone()], true, Ok(vec![vec![0x00, 0xca, 0x9a, 0x3b]])),
+            (opcodes::OpAdd::empty().unwrap(), vec![max.clone(), vec![]], true, Ok(vec![max.clone()])),
+            (opcodes::OpAdd::empty().unwrap(), vec![max.clone(), vec![1]], true, Err(TxScriptError::NumberTooBig(String::new()))),
+            (opcodes::Op1Add::empty().unwrap(), vec![max.clone()], true, Err(TxScriptError::NumberTooBig(String::new()))),
+            (opcodes::OpNumEqual::empty().unwrap(), vec![vec![0; 9], vec![]], true, Err(TxScriptError::InvalidState(String::new()))),
+            (
+                opcodes::OpGreaterThanOrEqual::empty().unwrap(),
+                vec![five, four],
+                false,
+                Err(TxScriptError::NumberTooBig(String::new())),
+            ),
+        ];
+
+        for (code, init, introspection_enabled, expected) in tests {
+            let mut vm = TxScriptEngine::new(&mut reused_values, &sig_cache);
+            vm.flags.introspection_enabled = introspection_enabled;
+            vm.dstack = init;
+            match (code.execute(&mut vm).map(|_| vm.dstack.clone()), expected) {
+                (Ok(dstack), Ok(expected)) => assert_eq!(dstack, expected, "Opcode {} returned an unexpected result", code.value()),
+                (Err(err), Err(expected)) => assert_eq!(
+                    std::mem::discriminant(&err),
+                    std::mem::discriminant(&expected),
+                    "Opcode {} returned an unexpected error {err}",
+                    code.value()
+                ),
+                (result, expected) => panic!("Opcode {} returned {result:?} instead of {expected:?}", code.value()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_introspection_opcodes_inactive() {
+        let (tx, input, utxo_entry) = make_mock_transaction(1);
+        let sig_cache = Cache::new(10_000);
+        let mut reused_values = SigHashReusedValues::new();
+
+        // Before activation, the introspection opcodes remain invalid
+        let code = opcodes::OpTxInputCount::empty().expect("Should accept empty");
+        let mut vm =
+            TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &mut reused_values, &sig_cache, Default::default())
+                .expect("Shouldn't fail");
+        assert!(matches!(code.execute(&mut vm), Err(TxScriptError::InvalidOpcode(_))));
+
+        // Once active, they still only apply to transaction inputs
+        let mut vm = TxScriptEngine::new(&mut reused_values, &sig_cache);
+        vm.flags.introspection_enabled = true;
+        assert!(matches!(code.execute(&mut vm), Err(TxScriptError::InvalidSource(_))));
+    }
+
     #[test]
     fn test_opreturn() {
         run_error_test_cases(vec![ErrorTestCase {
@@ -2,7 +2,7 @@ use crate::{cache::CachePolicy, db::DB, errors::StoreError};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use kaspa_utils::mem_size::MemSizeEstimator;
-use rocksdb::{Direction, IterateBounds, IteratorMode, ReadOptions};
+use rocksdb::IterateBounds;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{collections::hash_map::RandomState, error::Error, hash::BuildHasher, sync::Arc};
 
@@ -71,16 +71,12 @@ where
         TData: DeserializeOwned, // We need `DeserializeOwned` since the slice coming from `db.get_pinned` has short lifetime
     {
         let prefix_key = DbKey::prefix_only(&self.prefix);
-        let mut read_opts = ReadOptions::default();
-        read_opts.set_iterate_range(rocksdb::PrefixRange(prefix_key.as_ref()));
-        self.db.iterator_opt(IteratorMode::From(prefix_key.as_ref(), Direction::Forward), read_opts).map(move |iter_result| {
-            match iter_result {
-                Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
-                    Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
-                    Err(e) => Err(e.into()),
-                },
+        self.db.prefix_iterator(&prefix_key, None).map(move |iter_result| match iter_result {
+            Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
+                Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
                 Err(e) => Err(e.into()),
-            }
+            },
+            Err(e) => Err(e.into()),
         })
     }
 
@@ -187,15 +183,8 @@ where
             },
         );
 
-        let mut read_opts = ReadOptions::default();
-        read_opts.set_iterate_range(rocksdb::PrefixRange(db_key.as_ref()));
-
-        let mut db_iterator = match seek_from {
-            Some(seek_key) => {
-                self.db.iterator_opt(IteratorMode::From(DbKey::new(&self.prefix, seek_key).as_ref(), Direction::Forward), read_opts)
-            }
-            None => self.db.iterator_opt(IteratorMode::Start, read_opts),
-        };
+        let seek_key = seek_from.map(|seek_key| DbKey::new(&self.prefix, seek_key));
+        let mut db_iterator = self.db.prefix_iterator(&db_key, seek_key.as_ref().map(|key| key.as_ref()));
 
         if skip_first {
             db_iterator.next();
@@ -219,28 +208,28 @@ where
 mod tests {
     use super::*;
     use crate::{
-        create_temp_db,
-        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter},
+        create_memory_db, create_temp_db,
+        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter, WriteBatch},
     };
     use kaspa_hashes::Hash;
-    use rocksdb::WriteBatch;
 
     #[test]
     fn test_delete_all() {
-        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
-        let access = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Count(2), vec![1, 2]);
-
-        access.write_many(DirectDbWriter::new(&db), &mut (0..16).map(|i| (i.into(), 2))).unwrap();
-        assert_eq!(16, access.iterator().count());
-        access.delete_all(DirectDbWriter::new(&db)).unwrap();
-        assert_eq!(0, access.iterator().count());
-
-        access.write_many(DirectDbWriter::new(&db), &mut (0..16).map(|i| (i.into(), 2))).unwrap();
-        assert_eq!(16, access.iterator().count());
-        let mut batch = WriteBatch::default();
-        access.delete_all(BatchDbWriter::new(&mut batch)).unwrap();
-        assert_eq!(16, access.iterator().count());
-        db.write(batch).unwrap();
-        assert_eq!(0, access.iterator().count());
+        for (_lifetime, db) in [create_temp_db!(ConnBuilder::default().with_files_limit(10)), create_memory_db!()] {
+            let access = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Count(2), vec![1, 2]);
+
+            access.write_many(DirectDbWriter::new(&db), &mut (0..16).map(|i| (i.into(), 2))).unwrap();
+            assert_eq!(16, access.iterator().count());
+            access.delete_all(DirectDbWriter::new(&db)).unwrap();
+            assert_eq!(0, access.iterator().count());
+
+            access.write_many(DirectDbWriter::new(&db), &mut (0..16).map(|i| (i.into(), 2))).unwrap();
+            assert_eq!(16, access.iterator().count());
+            let mut batch = WriteBatch::default();
+            access.delete_all(BatchDbWriter::new(&mut batch)).unwrap();
+            assert_eq!(16, access.iterator().count());
+            db.write(batch).unwrap();
+            assert_eq!(0, access.iterator().count());
+        }
     }
 }
@@ -1,35 +1,58 @@
-use rocksdb::{DBWithThreadMode, MultiThreaded};
-use std::ops::{Deref, DerefMut};
-use std::path::PathBuf;
+use crate::{errors::StoreResult, writer::WriteBatch};
+use std::path::{Path, PathBuf};
 
+pub use backend::{DbBackend, DbIterator, DbSlice};
 pub use conn_builder::ConnBuilder;
-use kaspa_utils::fd_budget::FDGuard;
+pub use memory::MemoryDbBackend;
+pub use rocks::RocksDbBackend;
 
+mod backend;
 mod conn_builder;
+mod memory;
+mod rocks;
 
-/// The DB type used for Rustweaved stores
+/// The DB type used for Rustweaved stores. All key-value operations are delegated to the underlying
+/// [`DbBackend`], which is RocksDB unless an alternative engine is explicitly requested
 pub struct DB {
-    inner: DBWithThreadMode<MultiThreaded>,
-    _fd_guard: FDGuard,
+    backend: Box<dyn DbBackend>,
 }
 
 impl DB {
-    pub fn new(inner: DBWithThreadMode<MultiThreaded>, fd_guard: FDGuard) -> Self {
-        Self { inner, _fd_guard: fd_guard }
+    pub fn new(backend: impl DbBackend + 'static) -> Self {
+        Self { backend: Box::new(backend) }
     }
-}
 
-impl DerefMut for DB {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
+    /// Creates a DB which is backed by an in-memory ordered map and is never persisted
+    pub fn in_memory() -> Self {
+        Self::new(MemoryDbBackend::default())
+    }
+
+    pub fn get_pinned(&self, key: impl AsRef<[u8]>) -> StoreResult<Option<DbSlice<'_>>> {
+        self.backend.get_pinned(key.as_ref())
+    }
+
+    pub fn put(&self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) -> StoreResult<()> {
+        self.backend.put(key.as_ref(), value.as_ref())
     }
-}
 
-impl Deref for DB {
-    type Target = DBWithThreadMode<MultiThreaded>;
+    pub fn delete(&self, key: impl AsRef<[u8]>) -> StoreResult<()> {
+        self.backend.delete(key.as_ref())
+    }
+
+    /// Atomically applies all operations of `batch` in the order they were added
+    pub fn write(&self, batch: WriteBatch) -> StoreResult<()> {
+        self.backend.write(batch)
+    }
+
+    /// Iterates over all entries whose key starts with `prefix` in lexicographic key order.
+    /// If `seek_from` is provided, iteration starts from the first key which is greater or equal to it
+    pub fn prefix_iterator(&self, prefix: impl AsRef<[u8]>, seek_from: Option<&[u8]>) -> DbIterator<'_> {
+        self.backend.prefix_iterator(prefix.as_ref(), seek_from)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.inner
+    /// The path of the DB on disk, or `None` if the DB is not persisted
+    pub fn path(&self) -> Option<&Path> {
+        self.backend.path()
     }
 }
 
@@ -40,5 +63,5 @@ pub fn delete_db(db_dir: PathBuf) {
     }
     let options = rocksdb::Options::default();
     let path = db_dir.to_str().unwrap();
-    <DBWithThreadMode<MultiThreaded>>::destroy(&options, path).expect("DB is expected to be deletable");
+    <rocksdb::DBWithThreadMode<rocksdb::MultiThreaded>>::destroy(&options, path).expect("DB is expected to be deletable");
 }
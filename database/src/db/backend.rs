@@ -0,0 +1,51 @@
+use crate::{errors::StoreResult, writer::WriteBatch};
+use std::{ops::Deref, path::Path};
+
+/// A value read from the DB. Backends which can expose their own memory (such as RocksDB) return a
+/// pinned slice in order to avoid a copy, while others return an owned buffer
+pub enum DbSlice<'a> {
+    Pinned(rocksdb::DBPinnableSlice<'a>),
+    Owned(Vec<u8>),
+}
+
+impl Deref for DbSlice<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            DbSlice::Pinned(slice) => slice,
+            DbSlice::Owned(buf) => buf,
+        }
+    }
+}
+
+impl AsRef<[u8]> for DbSlice<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// An iterator over raw `(key, value)` DB entries
+pub type DbIterator<'a> = Box<dyn Iterator<Item = StoreResult<(Box<[u8]>, Box<[u8]>)>> + 'a>;
+
+/// The key-value operations a storage engine must provide in order to back a [`DB`](super::DB).
+///
+/// Keys are ordered lexicographically as raw bytes, which is what the store prefixes and buckets rely on
+pub trait DbBackend: Send + Sync {
+    fn get_pinned(&self, key: &[u8]) -> StoreResult<Option<DbSlice<'_>>>;
+
+    fn put(&self, key: &[u8], value: &[u8]) -> StoreResult<()>;
+
+    fn delete(&self, key: &[u8]) -> StoreResult<()>;
+
+    /// Atomically applies all operations of `batch` in the order they were added
+    fn write(&self, batch: WriteBatch) -> StoreResult<()>;
+
+    /// Iterates over all entries whose key starts with `prefix` in key order, starting from `seek_from` if provided
+    fn prefix_iterator<'a>(&'a self, prefix: &[u8], seek_from: Option<&[u8]>) -> DbIterator<'a>;
+
+    /// The path of the backend files on disk, if persisted
+    fn path(&self) -> Option<&Path> {
+        None
+    }
+}
@@ -0,0 +1,140 @@
+use super::backend::{DbBackend, DbIterator, DbSlice};
+use crate::{
+    errors::StoreResult,
+    writer::{BatchOp, WriteBatch},
+};
+use parking_lot::RwLock;
+use std::{collections::BTreeMap, ops::Bound};
+
+type OrderedMap = BTreeMap<Box<[u8]>, Box<[u8]>>;
+
+/// An in-memory backend over an ordered map. Aimed for tests and simulations which do not need persistence.
+///
+/// Note that unlike RocksDB iterators, which operate over an implicit snapshot, iterators of this backend
+/// observe writes which occur while iterating
+#[derive(Default)]
+pub struct MemoryDbBackend {
+    map: RwLock<OrderedMap>,
+}
+
+impl DbBackend for MemoryDbBackend {
+    fn get_pinned(&self, key: &[u8]) -> StoreResult<Option<DbSlice<'_>>> {
+        Ok(self.map.read().get(key).map(|value| DbSlice::Owned(value.to_vec())))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> StoreResult<()> {
+        self.map.write().insert(key.into(), value.into());
+        Ok(())
+    }
+
+    fn delete(&self, key: &[u8]) -> StoreResult<()> {
+        self.map.write().remove(key);
+        Ok(())
+    }
+
+    fn write(&self, batch: WriteBatch) -> StoreResult<()> {
+        // Holding the write lock for the whole batch makes it atomic to concurrent readers
+        let mut map = self.map.write();
+        for op in batch.ops() {
+            match op {
+                BatchOp::Put(key, value) => {
+                    map.insert(key.into(), value.into());
+                }
+                BatchOp::Delete(key) => {
+                    map.remove(key);
+                }
+                BatchOp::DeleteRange(from, to) => {
+                    if from < to {
+                        let keys: Vec<_> =
+                            map.range::<[u8], _>((Bound::Included(from), Bound::Excluded(to))).map(|(key, _)| key.clone()).collect();
+                        for key in keys {
+                            map.remove(&key);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn prefix_iterator<'a>(&'a self, prefix: &[u8], seek_from: Option<&[u8]>) -> DbIterator<'a> {
+        let start = seek_from.filter(|seek_key| *seek_key > prefix).unwrap_or(prefix);
+        Box::new(MemoryIterator { map: &self.map, prefix: prefix.into(), next_bound: Bound::Included(start.into()) })
+    }
+}
+
+/// Iterates the map lazily by re-acquiring the read lock per item, so that no lock is held between items
+struct MemoryIterator<'a> {
+    map: &'a RwLock<OrderedMap>,
+    prefix: Box<[u8]>,
+    next_bound: Bound<Box<[u8]>>,
+}
+
+impl Iterator for MemoryIterator<'_> {
+    type Item = StoreResult<(Box<[u8]>, Box<[u8]>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let map = self.map.read();
+        let lower = match &self.next_bound {
+            Bound::Included(key) => Bound::Included(&**key),
+            Bound::Excluded(key) => Bound::Excluded(&**key),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let (key, value) = map.range::<[u8], _>((lower, Bound::Unbounded)).next()?;
+        if !key.starts_with(&self.prefix) {
+            return None;
+        }
+        self.next_bound = Bound::Excluded(key.clone());
+        Some(Ok((key.clone(), value.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(backend: &MemoryDbBackend, prefix: &[u8], seek_from: Option<&[u8]>) -> Vec<Vec<u8>> {
+        backend.prefix_iterator(prefix, seek_from).map(|item| item.unwrap().0.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_prefix_iterator() {
+        let backend = MemoryDbBackend::default();
+        let entries: [&[u8]; 7] = [&[1], &[1, 0], &[1, 5], &[1, 255], &[2], &[2, 1], &[0, 1]];
+        for key in entries {
+            backend.put(key, &[]).unwrap();
+        }
+
+        assert_eq!(keys(&backend, &[1], None), vec![vec![1], vec![1, 0], vec![1, 5], vec![1, 255]]);
+        assert_eq!(keys(&backend, &[1], Some(&[1, 1])), vec![vec![1, 5], vec![1, 255]]);
+        // Seeking below the prefix starts from the prefix
+        assert_eq!(keys(&backend, &[2], Some(&[1, 5])), vec![vec![2], vec![2, 1]]);
+        assert!(keys(&backend, &[3], None).is_empty());
+
+        // Entries deleted ahead of the iterator are not observed
+        let mut iter = backend.prefix_iterator(&[1], None);
+        assert_eq!(&*iter.next().unwrap().unwrap().0, &[1]);
+        backend.delete(&[1, 5]).unwrap();
+        assert_eq!(iter.map(|item| item.unwrap().0.to_vec()).collect::<Vec<_>>(), vec![vec![1, 0], vec![1, 255]]);
+    }
+
+    #[test]
+    fn test_write_batch() {
+        let backend = MemoryDbBackend::default();
+        for i in 0..8u8 {
+            backend.put(&[1, i], &[i]).unwrap();
+        }
+
+        let mut batch = WriteBatch::default();
+        batch.delete_range([1, 2], [1, 6]);
+        batch.put([1, 3], [42]);
+        batch.delete([1, 7]);
+        // An empty range is a no-op
+        batch.delete_range([1, 1], [1, 0]);
+        backend.write(batch).unwrap();
+
+        assert_eq!(keys(&backend, &[1], None), vec![vec![1, 0], vec![1, 1], vec![1, 3], vec![1, 6]]);
+        assert_eq!(&*backend.get_pinned(&[1, 3]).unwrap().unwrap(), &[42]);
+        assert!(backend.get_pinned(&[1, 7]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,49 @@
+use super::backend::{DbBackend, DbIterator, DbSlice};
+use crate::{
+    errors::{StoreError, StoreResult},
+    writer::WriteBatch,
+};
+use kaspa_utils::fd_budget::FDGuard;
+use rocksdb::{DBWithThreadMode, Direction, IteratorMode, MultiThreaded, ReadOptions};
+use std::path::Path;
+
+/// The default RocksDB backend
+pub struct RocksDbBackend {
+    inner: DBWithThreadMode<MultiThreaded>,
+    _fd_guard: FDGuard,
+}
+
+impl RocksDbBackend {
+    pub fn new(inner: DBWithThreadMode<MultiThreaded>, fd_guard: FDGuard) -> Self {
+        Self { inner, _fd_guard: fd_guard }
+    }
+}
+
+impl DbBackend for RocksDbBackend {
+    fn get_pinned(&self, key: &[u8]) -> StoreResult<Option<DbSlice<'_>>> {
+        Ok(self.inner.get_pinned(key)?.map(DbSlice::Pinned))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> StoreResult<()> {
+        Ok(self.inner.put(key, value)?)
+    }
+
+    fn delete(&self, key: &[u8]) -> StoreResult<()> {
+        Ok(self.inner.delete(key)?)
+    }
+
+    fn write(&self, batch: WriteBatch) -> StoreResult<()> {
+        Ok(self.inner.write(batch.into_inner())?)
+    }
+
+    fn prefix_iterator<'a>(&'a self, prefix: &[u8], seek_from: Option<&[u8]>) -> DbIterator<'a> {
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_iterate_range(rocksdb::PrefixRange(prefix));
+        let mode = IteratorMode::From(seek_from.unwrap_or(prefix), Direction::Forward);
+        Box::new(self.inner.iterator_opt(mode, read_opts).map(|item| item.map_err(StoreError::from)))
+    }
+
+    fn path(&self) -> Option<&Path> {
+        Some(self.inner.path())
+    }
+}
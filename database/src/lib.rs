@@ -18,7 +18,7 @@ pub mod prelude {
     pub use super::item::{CachedDbItem, CachedDbSetItem};
     pub use super::key::DbKey;
     pub use super::set_access::{CachedDbSetAccess, DbSetAccess, ReadLock};
-    pub use super::writer::{BatchDbWriter, DbWriter, DirectDbWriter, DirectWriter, MemoryWriter};
-    pub use db::{delete_db, ConnBuilder, DB};
+    pub use super::writer::{BatchDbWriter, BatchOp, DbWriter, DirectDbWriter, DirectWriter, MemoryWriter, WriteBatch};
+    pub use db::{delete_db, ConnBuilder, DbBackend, DbIterator, DbSlice, MemoryDbBackend, RocksDbBackend, DB};
     pub use errors::{StoreError, StoreResult, StoreResultEmptyTuple, StoreResultExtensions};
 }
@@ -48,11 +48,18 @@ pub enum DatabaseStorePrefixes {
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    AnchorAddresses = 130,
 
     // ---- Indexes ----
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndexAcceptance = 195,
+    TxIndexAcceptedTransactions = 196,
+    TxIndexSink = 197,
+    AddressIndexTransactions = 198,
+    AddressIndexChainBlockKeys = 199,
+    AddressIndexSink = 200,
 
     // ---- Separator ----
     /// Reserved as a separator
@@ -2,7 +2,7 @@ use crate::{cache::CachePolicy, db::DB, errors::StoreError};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use parking_lot::{RwLock, RwLockReadGuard};
-use rocksdb::{IterateBounds, IteratorMode, ReadOptions};
+use rocksdb::IterateBounds;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     collections::{hash_map::RandomState, HashSet},
@@ -162,19 +162,13 @@ where
         TData: DeserializeOwned,
     {
         let db_key = DbKey::new_with_bucket(&self.prefix, &key, []);
-        let mut read_opts = ReadOptions::default();
-        read_opts.set_iterate_range(rocksdb::PrefixRange(db_key.as_ref()));
-
-        let mut db_iterator = self.db.iterator_opt(IteratorMode::Start, read_opts);
+        let mut db_iterator = self.db.prefix_iterator(&db_key, None);
 
         if skip_first {
             db_iterator.next();
         }
 
-        db_iterator.take(limit).map(move |item| match item {
-            Ok((key_bytes, _)) => Ok(key_bytes[db_key.prefix_len()..].into()),
-            Err(err) => Err(err.into()),
-        })
+        db_iterator.take(limit).map(move |item| item.map(|(key_bytes, _)| key_bytes[db_key.prefix_len()..].into()))
     }
 
     pub fn prefix(&self) -> &[u8] {
@@ -197,31 +191,31 @@ where
 mod tests {
     use super::*;
     use crate::{
-        create_temp_db,
-        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter},
+        create_memory_db, create_temp_db,
+        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter, WriteBatch},
     };
     use kaspa_hashes::Hash;
-    use rocksdb::WriteBatch;
 
     #[test]
     fn test_delete_bucket() {
-        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
-        let access = DbSetAccess::<Hash, u64>::new(db.clone(), vec![1, 2]);
+        for (_lifetime, db) in [create_temp_db!(ConnBuilder::default().with_files_limit(10)), create_memory_db!()] {
+            let access = DbSetAccess::<Hash, u64>::new(db.clone(), vec![1, 2]);
 
-        for i in 0..16 {
-            for j in 0..2 {
-                access.write(DirectDbWriter::new(&db), i.into(), i + j).unwrap();
+            for i in 0..16 {
+                for j in 0..2 {
+                    access.write(DirectDbWriter::new(&db), i.into(), i + j).unwrap();
+                }
             }
-        }
-        for i in 0..16 {
-            assert_eq!(2, access.bucket_iterator(i.into()).count());
-        }
-        access.delete_bucket(DirectDbWriter::new(&db), 3.into()).unwrap();
-        assert_eq!(0, access.bucket_iterator(3.into()).count());
+            for i in 0..16 {
+                assert_eq!(2, access.bucket_iterator(i.into()).count());
+            }
+            access.delete_bucket(DirectDbWriter::new(&db), 3.into()).unwrap();
+            assert_eq!(0, access.bucket_iterator(3.into()).count());
 
-        let mut batch = WriteBatch::default();
-        access.delete_bucket(BatchDbWriter::new(&mut batch), 6.into()).unwrap();
-        db.write(batch).unwrap();
-        assert_eq!(0, access.bucket_iterator(6.into()).count());
+            let mut batch = WriteBatch::default();
+            access.delete_bucket(BatchDbWriter::new(&mut batch), 6.into()).unwrap();
+            db.write(batch).unwrap();
+            assert_eq!(0, access.bucket_iterator(6.into()).count());
+        }
     }
 }
@@ -61,6 +61,16 @@ macro_rules! create_temp_db {
     }};
 }
 
+/// Creates an in-memory DB which is never persisted and vanishes once all references to it are dropped.
+/// Callers must keep the `DbLifetime` guard for as long as they wish the DB to exist.
+#[macro_export]
+macro_rules! create_memory_db {
+    () => {{
+        let db = std::sync::Arc::new($crate::prelude::DB::in_memory());
+        ($crate::utils::DbLifetime::without_destroy(std::sync::Arc::downgrade(&db)), db)
+    }};
+}
+
 /// Creates a DB within the provided directory path.
 /// Callers must keep the `TempDbLifetime` guard for as long as they wish the DB instance to exist.
 #[macro_export]
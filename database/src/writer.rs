@@ -1,16 +1,122 @@
 use kaspa_utils::refs::Refs;
-use rocksdb::WriteBatch;
 
-use crate::prelude::DB;
+use crate::prelude::{StoreError, DB};
+
+/// A single write operation recorded in a [`WriteBatch`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum BatchOp<'a> {
+    Put(&'a [u8], &'a [u8]),
+    Delete(&'a [u8]),
+    /// Deletes all keys in the range `[from, to)`
+    DeleteRange(&'a [u8], &'a [u8]),
+}
+
+/// An ordered collection of write operations which are applied atomically by [`DB::write`].
+///
+/// Operations are recorded straight into a native RocksDB batch, so that the default backend applies it without
+/// any copy. Other backends read the operations back from its serialized representation via [`WriteBatch::ops`]
+#[derive(Default)]
+pub struct WriteBatch {
+    inner: rocksdb::WriteBatch,
+}
+
+impl WriteBatch {
+    pub fn put<K, V>(&mut self, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.inner.put(key, value);
+    }
+
+    pub fn delete<K: AsRef<[u8]>>(&mut self, key: K) {
+        self.inner.delete(key);
+    }
+
+    pub fn delete_range<K>(&mut self, from: K, to: K)
+    where
+        K: AsRef<[u8]>,
+    {
+        self.inner.delete_range(from, to);
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Iterates over the recorded operations in the order they were added
+    pub fn ops(&self) -> BatchOps<'_> {
+        BatchOps { rep: self.inner.data().get(BATCH_HEADER_SIZE..).unwrap_or_default() }
+    }
+
+    pub(crate) fn into_inner(self) -> rocksdb::WriteBatch {
+        self.inner
+    }
+}
+
+// The serialized representation of a RocksDB batch (see `write_batch.cc`) is a header made of a fixed64
+// sequence number and a fixed32 count, followed by the records. A record is a tag followed by the key, and
+// by the value or the range end where applicable, each prefixed by its varint32 length
+const BATCH_HEADER_SIZE: usize = 12;
+const TAG_DELETION: u8 = 0x0;
+const TAG_VALUE: u8 = 0x1;
+const TAG_RANGE_DELETION: u8 = 0xF;
+
+/// An iterator over the operations of a [`WriteBatch`]
+pub struct BatchOps<'a> {
+    rep: &'a [u8],
+}
+
+impl<'a> BatchOps<'a> {
+    fn read_varint32(&mut self) -> u32 {
+        let rep = self.rep;
+        let mut value = 0u32;
+        for (i, &byte) in rep.iter().enumerate().take(5) {
+            value |= ((byte & 0x7f) as u32) << (7 * i);
+            if byte & 0x80 == 0 {
+                self.rep = &rep[i + 1..];
+                return value;
+            }
+        }
+        panic!("malformed write batch varint")
+    }
+
+    fn read_slice(&mut self) -> &'a [u8] {
+        let len = self.read_varint32() as usize;
+        let (slice, rest) = self.rep.split_at(len);
+        self.rep = rest;
+        slice
+    }
+}
+
+impl<'a> Iterator for BatchOps<'a> {
+    type Item = BatchOp<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&tag, rest) = self.rep.split_first()?;
+        self.rep = rest;
+        // Only default column family records are ever written by [`WriteBatch`]
+        Some(match tag {
+            TAG_VALUE => BatchOp::Put(self.read_slice(), self.read_slice()),
+            TAG_DELETION => BatchOp::Delete(self.read_slice()),
+            TAG_RANGE_DELETION => BatchOp::DeleteRange(self.read_slice(), self.read_slice()),
+            _ => panic!("unexpected write batch record tag {tag}"),
+        })
+    }
+}
 
 /// Abstraction over direct/batched DB writing
 pub trait DbWriter {
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>;
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error>;
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError>;
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>;
 }
@@ -34,7 +140,7 @@ impl<'a> DirectDbWriter<'a> {
 }
 
 impl DbWriter for DirectDbWriter<'_> {
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -42,11 +148,11 @@ impl DbWriter for DirectDbWriter<'_> {
         self.db.put(key, value)
     }
 
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError> {
         self.db.delete(key)
     }
 
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
@@ -69,7 +175,7 @@ impl<'a> BatchDbWriter<'a> {
 }
 
 impl DbWriter for BatchDbWriter<'_> {
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -78,12 +184,12 @@ impl DbWriter for BatchDbWriter<'_> {
         Ok(())
     }
 
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError> {
         self.batch.delete(key);
         Ok(())
     }
 
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
@@ -94,7 +200,7 @@ impl DbWriter for BatchDbWriter<'_> {
 
 impl<T: DbWriter> DbWriter for &mut T {
     #[inline]
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -103,12 +209,12 @@ impl<T: DbWriter> DbWriter for &mut T {
     }
 
     #[inline]
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError> {
         (*self).delete(key)
     }
 
     #[inline]
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
@@ -123,7 +229,7 @@ impl<T: DirectWriter> DirectWriter for &mut T {}
 pub struct MemoryWriter;
 
 impl DbWriter for MemoryWriter {
-    fn put<K, V>(&mut self, _key: K, _value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, _key: K, _value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -131,11 +237,11 @@ impl DbWriter for MemoryWriter {
         Ok(())
     }
 
-    fn delete<K: AsRef<[u8]>>(&mut self, _key: K) -> Result<(), rocksdb::Error> {
+    fn delete<K: AsRef<[u8]>>(&mut self, _key: K) -> Result<(), StoreError> {
         Ok(())
     }
 
-    fn delete_range<K>(&mut self, _from: K, _to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, _from: K, _to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
@@ -144,3 +250,30 @@ impl DbWriter for MemoryWriter {
 }
 
 impl DirectWriter for MemoryWriter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_ops() {
+        let long_value = vec![7u8; 300];
+        let mut batch = WriteBatch::default();
+        assert!(batch.is_empty());
+        batch.put([1, 2], &long_value);
+        batch.delete([3]);
+        batch.delete_range([4, 0], [4, 9]);
+        batch.put(b"", b"");
+        assert_eq!(batch.len(), 4);
+        assert_eq!(
+            batch.ops().collect::<Vec<_>>(),
+            vec![
+                BatchOp::Put(&[1, 2], &long_value),
+                BatchOp::Delete(&[3]),
+                BatchOp::DeleteRange(&[4, 0], &[4, 9]),
+                BatchOp::Put(b"", b""),
+            ]
+        );
+        assert_eq!(WriteBatch::default().ops().count(), 0);
+    }
+}
@@ -0,0 +1,90 @@
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::ScriptPublicKey};
+use kaspa_consensusmanager::spawn_blocking;
+use kaspa_database::prelude::StoreResult;
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{errors::AddressIndexResult, model::AddressTransactionsByScriptPublicKey};
+
+///Addressindex API targeted at retrieval calls.
+pub trait AddressIndexApi: Send + Sync + Debug {
+    /// Retrieve the transactions of the given script public keys from the addressindex db, ordered by accepting DAA score
+    /// and starting at `start_daa_score`.
+    ///
+    /// At least `limit` transactions are returned per script public key when available. The transactions sharing the DAA
+    /// score of the last one are always returned in full, so the next page can safely start right above that DAA score.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_address_transactions(
+        &self,
+        script_public_keys: &[ScriptPublicKey],
+        start_daa_score: u64,
+        limit: usize,
+    ) -> StoreResult<AddressTransactionsByScriptPublicKey>;
+
+    /// Retrieve the sink the addressindex was last synced to.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_addressindex_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the addressindex's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> AddressIndexResult<bool>;
+
+    /// Update the addressindex with a virtual selected chain change.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressIndexResult<()>;
+
+    /// Resync the addressindex from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> AddressIndexResult<()>;
+}
+
+/// Async proxy for the address index
+#[derive(Debug, Clone)]
+pub struct AddressIndexProxy {
+    inner: Arc<RwLock<dyn AddressIndexApi>>,
+}
+
+impl AddressIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn AddressIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_address_transactions(
+        self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        start_daa_score: u64,
+        limit: usize,
+    ) -> StoreResult<AddressTransactionsByScriptPublicKey> {
+        spawn_blocking(move || self.inner.read().get_address_transactions(&script_public_keys, start_daa_score, limit)).await.unwrap()
+    }
+
+    pub async fn get_addressindex_sink(self) -> StoreResult<Hash> {
+        spawn_blocking(move || self.inner.read().get_addressindex_sink()).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -0,0 +1,48 @@
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId, TransactionIndexType};
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A transaction accepted by the selected chain which spent from or paid to an indexed script public key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressTransaction {
+    pub transaction_id: TransactionId,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting chain block
+    pub accepting_daa_score: u64,
+    /// The merged block which included the accepted transaction
+    pub including_block_hash: Hash,
+    pub is_coinbase: bool,
+    /// The outputs of the transaction paying to the script public key, as (output index, amount) pairs
+    pub received_outputs: Vec<(TransactionIndexType, u64)>,
+    /// The total amount of the script public key UTXOs spent by the transaction
+    pub spent_amount: u64,
+}
+
+impl AddressTransaction {
+    /// Creates a new [`AddressTransaction`] without any received outputs or spent amount
+    pub fn new(
+        transaction_id: TransactionId,
+        accepting_block_hash: Hash,
+        accepting_daa_score: u64,
+        including_block_hash: Hash,
+        is_coinbase: bool,
+    ) -> Self {
+        Self {
+            transaction_id,
+            accepting_block_hash,
+            accepting_daa_score,
+            including_block_hash,
+            is_coinbase,
+            received_outputs: Vec::new(),
+            spent_amount: 0,
+        }
+    }
+}
+
+impl MemSizeEstimator for AddressTransaction {}
+
+/// A map of address transactions, ordered by accepting DAA score, by script public key
+pub type AddressTransactionsByScriptPublicKey = HashMap<ScriptPublicKey, Vec<AddressTransaction>>;
@@ -0,0 +1,217 @@
+use crate::{
+    api::AddressIndexApi,
+    errors::{AddressIndexError, AddressIndexResult},
+    model::AddressTransactionsByScriptPublicKey,
+    stores::store_manager::Store,
+    update_container::AddressIndexChanges,
+    IDENT,
+};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::ScriptPublicKey};
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::trace;
+use kaspa_database::prelude::{StoreResult, DB};
+use kaspa_hashes::Hash;
+use kaspa_index_core::chain_index::ChainIndex;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+/// AddressIndex maps script public keys to the accepted transactions which spent from or paid to them, ordered by the
+/// DAA score of their accepting chain block and following the virtual selected chain.
+/// Note: The AddressIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `addressindex::core::api::AddressIndexApi` for proper thread safety.
+pub struct AddressIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl AddressIndex {
+    /// Creates a new [`AddressIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> AddressIndexResult<Arc<RwLock<Self>>> {
+        Self { consensus_manager, store: Store::new(db) }.init()
+    }
+}
+
+impl ChainIndex for AddressIndex {
+    type Changes = AddressIndexChanges;
+    type Error = AddressIndexError;
+    const IDENT: &'static str = IDENT;
+
+    fn consensus_manager(&self) -> &Arc<ConsensusManager> {
+        &self.consensus_manager
+    }
+
+    fn get_sink(&self) -> StoreResult<Hash> {
+        self.store.get_sink()
+    }
+
+    fn commit_changes(&mut self, changes: Self::Changes) -> StoreResult<()> {
+        self.store.commit_changes(changes)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.store.delete_all()
+    }
+}
+
+impl AddressIndexApi for AddressIndex {
+    /// Retrieve the transactions of the given script public keys from the addressindex db.
+    fn get_address_transactions(
+        &self,
+        script_public_keys: &[ScriptPublicKey],
+        start_daa_score: u64,
+        limit: usize,
+    ) -> StoreResult<AddressTransactionsByScriptPublicKey> {
+        trace!(
+            "[{0}] retrieving transactions of {1} script public keys from DAA score {2}",
+            IDENT,
+            script_public_keys.len(),
+            start_daa_score
+        );
+
+        self.store.get_address_transactions(script_public_keys, start_daa_score, limit)
+    }
+
+    /// Retrieve the stored sink of the addressindex.
+    fn get_addressindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Updates the [AddressIndex] with the supplied virtual selected chain change:
+    /// deletes the address transactions of removed chain blocks and records those of added chain blocks in a single db write.
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressIndexResult<()> {
+        self.apply_chain_changes(&removed_chain_block_hashes, &added_chain_block_hashes, &added_chain_blocks_acceptance_data)
+    }
+
+    /// Checks to see if the [AddressIndex] is sync'd. This is done via comparing the addressindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the addressindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> AddressIndexResult<bool> {
+        self.is_chain_synced()
+    }
+
+    /// Deletes and reinstates the addressindex database, syncing it from scratch via the selected chain from the pruning point.
+    ///
+    /// **Notes:**
+    /// 1) Transactions accepted by the pruning point itself, or by blocks below it, are not indexed.
+    /// 2) resyncing while consensus notifies of chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> AddressIndexResult<()> {
+        self.resync_chain()
+    }
+}
+
+impl Debug for AddressIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddressIndex").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::AddressIndexApi, AddressIndex};
+    use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+    use kaspa_consensus_core::{api::ConsensusApi, coinbase::MinerData, tx::ScriptPublicKey};
+    use kaspa_consensusmanager::ConsensusManager;
+    use kaspa_database::create_temp_db;
+    use kaspa_database::prelude::ConnBuilder;
+    use kaspa_hashes::Hash;
+    use parking_lot::RwLock;
+    use std::sync::Arc;
+
+    fn add_chain(tc: &TestConsensus, root: Hash, hashes: impl Iterator<Item = u64>, script_public_key: &ScriptPublicKey) {
+        let miner_data = MinerData::new(script_public_key.clone(), vec![]);
+        futures::executor::block_on(tc.add_utxo_valid_chain(root, hashes.map(Hash::from), miner_data)).unwrap();
+    }
+
+    /// Asserts that the addressindex holds exactly the coinbase payouts to `script_public_key` accepted by the current
+    /// selected chain, and returns their count.
+    fn assert_matches_selected_chain(
+        tc: &TestConsensus,
+        addressindex: &Arc<RwLock<AddressIndex>>,
+        script_public_key: &ScriptPublicKey,
+    ) -> usize {
+        let chain = tc.get_virtual_chain_from_block(tc.pruning_point()).unwrap().added;
+        let mut expected = Vec::new();
+        for (hash, acceptance_data) in chain.iter().copied().zip(tc.get_blocks_acceptance_data(&chain).unwrap()) {
+            for mergeset_block in acceptance_data.iter() {
+                let transactions = tc.get_block(mergeset_block.block_hash).unwrap().transactions;
+                for entry in mergeset_block.accepted_transactions.iter() {
+                    let transaction = &transactions[entry.index_within_block as usize];
+                    if transaction.outputs.iter().any(|output| &output.script_public_key == script_public_key) {
+                        expected.push((entry.transaction_id, hash, mergeset_block.block_hash));
+                    }
+                }
+            }
+        }
+
+        let indexed = addressindex.read().get_address_transactions(std::slice::from_ref(script_public_key), 0, usize::MAX).unwrap();
+        let indexed = indexed.get(script_public_key).unwrap();
+        assert_eq!(indexed.len(), expected.len());
+        for (transaction, (transaction_id, accepting_block_hash, including_block_hash)) in indexed.iter().zip(expected) {
+            assert_eq!(transaction.transaction_id, transaction_id);
+            assert_eq!(transaction.accepting_block_hash, accepting_block_hash);
+            assert_eq!(transaction.accepting_daa_score, tc.get_header(accepting_block_hash).unwrap().daa_score);
+            assert_eq!(transaction.including_block_hash, including_block_hash);
+            assert!(transaction.is_coinbase);
+            assert!(!transaction.received_outputs.is_empty());
+            assert_eq!(transaction.spent_amount, 0);
+        }
+        assert_eq!(addressindex.read().get_addressindex_sink().unwrap(), tc.get_sink());
+        indexed.len()
+    }
+
+    #[test]
+    fn test_addressindex() {
+        kaspa_core::log::try_init_logger("INFO");
+
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+            })
+            .build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+        let (_addressindex_db_lifetime, addressindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let old_miner = ScriptPublicKey::from_vec(0, vec![1]);
+        let new_miner = ScriptPublicKey::from_vec(0, vec![2]);
+
+        // Build a first chain and sync the addressindex from scratch.
+        add_chain(&tc, config.genesis.hash, 1..=4, &old_miner);
+        let addressindex = AddressIndex::new(consensus_manager, addressindex_db).unwrap();
+        assert!(addressindex.read().is_synced().unwrap());
+        let old_count = assert_matches_selected_chain(&tc, &addressindex, &old_miner);
+        assert!(old_count > 1);
+
+        // Pages hold whole DAA scores and resume right above the last one.
+        let first_page =
+            addressindex.read().get_address_transactions(std::slice::from_ref(&old_miner), 0, 1).unwrap().remove(&old_miner).unwrap();
+        assert_eq!(first_page.len(), 1);
+        let next_daa_score = first_page[0].accepting_daa_score + 1;
+        let rest = addressindex.read().get_address_transactions(std::slice::from_ref(&old_miner), next_daa_score, usize::MAX).unwrap();
+        assert_eq!(rest.get(&old_miner).unwrap().len(), old_count - 1);
+
+        // Reorg into a longer competing chain paying to a different miner.
+        let old_sink = tc.get_sink();
+        add_chain(&tc, config.genesis.hash, 11..=16, &new_miner);
+        assert!(!addressindex.read().is_synced().unwrap());
+        let chain_path = tc.get_virtual_chain_from_block(old_sink).unwrap();
+        let acceptance_data = tc.get_blocks_acceptance_data(&chain_path.added).unwrap();
+        addressindex.write().update(Arc::new(chain_path.removed), Arc::new(chain_path.added), Arc::new(acceptance_data)).unwrap();
+        assert!(addressindex.read().is_synced().unwrap());
+        assert!(assert_matches_selected_chain(&tc, &addressindex, &new_miner) > 0);
+
+        // Transactions accepted only by the removed chain must no longer be indexed.
+        assert_eq!(assert_matches_selected_chain(&tc, &addressindex, &old_miner), 0);
+
+        drop(addressindex);
+        tc.shutdown(wait_handles);
+    }
+}
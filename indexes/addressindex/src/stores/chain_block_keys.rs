@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+
+use super::transactions::AddressTransactionKey;
+
+/// Reader API for `ChainBlockKeysStore`.
+pub trait ChainBlockKeysStoreReader {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<AddressTransactionKey>>>;
+}
+
+/// Keeps the keys of the address transactions written for each indexed chain block, so that
+/// they can be deleted when the block is removed from the selected chain.
+pub trait ChainBlockKeysStore: ChainBlockKeysStoreReader {
+    fn insert(
+        &mut self,
+        writer: &mut BatchDbWriter,
+        accepting_block_hash: Hash,
+        keys: Arc<Vec<AddressTransactionKey>>,
+    ) -> StoreResult<()>;
+    fn delete(&mut self, writer: &mut BatchDbWriter, accepting_block_hash: Hash) -> StoreResult<()>;
+
+    /// removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `ChainBlockKeysStore` trait
+#[derive(Clone)]
+pub struct DbChainBlockKeysStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Arc<Vec<AddressTransactionKey>>>,
+}
+
+impl DbChainBlockKeysStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressIndexChainBlockKeys.into()),
+        }
+    }
+}
+
+impl ChainBlockKeysStoreReader for DbChainBlockKeysStore {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<AddressTransactionKey>>> {
+        self.access.read(accepting_block_hash)
+    }
+}
+
+impl ChainBlockKeysStore for DbChainBlockKeysStore {
+    fn insert(
+        &mut self,
+        writer: &mut BatchDbWriter,
+        accepting_block_hash: Hash,
+        keys: Arc<Vec<AddressTransactionKey>>,
+    ) -> StoreResult<()> {
+        self.access.write(writer, accepting_block_hash, keys)
+    }
+
+    fn delete(&mut self, writer: &mut BatchDbWriter, accepting_block_hash: Hash) -> StoreResult<()> {
+        self.access.delete(writer, accepting_block_hash)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,4 @@
+mod chain_block_keys;
+mod sink;
+pub mod store_manager;
+mod transactions;
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachedDbItem, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+
+/// Reader API for `AddressIndexSinkStore`.
+pub trait AddressIndexSinkStoreReader {
+    fn get(&self) -> StoreResult<Hash>;
+}
+
+pub trait AddressIndexSinkStore: AddressIndexSinkStoreReader {
+    fn set(&mut self, writer: &mut BatchDbWriter, sink: Hash) -> StoreResult<()>;
+    fn remove(&mut self) -> Result<(), StoreError>;
+}
+
+/// A DB + cache implementation of `AddressIndexSinkStore` trait
+#[derive(Clone)]
+pub struct DbAddressIndexSinkStore {
+    db: Arc<DB>,
+    access: CachedDbItem<Hash>,
+}
+
+impl DbAddressIndexSinkStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::AddressIndexSink.into()) }
+    }
+}
+
+impl AddressIndexSinkStoreReader for DbAddressIndexSinkStore {
+    fn get(&self) -> StoreResult<Hash> {
+        self.access.read()
+    }
+}
+
+impl AddressIndexSinkStore for DbAddressIndexSinkStore {
+    fn set(&mut self, writer: &mut BatchDbWriter, sink: Hash) -> StoreResult<()> {
+        self.access.write(writer, &sink)
+    }
+
+    fn remove(&mut self) -> Result<(), StoreError> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::ScriptPublicKey;
+use kaspa_core::trace;
+use kaspa_database::prelude::WriteBatch;
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+
+use crate::{
+    model::AddressTransactionsByScriptPublicKey,
+    stores::{
+        chain_block_keys::{ChainBlockKeysStore, ChainBlockKeysStoreReader, DbChainBlockKeysStore},
+        sink::{AddressIndexSinkStore, AddressIndexSinkStoreReader, DbAddressIndexSinkStore},
+        transactions::{AddressTransactionKey, AddressTransactionsStore, AddressTransactionsStoreReader, DbAddressTransactionsStore},
+    },
+    update_container::AddressIndexChanges,
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    sink_store: DbAddressIndexSinkStore,
+    transactions_store: DbAddressTransactionsStore,
+    chain_block_keys_store: DbChainBlockKeysStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            sink_store: DbAddressIndexSinkStore::new(db.clone()),
+            transactions_store: DbAddressTransactionsStore::new(db.clone(), CachePolicy::Empty),
+            chain_block_keys_store: DbChainBlockKeysStore::new(db, CachePolicy::Count(1_000)),
+        }
+    }
+
+    pub fn get_address_transactions(
+        &self,
+        script_public_keys: &[ScriptPublicKey],
+        start_daa_score: u64,
+        limit: usize,
+    ) -> StoreResult<AddressTransactionsByScriptPublicKey> {
+        let mut transactions = AddressTransactionsByScriptPublicKey::with_capacity(script_public_keys.len());
+        for script_public_key in script_public_keys.iter() {
+            transactions.insert(script_public_key.clone(), self.transactions_store.get(script_public_key, start_daa_score, limit)?);
+        }
+        Ok(transactions)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically commits the given changes to the addressindex database.
+    ///
+    /// Address transactions of removed chain blocks are deleted before those of added chain blocks are written,
+    /// so a transaction re-accepted by the new chain ends up indexed under its new accepting block.
+    pub fn commit_changes(&mut self, changes: AddressIndexChanges) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+
+        for removed_block_hash in changes.removed_chain_blocks.iter().copied() {
+            let keys = match self.chain_block_keys_store.get(removed_block_hash) {
+                Ok(keys) => keys,
+                Err(StoreError::KeyNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            self.transactions_store.delete_many(&mut writer, &keys)?;
+            self.chain_block_keys_store.delete(&mut writer, removed_block_hash)?;
+        }
+
+        for added in changes.added_chain_blocks.into_iter() {
+            let transactions = added
+                .transactions
+                .into_iter()
+                .map(|(script_public_key, transaction)| {
+                    (
+                        AddressTransactionKey::new(&script_public_key, transaction.accepting_daa_score, transaction.transaction_id),
+                        transaction,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let keys = transactions.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+            self.chain_block_keys_store.insert(&mut writer, added.accepting_block_hash, Arc::new(keys))?;
+            self.transactions_store.insert_many(&mut writer, transactions)?;
+        }
+
+        self.sink_store.set(&mut writer, changes.sink)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Resets the addressindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear addressindex database...", IDENT);
+
+        self.sink_store.remove()?;
+        self.transactions_store.delete_all()?;
+        self.chain_block_keys_store.delete_all()?;
+
+        trace!("[{0}] clearing addressindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
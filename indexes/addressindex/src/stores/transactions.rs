@@ -0,0 +1,148 @@
+use std::{mem::size_of, sync::Arc};
+
+use kaspa_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion, TransactionId};
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::HASH_SIZE;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+use crate::model::AddressTransaction;
+
+/// [`ScriptPublicKeyBucket`].
+/// Consists of 2 bytes of little endian [ScriptPublicKeyVersion] bytes, 8 bytes of little endian script length,
+/// followed by a variable size of script bytes.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct ScriptPublicKeyBucket(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyBucket {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let mut bytes: Vec<u8> =
+            Vec::with_capacity(size_of::<ScriptPublicKeyVersion>() + size_of::<u64>() + script_public_key.script().len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(&(script_public_key.script().len() as u64).to_le_bytes());
+        bytes.extend_from_slice(script_public_key.script());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyBucket {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Full [AddressTransaction] access key.
+/// Consists of a variable amount of bytes of [ScriptPublicKeyBucket], 8 bytes of big endian accepting DAA score,
+/// so that the transactions of a bucket are iterated in DAA score order, and 32 bytes of [TransactionId].
+#[derive(Eq, Hash, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct AddressTransactionKey(Vec<u8>);
+
+impl AddressTransactionKey {
+    pub fn new(script_public_key: &ScriptPublicKey, accepting_daa_score: u64, transaction_id: TransactionId) -> Self {
+        let mut bytes = Self::seek_bytes(script_public_key, accepting_daa_score);
+        bytes.extend_from_slice(&transaction_id.as_bytes());
+        Self(bytes)
+    }
+
+    /// Returns the key preceding all the keys of `script_public_key` with an accepting DAA score of at least `accepting_daa_score`.
+    fn seek_key(script_public_key: &ScriptPublicKey, accepting_daa_score: u64) -> Self {
+        Self(Self::seek_bytes(script_public_key, accepting_daa_score))
+    }
+
+    fn seek_bytes(script_public_key: &ScriptPublicKey, accepting_daa_score: u64) -> Vec<u8> {
+        let bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let mut bytes = Vec::with_capacity(bucket.as_ref().len() + size_of::<u64>() + HASH_SIZE);
+        bytes.extend_from_slice(bucket.as_ref());
+        bytes.extend_from_slice(&accepting_daa_score.to_be_bytes());
+        bytes
+    }
+}
+
+impl AsRef<[u8]> for AddressTransactionKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl MemSizeEstimator for AddressTransactionKey {}
+
+/// Reader API for `AddressTransactionsStore`.
+pub trait AddressTransactionsStoreReader {
+    /// Returns the transactions of `script_public_key` by ascending accepting DAA score, starting at `start_daa_score`.
+    ///
+    /// Once `limit` transactions are collected, the remaining transactions sharing the DAA score of the last one are appended as well.
+    fn get(&self, script_public_key: &ScriptPublicKey, start_daa_score: u64, limit: usize) -> StoreResult<Vec<AddressTransaction>>;
+}
+
+pub trait AddressTransactionsStore: AddressTransactionsStoreReader {
+    fn insert_many(
+        &mut self,
+        writer: &mut BatchDbWriter,
+        transactions: Vec<(AddressTransactionKey, AddressTransaction)>,
+    ) -> StoreResult<()>;
+    fn delete_many(&mut self, writer: &mut BatchDbWriter, keys: &[AddressTransactionKey]) -> StoreResult<()>;
+
+    /// removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressTransactionsStore` trait
+#[derive(Clone)]
+pub struct DbAddressTransactionsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressTransactionKey, AddressTransaction>,
+}
+
+impl DbAddressTransactionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressIndexTransactions.into()),
+        }
+    }
+}
+
+impl AddressTransactionsStoreReader for DbAddressTransactionsStore {
+    fn get(&self, script_public_key: &ScriptPublicKey, start_daa_score: u64, limit: usize) -> StoreResult<Vec<AddressTransaction>> {
+        let bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let seek_key = AddressTransactionKey::seek_key(script_public_key, start_daa_score);
+        let mut transactions: Vec<AddressTransaction> = Vec::new();
+        for item in self.access.seek_iterator(Some(bucket.as_ref()), Some(seek_key), usize::MAX, false) {
+            let (_, transaction) = item.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            if transactions.len() >= limit
+                && transactions.last().is_some_and(|last| last.accepting_daa_score != transaction.accepting_daa_score)
+            {
+                break;
+            }
+            transactions.push(transaction);
+        }
+        Ok(transactions)
+    }
+}
+
+impl AddressTransactionsStore for DbAddressTransactionsStore {
+    fn insert_many(
+        &mut self,
+        writer: &mut BatchDbWriter,
+        transactions: Vec<(AddressTransactionKey, AddressTransaction)>,
+    ) -> StoreResult<()> {
+        if transactions.is_empty() {
+            return Ok(());
+        }
+        self.access.write_many(writer, &mut transactions.into_iter())
+    }
+
+    fn delete_many(&mut self, writer: &mut BatchDbWriter, keys: &[AddressTransactionKey]) -> StoreResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        self.access.delete_many(writer, &mut keys.iter().cloned())
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
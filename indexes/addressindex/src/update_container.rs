@@ -0,0 +1,183 @@
+use kaspa_consensus_core::{
+    acceptance_data::AcceptanceData,
+    errors::consensus::ConsensusResult,
+    tx::{ScriptPublicKey, Transaction, TransactionId, TransactionOutpoint},
+    utxo::utxo_diff::UtxoDiff,
+};
+use kaspa_consensusmanager::ConsensusSessionBlocking;
+use kaspa_hashes::Hash;
+use kaspa_index_core::chain_index::ChainChangesBuilder;
+use std::collections::HashMap;
+
+use crate::model::AddressTransaction;
+
+/// The address transactions of a single chain block.
+pub struct ChainBlockAddressTransactions {
+    pub accepting_block_hash: Hash,
+    pub transactions: Vec<(ScriptPublicKey, AddressTransaction)>,
+}
+
+/// A struct holding all changes to the addressindex, resulting from a virtual selected chain change.
+pub struct AddressIndexChanges {
+    /// Chain blocks removed from the selected chain, ordered from high to low
+    pub removed_chain_blocks: Vec<Hash>,
+    /// Chain blocks added to the selected chain, ordered from low to high
+    pub added_chain_blocks: Vec<ChainBlockAddressTransactions>,
+    /// The new sink of the selected chain
+    pub sink: Hash,
+}
+
+impl ChainChangesBuilder for AddressIndexChanges {
+    fn new(sink: Hash) -> Self {
+        Self { removed_chain_blocks: Vec::new(), added_chain_blocks: Vec::new(), sink }
+    }
+
+    fn remove_chain_blocks(&mut self, removed_chain_block_hashes: &[Hash]) {
+        self.removed_chain_blocks.extend_from_slice(removed_chain_block_hashes);
+    }
+
+    /// Fetches the transactions accepted by the chain block and its UTXO diff from consensus.
+    fn collect_chain_block(
+        &mut self,
+        session: &ConsensusSessionBlocking<'_>,
+        hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> ConsensusResult<()> {
+        let mergeset_transactions = acceptance_data
+            .iter()
+            .map(|mergeset_block| Ok((mergeset_block, session.get_block(mergeset_block.block_hash)?.transactions)))
+            .collect::<ConsensusResult<Vec<_>>>()?;
+        let accepted_transactions = mergeset_transactions.iter().flat_map(|(mergeset_block, transactions)| {
+            mergeset_block
+                .accepted_transactions
+                .iter()
+                .map(|entry| (mergeset_block.block_hash, &transactions[entry.index_within_block as usize]))
+        });
+        let utxo_diff = session.get_block_utxo_diff(hash)?;
+        self.add_chain_block(hash, session.get_header(hash)?.daa_score, accepted_transactions, &utxo_diff);
+        Ok(())
+    }
+}
+
+impl AddressIndexChanges {
+    /// Add a chain block along with the transactions it accepted, given as (including block hash, transaction) pairs in
+    /// acceptance order, and its UTXO diff.
+    ///
+    /// The script public keys of spent outputs are resolved from the removed entries of the UTXO diff, or from the outputs
+    /// of transactions accepted earlier by the same chain block, since these never reach the diff.
+    pub fn add_chain_block<'a>(
+        &mut self,
+        accepting_block_hash: Hash,
+        accepting_daa_score: u64,
+        accepted_transactions: impl Iterator<Item = (Hash, &'a Transaction)>,
+        utxo_diff: &UtxoDiff,
+    ) {
+        let mut created_outputs: HashMap<TransactionOutpoint, (&ScriptPublicKey, u64)> = HashMap::new();
+        let mut transactions: HashMap<(ScriptPublicKey, TransactionId), AddressTransaction> = HashMap::new();
+
+        for (including_block_hash, transaction) in accepted_transactions {
+            let transaction_id = transaction.id();
+            let new_transaction = || {
+                AddressTransaction::new(
+                    transaction_id,
+                    accepting_block_hash,
+                    accepting_daa_score,
+                    including_block_hash,
+                    transaction.is_coinbase(),
+                )
+            };
+
+            for input in transaction.inputs.iter() {
+                let spent = match utxo_diff.remove.get(&input.previous_outpoint) {
+                    Some(utxo_entry) => Some((&utxo_entry.script_public_key, utxo_entry.amount)),
+                    None => created_outputs.get(&input.previous_outpoint).copied(),
+                };
+                if let Some((script_public_key, amount)) = spent {
+                    transactions.entry((script_public_key.clone(), transaction_id)).or_insert_with(new_transaction).spent_amount +=
+                        amount;
+                }
+            }
+
+            for (index, output) in transaction.outputs.iter().enumerate() {
+                transactions
+                    .entry((output.script_public_key.clone(), transaction_id))
+                    .or_insert_with(new_transaction)
+                    .received_outputs
+                    .push((index as u32, output.value));
+                created_outputs
+                    .insert(TransactionOutpoint::new(transaction_id, index as u32), (&output.script_public_key, output.value));
+            }
+        }
+
+        let transactions =
+            transactions.into_iter().map(|((script_public_key, _), transaction)| (script_public_key, transaction)).collect();
+        self.added_chain_blocks.push(ChainBlockAddressTransactions { accepting_block_hash, transactions });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutput, UtxoEntry},
+    };
+
+    #[test]
+    fn test_add_chain_block() {
+        let (x, y, z) =
+            (ScriptPublicKey::from_vec(0, vec![1]), ScriptPublicKey::from_vec(0, vec![2]), ScriptPublicKey::from_vec(0, vec![3]));
+        let (accepting_block_hash, including_block_hash) = (Hash::from(1), Hash::from(2));
+
+        // `first` spends a UTXO of `x` found in the diff, and `second` spends the output `first` created for `y`
+        let spent_outpoint = TransactionOutpoint::new(Hash::from(3), 0);
+        let first = Transaction::new(
+            0,
+            vec![TransactionInput::new(spent_outpoint, vec![], 0, 1)],
+            vec![TransactionOutput::new(70, y.clone()), TransactionOutput::new(20, x.clone())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let second = Transaction::new(
+            0,
+            vec![TransactionInput::new(TransactionOutpoint::new(first.id(), 0), vec![], 0, 1)],
+            vec![TransactionOutput::new(60, z.clone())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let mut utxo_diff = UtxoDiff::default();
+        utxo_diff.remove.insert(spent_outpoint, UtxoEntry::new(100, x.clone(), 5, false));
+
+        let mut changes = AddressIndexChanges::new(accepting_block_hash);
+        changes.add_chain_block(
+            accepting_block_hash,
+            10,
+            [(including_block_hash, &first), (including_block_hash, &second)].into_iter(),
+            &utxo_diff,
+        );
+
+        let transactions: HashMap<_, _> = changes.added_chain_blocks[0]
+            .transactions
+            .iter()
+            .map(|(script_public_key, transaction)| ((script_public_key.clone(), transaction.transaction_id), transaction.clone()))
+            .collect();
+        assert_eq!(transactions.len(), 4);
+
+        let x_first = &transactions[&(x.clone(), first.id())];
+        assert_eq!((x_first.spent_amount, x_first.received_outputs.clone()), (100, vec![(1, 20)]));
+        let y_first = &transactions[&(y.clone(), first.id())];
+        assert_eq!((y_first.spent_amount, y_first.received_outputs.clone()), (0, vec![(0, 70)]));
+        let y_second = &transactions[&(y, second.id())];
+        assert_eq!((y_second.spent_amount, y_second.received_outputs.clone()), (70, vec![]));
+        let z_second = &transactions[&(z, second.id())];
+        assert_eq!((z_second.spent_amount, z_second.received_outputs.clone()), (0, vec![(0, 60)]));
+        assert!(transactions.values().all(|transaction| transaction.accepting_daa_score == 10
+            && transaction.accepting_block_hash == accepting_block_hash
+            && transaction.including_block_hash == including_block_hash
+            && !transaction.is_coinbase));
+    }
+}
@@ -0,0 +1,353 @@
+//!
+//! Synchronization logic shared by the indexes which follow the virtual selected chain, from the pruning point to the sink.
+//!
+//! Each index only provides its store access and a [`ChainChangesBuilder`] turning chain blocks into store-specific changes,
+//! while catching up, applying chain changes, resyncing and handling consensus resets are provided by [`ChainIndex`].
+//!
+
+use kaspa_consensus_core::{
+    acceptance_data::AcceptanceData,
+    errors::consensus::{ConsensusError, ConsensusResult},
+};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler, ConsensusSessionBlocking};
+use kaspa_core::{info, trace, warn};
+use kaspa_database::prelude::{StoreError, StoreResult};
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 256; // Number of chain blocks whose acceptance data is fetched from consensus at once.
+
+/// Builds the store-specific changes of a chain index, resulting from a virtual selected chain change.
+pub trait ChainChangesBuilder {
+    /// Creates empty changes, leading the index to the given sink
+    fn new(sink: Hash) -> Self;
+
+    /// Add the hashes of chain blocks removed from the selected chain.
+    fn remove_chain_blocks(&mut self, removed_chain_block_hashes: &[Hash]);
+
+    /// Collects the changes of a chain block added to the selected chain, given the acceptance data of its mergeset.
+    /// Any additional data is fetched from consensus through `session`.
+    fn collect_chain_block(
+        &mut self,
+        session: &ConsensusSessionBlocking<'_>,
+        hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> ConsensusResult<()>;
+}
+
+/// An index following the virtual selected chain.
+///
+/// Implementors provide access to their store, all the chain following logic being provided on top of it.
+pub trait ChainIndex: Send + Sync + Sized + 'static {
+    type Changes: ChainChangesBuilder;
+    type Error: From<StoreError> + From<ConsensusError> + Debug;
+
+    /// Name of the index, used for logging
+    const IDENT: &'static str;
+
+    fn consensus_manager(&self) -> &Arc<ConsensusManager>;
+
+    /// Returns the sink committed to the store
+    fn get_sink(&self) -> StoreResult<Hash>;
+
+    fn commit_changes(&mut self, changes: Self::Changes) -> StoreResult<()>;
+
+    fn delete_all(&mut self) -> StoreResult<()>;
+
+    /// Brings the index up to date with consensus, by catching up from its stored sink or by resyncing it if it cannot,
+    /// and registers a handler resyncing it on consensus resets.
+    fn init(mut self) -> Result<Arc<RwLock<Self>>, Self::Error> {
+        if !self.is_chain_synced()? && !self.catch_up()? {
+            self.resync_chain()?;
+        }
+        let consensus_manager = self.consensus_manager().clone();
+        let index = Arc::new(RwLock::new(self));
+        consensus_manager.register_consensus_reset_handler(Arc::new(ChainIndexResetHandler { index: Arc::downgrade(&index) }));
+        Ok(index)
+    }
+
+    /// Checks to see if the index is sync'd. This is done via comparing the committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the index and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_chain_synced(&self) -> Result<bool, Self::Error> {
+        trace!("[{0}] checking sync status...", Self::IDENT);
+
+        let consensus = self.consensus_manager().consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.get_sink() {
+            Ok(sink) => {
+                let res = sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", Self::IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                //Means the sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", Self::IDENT, false);
+                Ok(false)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Tries to bring the index up to date by following the chain path from the stored sink to the consensus sink.
+    /// Returns `false` if the stored sink is missing or no longer known to consensus, in which case a resync is required.
+    fn catch_up(&mut self) -> Result<bool, Self::Error> {
+        let stored_sink = match self.get_sink() {
+            Ok(sink) => sink,
+            Err(StoreError::KeyNotFound(_)) => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+
+        let consensus = self.consensus_manager().consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let chain_path = match session.get_virtual_chain_from_block(stored_sink) {
+            Ok(chain_path) => chain_path,
+            Err(err) => {
+                warn!("[{0}] unable to catch up from stored sink {1}: {2}", Self::IDENT, stored_sink, err);
+                return Ok(false);
+            }
+        };
+        trace!(
+            "[{0}] catching up, removing {1} and adding {2} chain blocks",
+            Self::IDENT,
+            chain_path.removed.len(),
+            chain_path.added.len()
+        );
+
+        let mut changes = Self::Changes::new(new_sink(&session, &chain_path.removed, &chain_path.added)?);
+        changes.remove_chain_blocks(&chain_path.removed);
+        self.commit_changes(changes)?;
+        self.add_chain_blocks_in_chunks(&session, &chain_path.added)?;
+        Ok(true)
+    }
+
+    /// Applies the supplied virtual selected chain change: reverts the changes of removed chain blocks and records those
+    /// of added chain blocks in a single db write.
+    fn apply_chain_changes(
+        &mut self,
+        removed_chain_block_hashes: &[Hash],
+        added_chain_block_hashes: &[Hash],
+        added_chain_blocks_acceptance_data: &[Arc<AcceptanceData>],
+    ) -> Result<(), Self::Error> {
+        trace!("[{0}] updating...", Self::IDENT);
+        trace!("[{0}] removing {1} chain blocks", Self::IDENT, removed_chain_block_hashes.len());
+        trace!("[{0}] adding {1} chain blocks", Self::IDENT, added_chain_block_hashes.len());
+
+        let consensus = self.consensus_manager().consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut changes = Self::Changes::new(new_sink(&session, removed_chain_block_hashes, added_chain_block_hashes)?);
+        changes.remove_chain_blocks(removed_chain_block_hashes);
+        for (hash, acceptance_data) in added_chain_block_hashes.iter().copied().zip(added_chain_blocks_acceptance_data.iter()) {
+            changes.collect_chain_block(&session, hash, acceptance_data)?;
+        }
+
+        self.commit_changes(changes)?;
+        Ok(())
+    }
+
+    /// Deletes and reinstates the index database, syncing it from scratch via the selected chain from the pruning point.
+    ///
+    /// **Notes:**
+    /// 1) Transactions accepted by the pruning point itself, or by blocks below it, are not indexed.
+    /// 2) resyncing while consensus notifies of chain changes, may result in a corrupted db.
+    fn resync_chain(&mut self) -> Result<(), Self::Error> {
+        info!("Resyncing the {}...", Self::IDENT);
+
+        self.delete_all()?;
+        let consensus = self.consensus_manager().consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let pruning_point = session.pruning_point();
+        let chain_path = session.get_virtual_chain_from_block(pruning_point)?;
+        trace!("[{0}] resyncing {1} chain blocks from pruning point {2}", Self::IDENT, chain_path.added.len(), pruning_point);
+
+        // Commit the pruning point as sink first, so that an empty chain path still results in a synced index
+        self.commit_changes(Self::Changes::new(pruning_point))?;
+        self.add_chain_blocks_in_chunks(&session, &chain_path.added)?;
+
+        Ok(())
+    }
+
+    /// Adds the given chain blocks, fetching their acceptance data from consensus in chunks of [`RESYNC_CHUNK_SIZE`].
+    fn add_chain_blocks_in_chunks(
+        &mut self,
+        session: &ConsensusSessionBlocking<'_>,
+        chain_block_hashes: &[Hash],
+    ) -> Result<(), Self::Error> {
+        for chunk in chain_block_hashes.chunks(RESYNC_CHUNK_SIZE) {
+            let acceptance_data = session.get_blocks_acceptance_data(chunk)?;
+            let mut changes = Self::Changes::new(*chunk.last().expect("chunks are never empty"));
+            for (hash, acceptance_data) in chunk.iter().copied().zip(acceptance_data.iter()) {
+                changes.collect_chain_block(session, hash, acceptance_data)?;
+            }
+            self.commit_changes(changes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the sink of the selected chain resulting from the given chain change.
+fn new_sink(session: &ConsensusSessionBlocking<'_>, removed: &[Hash], added: &[Hash]) -> ConsensusResult<Hash> {
+    match (added.last(), removed.last()) {
+        (Some(sink), _) => Ok(*sink),
+        // The selected chain only retreated, so the new sink is the selected parent of the lowest removed block
+        (None, Some(lowest_removed)) => Ok(session.get_ghostdag_data(*lowest_removed)?.selected_parent),
+        (None, None) => Ok(session.get_sink()),
+    }
+}
+
+/// Resyncs a chain index when the consensus is reset to a staging consensus
+struct ChainIndexResetHandler<T: ChainIndex> {
+    index: Weak<RwLock<T>>,
+}
+
+impl<T: ChainIndex> ConsensusResetHandler for ChainIndexResetHandler<T> {
+    fn handle_consensus_reset(&self) {
+        if let Some(index) = self.index.upgrade() {
+            index.write().resync_chain().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+    use kaspa_consensus_core::{api::ConsensusApi, coinbase::MinerData, tx::ScriptPublicKey};
+    use kaspa_database::prelude::DbKey;
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    enum TestIndexError {
+        #[error("{0}")]
+        StoreError(#[from] StoreError),
+
+        #[error("{0}")]
+        ConsensusError(#[from] ConsensusError),
+    }
+
+    struct TestChanges {
+        sink: Hash,
+        removed: Vec<Hash>,
+        added: Vec<Hash>,
+    }
+
+    impl ChainChangesBuilder for TestChanges {
+        fn new(sink: Hash) -> Self {
+            Self { sink, removed: vec![], added: vec![] }
+        }
+
+        fn remove_chain_blocks(&mut self, removed_chain_block_hashes: &[Hash]) {
+            self.removed.extend_from_slice(removed_chain_block_hashes);
+        }
+
+        fn collect_chain_block(
+            &mut self,
+            _session: &ConsensusSessionBlocking<'_>,
+            hash: Hash,
+            acceptance_data: &AcceptanceData,
+        ) -> ConsensusResult<()> {
+            assert!(!acceptance_data.is_empty());
+            self.added.push(hash);
+            Ok(())
+        }
+    }
+
+    /// An in-memory index of the selected chain blocks above the pruning point
+    struct TestIndex {
+        consensus_manager: Arc<ConsensusManager>,
+        sink: Option<Hash>,
+        chain: Vec<Hash>,
+    }
+
+    impl ChainIndex for TestIndex {
+        type Changes = TestChanges;
+        type Error = TestIndexError;
+        const IDENT: &'static str = "test index";
+
+        fn consensus_manager(&self) -> &Arc<ConsensusManager> {
+            &self.consensus_manager
+        }
+
+        fn get_sink(&self) -> StoreResult<Hash> {
+            self.sink.ok_or(StoreError::KeyNotFound(DbKey::prefix_only(b"sink")))
+        }
+
+        fn commit_changes(&mut self, changes: Self::Changes) -> StoreResult<()> {
+            for hash in changes.removed {
+                assert_eq!(self.chain.pop(), Some(hash));
+            }
+            self.chain.extend(changes.added);
+            self.sink = Some(changes.sink);
+            Ok(())
+        }
+
+        fn delete_all(&mut self) -> StoreResult<()> {
+            self.chain.clear();
+            self.sink = None;
+            Ok(())
+        }
+    }
+
+    fn add_chain(tc: &TestConsensus, root: Hash, hashes: impl Iterator<Item = u64>) {
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+        futures::executor::block_on(tc.add_utxo_valid_chain(root, hashes.map(Hash::from), miner_data)).unwrap();
+    }
+
+    fn assert_matches_selected_chain(tc: &TestConsensus, index: &RwLock<TestIndex>) {
+        let index = index.read();
+        assert!(index.is_chain_synced().unwrap());
+        assert_eq!(index.chain, tc.get_virtual_chain_from_block(tc.pruning_point()).unwrap().added);
+    }
+
+    #[test]
+    fn test_chain_index_sync() {
+        kaspa_core::log::try_init_logger("INFO");
+
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+            })
+            .build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+
+        // Sync a new index from scratch
+        add_chain(&tc, config.genesis.hash, 1..=4);
+        let index = TestIndex { consensus_manager, sink: None, chain: vec![] }.init().unwrap();
+        assert_matches_selected_chain(&tc, &index);
+
+        // Apply a notified reorg into a longer competing chain
+        let old_sink = tc.get_sink();
+        add_chain(&tc, Hash::from(2), 11..=14);
+        assert!(!index.read().is_chain_synced().unwrap());
+        let chain_path = tc.get_virtual_chain_from_block(old_sink).unwrap();
+        assert_eq!(chain_path.removed.len(), 2);
+        let acceptance_data = tc.get_blocks_acceptance_data(&chain_path.added).unwrap();
+        index.write().apply_chain_changes(&chain_path.removed, &chain_path.added, &acceptance_data).unwrap();
+        assert_matches_selected_chain(&tc, &index);
+
+        // Catch up with a reorg which was not notified
+        add_chain(&tc, Hash::from(12), 21..=25);
+        assert!(index.write().catch_up().unwrap());
+        assert_matches_selected_chain(&tc, &index);
+
+        // Catching up from a sink unknown to consensus is not possible, but resyncing is
+        index.write().sink = Some(Hash::from(1000));
+        assert!(!index.write().catch_up().unwrap());
+        index.write().resync_chain().unwrap();
+        assert_matches_selected_chain(&tc, &index);
+
+        drop(index);
+        tc.shutdown(wait_handles);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod chain_index;
 pub mod connection;
 pub mod indexed_utxos;
 pub mod notification;
@@ -0,0 +1,30 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The acceptance of a transaction by a chain block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxAcceptance {
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting chain block
+    pub accepting_daa_score: u64,
+    /// The merged block which included the accepted transaction
+    pub including_block_hash: Hash,
+    /// The index of the transaction within the including block
+    pub index_within_block: u32,
+}
+
+impl TxAcceptance {
+    /// Creates a new [`TxAcceptance`]
+    pub fn new(accepting_block_hash: Hash, accepting_daa_score: u64, including_block_hash: Hash, index_within_block: u32) -> Self {
+        Self { accepting_block_hash, accepting_daa_score, including_block_hash, index_within_block }
+    }
+}
+
+impl MemSizeEstimator for TxAcceptance {}
+
+/// A map of transaction acceptances by transaction id
+pub type TxAcceptanceByTransactionId = HashMap<TransactionId, TxAcceptance>;
@@ -0,0 +1,191 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::TxAcceptanceByTransactionId,
+    stores::store_manager::Store,
+    update_container::TxIndexChanges,
+    IDENT,
+};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::trace;
+use kaspa_database::prelude::{StoreResult, DB};
+use kaspa_hashes::Hash;
+use kaspa_index_core::chain_index::ChainIndex;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+/// TxIndex maps accepted transaction ids to the chain block which accepted them, following the virtual selected chain.
+/// Note: The TxIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        Self { consensus_manager, store: Store::new(db) }.init()
+    }
+}
+
+impl ChainIndex for TxIndex {
+    type Changes = TxIndexChanges;
+    type Error = TxIndexError;
+    const IDENT: &'static str = IDENT;
+
+    fn consensus_manager(&self) -> &Arc<ConsensusManager> {
+        &self.consensus_manager
+    }
+
+    fn get_sink(&self) -> StoreResult<Hash> {
+        self.store.get_sink()
+    }
+
+    fn commit_changes(&mut self, changes: Self::Changes) -> StoreResult<()> {
+        self.store.commit_changes(&changes)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.store.delete_all()
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    /// Retrieve the acceptances of the given transactions from the txindex db.
+    fn get_transaction_acceptances(&self, transaction_ids: &[TransactionId]) -> StoreResult<TxAcceptanceByTransactionId> {
+        trace!("[{0}] retrieving acceptances of {1} transactions", IDENT, transaction_ids.len());
+
+        self.store.get_acceptances(transaction_ids)
+    }
+
+    /// Retrieve the stored sink of the txindex.
+    fn get_txindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Updates the [TxIndex] with the supplied virtual selected chain change:
+    /// reverts the acceptances of removed chain blocks and records those of added chain blocks in a single db write.
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        self.apply_chain_changes(&removed_chain_block_hashes, &added_chain_block_hashes, &added_chain_blocks_acceptance_data)
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd. This is done via comparing the txindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the txindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        self.is_chain_synced()
+    }
+
+    /// Deletes and reinstates the txindex database, syncing it from scratch via the selected chain from the pruning point.
+    ///
+    /// **Notes:**
+    /// 1) Transactions accepted by the pruning point itself, or by blocks below it, are not indexed.
+    /// 2) resyncing while consensus notifies of chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> TxIndexResult<()> {
+        self.resync_chain()
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::TxIndexApi, TxIndex};
+    use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+    use kaspa_consensus_core::{api::ConsensusApi, coinbase::MinerData, tx::ScriptPublicKey};
+    use kaspa_consensusmanager::ConsensusManager;
+    use kaspa_database::create_temp_db;
+    use kaspa_database::prelude::ConnBuilder;
+    use kaspa_hashes::Hash;
+    use parking_lot::RwLock;
+    use std::sync::Arc;
+
+    fn add_chain(tc: &TestConsensus, root: Hash, hashes: impl Iterator<Item = u64>, extra_data: Vec<u8>) {
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), extra_data);
+        futures::executor::block_on(tc.add_utxo_valid_chain(root, hashes.map(Hash::from), miner_data)).unwrap();
+    }
+
+    /// Asserts that the txindex maps every transaction accepted by the current selected chain to its accepting block.
+    fn assert_matches_selected_chain(tc: &TestConsensus, txindex: &Arc<RwLock<TxIndex>>) -> usize {
+        let chain = tc.get_virtual_chain_from_block(tc.pruning_point()).unwrap().added;
+        let mut count = 0;
+        for (hash, acceptance_data) in chain.iter().copied().zip(tc.get_blocks_acceptance_data(&chain).unwrap()) {
+            for mergeset_block in acceptance_data.iter() {
+                let ids = mergeset_block.accepted_transactions.iter().map(|entry| entry.transaction_id).collect::<Vec<_>>();
+                let acceptances = txindex.read().get_transaction_acceptances(&ids).unwrap();
+                for entry in mergeset_block.accepted_transactions.iter() {
+                    let acceptance = acceptances.get(&entry.transaction_id).expect("expected an indexed transaction");
+                    assert_eq!(acceptance.accepting_block_hash, hash);
+                    assert_eq!(acceptance.accepting_daa_score, tc.get_header(hash).unwrap().daa_score);
+                    assert_eq!(acceptance.including_block_hash, mergeset_block.block_hash);
+                    assert_eq!(acceptance.index_within_block, entry.index_within_block);
+                    count += 1;
+                }
+            }
+        }
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), tc.get_sink());
+        count
+    }
+
+    #[test]
+    fn test_txindex() {
+        kaspa_core::log::try_init_logger("INFO");
+
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+            })
+            .build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+        let (_txindex_db_lifetime, txindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+
+        // Build a first chain and sync the txindex from scratch.
+        add_chain(&tc, config.genesis.hash, 1..=4, vec![]);
+        let txindex = TxIndex::new(consensus_manager, txindex_db).unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert!(assert_matches_selected_chain(&tc, &txindex) > 0);
+
+        let old_sink = tc.get_sink();
+        let old_chain = tc.get_virtual_chain_from_block(tc.pruning_point()).unwrap().added;
+        let old_acceptance_data = tc.get_blocks_acceptance_data(&old_chain).unwrap();
+
+        // Reorg into a longer competing chain with distinct coinbase transactions.
+        add_chain(&tc, config.genesis.hash, 11..=16, vec![1]);
+        assert!(!txindex.read().is_synced().unwrap());
+        let chain_path = tc.get_virtual_chain_from_block(old_sink).unwrap();
+        assert_eq!(chain_path.removed.len(), old_chain.len());
+        let acceptance_data = tc.get_blocks_acceptance_data(&chain_path.added).unwrap();
+        txindex.write().update(Arc::new(chain_path.removed), Arc::new(chain_path.added), Arc::new(acceptance_data)).unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert!(assert_matches_selected_chain(&tc, &txindex) > 0);
+
+        // Transactions accepted only by the removed chain must no longer be indexed.
+        let old_ids = old_acceptance_data
+            .iter()
+            .flat_map(|acceptance_data| acceptance_data.iter())
+            .filter(|mergeset_block| mergeset_block.block_hash != config.genesis.hash)
+            .flat_map(|mergeset_block| mergeset_block.accepted_transactions.iter().map(|entry| entry.transaction_id))
+            .collect::<Vec<_>>();
+        assert!(!old_ids.is_empty());
+        assert!(txindex.read().get_transaction_acceptances(&old_ids).unwrap().is_empty());
+
+        drop(txindex);
+        tc.shutdown(wait_handles);
+    }
+}
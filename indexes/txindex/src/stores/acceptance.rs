@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+use crate::model::TxAcceptance;
+
+/// Reader API for `TxAcceptanceStore`.
+pub trait TxAcceptanceStoreReader {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<TxAcceptance>;
+}
+
+pub trait TxAcceptanceStore: TxAcceptanceStoreReader {
+    fn insert_many(&mut self, writer: &mut BatchDbWriter, acceptances: &[(TransactionId, TxAcceptance)]) -> StoreResult<()>;
+    fn delete(&mut self, writer: &mut BatchDbWriter, transaction_id: TransactionId) -> StoreResult<()>;
+
+    /// removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxAcceptanceStore` trait
+#[derive(Clone)]
+pub struct DbTxAcceptanceStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxAcceptance>,
+}
+
+impl DbTxAcceptanceStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptance.into()) }
+    }
+}
+
+impl TxAcceptanceStoreReader for DbTxAcceptanceStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<TxAcceptance> {
+        self.access.read(transaction_id)
+    }
+}
+
+impl TxAcceptanceStore for DbTxAcceptanceStore {
+    fn insert_many(&mut self, writer: &mut BatchDbWriter, acceptances: &[(TransactionId, TxAcceptance)]) -> StoreResult<()> {
+        if acceptances.is_empty() {
+            return Ok(());
+        }
+        self.access.write_many(writer, &mut acceptances.iter().copied())
+    }
+
+    fn delete(&mut self, writer: &mut BatchDbWriter, transaction_id: TransactionId) -> StoreResult<()> {
+        self.access.delete(writer, transaction_id)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+
+/// Reader API for `AcceptedTransactionsStore`.
+pub trait AcceptedTransactionsStoreReader {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<TransactionId>>>;
+}
+
+/// Keeps the ids of the transactions accepted by each indexed chain block, so that
+/// acceptances can be reverted when the block is removed from the selected chain.
+pub trait AcceptedTransactionsStore: AcceptedTransactionsStoreReader {
+    fn insert(
+        &mut self,
+        writer: &mut BatchDbWriter,
+        accepting_block_hash: Hash,
+        transaction_ids: Arc<Vec<TransactionId>>,
+    ) -> StoreResult<()>;
+    fn delete(&mut self, writer: &mut BatchDbWriter, accepting_block_hash: Hash) -> StoreResult<()>;
+
+    /// removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AcceptedTransactionsStore` trait
+#[derive(Clone)]
+pub struct DbAcceptedTransactionsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Arc<Vec<TransactionId>>>,
+}
+
+impl DbAcceptedTransactionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptedTransactions.into()),
+        }
+    }
+}
+
+impl AcceptedTransactionsStoreReader for DbAcceptedTransactionsStore {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<TransactionId>>> {
+        self.access.read(accepting_block_hash)
+    }
+}
+
+impl AcceptedTransactionsStore for DbAcceptedTransactionsStore {
+    fn insert(
+        &mut self,
+        writer: &mut BatchDbWriter,
+        accepting_block_hash: Hash,
+        transaction_ids: Arc<Vec<TransactionId>>,
+    ) -> StoreResult<()> {
+        self.access.write(writer, accepting_block_hash, transaction_ids)
+    }
+
+    fn delete(&mut self, writer: &mut BatchDbWriter, accepting_block_hash: Hash) -> StoreResult<()> {
+        self.access.delete(writer, accepting_block_hash)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
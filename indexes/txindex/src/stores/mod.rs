@@ -0,0 +1,4 @@
+mod acceptance;
+mod accepted_transactions;
+mod sink;
+pub mod store_manager;
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::trace;
+use kaspa_database::prelude::WriteBatch;
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+
+use crate::{
+    model::{TxAcceptance, TxAcceptanceByTransactionId},
+    stores::{
+        acceptance::{DbTxAcceptanceStore, TxAcceptanceStore, TxAcceptanceStoreReader},
+        accepted_transactions::{AcceptedTransactionsStore, AcceptedTransactionsStoreReader, DbAcceptedTransactionsStore},
+        sink::{DbTxIndexSinkStore, TxIndexSinkStore, TxIndexSinkStoreReader},
+    },
+    update_container::TxIndexChanges,
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    sink_store: DbTxIndexSinkStore,
+    acceptance_store: DbTxAcceptanceStore,
+    accepted_transactions_store: DbAcceptedTransactionsStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            sink_store: DbTxIndexSinkStore::new(db.clone()),
+            acceptance_store: DbTxAcceptanceStore::new(db.clone(), CachePolicy::Count(10_000)),
+            accepted_transactions_store: DbAcceptedTransactionsStore::new(db, CachePolicy::Count(1_000)),
+        }
+    }
+
+    pub fn get_acceptances(&self, transaction_ids: &[TransactionId]) -> StoreResult<TxAcceptanceByTransactionId> {
+        let mut acceptances = TxAcceptanceByTransactionId::with_capacity(transaction_ids.len());
+        for transaction_id in transaction_ids.iter().copied() {
+            match self.acceptance_store.get(transaction_id) {
+                Ok(acceptance) => {
+                    acceptances.insert(transaction_id, acceptance);
+                }
+                Err(StoreError::KeyNotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(acceptances)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically commits the given changes to the txindex database.
+    ///
+    /// Acceptances of removed chain blocks are reverted before the acceptances of added chain blocks are written,
+    /// so a transaction re-accepted by the new chain ends up pointing to its new accepting block.
+    pub fn commit_changes(&mut self, changes: &TxIndexChanges) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+
+        for removed_block_hash in changes.removed_chain_blocks.iter().copied() {
+            let transaction_ids = match self.accepted_transactions_store.get(removed_block_hash) {
+                Ok(transaction_ids) => transaction_ids,
+                Err(StoreError::KeyNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            for transaction_id in transaction_ids.iter().copied() {
+                if self.is_accepted_by(transaction_id, removed_block_hash)? {
+                    self.acceptance_store.delete(&mut writer, transaction_id)?;
+                }
+            }
+            self.accepted_transactions_store.delete(&mut writer, removed_block_hash)?;
+        }
+
+        for added in changes.added_chain_blocks.iter() {
+            let transaction_ids = added.acceptances.iter().map(|(transaction_id, _)| *transaction_id).collect::<Vec<_>>();
+            self.accepted_transactions_store.insert(&mut writer, added.accepting_block_hash, Arc::new(transaction_ids))?;
+            self.acceptance_store.insert_many(&mut writer, &added.acceptances)?;
+        }
+
+        self.sink_store.set(&mut writer, changes.sink)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    fn is_accepted_by(&self, transaction_id: TransactionId, accepting_block_hash: Hash) -> StoreResult<bool> {
+        match self.acceptance_store.get(transaction_id) {
+            Ok(TxAcceptance { accepting_block_hash: hash, .. }) => Ok(hash == accepting_block_hash),
+            Err(StoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Resets the txindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear txindex database...", IDENT);
+
+        self.sink_store.remove()?;
+        self.acceptance_store.delete_all()?;
+        self.accepted_transactions_store.delete_all()?;
+
+        trace!("[{0}] clearing txindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, errors::consensus::ConsensusResult, tx::TransactionId};
+use kaspa_consensusmanager::ConsensusSessionBlocking;
+use kaspa_hashes::Hash;
+use kaspa_index_core::chain_index::ChainChangesBuilder;
+
+use crate::model::TxAcceptance;
+
+/// The transaction acceptances of a single chain block.
+pub struct ChainBlockAcceptances {
+    pub accepting_block_hash: Hash,
+    pub acceptances: Vec<(TransactionId, TxAcceptance)>,
+}
+
+/// A struct holding all changes to the txindex, resulting from a virtual selected chain change.
+pub struct TxIndexChanges {
+    /// Chain blocks removed from the selected chain, ordered from high to low
+    pub removed_chain_blocks: Vec<Hash>,
+    /// Chain blocks added to the selected chain, ordered from low to high
+    pub added_chain_blocks: Vec<ChainBlockAcceptances>,
+    /// The new sink of the selected chain
+    pub sink: Hash,
+}
+
+impl ChainChangesBuilder for TxIndexChanges {
+    fn new(sink: Hash) -> Self {
+        Self { removed_chain_blocks: Vec::new(), added_chain_blocks: Vec::new(), sink }
+    }
+
+    fn remove_chain_blocks(&mut self, removed_chain_block_hashes: &[Hash]) {
+        self.removed_chain_blocks.extend_from_slice(removed_chain_block_hashes);
+    }
+
+    fn collect_chain_block(
+        &mut self,
+        session: &ConsensusSessionBlocking<'_>,
+        hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> ConsensusResult<()> {
+        self.add_chain_block(hash, session.get_header(hash)?.daa_score, acceptance_data);
+        Ok(())
+    }
+}
+
+impl TxIndexChanges {
+    /// Add a chain block along with the acceptance data of its mergeset.
+    pub fn add_chain_block(&mut self, accepting_block_hash: Hash, accepting_daa_score: u64, acceptance_data: &AcceptanceData) {
+        let acceptances = acceptance_data
+            .iter()
+            .flat_map(|mergeset_block| {
+                mergeset_block.accepted_transactions.iter().map(move |entry| {
+                    (
+                        entry.transaction_id,
+                        TxAcceptance::new(
+                            accepting_block_hash,
+                            accepting_daa_score,
+                            mergeset_block.block_hash,
+                            entry.index_within_block,
+                        ),
+                    )
+                })
+            })
+            .collect();
+        self.added_chain_blocks.push(ChainBlockAcceptances { accepting_block_hash, acceptances });
+    }
+}
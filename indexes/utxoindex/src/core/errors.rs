@@ -3,6 +3,8 @@ use thiserror::Error;
 
 use crate::IDENT;
 use kaspa_database::prelude::StoreError;
+use kaspa_hashes::Hash;
+use kaspa_utils::snapshot_file::SnapshotFileError;
 
 /// Errors originating from the [`UtxoIndex`].
 #[derive(Error, Debug)]
@@ -12,6 +14,18 @@ pub enum UtxoIndexError {
 
     #[error("[{IDENT}]: {0}")]
     DBResetError(#[from] io::Error),
+
+    #[error("[{IDENT}]: {0}")]
+    SnapshotFileError(#[from] SnapshotFileError),
+
+    #[error("[{IDENT}]: snapshot tips do not match the consensus virtual parents")]
+    SnapshotTipsMismatch,
+
+    #[error("[{IDENT}]: snapshot circulating supply {0} does not match the sum of its utxos {1}")]
+    SnapshotSupplyMismatch(u64, u64),
+
+    #[error("[{IDENT}]: snapshot utxo multiset {0} does not match the consensus virtual utxo multiset {1}")]
+    SnapshotMultisetMismatch(Hash, Hash),
 }
 
 /// Results originating from the [`UtxoIndex`].
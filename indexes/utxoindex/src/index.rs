@@ -2,20 +2,28 @@ use crate::{
     api::UtxoIndexApi,
     errors::{UtxoIndexError, UtxoIndexResult},
     model::{CirculatingSupply, UtxoChanges, UtxoSetByScriptPublicKey},
+    snapshot::{write_snapshot, SnapshotReader},
     stores::store_manager::Store,
     update_container::UtxoIndexChanges,
     IDENT,
 };
-use kaspa_consensus_core::{tx::ScriptPublicKeys, utxo::utxo_diff::UtxoDiff, BlockHashSet};
+use kaspa_consensus_core::{
+    muhash::MuHashExtensions,
+    tx::{ScriptPublicKeys, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
+    BlockHashSet,
+};
 use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
-use kaspa_core::{info, trace};
+use kaspa_core::{info, trace, warn};
 use kaspa_database::prelude::{StoreError, StoreResult, DB};
 use kaspa_hashes::Hash;
 use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
+use kaspa_muhash::MuHash;
 use kaspa_utils::arc::ArcExtensions;
 use parking_lot::RwLock;
 use std::{
     fmt::Debug,
+    path::Path,
     sync::{Arc, Weak},
 };
 
@@ -32,14 +40,110 @@ pub struct UtxoIndex {
 impl UtxoIndex {
     /// Creates a new [`UtxoIndex`] within a [`RwLock`]
     pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> UtxoIndexResult<Arc<RwLock<Self>>> {
+        Self::new_with_snapshot(consensus_manager, db, None)
+    }
+
+    /// Creates a new [`UtxoIndex`] within a [`RwLock`].
+    ///
+    /// If the utxoindex is not synced, it is restored from the supplied snapshot file when possible,
+    /// falling back to a resync from the consensus database otherwise.
+    pub fn new_with_snapshot(
+        consensus_manager: Arc<ConsensusManager>,
+        db: Arc<DB>,
+        snapshot_path: Option<&Path>,
+    ) -> UtxoIndexResult<Arc<RwLock<Self>>> {
         let mut utxoindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
         if !utxoindex.is_synced()? {
-            utxoindex.resync()?;
+            match snapshot_path.map(|path| utxoindex.import_snapshot(path)) {
+                Some(Ok(())) => {}
+                Some(Err(err)) => {
+                    warn!("Failed importing the utxoindex snapshot: {}", err);
+                    utxoindex.resync()?;
+                }
+                None => utxoindex.resync()?,
+            }
         }
         let utxoindex = Arc::new(RwLock::new(utxoindex));
         consensus_manager.register_consensus_reset_handler(Arc::new(UtxoIndexConsensusResetHandler::new(Arc::downgrade(&utxoindex))));
         Ok(utxoindex)
     }
+
+    /// Exports the utxoindex stores (utxo entries, circulating supply and tips) into a snapshot file.
+    ///
+    /// Note: Use a read lock when accessing this method
+    pub fn export_snapshot(&self, path: &Path) -> UtxoIndexResult<()> {
+        info!("Exporting a utxoindex snapshot to {}", path.display());
+
+        let tips = self.store.get_tips()?;
+        let circulating_supply = self.store.get_circulating_supply()?;
+        let utxos = self.store.iter_utxos().map(|res| -> UtxoIndexResult<_> {
+            let (script_public_key, outpoint, entry) = res?;
+            Ok((outpoint, UtxoEntry::new(entry.amount, script_public_key, entry.block_daa_score, entry.is_coinbase)))
+        });
+        let count = write_snapshot(path, &tips, circulating_supply, utxos)?;
+
+        info!("Exported {} utxos to the utxoindex snapshot {}", count, path.display());
+        Ok(())
+    }
+
+    /// Deletes and reinstates the utxoindex database from a snapshot file, validating it against the consensus virtual state:
+    /// the snapshot tips must match the virtual parents and its utxos must match the virtual UTXO set multiset.
+    ///
+    /// **Notes:**
+    /// 1) If the snapshot is invalid, the utxoindex database is left empty i.e. not synced.
+    /// 2) importing while consensus notifies of utxo differences, may result in a corrupted db.
+    pub fn import_snapshot(&mut self, path: &Path) -> UtxoIndexResult<()> {
+        info!("Importing the utxoindex from snapshot {}...", path.display());
+
+        self.store.delete_all()?;
+        let res = self.import_snapshot_utxos(path);
+        if res.is_err() {
+            self.store.delete_all()?;
+        }
+        res
+    }
+
+    fn import_snapshot_utxos(&mut self, path: &Path) -> UtxoIndexResult<()> {
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut reader = SnapshotReader::open(path)?;
+        if reader.tips != session.get_virtual_parents() {
+            return Err(UtxoIndexError::SnapshotTipsMismatch);
+        }
+
+        let mut multiset = MuHash::new();
+        let mut circulating_supply: CirculatingSupply = 0;
+        let mut count = 0usize;
+        while let Some(chunk) = reader.next_chunk()? {
+            trace!("[{0}] importing a chunk of {1} utxos from the snapshot", IDENT, chunk.len());
+            count += chunk.len();
+            chunk.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+
+            let mut utxoindex_changes = UtxoIndexChanges::new();
+            utxoindex_changes.add_utxos_from_vector(chunk);
+            circulating_supply += utxoindex_changes.supply_change as CirculatingSupply;
+
+            self.store.update_utxo_state(&utxoindex_changes.utxo_changes.added, &utxoindex_changes.utxo_changes.removed, false)?;
+        }
+        let tips = reader.tips.clone();
+        let snapshot_supply = reader.circulating_supply;
+        reader.finish()?;
+
+        if snapshot_supply != circulating_supply {
+            return Err(UtxoIndexError::SnapshotSupplyMismatch(snapshot_supply, circulating_supply));
+        }
+        let (snapshot_multiset, virtual_multiset) = (multiset.finalize(), session.get_virtual_utxo_multiset().finalize());
+        if snapshot_multiset != virtual_multiset {
+            return Err(UtxoIndexError::SnapshotMultisetMismatch(snapshot_multiset, virtual_multiset));
+        }
+
+        self.store.insert_circulating_supply(circulating_supply, false)?;
+        self.store.set_tips(tips, false)?;
+
+        info!("Imported {} utxos into the utxoindex", count);
+        Ok(())
+    }
 }
 
 impl UtxoIndexApi for UtxoIndex {
@@ -213,7 +317,10 @@ impl ConsensusResetHandler for UtxoIndexConsensusResetHandler {
 
 #[cfg(test)]
 mod tests {
-    use crate::{api::UtxoIndexApi, model::CirculatingSupply, testutils::virtual_change_emulator::VirtualChangeEmulator, UtxoIndex};
+    use crate::{
+        api::UtxoIndexApi, errors::UtxoIndexError, model::CirculatingSupply,
+        testutils::virtual_change_emulator::VirtualChangeEmulator, UtxoIndex,
+    };
     use kaspa_consensus::{
         config::Config,
         consensus::test_consensus::TestConsensus,
@@ -225,12 +332,14 @@ mod tests {
     };
     use kaspa_consensus_core::{
         api::ConsensusApi,
+        muhash::MuHashExtensions,
         utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff},
     };
     use kaspa_consensusmanager::ConsensusManager;
     use kaspa_core::info;
     use kaspa_database::create_temp_db;
     use kaspa_database::prelude::ConnBuilder;
+    use kaspa_muhash::MuHash;
     use std::{collections::HashSet, sync::Arc, time::Instant};
 
     /// TODO: use proper Simnet when implemented.
@@ -381,4 +490,58 @@ mod tests {
         drop(utxoindex);
         drop(tc);
     }
+
+    #[test]
+    fn test_utxoindex_snapshot() {
+        kaspa_core::log::try_init_logger("INFO");
+
+        // Initialize a test consensus with a virtual utxo set and its multiset.
+        let mut virtual_change_emulator = VirtualChangeEmulator::new();
+        virtual_change_emulator.fill_utxo_collection(5_000, 50);
+        let mut multiset = MuHash::new();
+        virtual_change_emulator.utxo_collection.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+        let config = Config::new(DEVNET_PARAMS);
+        let tc = Arc::new(TestConsensus::new(&config));
+        let test_consensus_virtual_state = Arc::new(VirtualState {
+            parents: Vec::from_iter(virtual_change_emulator.tips.clone()),
+            multiset,
+            utxo_diff: UtxoDiff::new(virtual_change_emulator.utxo_collection.clone(), UtxoCollection::new()),
+            ..Default::default()
+        });
+        tc.virtual_stores.write().utxo_set.write_diff(&test_consensus_virtual_state.utxo_diff).expect("expected write diff");
+        tc.virtual_stores.write().state.set(test_consensus_virtual_state).expect("setting of state");
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+
+        // Export a snapshot from a synced utxoindex.
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = snapshot_dir.path().join("utxoindex.snapshot");
+        let (_source_db_lifetime, source_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let source = UtxoIndex::new(consensus_manager.clone(), source_db).unwrap();
+        source.read().export_snapshot(&snapshot_path).unwrap();
+
+        // Restore another utxoindex from the snapshot.
+        let (_target_db_lifetime, target_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let target = UtxoIndex::new_with_snapshot(consensus_manager.clone(), target_db, Some(&snapshot_path)).unwrap();
+        assert!(target.read().is_synced().unwrap());
+        assert_eq!(target.read().get_circulating_supply().unwrap(), virtual_change_emulator.circulating_supply);
+        assert_eq!(target.read().get_all_outpoints().unwrap(), source.read().get_all_outpoints().unwrap());
+        let script_public_keys = HashSet::from_iter(virtual_change_emulator.script_public_key_pool.iter().cloned());
+        assert_eq!(
+            target.read().get_balance_by_script_public_keys(script_public_keys.clone()).unwrap(),
+            source.read().get_balance_by_script_public_keys(script_public_keys).unwrap()
+        );
+
+        // A snapshot whose utxos do not match the consensus virtual utxo multiset is rejected.
+        let tampered_virtual_state =
+            Arc::new(VirtualState { parents: Vec::from_iter(virtual_change_emulator.tips.clone()), ..Default::default() });
+        tc.virtual_stores.write().state.set(tampered_virtual_state).expect("setting of state");
+        assert!(matches!(target.write().import_snapshot(&snapshot_path), Err(UtxoIndexError::SnapshotMultisetMismatch(_, _))));
+        assert!(!target.read().is_synced().unwrap());
+
+        // Deconstruct
+        drop(source);
+        drop(target);
+        drop(consensus_manager);
+        drop(tc);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod core; //all things visible to the outside
 mod index;
+mod snapshot;
 mod stores;
 mod update_container;
 
@@ -0,0 +1,80 @@
+//! Snapshot files of the utxoindex stores, built on the [`kaspa_utils::snapshot_file`] format.
+//!
+//! The snapshot values are laid out as follows:
+//! 1) the utxoindex tips and circulating supply
+//! 2) the indexed utxos, as a chunked collection
+
+use crate::{errors::UtxoIndexResult, model::CirculatingSupply};
+use kaspa_consensus_core::{
+    tx::{TransactionOutpoint, UtxoEntry},
+    BlockHashSet,
+};
+use kaspa_hashes::Hash;
+use kaspa_utils::snapshot_file::{SnapshotFileReader, SnapshotFileWriter, MAGIC_SIZE};
+use std::{fs::File, io::BufReader, path::Path};
+
+/// Magic bytes opening every utxoindex snapshot file
+pub const SNAPSHOT_MAGIC: [u8; MAGIC_SIZE] = *b"RWUTXIDX";
+
+/// Current version of the snapshot file format
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+/// Number of utxos written per snapshot chunk
+const SNAPSHOT_CHUNK_SIZE: usize = 2048;
+
+/// Writes a snapshot of the utxoindex stores into `path` and returns the number of written utxos
+pub(crate) fn write_snapshot(
+    path: &Path,
+    tips: &BlockHashSet,
+    circulating_supply: CirculatingSupply,
+    utxos: impl Iterator<Item = UtxoIndexResult<(TransactionOutpoint, UtxoEntry)>>,
+) -> UtxoIndexResult<u64> {
+    let mut writer = SnapshotFileWriter::create(path, &SNAPSHOT_MAGIC, SNAPSHOT_VERSION)?;
+    writer.write(&tips.iter().copied().collect::<Vec<Hash>>())?;
+    writer.write(&circulating_supply)?;
+
+    let mut count = 0u64;
+    let mut chunk = Vec::with_capacity(SNAPSHOT_CHUNK_SIZE);
+    for utxo in utxos {
+        chunk.push(utxo?);
+        if chunk.len() == SNAPSHOT_CHUNK_SIZE {
+            writer.write_chunk(&chunk)?;
+            count += chunk.len() as u64;
+            chunk.clear();
+        }
+    }
+    writer.write_chunk(&chunk)?;
+    count += chunk.len() as u64;
+    writer.end_chunks()?;
+
+    writer.finish()?;
+    Ok(count)
+}
+
+/// Sequential reader of a utxoindex snapshot.
+///
+/// Note: the snapshot checksum is only verified by [`SnapshotReader::finish`], after all chunks were read.
+pub(crate) struct SnapshotReader {
+    reader: SnapshotFileReader<BufReader<File>>,
+    pub tips: BlockHashSet,
+    pub circulating_supply: CirculatingSupply,
+}
+
+impl SnapshotReader {
+    pub fn open(path: &Path) -> UtxoIndexResult<Self> {
+        let mut reader = SnapshotFileReader::open(path, &SNAPSHOT_MAGIC, SNAPSHOT_VERSION)?;
+        let tips: Vec<Hash> = reader.read()?;
+        let circulating_supply = reader.read()?;
+        Ok(Self { reader, tips: tips.into_iter().collect(), circulating_supply })
+    }
+
+    /// Reads the next chunk of utxos, returning `None` once all chunks were read
+    pub fn next_chunk(&mut self) -> UtxoIndexResult<Option<Vec<(TransactionOutpoint, UtxoEntry)>>> {
+        Ok(self.reader.next_chunk()?)
+    }
+
+    /// Verifies the snapshot checksum and that no data follows it
+    pub fn finish(self) -> UtxoIndexResult<()> {
+        Ok(self.reader.finish()?)
+    }
+}
@@ -4,7 +4,7 @@ use kaspa_consensus_core::tx::{
     ScriptPublicKey, ScriptPublicKeyVersion, ScriptPublicKeys, ScriptVec, TransactionIndexType, TransactionOutpoint,
 };
 use kaspa_core::debug;
-use kaspa_database::prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB};
+use kaspa_database::prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
 use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
@@ -152,6 +152,19 @@ impl DbUtxoSetByScriptPublicKeyStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
         Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::UtxoIndex.into()) }
     }
+
+    /// Iterates over all the indexed utxos, ordered by script public key bucket.
+    pub fn iterator(&self) -> impl Iterator<Item = StoreResult<(ScriptPublicKey, TransactionOutpoint, CompactUtxoEntry)>> + '_ {
+        self.access.iterator().map(|res| {
+            let (key, entry) = res.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            let (bucket, outpoint) = key.split_at(key.len() - TRANSACTION_OUTPOINT_KEY_SIZE);
+            Ok((
+                ScriptPublicKeyBucket(bucket.to_vec()).into(),
+                TransactionOutpointKey(<[u8; TRANSACTION_OUTPOINT_KEY_SIZE]>::try_from(outpoint).unwrap()).into(),
+                entry,
+            ))
+        })
+    }
 }
 
 impl UtxoSetByScriptPublicKeyStoreReader for DbUtxoSetByScriptPublicKeyStore {
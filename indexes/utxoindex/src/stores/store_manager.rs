@@ -1,7 +1,7 @@
 use std::{collections::HashSet, sync::Arc};
 
 use kaspa_consensus_core::{
-    tx::{ScriptPublicKeys, TransactionOutpoint},
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
     BlockHashSet,
 };
 use kaspa_core::trace;
@@ -9,7 +9,7 @@ use kaspa_database::prelude::{CachePolicy, StoreResult, DB};
 use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
 
 use crate::{
-    model::UtxoSetByScriptPublicKey,
+    model::{CompactUtxoEntry, UtxoSetByScriptPublicKey},
     stores::{
         indexed_utxos::{DbUtxoSetByScriptPublicKeyStore, UtxoSetByScriptPublicKeyStore, UtxoSetByScriptPublicKeyStoreReader},
         supply::{CirculatingSupplyStore, CirculatingSupplyStoreReader, DbCirculatingSupplyStore},
@@ -42,6 +42,11 @@ impl Store {
         self.utxos_by_script_public_key_store.get_balance_from_script_public_keys(script_public_keys)
     }
 
+    /// Iterates over all the indexed utxos.
+    pub fn iter_utxos(&self) -> impl Iterator<Item = StoreResult<(ScriptPublicKey, TransactionOutpoint, CompactUtxoEntry)>> + '_ {
+        self.utxos_by_script_public_key_store.iterator()
+    }
+
     // This can have a big memory footprint, so it should be used only for tests.
     pub fn get_all_outpoints(&self) -> StoreResult<HashSet<TransactionOutpoint>> {
         self.utxos_by_script_public_key_store.get_all_outpoints()
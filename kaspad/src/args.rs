@@ -1,4 +1,5 @@
 use clap::{arg, Arg, ArgAction, Command};
+use kaspa_addressmanager::DEFAULT_BAN_DURATION;
 use kaspa_consensus_core::{
     config::Config,
     network::{NetworkId, NetworkType},
@@ -48,11 +49,14 @@ pub struct Args {
     #[serde(rename = "addpeer")]
     #[serde_as(as = "Vec<DisplayFromStr>")]
     pub add_peers: Vec<ContextualNetAddress>,
+    pub whitelist: Vec<String>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub listen: Option<ContextualNetAddress>,
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub addressindex: bool,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -69,12 +73,23 @@ pub struct Args {
     pub devnet: bool,
     pub simnet: bool,
     pub archival: bool,
+    pub retention_period_days: Option<f64>,
+    pub light: bool,
     pub sanity: bool,
     pub yes: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub externalip: Option<ContextualNetAddress>,
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub prometheus_listen: Option<ContextualNetAddress>,
+    pub import_utxoindex_snapshot: Option<String>,
+    pub export_utxoindex_snapshot: Option<String>,
+    pub import_pruning_snapshot: Option<String>,
+    pub export_pruning_snapshot: Option<String>,
+    pub verify_db: bool,
+    pub repair_db: bool,
+    pub reorg_alert_depth: Option<u64>,
     pub block_template_cache_lifetime: Option<u64>,
 
     #[cfg(feature = "devnet-prealloc")]
@@ -84,6 +99,16 @@ pub struct Args {
     #[cfg(feature = "devnet-prealloc")]
     pub prealloc_amount: u64,
 
+    pub p2p_encryption: bool,
+    pub p2p_allowlist: Vec<String>,
+    pub require_encryption: bool,
+    pub asn_map: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub proxy: Option<ContextualNetAddress>,
+    pub dandelion: bool,
+
+    pub ban_duration_sec: u64,
+
     pub disable_upnp: bool,
     #[serde(rename = "nodnsseed")]
     pub disable_dns_seeding: bool,
@@ -102,6 +127,8 @@ impl Default for Args {
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            addressindex: false,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
@@ -114,6 +141,8 @@ impl Default for Args {
             devnet: false,
             simnet: false,
             archival: false,
+            retention_period_days: None,
+            light: false,
             sanity: false,
             logdir: None,
             rpclisten: None,
@@ -121,11 +150,20 @@ impl Default for Args {
             log_level: "INFO".into(),
             connect_peers: vec![],
             add_peers: vec![],
+            whitelist: vec![],
             listen: None,
             user_agent_comments: vec![],
             yes: false,
             perf_metrics: false,
             perf_metrics_interval_sec: 10,
+            prometheus_listen: None,
+            import_utxoindex_snapshot: None,
+            export_utxoindex_snapshot: None,
+            import_pruning_snapshot: None,
+            export_pruning_snapshot: None,
+            verify_db: false,
+            repair_db: false,
+            reorg_alert_depth: None,
             externalip: None,
             block_template_cache_lifetime: None,
 
@@ -136,6 +174,15 @@ impl Default for Args {
             #[cfg(feature = "devnet-prealloc")]
             prealloc_amount: 1_000_000,
 
+            p2p_encryption: false,
+            p2p_allowlist: vec![],
+            require_encryption: false,
+            asn_map: None,
+            proxy: None,
+            dandelion: false,
+
+            ban_duration_sec: DEFAULT_BAN_DURATION.as_secs(),
+
             disable_upnp: false,
             disable_dns_seeding: false,
             disable_grpc: false,
@@ -147,11 +194,17 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
+        config.addressindex = self.addressindex;
         config.disable_upnp = self.disable_upnp;
+        config.enable_dandelion = self.dandelion;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
         config.is_archival = self.archival;
+        config.retention_depth = self.retention_period_days.map(|days| (days * 86400.0 * config.bps() as f64) as u64);
+        config.is_light = self.light;
+        config.reorg_alert_depth = self.reorg_alert_depth;
         // TODO: change to `config.enable_sanity_checks = self.sanity` when we reach stable versions
         config.enable_sanity_checks = true;
         config.user_agent_comments.clone_from(&self.user_agent_comments);
@@ -269,6 +322,15 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(ContextualNetAddress))
                 .help("Add peers to connect with at startup."),
         )
+        .arg(
+            Arg::new("whitelist")
+                .long("whitelist")
+                .value_name("CIDR")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Trust peers of the specified network (eg. 192.168.1.0/24): they are exempt from the inbound limit, never banned and get their transactions relayed with priority."),
+        )
         .arg(
             Arg::new("listen")
                 .long("listen")
@@ -311,6 +373,8 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction index"))
+        .arg(arg!(--addressindex "Enable the address transaction history index"))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -332,6 +396,15 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         .arg(arg!(--devnet "Use the development test network"))
         .arg(arg!(--simnet "Use the simulation test network"))
         .arg(arg!(--archival "Run as an archival node: avoids deleting old block data when moving the pruning point (Warning: heavy disk usage)"))
+        .arg(
+            Arg::new("retention-period-days")
+                .long("retention-period-days")
+                .value_name("DAYS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Keep full block data for the given number of days below the pruning point and only prune older data (partial archival)."),
+        )
+        .arg(arg!(--light "Run as a light node: syncs and verifies block headers only, keeping neither block bodies nor a UTXO set (serves header-chain RPC only)"))
         .arg(arg!(--sanity "Enable various sanity checks which might be compute-intensive (mostly performed during pruning)"))
         .arg(arg!(--yes "Answer yes to all interactive console questions"))
         .arg(
@@ -358,6 +431,93 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .value_parser(clap::value_parser!(u64))
                 .help("Interval in seconds for performance metrics collection."),
         )
+        .arg(
+            Arg::new("prometheus-listen")
+                .long("prometheus-listen")
+                .value_name("IP[:PORT]")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("127.0.0.1")
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to serve Prometheus metrics on at /metrics (default: disabled, default port: 9110)."),
+        )
+        .arg(
+            Arg::new("import-utxoindex-snapshot")
+                .long("import-utxoindex-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Restore the UTXO index from a snapshot file instead of resyncing it (requires --utxoindex)."),
+        )
+        .arg(
+            Arg::new("export-utxoindex-snapshot")
+                .long("export-utxoindex-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Export the UTXO index to a snapshot file once it is synced on startup (requires --utxoindex)."),
+        )
+        .arg(
+            Arg::new("import-pruning-snapshot")
+                .long("import-pruning-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Bootstrap consensus from a pruning point snapshot file instead of downloading the pruning point proof and UTXO set from peers."),
+        )
+        .arg(
+            Arg::new("export-pruning-snapshot")
+                .long("export-pruning-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Export the current pruning point, its proof, trusted data and UTXO set to a snapshot file on startup."),
+        )
+        .arg(arg!(--"verify-db" "Verify the consistency of the consensus database, report any inconsistencies and exit"))
+        .arg(arg!(--"repair-db" "With --verify-db, repair inconsistent derived stores (the selected chain index) by resyncing them from the other consensus stores"))
+        .arg(
+            Arg::new("reorg-alert-depth")
+                .long("reorg-alert-depth")
+                .value_name("BLOCKS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Log a warning and emit a ReorgAlert notification for every reorg removing at least this number of chain blocks."),
+        )
+        .arg(arg!(--"p2p-encryption" "Encrypt P2P connections with peers supporting it, falling back to plaintext for other peers"))
+        .arg(
+            Arg::new("p2p-allowlist")
+                .long("p2p-allowlist")
+                .value_name("PUBKEY")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Only connect with encrypted peers holding one of the specified static public keys (implies --p2p-encryption)."),
+        )
+        .arg(arg!(--"require-encryption" "Refuse plaintext P2P connections so that they cannot be downgraded (implies --p2p-encryption)"))
+        .arg(
+            Arg::new("asn-map")
+                .long("asn-map")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Diversify outbound peers by autonomous system using a file of 'CIDR ASN' lines (default: by IP prefix)."),
+        )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Connect to outbound P2P peers via a SOCKS5 proxy, required for connecting to .onion peers (default port: 9050). Disables DNS seeding."),
+        )
+        .arg(
+            Arg::new("ban-duration-sec")
+                .long("ban-duration-sec")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Duration in seconds of bans of misbehaving peers (default: 86400)."),
+        )
+        .arg(arg!(--dandelion "Hide the origin of relayed transactions by forwarding them to a single peer (Dandelion++ stem phase) before broadcasting them."))
         .arg(arg!(--"disable-upnp" "Disable upnp"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
@@ -422,6 +582,7 @@ impl Args {
             async_threads: arg_match_unwrap_or::<usize>(&m, "async_threads", defaults.async_threads),
             connect_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "connect-peers", defaults.connect_peers),
             add_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "add-peers", defaults.add_peers),
+            whitelist: arg_match_many_unwrap_or::<String>(&m, "whitelist", defaults.whitelist),
             listen: m.get_one::<ContextualNetAddress>("listen").cloned().or(defaults.listen),
             outbound_target: arg_match_unwrap_or::<usize>(&m, "outpeers", defaults.outbound_target),
             inbound_limit: arg_match_unwrap_or::<usize>(&m, "maxinpeers", defaults.inbound_limit),
@@ -431,19 +592,44 @@ impl Args {
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            addressindex: arg_match_unwrap_or::<bool>(&m, "addressindex", defaults.addressindex),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
             simnet: arg_match_unwrap_or::<bool>(&m, "simnet", defaults.simnet),
             archival: arg_match_unwrap_or::<bool>(&m, "archival", defaults.archival),
+            retention_period_days: m.get_one::<f64>("retention-period-days").cloned().or(defaults.retention_period_days),
+            light: arg_match_unwrap_or::<bool>(&m, "light", defaults.light),
             sanity: arg_match_unwrap_or::<bool>(&m, "sanity", defaults.sanity),
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(&m, "user_agent_comments", defaults.user_agent_comments),
             externalip: m.get_one::<ContextualNetAddress>("externalip").cloned(),
             perf_metrics: arg_match_unwrap_or::<bool>(&m, "perf-metrics", defaults.perf_metrics),
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
+            prometheus_listen: m.get_one::<ContextualNetAddress>("prometheus-listen").cloned().or(defaults.prometheus_listen),
+            import_utxoindex_snapshot: m
+                .get_one::<String>("import-utxoindex-snapshot")
+                .cloned()
+                .or(defaults.import_utxoindex_snapshot),
+            export_utxoindex_snapshot: m
+                .get_one::<String>("export-utxoindex-snapshot")
+                .cloned()
+                .or(defaults.export_utxoindex_snapshot),
+            import_pruning_snapshot: m.get_one::<String>("import-pruning-snapshot").cloned().or(defaults.import_pruning_snapshot),
+            export_pruning_snapshot: m.get_one::<String>("export-pruning-snapshot").cloned().or(defaults.export_pruning_snapshot),
+            verify_db: arg_match_unwrap_or::<bool>(&m, "verify-db", defaults.verify_db),
+            repair_db: arg_match_unwrap_or::<bool>(&m, "repair-db", defaults.repair_db),
+            reorg_alert_depth: m.get_one::<u64>("reorg-alert-depth").cloned().or(defaults.reorg_alert_depth),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
+            p2p_encryption: arg_match_unwrap_or::<bool>(&m, "p2p-encryption", defaults.p2p_encryption),
+            p2p_allowlist: arg_match_many_unwrap_or::<String>(&m, "p2p-allowlist", defaults.p2p_allowlist),
+            require_encryption: arg_match_unwrap_or::<bool>(&m, "require-encryption", defaults.require_encryption),
+            asn_map: m.get_one::<String>("asn-map").cloned().or(defaults.asn_map),
+            proxy: m.get_one::<ContextualNetAddress>("proxy").cloned().or(defaults.proxy),
+            dandelion: arg_match_unwrap_or::<bool>(&m, "dandelion", defaults.dandelion),
+            ban_duration_sec: arg_match_unwrap_or::<u64>(&m, "ban-duration-sec", defaults.ban_duration_sec),
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
@@ -549,8 +735,12 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --txindex                             Enable the transaction index
+      --addressindex                        Enable the address transaction history index
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
+      --retention-period-days=              Keep full block data for the given number of days below the pruning
+                                            point and only prune older data
       --protocol-version=                   Use non default p2p protocol version (default: 5)
       --enable-unsynced-mining              Allow the node to accept blocks from RPC while not synced
                                             (required when initiating a new network from genesis)
@@ -560,4 +750,14 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --override-dag-params-file=           Overrides DAG params (allowed only on devnet)
   -s, --service=                            Service command {install, remove, start, stop}
       --nogrpc                              Don't initialize the gRPC server
+      --prometheus-listen=                  Interface:port to serve Prometheus metrics on at /metrics
+      --import-utxoindex-snapshot=          Restore the UTXO index from a snapshot file instead of resyncing it
+      --export-utxoindex-snapshot=          Export the UTXO index to a snapshot file on startup
+      --import-pruning-snapshot=            Bootstrap consensus from a pruning point snapshot file
+      --export-pruning-snapshot=            Export the pruning point and its UTXO set to a snapshot file on startup
+      --verify-db                           Verify the consistency of the consensus database and exit
+      --repair-db                           With --verify-db, resync inconsistent derived stores
+      --reorg-alert-depth=                  Alert on reorgs removing at least this number of chain blocks
+      --asn-map=                            Diversify outbound peers by autonomous system using the given ASN map file
+      --whitelist=                          Trust peers of the given network (eg. 192.168.1.0/24 or ::1/128)
 */
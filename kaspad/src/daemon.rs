@@ -1,12 +1,20 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_channel::unbounded;
+use ipnet::IpNet;
 use kaspa_consensus_core::{
     config::ConfigBuilder,
     errors::config::{ConfigError, ConfigResult},
 };
 use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
-use kaspa_core::{core::Core, info, trace};
+use kaspa_core::{core::Core, info, trace, warn};
 use kaspa_core::{kaspad_env::version, task::tick::TickService};
 use kaspa_database::prelude::CachePolicy;
 use kaspa_grpc_server::service::GrpcService;
@@ -14,14 +22,19 @@ use kaspa_notify::{address::tracker::Tracker, subscription::context::Subscriptio
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::networking::ContextualNetAddress;
-use kaspa_utils_tower::counters::TowerConnectionCounters;
+use kaspa_utils_tower::{
+    counters::TowerConnectionCounters,
+    socks::{Socks5Proxy, DEFAULT_SOCKS5_PORT},
+};
 
-use kaspa_addressmanager::AddressManager;
+use kaspa_addressmanager::{netgroup::AsnMap, AddressManager};
 use kaspa_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
 use kaspa_consensus::{
-    consensus::factory::MultiConsensusManagementStore, model::stores::headers::DbHeadersStore, pipeline::monitor::ConsensusMonitor,
+    consensus::{factory::MultiConsensusManagementStore, verifier::ConsensusDbVerifier},
+    model::stores::headers::DbHeadersStore,
+    pipeline::monitor::ConsensusMonitor,
 };
-use kaspa_consensusmanager::ConsensusManager;
+use kaspa_consensusmanager::{snapshot::export_pruning_snapshot, ConsensusManager};
 use kaspa_core::task::runtime::AsyncRuntime;
 use kaspa_index_processor::service::IndexService;
 use kaspa_mining::{
@@ -30,8 +43,12 @@ use kaspa_mining::{
     MiningCounters,
 };
 use kaspa_p2p_flows::{flow_context::FlowContext, service::P2pService};
+use kaspa_p2p_lib::encryption::{load_or_create_secret_key, EncryptionConfig, XOnlyPublicKey};
 
+use kaspa_addressindex::{api::AddressIndexProxy, AddressIndex};
 use kaspa_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use kaspa_prometheus::{PrometheusService, DEFAULT_PROMETHEUS_PORT};
+use kaspa_txindex::{api::TxIndexProxy, TxIndex};
 use kaspa_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
 use kaspa_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
@@ -47,11 +64,15 @@ pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 use crate::args::Args;
 
 const DEFAULT_DATA_DIR: &str = "datadir";
+const DEFAULT_LIGHT_DATA_DIR: &str = "datadir-light";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
+const TXINDEX_DB: &str = "txindex";
+const ADDRESSINDEX_DB: &str = "addressindex";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
+const P2P_KEY_FILE: &str = "p2p.key";
 
 fn get_home_dir() -> PathBuf {
     #[cfg(target_os = "windows")]
@@ -86,6 +107,12 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.logdir.is_some() && args.no_log_files {
         return Err(ConfigError::MixedLogDirAndNoLogFiles);
     }
+    if let Some(key) = args.p2p_allowlist.iter().find(|key| XOnlyPublicKey::from_str(key).is_err()) {
+        return Err(ConfigError::InvalidP2pAllowlistKey(key.clone()));
+    }
+    if let Some(network) = args.whitelist.iter().find(|network| IpNet::from_str(network).is_err()) {
+        return Err(ConfigError::InvalidWhitelistNetwork(network.clone()));
+    }
     if args.ram_scale < 0.1 {
         return Err(ConfigError::RamScaleTooLow);
     }
@@ -95,6 +122,30 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.light && (args.archival || args.utxoindex || args.txindex || args.addressindex) {
+        return Err(ConfigError::LightWithFullNodeData);
+    }
+    if args.retention_period_days.is_some() && (args.archival || args.light) {
+        return Err(ConfigError::RetentionPeriodWithArchivalOrLight);
+    }
+    if args.retention_period_days.is_some_and(|days| days.is_nan() || days <= 0.0) {
+        return Err(ConfigError::RetentionPeriodNotPositive);
+    }
+    if !args.utxoindex && (args.import_utxoindex_snapshot.is_some() || args.export_utxoindex_snapshot.is_some()) {
+        return Err(ConfigError::UtxoIndexSnapshotWithoutUtxoIndex);
+    }
+    if args.light && args.export_pruning_snapshot.is_some() {
+        return Err(ConfigError::PruningSnapshotExportWithLight);
+    }
+    if args.repair_db && !args.verify_db {
+        return Err(ConfigError::RepairDbWithoutVerifyDb);
+    }
+    if args.reorg_alert_depth == Some(0) {
+        return Err(ConfigError::ReorgAlertDepthNotPositive);
+    }
+    if args.ban_duration_sec == 0 {
+        return Err(ConfigError::BanDurationNotPositive);
+    }
     Ok(())
 }
 
@@ -122,6 +173,44 @@ fn get_user_approval_or_exit(message: &str, approve: bool) {
     }
 }
 
+/// Verifies the consensus DB and logs all inconsistencies found. If `repair` is set, derived stores are
+/// resynced and verified again. Returns the process exit code: `0` if the DB is (or was made) consistent
+fn verify_consensus_db(verifier: ConsensusDbVerifier, repair: bool) -> i32 {
+    info!("Verifying the consistency of the consensus database...");
+    let mut findings = verifier.verify();
+    for finding in findings.iter() {
+        warn!("DB inconsistency: {}", finding);
+    }
+
+    if repair && findings.iter().any(|finding| finding.is_derived()) {
+        info!("Resyncing the selected chain index...");
+        match verifier.repair_selected_chain() {
+            Ok(indexed) => info!("Selected chain index was resynced with {} chain blocks", indexed),
+            Err(err) => {
+                warn!("Failed resyncing the selected chain index: {}", err);
+                return 1;
+            }
+        }
+        findings = verifier.verify();
+        for finding in findings.iter() {
+            warn!("DB inconsistency remaining after repair: {}", finding);
+        }
+    }
+
+    if findings.is_empty() {
+        info!("Consensus database verification completed, no inconsistencies were found");
+        return 0;
+    }
+    if findings.iter().any(|finding| finding.is_derived()) && !repair {
+        warn!("Some inconsistencies are in derived stores and can be repaired by rerunning with --verify-db --repair-db");
+    }
+    if findings.iter().any(|finding| !finding.is_derived()) {
+        warn!("Some inconsistencies cannot be repaired locally, restart with --reset-db in order to resync the node");
+    }
+    warn!("Consensus database verification completed, {} inconsistencies were found", findings.len());
+    1
+}
+
 /// Runtime configuration struct for the application.
 #[derive(Default)]
 pub struct Runtime {
@@ -208,6 +297,20 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
+    let address_files_limit = if args.addressindex {
+        let address_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= address_files_limit;
+        address_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -224,7 +327,8 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     // TODO: Validate `config` forms a valid set of properties
 
     let app_dir = get_app_dir_from_args(args);
-    let db_dir = app_dir.join(network.to_prefixed()).join(DEFAULT_DATA_DIR);
+    // Light nodes keep a header-only consensus which must never be mixed with full node data
+    let db_dir = app_dir.join(network.to_prefixed()).join(if args.light { DEFAULT_LIGHT_DATA_DIR } else { DEFAULT_DATA_DIR });
 
     // Print package name and version
     info!("{} v{}", env!("CARGO_PKG_NAME"), version());
@@ -243,6 +347,8 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
+    let addressindex_db_dir = db_dir.join(ADDRESSINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -262,6 +368,14 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
+    if args.addressindex {
+        info!("Addressindex Data directory {}", addressindex_db_dir.display());
+        fs::create_dir_all(addressindex_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -333,6 +447,12 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
+        if args.addressindex {
+            fs::create_dir_all(addressindex_db_dir.as_path()).unwrap();
+        }
 
         // Reopen the DB
         meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -346,12 +466,57 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         get_user_approval_or_exit("--archival is set to false although the node was previously archival. Proceeding may delete archived data. Do you confirm? (y/n)", args.yes);
     }
 
+    if args.verify_db {
+        match MultiConsensusManagementStore::new(meta_db.clone()).active_consensus_dir_name().unwrap() {
+            Some(dir_name) => {
+                let consensus_db = kaspa_database::prelude::ConnBuilder::default()
+                    .with_db_path(consensus_db_dir.join(dir_name))
+                    .with_files_limit(fd_remaining)
+                    .build()
+                    .unwrap();
+                exit(verify_consensus_db(ConsensusDbVerifier::new(consensus_db, config.clone()), args.repair_db));
+            }
+            None => {
+                info!("Consensus not initialized yet. Nothing to verify.");
+                exit(0);
+            }
+        }
+    }
+
     let connect_peers = args.connect_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect::<Vec<_>>();
     let add_peers = args.add_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect();
     let p2p_server_addr = args.listen.unwrap_or(ContextualNetAddress::unspecified()).normalize(config.default_p2p_port());
     // connect_peers means no DNS seeding and no outbound peers
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
-    let dns_seeders = if connect_peers.is_empty() && !args.disable_dns_seeding { config.dns_seeders } else { &[] };
+    // Light nodes cannot serve block bodies nor UTXO sets to peers, hence they do not accept inbound connections
+    let inbound_limit = if args.light { 0 } else { args.inbound_limit };
+    // Networks were already checked by `validate_args`
+    let whitelist = args.whitelist.iter().map(|network| IpNet::from_str(network).unwrap().trunc()).collect();
+    // DNS seeders are queried directly, hence seeding is disabled when a proxy is used so that no DNS query leaks out of it
+    let dns_seeders =
+        if connect_peers.is_empty() && !args.disable_dns_seeding && args.proxy.is_none() { config.dns_seeders } else { &[] };
+
+    let p2p_encryption = (args.p2p_encryption || args.require_encryption || !args.p2p_allowlist.is_empty()).then(|| {
+        let key_path = app_dir.join(network.to_prefixed()).join(P2P_KEY_FILE);
+        let secret_key = load_or_create_secret_key(&key_path)
+            .unwrap_or_else(|err| panic!("Failed loading the P2P key from {}: {}", key_path.display(), err));
+        let mut encryption = EncryptionConfig::new(secret_key);
+        if !args.p2p_allowlist.is_empty() {
+            // Keys were already checked by `validate_args`
+            encryption = encryption.with_allowlist(args.p2p_allowlist.iter().map(|key| XOnlyPublicKey::from_str(key).unwrap()));
+        }
+        if args.require_encryption {
+            encryption = encryption.with_required_encryption();
+        }
+        info!("P2P encryption is enabled, node public key: {}", encryption.public_key());
+        Arc::new(encryption)
+    });
+
+    let p2p_proxy = args.proxy.map(|proxy| {
+        let proxy = Socks5Proxy::new(proxy.normalize(DEFAULT_SOCKS5_PORT).into());
+        info!("Outbound P2P connections go through the SOCKS5 proxy at {}", proxy.address());
+        proxy
+    });
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
 
@@ -385,6 +550,11 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         fd_remaining,
     ));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+    if let Some(path) = args.export_pruning_snapshot.as_deref() {
+        if let Err(err) = export_pruning_snapshot(&consensus_manager, &config, Path::new(path)) {
+            warn!("Failed exporting the pruning point snapshot: {}", err);
+        }
+    }
     let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
 
     let perf_monitor_builder = PerfMonitorBuilder::new()
@@ -403,21 +573,55 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
     };
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex || args.addressindex {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            let utxoindex = UtxoIndex::new_with_snapshot(
+                consensus_manager.clone(),
+                utxoindex_db,
+                args.import_utxoindex_snapshot.as_deref().map(Path::new),
+            )
             .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+            if let Some(path) = args.export_utxoindex_snapshot.as_deref() {
+                if let Err(err) = utxoindex.read().export_snapshot(Path::new(path)) {
+                    warn!("Failed exporting the utxoindex snapshot: {}", err);
+                }
+            }
+            UtxoIndexProxy::new(utxoindex)
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(txindex_db_dir)
+                .with_files_limit(tx_files_limit)
+                .build()
+                .unwrap();
+            TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap())
+        });
+        let addressindex = args.addressindex.then(|| {
+            let addressindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(addressindex_db_dir)
+                .with_files_limit(address_files_limit)
+                .build()
+                .unwrap();
+            AddressIndexProxy::new(AddressIndex::new(consensus_manager.clone(), addressindex_db).unwrap())
+        });
+        let index_service =
+            Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), utxoindex, txindex, addressindex));
         Some(index_service)
     } else {
         None
     };
 
     let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
+    if let Some(asn_map_path) = args.asn_map.as_deref() {
+        let asn_map = AsnMap::load(Path::new(asn_map_path)).unwrap_or_else(|err| panic!("Failed loading the ASN map: {}", err));
+        address_manager.lock().set_asn_map(asn_map);
+    }
 
     let mining_monitor = Arc::new(MiningMonitor::new(mining_counters.clone(), tx_script_cache_counters.clone(), tick_service.clone()));
     let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
@@ -426,7 +630,7 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         config.max_block_mass,
         config.ram_scale,
         config.block_template_cache_lifetime,
-        mining_counters,
+        mining_counters.clone(),
     )));
 
     let flow_context = Arc::new(FlowContext::new(
@@ -443,10 +647,15 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         add_peers,
         p2p_server_addr,
         outbound_target,
-        args.inbound_limit,
+        inbound_limit,
+        whitelist,
+        Duration::from_secs(args.ban_duration_sec),
         dns_seeders,
         config.default_p2p_port(),
         p2p_tower_counters.clone(),
+        p2p_encryption,
+        p2p_proxy,
+        args.import_pruning_snapshot.as_ref().map(PathBuf::from),
     ));
 
     let rpc_core_service = Arc::new(RpcCoreService::new(
@@ -456,7 +665,9 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         mining_manager,
         flow_context,
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        index_service.as_ref().and_then(|x| x.txindex()),
+        index_service.as_ref().and_then(|x| x.addressindex()),
         config.clone(),
         core.clone(),
         processing_counters,
@@ -466,6 +677,9 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         p2p_tower_counters.clone(),
         grpc_tower_counters.clone(),
     ));
+    let prometheus_service = args.prometheus_listen.map(|address| {
+        Arc::new(PrometheusService::new(address.normalize(DEFAULT_PROMETHEUS_PORT), rpc_core_service.clone(), mining_counters))
+    });
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
     let grpc_service = if !args.disable_grpc {
         Some(Arc::new(GrpcService::new(
@@ -491,6 +705,9 @@ do you confirm? (answer y/n or pass --yes to the Rustweaved command line to conf
         async_runtime.register(Arc::new(port_mapping_extender_svc))
     };
     async_runtime.register(rpc_core_service.clone());
+    if let Some(prometheus_service) = prometheus_service {
+        async_runtime.register(prometheus_service)
+    }
     if let Some(grpc_service) = grpc_service {
         async_runtime.register(grpc_service)
     }
@@ -0,0 +1,249 @@
+//! Rendering of the node metrics in the Prometheus text exposition format.
+//!
+//! See <https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format>
+
+use kaspa_mining::MempoolCountersSnapshot;
+use kaspa_rpc_core::{BandwidthMetrics, ConnectionMetrics, ConsensusMetrics, GetMetricsResponse, ProcessMetrics};
+use std::fmt::Write;
+
+/// Content type of a response carrying metrics in the Prometheus text format
+pub const TEXT_FORMAT_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Prefix of all the exported metric names
+pub const METRIC_NAME_PREFIX: &str = "rustweave";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    /// A cumulative value which only ever increases over the node lifetime
+    Counter,
+    /// A value which can arbitrarily go up and down
+    Gauge,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricType::Counter => "counter",
+            MetricType::Gauge => "gauge",
+        }
+    }
+}
+
+/// Accumulates metric samples, each with its help and type metadata, into a Prometheus text document
+#[derive(Default)]
+pub struct TextEncoder {
+    buffer: String,
+}
+
+impl TextEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, value: u64) -> &mut Self {
+        self.sample(MetricType::Counter, name, help, value as f64)
+    }
+
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) -> &mut Self {
+        self.sample(MetricType::Gauge, name, help, value)
+    }
+
+    fn sample(&mut self, metric_type: MetricType, name: &str, help: &str, value: f64) -> &mut Self {
+        // Writing to a String never fails
+        writeln!(self.buffer, "# HELP {METRIC_NAME_PREFIX}_{name} {help}").unwrap();
+        writeln!(self.buffer, "# TYPE {METRIC_NAME_PREFIX}_{name} {}", metric_type.as_str()).unwrap();
+        writeln!(self.buffer, "{METRIC_NAME_PREFIX}_{name} {}", format_value(value)).unwrap();
+        self
+    }
+
+    pub fn finish(self) -> String {
+        self.buffer
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value.is_sign_positive() { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders the node metrics in the Prometheus text format
+pub fn encode_metrics(metrics: &GetMetricsResponse, mempool: &MempoolCountersSnapshot) -> String {
+    let mut encoder = TextEncoder::new();
+    if let Some(process) = metrics.process_metrics.as_ref() {
+        encode_process_metrics(&mut encoder, process);
+    }
+    if let Some(connections) = metrics.connection_metrics.as_ref() {
+        encode_connection_metrics(&mut encoder, connections);
+    }
+    if let Some(bandwidth) = metrics.bandwidth_metrics.as_ref() {
+        encode_bandwidth_metrics(&mut encoder, bandwidth);
+    }
+    if let Some(consensus) = metrics.consensus_metrics.as_ref() {
+        encode_consensus_metrics(&mut encoder, consensus);
+    }
+    encode_mempool_metrics(&mut encoder, mempool);
+    encoder.finish()
+}
+
+fn encode_process_metrics(encoder: &mut TextEncoder, metrics: &ProcessMetrics) {
+    encoder
+        .gauge("process_resident_memory_bytes", "Resident memory size of the node process", metrics.resident_set_size as f64)
+        .gauge("process_virtual_memory_bytes", "Virtual memory size of the node process", metrics.virtual_memory_size as f64)
+        .gauge("process_cpu_cores", "Number of CPU cores available to the node process", metrics.core_num as f64)
+        .gauge("process_cpu_usage_ratio", "CPU usage of the node process", metrics.cpu_usage as f64)
+        .gauge("process_open_fds", "Number of file descriptors open by the node process", metrics.fd_num as f64)
+        .counter("storage_read_bytes_total", "Bytes read from storage by the node process", metrics.disk_io_read_bytes)
+        .counter("storage_written_bytes_total", "Bytes written to storage by the node process", metrics.disk_io_write_bytes);
+}
+
+fn encode_connection_metrics(encoder: &mut TextEncoder, metrics: &ConnectionMetrics) {
+    encoder
+        .gauge("p2p_active_peers", "Number of active P2P peers", metrics.active_peers as f64)
+        .gauge("wrpc_borsh_live_connections", "Number of live wRPC Borsh connections", metrics.borsh_live_connections as f64)
+        .counter("wrpc_borsh_connection_attempts_total", "wRPC Borsh connection attempts", metrics.borsh_connection_attempts)
+        .counter("wrpc_borsh_handshake_failures_total", "wRPC Borsh handshake failures", metrics.borsh_handshake_failures)
+        .gauge("wrpc_json_live_connections", "Number of live wRPC JSON connections", metrics.json_live_connections as f64)
+        .counter("wrpc_json_connection_attempts_total", "wRPC JSON connection attempts", metrics.json_connection_attempts)
+        .counter("wrpc_json_handshake_failures_total", "wRPC JSON handshake failures", metrics.json_handshake_failures);
+}
+
+fn encode_bandwidth_metrics(encoder: &mut TextEncoder, metrics: &BandwidthMetrics) {
+    encoder
+        .counter("p2p_sent_bytes_total", "Bytes sent to P2P peers", metrics.p2p_bytes_tx)
+        .counter("p2p_received_bytes_total", "Bytes received from P2P peers", metrics.p2p_bytes_rx)
+        .counter("grpc_sent_bytes_total", "Bytes sent to gRPC clients", metrics.grpc_bytes_tx)
+        .counter("grpc_received_bytes_total", "Bytes received from gRPC clients", metrics.grpc_bytes_rx)
+        .counter("wrpc_borsh_sent_bytes_total", "Bytes sent to wRPC Borsh clients", metrics.borsh_bytes_tx)
+        .counter("wrpc_borsh_received_bytes_total", "Bytes received from wRPC Borsh clients", metrics.borsh_bytes_rx)
+        .counter("wrpc_json_sent_bytes_total", "Bytes sent to wRPC JSON clients", metrics.json_bytes_tx)
+        .counter("wrpc_json_received_bytes_total", "Bytes received from wRPC JSON clients", metrics.json_bytes_rx);
+}
+
+fn encode_consensus_metrics(encoder: &mut TextEncoder, metrics: &ConsensusMetrics) {
+    encoder
+        .counter("consensus_blocks_submitted_total", "Blocks submitted to consensus", metrics.node_blocks_submitted_count)
+        .counter("consensus_headers_processed_total", "Headers processed by consensus", metrics.node_headers_processed_count)
+        .counter(
+            "consensus_dependencies_processed_total",
+            "Block dependencies processed by consensus",
+            metrics.node_dependencies_processed_count,
+        )
+        .counter("consensus_bodies_processed_total", "Block bodies processed by consensus", metrics.node_bodies_processed_count)
+        .counter(
+            "consensus_transactions_processed_total",
+            "Transactions processed by consensus",
+            metrics.node_transactions_processed_count,
+        )
+        .counter(
+            "consensus_chain_blocks_processed_total",
+            "Chain blocks processed by consensus",
+            metrics.node_chain_blocks_processed_count,
+        )
+        .counter("consensus_mass_processed_total", "Mass processed by consensus", metrics.node_mass_processed_count)
+        .gauge("storage_blocks", "Number of blocks in the consensus database", metrics.node_database_blocks_count as f64)
+        .gauge("storage_headers", "Number of headers in the consensus database", metrics.node_database_headers_count as f64)
+        .gauge("consensus_tip_hashes", "Number of DAG tips", metrics.network_tip_hashes_count as f64)
+        .gauge("consensus_difficulty", "Network difficulty", metrics.network_difficulty)
+        .gauge(
+            "consensus_past_median_time_milliseconds",
+            "Past median time of the virtual block",
+            metrics.network_past_median_time as f64,
+        )
+        .gauge(
+            "consensus_virtual_parent_hashes",
+            "Number of parents of the virtual block",
+            metrics.network_virtual_parent_hashes_count as f64,
+        )
+        .gauge("consensus_virtual_daa_score", "DAA score of the virtual block", metrics.network_virtual_daa_score as f64)
+        .gauge("mempool_transactions", "Number of transactions in the mempool", metrics.network_mempool_size as f64);
+}
+
+fn encode_mempool_metrics(encoder: &mut TextEncoder, metrics: &MempoolCountersSnapshot) {
+    encoder
+        .counter(
+            "mempool_high_priority_transactions_total",
+            "High priority transactions inserted in the mempool",
+            metrics.high_priority_tx_counts,
+        )
+        .counter(
+            "mempool_low_priority_transactions_total",
+            "Low priority transactions inserted in the mempool",
+            metrics.low_priority_tx_counts,
+        )
+        .counter("mempool_block_transactions_total", "Transactions of the blocks handled by the mempool", metrics.block_tx_counts)
+        .counter("mempool_accepted_transactions_total", "Mempool transactions accepted by a block", metrics.tx_accepted_counts)
+        .counter("mempool_inputs_total", "Inputs of the transactions inserted in the mempool", metrics.input_counts)
+        .counter("mempool_outputs_total", "Outputs of the transactions inserted in the mempool", metrics.output_counts)
+        .gauge(
+            "mempool_ready_transactions",
+            "Number of mempool transactions ready for a block template",
+            metrics.ready_txs_sample as f64,
+        )
+        .gauge("mempool_orphans", "Number of orphan transactions in the mempool", metrics.orphans_sample as f64)
+        .gauge(
+            "mempool_accepted_transactions",
+            "Number of recently accepted transactions tracked by the mempool",
+            metrics.accepted_sample as f64,
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_text_encoder() {
+        let mut encoder = TextEncoder::new();
+        encoder.counter("foo_total", "A counter", 42).gauge("bar", "A gauge", 0.5).gauge("baz", "An undefined gauge", f64::NAN);
+        let expected = "\
+# HELP rustweave_foo_total A counter
+# TYPE rustweave_foo_total counter
+rustweave_foo_total 42
+# HELP rustweave_bar A gauge
+# TYPE rustweave_bar gauge
+rustweave_bar 0.5
+# HELP rustweave_baz An undefined gauge
+# TYPE rustweave_baz gauge
+rustweave_baz NaN
+";
+        assert_eq!(expected, encoder.finish());
+    }
+
+    #[test]
+    fn test_encode_metrics() {
+        let metrics = GetMetricsResponse {
+            server_time: 0,
+            process_metrics: None,
+            connection_metrics: Some(ConnectionMetrics { active_peers: 8, ..Default::default() }),
+            bandwidth_metrics: None,
+            consensus_metrics: Some(ConsensusMetrics { network_mempool_size: 12, ..Default::default() }),
+        };
+        let mempool = MempoolCountersSnapshot {
+            elapsed_time: Duration::from_secs(1),
+            high_priority_tx_counts: 0,
+            low_priority_tx_counts: 0,
+            block_tx_counts: 0,
+            tx_accepted_counts: 3,
+            input_counts: 0,
+            output_counts: 0,
+            ready_txs_sample: 0,
+            txs_sample: 0,
+            orphans_sample: 0,
+            accepted_sample: 0,
+        };
+        let text = encode_metrics(&metrics, &mempool);
+        let lines = text.lines().filter(|line| !line.starts_with('#')).collect::<Vec<_>>();
+        assert!(lines.contains(&"rustweave_p2p_active_peers 8"));
+        assert!(lines.contains(&"rustweave_mempool_transactions 12"));
+        assert!(lines.contains(&"rustweave_mempool_accepted_transactions_total 3"));
+        // Absent metric groups are omitted
+        assert!(!text.contains("rustweave_process_"));
+        assert!(!text.contains("rustweave_p2p_sent_bytes_total"));
+    }
+}
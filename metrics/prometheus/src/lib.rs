@@ -0,0 +1,7 @@
+pub mod encoder;
+pub mod service;
+
+pub use service::PrometheusService;
+
+/// Default port of the Prometheus metrics listener
+pub const DEFAULT_PROMETHEUS_PORT: u16 = 9110;
@@ -0,0 +1,111 @@
+use crate::encoder::{encode_metrics, TEXT_FORMAT_CONTENT_TYPE};
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use kaspa_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use kaspa_mining::MiningCounters;
+use kaspa_rpc_core::{api::rpc::RpcApi, RpcResult};
+use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+/// Path of the HTTP endpoint serving the metrics
+pub const METRICS_PATH: &str = "/metrics";
+
+/// HTTP service exposing the node metrics to a Prometheus scraper
+pub struct PrometheusService {
+    listen_address: NetAddress,
+    core_service: Arc<RpcCoreService>,
+    mining_counters: Arc<MiningCounters>,
+    shutdown: SingleTrigger,
+}
+
+impl PrometheusService {
+    pub const IDENT: &'static str = "prometheus-service";
+
+    pub fn new(listen_address: NetAddress, core_service: Arc<RpcCoreService>, mining_counters: Arc<MiningCounters>) -> Self {
+        Self { listen_address, core_service, mining_counters, shutdown: Default::default() }
+    }
+
+    /// Collects the current node metrics and renders them in the Prometheus text format
+    pub async fn render(&self) -> RpcResult<String> {
+        let metrics = self.core_service.get_metrics(true, true, true, true).await?;
+        Ok(encode_metrics(&metrics, &self.mining_counters.snapshot()))
+    }
+
+    async fn handle(self: Arc<Self>, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::GET || request.uri().path() != METRICS_PATH {
+            return Self::response(StatusCode::NOT_FOUND, "Not Found".to_string());
+        }
+        match self.render().await {
+            Ok(text) => {
+                let mut response = Self::response(StatusCode::OK, text);
+                response.headers_mut().insert(CONTENT_TYPE, TEXT_FORMAT_CONTENT_TYPE.parse().unwrap());
+                response
+            }
+            Err(err) => {
+                warn!("{} failed to collect the node metrics: {}", Self::IDENT, err);
+                Self::response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+        }
+    }
+
+    fn response(status: StatusCode, body: String) -> Response<Body> {
+        let mut response = Response::new(Body::from(body));
+        *response.status_mut() = status;
+        response
+    }
+}
+
+impl AsyncService for PrometheusService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let address: SocketAddr = self.listen_address.into();
+            let service = self.clone();
+            let make_service = make_service_fn(move |_| {
+                let service = service.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request| {
+                        let service = service.clone();
+                        async move { Ok::<_, Infallible>(service.handle(request).await) }
+                    }))
+                }
+            });
+            let server = Server::try_bind(&address)
+                .map_err(|err| AsyncServiceError::Service(format!("{} failed to bind {}: {}", Self::IDENT, address, err)))?
+                .serve(make_service)
+                .with_graceful_shutdown(shutdown_signal);
+            info!("Prometheus metrics exporter listening on http://{}{}", address, METRICS_PATH);
+
+            // Keep the server running until a service shutdown signal is received
+            server.await.map_err(|err| AsyncServiceError::Service(err.to_string()))
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
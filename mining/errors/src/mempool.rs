@@ -75,6 +75,24 @@ pub enum RuleError {
 
     #[error("Rejected tx {0} from mempool due to incomputable storage mass")]
     RejectStorageMassIncomputable(TransactionId),
+
+    #[error("replacement transaction {0} feerate {1} is not strictly higher than feerate {2} of conflicting transaction {3}")]
+    RejectReplacementFeerate(TransactionId, f64, f64, TransactionId),
+
+    #[error("replacement transaction {0} would evict transactions with a total mass of {1}, larger than the maximum of {2}")]
+    RejectReplacementMass(TransactionId, u64, u64),
+
+    #[error("replacement transaction {0} pays a fee of {1} sompi where at least {2} sompi are required to evict the conflicting transactions")]
+    RejectReplacementFee(TransactionId, u64, u64),
+
+    #[error("replacement transaction {0} spends an output of transaction {1} which it would evict")]
+    RejectReplacementSpendsEvicted(TransactionId, TransactionId),
+
+    #[error("transaction {0} has {1} ancestors in the mempool, more than the maximum allowed ({2})")]
+    RejectTooManyAncestors(TransactionId, usize, u64),
+
+    #[error("transaction {0} would give its mempool ancestor {1} {2} descendants, more than the maximum allowed ({3})")]
+    RejectTooManyDescendants(TransactionId, TransactionId, usize, u64),
 }
 
 impl From<NonStandardError> for RuleError {
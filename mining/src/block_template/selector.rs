@@ -215,16 +215,18 @@ impl TransactionsSelector {
     /// calc_tx_value calculates a value to be used in transaction selection.
     /// The higher the number the more likely it is that the transaction will be
     /// included in the block.
+    ///
+    /// The value is based on the package feerate of the transaction when higher than
+    /// its own, so a high-fee child lifts its low-fee parent into the template.
     fn calc_tx_value(&self, transaction: &CandidateTransaction) -> f64 {
         let mass_limit = self.policy.max_block_mass as f64;
-        let mass = transaction.calculated_mass as f64;
-        let fee = transaction.calculated_fee as f64;
+        let feerate = transaction.selection_feerate();
         if transaction.tx.subnetwork_id.is_builtin_or_native() {
-            fee / mass / mass_limit
+            feerate / mass_limit
         } else {
             // TODO: Replace with real gas once implemented
             let gas_limit = u64::MAX as f64;
-            fee / mass / mass_limit + transaction.tx.gas as f64 / gas_limit
+            feerate / mass_limit + transaction.tx.gas as f64 / gas_limit
         }
     }
 }
@@ -318,6 +320,6 @@ mod tests {
         let calculated_mass = transaction_estimated_serialized_size(&tx);
         let calculated_fee = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
 
-        CandidateTransaction { tx, calculated_fee, calculated_mass }
+        CandidateTransaction { tx, calculated_fee, calculated_mass, package_fee: calculated_fee, package_mass: calculated_mass }
     }
 }
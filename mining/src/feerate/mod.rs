@@ -0,0 +1,146 @@
+//! Feerate estimation, based on the transactions pending in the mempool and on the
+//! feerates paid by recently accepted mempool transactions.
+//!
+//! All feerates are expressed in sompi per gram of transaction mass.
+
+use crate::mempool::config::Config;
+use std::{collections::VecDeque, sync::Arc};
+
+/// A feerate along with the estimated time it takes for a transaction paying it to be included in a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeerateBucket {
+    pub feerate: f64,
+    pub estimated_seconds: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeerateEstimations {
+    /// A feerate expected to get a transaction included in the next block
+    pub priority_bucket: FeerateBucket,
+    /// A feerate expected to get a transaction included within about a minute
+    pub normal_bucket: FeerateBucket,
+    /// The lowest feerate accepted by the mempool
+    pub low_bucket: FeerateBucket,
+}
+
+pub(crate) struct FeerateEstimator {
+    /// Mempool config
+    config: Arc<Config>,
+
+    /// Feerates of the most recently accepted mempool transactions, the oldest first
+    recent_feerates: VecDeque<f64>,
+}
+
+impl FeerateEstimator {
+    pub(crate) fn new(config: Arc<Config>) -> Self {
+        let recent_feerates = VecDeque::with_capacity(config.feerate_estimator_window as usize);
+        Self { config, recent_feerates }
+    }
+
+    /// Registers the feerate of a mempool transaction which got accepted by a block
+    pub(crate) fn add_accepted_feerate(&mut self, feerate: f64) {
+        if self.recent_feerates.len() >= self.config.feerate_estimator_window as usize {
+            self.recent_feerates.pop_front();
+        }
+        self.recent_feerates.push_back(feerate);
+    }
+
+    /// Estimates the feerates of the priority levels given the `(feerate, mass)` pairs of all pending transactions
+    pub(crate) fn estimate(&self, mut pending: Vec<(f64, u64)>) -> FeerateEstimations {
+        pending.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let minimum_feerate = self.config.minimum_relay_transaction_fee as f64 / 1000.0;
+        let block_mass = self.config.maximum_mass_per_block;
+        let blocks_per_minute = u64::max(60_000 / self.config.target_time_per_block, 1);
+
+        let priority_feerate = f64::max(Self::feerate_at_mass_depth(&pending, block_mass), self.recent_percentile(0.5));
+        let normal_feerate =
+            f64::max(Self::feerate_at_mass_depth(&pending, block_mass * blocks_per_minute), self.recent_percentile(0.25))
+                .min(priority_feerate);
+
+        let bucket = |feerate: f64| {
+            let feerate = feerate.max(minimum_feerate);
+            FeerateBucket { feerate, estimated_seconds: self.estimate_seconds(&pending, feerate) }
+        };
+
+        FeerateEstimations {
+            priority_bucket: bucket(priority_feerate),
+            normal_bucket: bucket(normal_feerate),
+            low_bucket: bucket(minimum_feerate),
+        }
+    }
+
+    /// Returns the feerate of the first transaction exceeding `mass_depth` in the cumulative mass of the
+    /// sorted `pending` transactions, or zero if all pending transactions fit within `mass_depth`
+    fn feerate_at_mass_depth(pending: &[(f64, u64)], mass_depth: u64) -> f64 {
+        let mut cumulative_mass = 0;
+        for &(feerate, mass) in pending.iter() {
+            cumulative_mass += mass;
+            if cumulative_mass > mass_depth {
+                return feerate;
+            }
+        }
+        0.0
+    }
+
+    /// Estimates the time needed for the pending transactions paying more than `feerate` to be mined
+    /// before a transaction paying `feerate` is itself included in a block
+    fn estimate_seconds(&self, pending: &[(f64, u64)], feerate: f64) -> f64 {
+        let mass_ahead: u64 = pending.iter().take_while(|(x, _)| *x > feerate).map(|(_, mass)| mass).sum();
+        let blocks = mass_ahead / self.config.maximum_mass_per_block + 1;
+        (blocks * self.config.target_time_per_block) as f64 / 1000.0
+    }
+
+    fn recent_percentile(&self, percentile: f64) -> f64 {
+        if self.recent_feerates.is_empty() {
+            return 0.0;
+        }
+        let mut feerates = self.recent_feerates.iter().copied().collect::<Vec<_>>();
+        feerates.sort_by(|a, b| a.total_cmp(b));
+        feerates[((feerates.len() - 1) as f64 * percentile) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimator() -> FeerateEstimator {
+        FeerateEstimator::new(Arc::new(Config::build_default(1000, false, 500_000)))
+    }
+
+    #[test]
+    fn test_feerate_estimations() {
+        let mut estimator = estimator();
+
+        // An empty mempool with no history suggests the minimum feerate at all levels
+        let estimations = estimator.estimate(vec![]);
+        assert_eq!(estimations.low_bucket, FeerateBucket { feerate: 1.0, estimated_seconds: 1.0 });
+        assert_eq!(estimations.normal_bucket, estimations.low_bucket);
+        assert_eq!(estimations.priority_bucket, estimations.low_bucket);
+
+        // Fill the mempool with 3 blocks worth of mass paying decreasing feerates
+        let pending = vec![(10.0, 500_000), (5.0, 500_000), (2.0, 500_000)];
+        let estimations = estimator.estimate(pending.clone());
+        assert_eq!(estimations.priority_bucket, FeerateBucket { feerate: 5.0, estimated_seconds: 2.0 });
+        assert_eq!(estimations.normal_bucket, FeerateBucket { feerate: 1.0, estimated_seconds: 4.0 });
+        assert_eq!(estimations.low_bucket, FeerateBucket { feerate: 1.0, estimated_seconds: 4.0 });
+
+        // Recently accepted transactions paying high feerates raise the priority and normal levels
+        (0..100).for_each(|_| estimator.add_accepted_feerate(20.0));
+        let estimations = estimator.estimate(pending);
+        assert_eq!(estimations.priority_bucket, FeerateBucket { feerate: 20.0, estimated_seconds: 1.0 });
+        assert_eq!(estimations.normal_bucket, FeerateBucket { feerate: 20.0, estimated_seconds: 1.0 });
+        assert_eq!(estimations.low_bucket.feerate, 1.0);
+    }
+
+    #[test]
+    fn test_recent_feerates_window() {
+        let mut estimator = estimator();
+        let window = estimator.config.feerate_estimator_window as usize;
+        (0..window).for_each(|_| estimator.add_accepted_feerate(20.0));
+        (0..window).for_each(|_| estimator.add_accepted_feerate(3.0));
+        assert_eq!(estimator.recent_feerates.len(), window);
+        assert_eq!(estimator.estimate(vec![]).priority_bucket.feerate, 3.0);
+    }
+}
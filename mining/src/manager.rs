@@ -2,19 +2,22 @@ use crate::{
     block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
+    feerate::FeerateEstimations,
     mempool::{
         config::Config,
-        model::tx::{MempoolTransaction, TxRemovalReason},
+        model::tx::{MempoolTransaction, TransactionPostValidation, TxRemovalReason},
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
-        tx::{Orphan, Priority},
+        tx::{Orphan, Priority, RbfPolicy},
         Mempool,
     },
     model::{
         candidate_tx::CandidateTransaction,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         topological_sort::IntoIterTopologically,
+        tx_ancestry::TransactionAncestry,
+        tx_insert::TransactionInsertion,
         tx_query::TransactionQuery,
     },
     MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
@@ -212,6 +215,9 @@ impl MiningManager {
     /// adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
+    /// When `rbf_policy` is allowed, the transaction may replace the mempool transactions it double spends,
+    /// in which case the ids of the evicted transactions are returned in the insertion outcome.
+    ///
     /// The returned transactions are clones of objects owned by the mempool.
     pub fn validate_and_insert_transaction(
         &self,
@@ -219,8 +225,9 @@ impl MiningManager {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan)
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan, rbf_policy)
     }
 
     /// Exposed only for tests. Ordinary users should call `validate_and_insert_transaction` instead
@@ -230,16 +237,17 @@ impl MiningManager {
         transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
         // read lock on mempool
-        let mut transaction = self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction)?;
+        let mut transaction = self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction, rbf_policy)?;
         // no lock on mempool
         let validation_result = validate_mempool_transaction(consensus, &mut transaction);
         // write lock on mempool
         let mut mempool = self.mempool.write();
-        if let Some(accepted_transaction) =
-            mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan)?
-        {
+        let TransactionPostValidation { removed, accepted } =
+            mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan, rbf_policy)?;
+        if let Some(accepted_transaction) = accepted {
             let unorphaned_transactions = mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction);
             drop(mempool);
 
@@ -247,12 +255,16 @@ impl MiningManager {
             let mut accepted_transactions = Vec::with_capacity(unorphaned_transactions.len() + 1);
             // We include the original accepted transaction as well
             accepted_transactions.push(accepted_transaction);
-            accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions));
+            accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(
+                consensus,
+                unorphaned_transactions,
+                rbf_policy,
+            ));
             self.counters.increase_tx_counts(1, priority);
 
-            Ok(accepted_transactions)
+            Ok(TransactionInsertion::new(removed, accepted_transactions))
         } else {
-            Ok(vec![])
+            Ok(TransactionInsertion::default())
         }
     }
 
@@ -260,6 +272,7 @@ impl MiningManager {
         &self,
         consensus: &dyn ConsensusApi,
         mut incoming_transactions: Vec<MempoolTransaction>,
+        rbf_policy: RbfPolicy,
     ) -> Vec<Arc<Transaction>> {
         // The capacity used here may be exceeded (see next comment).
         let mut accepted_transactions = Vec::with_capacity(incoming_transactions.len());
@@ -297,13 +310,14 @@ impl MiningManager {
                         transaction,
                         priority,
                         Orphan::Forbidden,
+                        rbf_policy,
                     ) {
-                        Ok(Some(accepted_transaction)) => {
+                        Ok(TransactionPostValidation { accepted: Some(accepted_transaction), .. }) => {
                             accepted_transactions.push(accepted_transaction.clone());
                             self.counters.increase_tx_counts(1, priority);
                             mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction)
                         }
-                        Ok(None) => vec![],
+                        Ok(TransactionPostValidation { accepted: None, .. }) => vec![],
                         Err(err) => {
                             debug!("Failed to unorphan transaction {0} due to rule error: {1}", orphan_id, err);
                             vec![]
@@ -327,6 +341,7 @@ impl MiningManager {
         transactions: Vec<Transaction>,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
         const TRANSACTION_CHUNK_SIZE: usize = 250;
 
@@ -344,7 +359,7 @@ impl MiningManager {
             let mempool = self.mempool.read();
             let txs = chunk.filter_map(|tx| {
                 let transaction_id = tx.id();
-                match mempool.pre_validate_and_populate_transaction(consensus, tx) {
+                match mempool.pre_validate_and_populate_transaction(consensus, tx, rbf_policy) {
                     Ok(tx) => Some(tx),
                     Err(RuleError::RejectAlreadyAccepted(transaction_id)) => {
                         debug!("Ignoring already accepted transaction {}", transaction_id);
@@ -386,13 +401,20 @@ impl MiningManager {
             let mut mempool = self.mempool.write();
             let txs = chunk.flat_map(|(transaction, validation_result)| {
                 let transaction_id = transaction.id();
-                match mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan) {
-                    Ok(Some(accepted_transaction)) => {
+                match mempool.post_validate_and_insert_transaction(
+                    consensus,
+                    validation_result,
+                    transaction,
+                    priority,
+                    orphan,
+                    rbf_policy,
+                ) {
+                    Ok(TransactionPostValidation { accepted: Some(accepted_transaction), .. }) => {
                         insert_results.push(Ok(accepted_transaction.clone()));
                         self.counters.increase_tx_counts(1, priority);
                         mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction)
                     }
-                    Ok(None) => {
+                    Ok(TransactionPostValidation { accepted: None, .. }) => {
                         // Either orphaned or already existing in the mempool
                         vec![]
                     }
@@ -406,8 +428,9 @@ impl MiningManager {
             unorphaned_transactions.extend(txs);
         }
 
-        insert_results
-            .extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions).into_iter().map(Ok));
+        insert_results.extend(
+            self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions, rbf_policy).into_iter().map(Ok),
+        );
         insert_results
     }
 
@@ -436,6 +459,12 @@ impl MiningManager {
         self.mempool.read().get_transaction(transaction_id, query)
     }
 
+    /// Returns the in-mempool ancestors and descendants of a transaction of the transaction pool
+    /// along with the aggregate feerate of their package, or `None` if the pool does not hold it.
+    pub fn get_transaction_ancestry(&self, transaction_id: &TransactionId) -> Option<TransactionAncestry> {
+        self.mempool.read().get_transaction_ancestry(transaction_id)
+    }
+
     /// Returns whether the mempool holds this transaction in any form.
     pub fn has_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> bool {
         self.mempool.read().has_transaction(transaction_id, query)
@@ -464,6 +493,18 @@ impl MiningManager {
         (transactions, orphans)
     }
 
+    /// Returns shared references to the transactions of the pools selected by `query` whose ids satisfy `filter`.
+    ///
+    /// The filter is applied under the mempool read lock and only matching transactions are collected,
+    /// which makes this suitable for frequent lookups such as compact block reconstruction.
+    pub fn get_shared_transactions_by_filter(
+        &self,
+        query: TransactionQuery,
+        filter: impl Fn(&TransactionId) -> bool,
+    ) -> Vec<Arc<Transaction>> {
+        self.mempool.read().get_shared_transactions_by_filter(query, filter)
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -495,7 +536,8 @@ impl MiningManager {
         let unorphaned_transactions = self.mempool.write().handle_new_block_transactions(block_daa_score, block_transactions)?;
 
         // alternate no & write lock on mempool
-        let accepted_transactions = self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions);
+        let accepted_transactions =
+            self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions, RbfPolicy::Forbidden);
 
         Ok(accepted_transactions)
     }
@@ -717,6 +759,11 @@ impl MiningManager {
         }
     }
 
+    /// Returns the feerates expected to get a transaction included in a block at each priority level
+    pub fn get_feerate_estimations(&self) -> FeerateEstimations {
+        self.mempool.read().get_feerate_estimations()
+    }
+
     /// is_transaction_output_dust returns whether or not the passed transaction output
     /// amount is considered dust or not based on the configured minimum transaction
     /// relay fee.
@@ -759,6 +806,9 @@ impl MiningManagerProxy {
     /// Validates a transaction and adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
+    /// When `rbf_policy` is allowed, the transaction may replace the mempool transactions it double spends,
+    /// in which case the ids of the evicted transactions are returned in the insertion outcome.
+    ///
     /// The returned transactions are clones of objects owned by the mempool.
     pub async fn validate_and_insert_transaction(
         self,
@@ -766,8 +816,12 @@ impl MiningManagerProxy {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        consensus.clone().spawn_blocking(move |c| self.inner.validate_and_insert_transaction(c, transaction, priority, orphan)).await
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        consensus
+            .clone()
+            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction(c, transaction, priority, orphan, rbf_policy))
+            .await
     }
 
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
@@ -781,10 +835,11 @@ impl MiningManagerProxy {
         transactions: Vec<Transaction>,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
         consensus
             .clone()
-            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction_batch(c, transactions, priority, orphan))
+            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction_batch(c, transactions, priority, orphan, rbf_policy))
             .await
     }
 
@@ -819,6 +874,12 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_transaction(&transaction_id, query)).await.unwrap()
     }
 
+    /// Returns the in-mempool ancestors and descendants of a transaction of the transaction pool
+    /// along with the aggregate feerate of their package, or `None` if the pool does not hold it.
+    pub async fn get_transaction_ancestry(self, transaction_id: TransactionId) -> Option<TransactionAncestry> {
+        spawn_blocking(move || self.inner.get_transaction_ancestry(&transaction_id)).await.unwrap()
+    }
+
     /// Returns whether the mempool holds this transaction in any form.
     pub async fn has_transaction(self, transaction_id: TransactionId, query: TransactionQuery) -> bool {
         spawn_blocking(move || self.inner.has_transaction(&transaction_id, query)).await.unwrap()
@@ -832,6 +893,15 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
     }
 
+    /// Returns shared references to the transactions of the pools selected by `query`.
+    pub async fn get_shared_transactions_by_filter(
+        self,
+        query: TransactionQuery,
+        filter: impl Fn(&TransactionId) -> bool + Send + 'static,
+    ) -> Vec<Arc<Transaction>> {
+        spawn_blocking(move || self.inner.get_shared_transactions_by_filter(query, filter)).await.unwrap()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -868,6 +938,12 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.unknown_transactions(transactions)).await.unwrap()
     }
 
+    /// Returns the feerates expected to get a transaction included in a block at each priority level.
+    /// For more details, see [`FeerateEstimations`].
+    pub async fn get_feerate_estimations(self) -> FeerateEstimations {
+        spawn_blocking(move || self.inner.get_feerate_estimations()).await.unwrap()
+    }
+
     pub fn snapshot(&self) -> MempoolCountersSnapshot {
         self.inner.counters.snapshot()
     }
@@ -7,9 +7,9 @@ mod tests {
         mempool::{
             config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
             errors::RuleError,
-            tx::{Orphan, Priority},
+            tx::{Orphan, Priority, RbfPolicy},
         },
-        model::{candidate_tx::CandidateTransaction, tx_query::TransactionQuery},
+        model::{candidate_tx::CandidateTransaction, tx_insert::TransactionInsertion, tx_query::TransactionQuery},
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -52,6 +52,7 @@ mod tests {
                 transaction.clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "inserting a valid transaction failed");
         }
@@ -100,6 +101,7 @@ mod tests {
             transaction_not_an_orphan.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "inserting the child transaction {} into the mempool failed", transaction_not_an_orphan.id());
         let (transactions_from_pool, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly);
@@ -132,6 +134,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         ));
 
         assert_eq!(
@@ -158,6 +161,7 @@ mod tests {
             transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "mempool should have accepted a valid transaction but did not");
 
@@ -167,6 +171,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double submit of the same transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDuplicate(transaction_id))) = result {
@@ -200,8 +205,13 @@ mod tests {
             transaction.id()
         );
 
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), transaction.clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the mempool should accept a valid transaction when it is able to populate its UTXO entries");
 
         let mut double_spending_transaction = transaction.clone();
@@ -217,6 +227,7 @@ mod tests {
             double_spending_transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double spend transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, transaction_id))) = result {
@@ -235,6 +246,266 @@ mod tests {
         }
     }
 
+    // test_replace_by_fee verifies that, when RBF is allowed, a transaction double-spending a transaction already in the mempool
+    // replaces it along with its descendants only if it pays a strictly higher feerate and enough fees.
+    #[test]
+    fn test_replace_by_fee() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_RUSTWEAVE]);
+        let transaction = create_transaction(&funding_tx, 1000);
+        let child_tx = create_transaction(&transaction, 1000);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        for tx in [&transaction, &child_tx] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::High,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        // An orphan redeeming the descendant is evicted along with the replaced chain
+        let mut orphan_tx = create_transaction(&child_tx, 1000);
+        let unknown_funding_tx = create_transaction_without_input(vec![600 * SOMPI_PER_RUSTWEAVE]);
+        orphan_tx.inputs.push(create_transaction(&unknown_funding_tx, 1000).inputs[0].clone());
+        orphan_tx.finalize();
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            orphan_tx.clone(),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the mempool should accept the orphan transaction {}", orphan_tx.id());
+        assert!(mining_manager.has_transaction(&orphan_tx.id(), TransactionQuery::OrphansOnly));
+
+        // A replacement paying a lower feerate is rejected
+        let replacement = create_transaction(&funding_tx, 999);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement,
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        match result {
+            Err(MiningManagerError::MempoolError(RuleError::RejectReplacementFeerate(_, _, _, conflict_id))) => {
+                assert_eq!(transaction.id(), conflict_id, "the error should refer to the conflicting transaction")
+            }
+            _ => panic!("the mempool should reject a replacement with a lower feerate but returns {result:?}"),
+        }
+
+        // A replacement paying a higher feerate but not covering the fees of the evicted chain is rejected
+        let replacement = create_transaction(&funding_tx, 1500);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement,
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectReplacementFee(_, 1500, _)))),
+            "the mempool should reject a replacement with an insufficient fee increment but returns {result:?}"
+        );
+        assert!(mining_manager.has_transaction(&transaction.id(), TransactionQuery::TransactionsOnly));
+        assert!(mining_manager.has_transaction(&child_tx.id(), TransactionQuery::TransactionsOnly));
+
+        // A replacement paying enough fees evicts the conflicting transaction and its descendant
+        let replacement = create_transaction(&funding_tx, 5000);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement.clone(),
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        assert!(result.is_ok(), "the mempool should accept a valid replacement but returns {result:?}");
+        let insertion = result.unwrap();
+        assert_eq!(
+            vec![transaction.id(), child_tx.id(), orphan_tx.id()],
+            insertion.removed,
+            "the replaced chain and its orphan redeemer should be reported as removed"
+        );
+        assert_eq!(1, insertion.accepted.len());
+        assert_eq!(replacement.id(), insertion.accepted[0].id());
+        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
+        assert_eq!(1, populated_txs.len(), "the mempool should only contain the replacement");
+        assert_eq!(replacement.id(), populated_txs[0].id());
+        assert!(orphans.is_empty());
+    }
+
+    // test_replace_by_fee_in_full_mempool verifies that the slots freed by the transactions a replacement evicts are
+    // accounted for before evicting anything, so that no other transaction is removed to make room for the replacement.
+    #[test]
+    fn test_replace_by_fee_in_full_mempool() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        // Limit the transaction pool to 2 transactions
+        config.maximum_transaction_count = 2;
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_RUSTWEAVE]);
+        let other_funding_tx = create_transaction_without_input(vec![600 * SOMPI_PER_RUSTWEAVE]);
+        let transaction = create_transaction(&funding_tx, 1000);
+        let other_tx = create_transaction(&other_funding_tx, 1000);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        consensus.add_transaction(other_funding_tx, 1);
+        for tx in [&transaction, &other_tx] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        let replacement = create_transaction(&funding_tx, 5000);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        assert!(result.is_ok(), "the mempool should accept a valid replacement but returns {result:?}");
+        assert_eq!(vec![transaction.id()], result.unwrap().removed, "only the replaced transaction should be removed");
+        assert!(mining_manager.has_transaction(&replacement.id(), TransactionQuery::TransactionsOnly));
+        assert!(mining_manager.has_transaction(&other_tx.id(), TransactionQuery::TransactionsOnly));
+    }
+
+    // test_transaction_ancestry verifies the ancestry of chained mempool transactions and that a high-fee child
+    // raises the package feerate of its low-fee parent when offered as a block template candidate.
+    #[test]
+    fn test_transaction_ancestry() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_RUSTWEAVE]);
+        let parent_tx = create_transaction(&funding_tx, 1000);
+        let child_tx = create_transaction(&parent_tx, 100_000);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        for tx in [&parent_tx, &child_tx] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        let parent_mass = mining_manager.get_transaction(&parent_tx.id(), TransactionQuery::TransactionsOnly).unwrap().tx.mass();
+        let child_mass = mining_manager.get_transaction(&child_tx.id(), TransactionQuery::TransactionsOnly).unwrap().tx.mass();
+
+        let parent_ancestry = mining_manager.get_transaction_ancestry(&parent_tx.id()).expect("the parent is in the mempool");
+        assert!(parent_ancestry.ancestors.is_empty());
+        assert_eq!(vec![child_tx.id()], parent_ancestry.descendants);
+        let child_ancestry = mining_manager.get_transaction_ancestry(&child_tx.id()).expect("the child is in the mempool");
+        assert_eq!(vec![parent_tx.id()], child_ancestry.ancestors);
+        assert!(child_ancestry.descendants.is_empty());
+        for ancestry in [&parent_ancestry, &child_ancestry] {
+            assert_eq!(101_000, ancestry.package_fee);
+            assert_eq!(parent_mass + child_mass, ancestry.package_mass);
+        }
+        assert!(mining_manager.get_transaction_ancestry(&funding_tx.id()).is_none());
+
+        // Only the parent is a candidate, carrying the package it forms with its child
+        let candidates = mining_manager.block_candidate_transactions();
+        assert_eq!(1, candidates.len());
+        assert_eq!(parent_tx.id(), candidates[0].tx.id());
+        assert_eq!((1000, parent_mass), (candidates[0].calculated_fee, candidates[0].calculated_mass));
+        assert_eq!((101_000, parent_mass + child_mass), (candidates[0].package_fee, candidates[0].package_mass));
+        assert!(candidates[0].selection_feerate() > 1000.0 / parent_mass as f64);
+    }
+
+    // test_package_limits verifies that the mempool bounds the count of in-mempool ancestors and descendants of transactions,
+    // accounting for the transactions a replacement evicts.
+    #[test]
+    fn test_package_limits() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_ancestor_count = 2;
+        config.maximum_descendant_count = 3;
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        // A parent with 3 outputs, each redeemable by a distinct child
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_RUSTWEAVE]);
+        let mut parent_tx = create_transaction(&funding_tx, 1000);
+        let output = parent_tx.outputs[0].clone();
+        parent_tx.outputs = vec![TransactionOutput::new(output.value / 3, output.script_public_key); 3];
+        parent_tx.finalize();
+        consensus.add_transaction(funding_tx, 1);
+        let create_child = |index: u32, fee: u64| {
+            let mut child_tx = create_transaction(&parent_tx, fee);
+            child_tx.inputs[0].previous_outpoint.index = index;
+            child_tx.finalize();
+            child_tx
+        };
+        let insert = |tx: &Transaction, rbf_policy: RbfPolicy| {
+            mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                rbf_policy,
+            )
+        };
+
+        let child_tx_0 = create_child(0, 1000);
+        let grandchild_tx = create_transaction(&child_tx_0, 1000);
+        for tx in [&parent_tx, &child_tx_0, &grandchild_tx] {
+            assert!(insert(tx, RbfPolicy::Forbidden).is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        // A transaction with 3 ancestors is rejected
+        let great_grandchild_tx = create_transaction(&grandchild_tx, 1000);
+        let result = insert(&great_grandchild_tx, RbfPolicy::Forbidden);
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectTooManyAncestors(_, 3, 2)))),
+            "the mempool should reject a transaction exceeding the ancestor limit but returns {result:?}"
+        );
+
+        // The parent gets its third descendant but no fourth one
+        let child_tx_1 = create_child(1, 1000);
+        assert!(
+            insert(&child_tx_1, RbfPolicy::Forbidden).is_ok(),
+            "the mempool should accept the valid transaction {}",
+            child_tx_1.id()
+        );
+        let result = insert(&create_child(2, 1000), RbfPolicy::Forbidden);
+        match result {
+            Err(MiningManagerError::MempoolError(RuleError::RejectTooManyDescendants(_, ancestor_id, 4, 3))) => {
+                assert_eq!(parent_tx.id(), ancestor_id, "the error should refer to the parent")
+            }
+            _ => panic!("the mempool should reject a transaction exceeding the descendant limit but returns {result:?}"),
+        }
+
+        // A replacement evicting a descendant of the parent takes its slot
+        let replacement = create_child(1, 100_000);
+        assert!(insert(&replacement, RbfPolicy::Allowed).is_ok(), "the mempool should accept the replacement {}", replacement.id());
+        assert!(!mining_manager.has_transaction(&child_tx_1.id(), TransactionQuery::All));
+
+        // The parent package is evaluated along with its best paying descendant, the replacement
+        let parent_mass = mining_manager.get_transaction(&parent_tx.id(), TransactionQuery::TransactionsOnly).unwrap().tx.mass();
+        let replacement_mass =
+            mining_manager.get_transaction(&replacement.id(), TransactionQuery::TransactionsOnly).unwrap().tx.mass();
+        let candidates = mining_manager.block_candidate_transactions();
+        assert_eq!(1, candidates.len());
+        assert_eq!((101_000, parent_mass + replacement_mass), (candidates[0].package_fee, candidates[0].package_mass));
+    }
+
     // test_handle_new_block_transactions verifies that all the transactions in the block were successfully removed from the mempool.
     #[test]
     fn test_handle_new_block_transactions() {
@@ -250,6 +521,7 @@ mod tests {
                 transaction.tx.as_ref().clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "the insertion of a new valid transaction in the mempool failed");
         }
@@ -308,6 +580,7 @@ mod tests {
             transaction_in_the_mempool.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok());
 
@@ -340,8 +613,13 @@ mod tests {
         assert_eq!(parent_txs.len(), TX_PAIRS_COUNT);
         assert_eq!(child_txs.len(), TX_PAIRS_COUNT);
         for orphan in child_txs.iter() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), orphan.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                orphan.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid orphan transaction {}", orphan.id());
         }
         let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
@@ -485,10 +763,15 @@ mod tests {
         );
 
         // Add the remaining parent transaction into the mempool
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_txs[0].clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_txs[0].clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion of the remaining parent transaction in the mempool failed");
-        let unorphaned_txs = result.unwrap();
+        let unorphaned_txs = result.unwrap().accepted;
         let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
         assert_eq!(
             unorphaned_txs.len(), SKIPPED_TXS + 1,
@@ -592,8 +875,13 @@ mod tests {
 
         // Try submit children while rejecting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Forbidden);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_err(), "mempool should reject an orphan transaction with {:?} when asked to do so", test.priority);
             if let Err(MiningManagerError::MempoolError(RuleError::RejectDisallowedOrphan(transaction_id))) = result {
                 assert_eq!(
@@ -613,8 +901,13 @@ mod tests {
 
         // Try submit children while accepting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert_eq!(
                 test.should_enter_orphan_pool,
                 result.is_ok(),
@@ -622,7 +915,7 @@ mod tests {
                 test.name,
                 test.insert_result()
             );
-            if let Ok(unorphaned_txs) = result {
+            if let Ok(TransactionInsertion { accepted: unorphaned_txs, .. }) = result {
                 assert!(unorphaned_txs.is_empty(), "mempool should unorphan no transaction since it only contains orphans");
             } else if let Err(MiningManagerError::MempoolError(RuleError::RejectOrphanPoolIsFull(pool_len, config_len))) = result {
                 assert_eq!(
@@ -642,10 +935,15 @@ mod tests {
 
         // Submit all the parents
         for (i, (tx, test)) in parent_txs.iter().zip(tests.iter()).enumerate() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "mempool should accept a valid transaction with {:?} when asked to do so", test.priority,);
-            let unorphaned_txs = result.as_ref().unwrap();
+            let unorphaned_txs = &result.as_ref().unwrap().accepted;
             assert_eq!(
                 test.should_unorphan,
                 unorphaned_txs.len() > 1,
@@ -682,8 +980,13 @@ mod tests {
 
         // Add to mempool a transaction that spends child_tx_2 (as high priority)
         let spending_tx = create_transaction(&child_tx_2, 1_000);
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), spending_tx.clone(), Priority::High, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            spending_tx.clone(),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion in the mempool of the spending transaction failed");
 
         // Revalidate, to make sure spending_tx is still valid
@@ -737,11 +1040,21 @@ mod tests {
         let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
 
         for (parent_tx, child_tx) in parent_txs.iter().zip(child_txs.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                parent_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid parent transaction {}", parent_tx.id());
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), child_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                child_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid child transaction {}", parent_tx.id());
         }
 
@@ -940,7 +1253,10 @@ mod tests {
         // Make the funding amounts always different so that funding txs have different ids
         (0..count)
             .map(|i| {
-                create_parent_and_children_transactions(consensus, vec![500 * SOMPI_PER_RUSTWEAVE, 3_000 * SOMPI_PER_RUSTWEAVE + i as u64])
+                create_parent_and_children_transactions(
+                    consensus,
+                    vec![500 * SOMPI_PER_RUSTWEAVE, 3_000 * SOMPI_PER_RUSTWEAVE + i as u64],
+                )
             })
             .unzip()
     }
@@ -13,6 +13,11 @@ pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS: u64 = 100_000;
 
+/// DEFAULT_MAXIMUM_ANCESTOR_COUNT and DEFAULT_MAXIMUM_DESCENDANT_COUNT bound the chains of dependent transactions
+/// in the mempool, hence the cost of evaluating the packages they form when building block templates.
+pub(crate) const DEFAULT_MAXIMUM_ANCESTOR_COUNT: u64 = 25;
+pub(crate) const DEFAULT_MAXIMUM_DESCENDANT_COUNT: u64 = 25;
+
 // TODO: when rusty-kaspa nodes run most of the network, consider increasing this value
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 50;
 
@@ -27,6 +32,10 @@ pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
 pub(crate) const DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 pub(crate) const DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 
+/// DEFAULT_FEERATE_ESTIMATOR_WINDOW specifies how many of the most recently accepted mempool transactions
+/// are taken into account by the feerate estimator.
+pub(crate) const DEFAULT_FEERATE_ESTIMATOR_WINDOW: u64 = 10_000;
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub maximum_transaction_count: u64,
@@ -42,11 +51,15 @@ pub struct Config {
     pub orphan_expire_scan_interval_daa_score: u64,
     pub maximum_orphan_transaction_mass: u64,
     pub maximum_orphan_transaction_count: u64,
+    pub maximum_ancestor_count: u64,
+    pub maximum_descendant_count: u64,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
+    pub target_time_per_block: u64,
+    pub feerate_estimator_window: u64,
 }
 
 impl Config {
@@ -65,11 +78,15 @@ impl Config {
         orphan_expire_scan_interval_daa_score: u64,
         maximum_orphan_transaction_mass: u64,
         maximum_orphan_transaction_count: u64,
+        maximum_ancestor_count: u64,
+        maximum_descendant_count: u64,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
         minimum_relay_transaction_fee: u64,
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
+        target_time_per_block: u64,
+        feerate_estimator_window: u64,
     ) -> Self {
         Self {
             maximum_transaction_count,
@@ -85,11 +102,15 @@ impl Config {
             orphan_expire_scan_interval_daa_score,
             maximum_orphan_transaction_mass,
             maximum_orphan_transaction_count,
+            maximum_ancestor_count,
+            maximum_descendant_count,
             accept_non_standard,
             maximum_mass_per_block,
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
+            target_time_per_block,
+            feerate_estimator_window,
         }
     }
 
@@ -113,11 +134,15 @@ impl Config {
             orphan_expire_scan_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
             maximum_orphan_transaction_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
+            maximum_ancestor_count: DEFAULT_MAXIMUM_ANCESTOR_COUNT,
+            maximum_descendant_count: DEFAULT_MAXIMUM_DESCENDANT_COUNT,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
+            target_time_per_block: target_milliseconds_per_block,
+            feerate_estimator_window: DEFAULT_FEERATE_ESTIMATOR_WINDOW,
         }
     }
 
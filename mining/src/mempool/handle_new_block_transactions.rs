@@ -31,6 +31,9 @@ impl Mempool {
             // its redeemers in the orphan pool. We give those a chance to be unorphaned and included
             // in the next block template.
             if !self.orphan_pool.has(&transaction_id) {
+                if let Some(mempool_transaction) = self.transaction_pool.get(&transaction_id) {
+                    self.feerate_estimator.add_accepted_feerate(mempool_transaction.fee_rate());
+                }
                 self.remove_transaction(&transaction_id, false, TxRemovalReason::Accepted, "")?;
             }
             self.remove_double_spends(transaction)?;
@@ -75,6 +78,7 @@ impl Mempool {
         }
         transactions_to_remove.iter().try_for_each(|x| {
             self.remove_transaction(x, true, TxRemovalReason::DoubleSpend, format!(" favouring {}", transaction.id()).as_str())
+                .map(|_| ())
         })
     }
 }
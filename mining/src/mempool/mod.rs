@@ -1,7 +1,9 @@
 use crate::{
+    feerate::{FeerateEstimations, FeerateEstimator},
     model::{
         candidate_tx::CandidateTransaction,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        tx_ancestry::TransactionAncestry,
         tx_query::TransactionQuery,
     },
     MiningCounters,
@@ -12,7 +14,7 @@ use self::{
     model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
     tx::Priority,
 };
-use kaspa_consensus_core::tx::{MutableTransaction, TransactionId};
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
 use kaspa_core::time::Stopwatch;
 use std::sync::Arc;
 
@@ -46,6 +48,7 @@ pub(crate) struct Mempool {
     transaction_pool: TransactionsPool,
     orphan_pool: OrphanPool,
     accepted_transactions: AcceptedTransactions,
+    feerate_estimator: FeerateEstimator,
     counters: Arc<MiningCounters>,
 }
 
@@ -54,7 +57,8 @@ impl Mempool {
         let transaction_pool = TransactionsPool::new(config.clone());
         let orphan_pool = OrphanPool::new(config.clone());
         let accepted_transactions = AcceptedTransactions::new(config.clone());
-        Self { config, transaction_pool, orphan_pool, accepted_transactions, counters }
+        let feerate_estimator = FeerateEstimator::new(config.clone());
+        Self { config, transaction_pool, orphan_pool, accepted_transactions, feerate_estimator, counters }
     }
 
     pub(crate) fn get_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> Option<MutableTransaction> {
@@ -68,6 +72,10 @@ impl Mempool {
         transaction.map(|x| x.mtx.clone())
     }
 
+    pub(crate) fn get_transaction_ancestry(&self, transaction_id: &TransactionId) -> Option<TransactionAncestry> {
+        self.transaction_pool.get_ancestry(transaction_id)
+    }
+
     pub(crate) fn has_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> bool {
         (query.include_transaction_pool() && self.transaction_pool.has(transaction_id))
             || (query.include_orphan_pool() && self.orphan_pool.has(transaction_id))
@@ -79,6 +87,19 @@ impl Mempool {
         (transactions, orphans)
     }
 
+    pub(crate) fn get_shared_transactions_by_filter(
+        &self,
+        query: TransactionQuery,
+        filter: impl Fn(&TransactionId) -> bool,
+    ) -> Vec<Arc<Transaction>> {
+        let mut transactions =
+            if query.include_transaction_pool() { self.transaction_pool.get_shared_transactions_by_filter(&filter) } else { vec![] };
+        if query.include_orphan_pool() {
+            transactions.extend(self.orphan_pool.get_shared_transactions_by_filter(&filter));
+        }
+        transactions
+    }
+
     pub(crate) fn get_all_transaction_ids(&self, query: TransactionQuery) -> (Vec<TransactionId>, Vec<TransactionId>) {
         let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transaction_ids() } else { vec![] };
         let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transaction_ids() } else { vec![] };
@@ -138,6 +159,12 @@ impl Mempool {
         self.accepted_transactions.unaccepted(&mut transactions.into_iter())
     }
 
+    pub(crate) fn get_feerate_estimations(&self) -> FeerateEstimations {
+        let _sw = Stopwatch::<10>::with_threshold("get_feerate_estimations op");
+        let pending = self.transaction_pool.all().values().map(|x| (x.fee_rate(), x.mtx.tx.mass())).collect();
+        self.feerate_estimator.estimate(pending)
+    }
+
     pub(crate) fn unknown_transactions(&self, transactions: Vec<TransactionId>) -> Vec<TransactionId> {
         let mut not_in_pools_txs = transactions
             .into_iter()
@@ -158,4 +185,14 @@ pub mod tx {
         Forbidden,
         Allowed,
     }
+
+    /// Replace by fee (RBF) policy
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RbfPolicy {
+        /// A transaction double spending an outpoint already spent in the mempool is rejected
+        Forbidden,
+        /// A transaction double spending outpoints already spent in the mempool replaces the conflicting
+        /// transactions and their descendants, provided it pays a strictly higher feerate and enough fees
+        Allowed,
+    }
 }
@@ -9,8 +9,11 @@ use crate::{
         TransactionIdSet,
     },
 };
-use kaspa_consensus_core::tx::{MutableTransaction, TransactionId};
-use std::collections::{hash_set::Iter, HashMap, HashSet, VecDeque};
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
+use std::{
+    collections::{hash_set::Iter, HashMap, HashSet, VecDeque},
+    sync::Arc,
+};
 
 pub(crate) type TransactionsEdges = HashMap<TransactionId, TransactionIdSet>;
 
@@ -99,6 +102,11 @@ pub(crate) trait Pool {
         self.all().values().map(|x| x.mtx.clone()).collect()
     }
 
+    /// Returns a vector with shared references to the transactions in the pool whose ids satisfy `filter`.
+    fn get_shared_transactions_by_filter(&self, filter: &impl Fn(&TransactionId) -> bool) -> Vec<Arc<Transaction>> {
+        self.all().iter().filter(|(id, _)| filter(id)).map(|(_, x)| x.mtx.tx.clone()).collect()
+    }
+
     /// Returns a vector with ids of all the transactions in the pool.
     fn get_all_transaction_ids(&self) -> Vec<TransactionId> {
         self.all().keys().cloned().collect()
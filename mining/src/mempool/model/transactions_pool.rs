@@ -10,7 +10,9 @@ use crate::{
         },
         tx::Priority,
     },
-    model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex},
+    model::{
+        candidate_tx::CandidateTransaction, topological_index::TopologicalIndex, tx_ancestry::TransactionAncestry, TransactionIdSet,
+    },
 };
 use kaspa_consensus_core::{
     tx::TransactionId,
@@ -18,7 +20,7 @@ use kaspa_consensus_core::{
 };
 use kaspa_core::{time::unix_now, trace, warn};
 use std::{
-    collections::{hash_map::Keys, hash_set::Iter, HashSet},
+    collections::{hash_map::Keys, hash_set::Iter, HashMap, HashSet, VecDeque},
     sync::Arc,
 };
 
@@ -92,7 +94,7 @@ impl TransactionsPool {
     }
 
     /// Add a mempool transaction to the pool
-    pub(crate) fn add_mempool_transaction(&mut self, transaction: MempoolTransaction) -> RuleResult<()> {
+    pub(crate) fn add_mempool_transaction(&mut self, mut transaction: MempoolTransaction) -> RuleResult<()> {
         let id = transaction.id();
 
         assert!(!self.all_transactions.contains_key(&id), "transaction {id} to be added already exists in the transactions pool");
@@ -107,11 +109,20 @@ impl TransactionsPool {
         if parents.is_empty() {
             self.ready_transactions.insert(id);
         }
-        for parent_id in parents {
-            let entry = self.chained_transactions.entry(parent_id).or_default();
+        for parent_id in parents.iter() {
+            let entry = self.chained_transactions.entry(*parent_id).or_default();
             entry.insert(id);
         }
 
+        // Aggregate the package of the transaction with its ancestors, each of them gaining a descendant
+        let ancestors = self.get_ancestor_ids_from(parents.into_iter());
+        for ancestor_id in ancestors.iter() {
+            let ancestor = self.all_transactions.get_mut(ancestor_id).unwrap();
+            ancestor.descendant_count += 1;
+            transaction.package_fee += ancestor.mtx.calculated_fee.unwrap();
+            transaction.package_mass += ancestor.mtx.tx.mass();
+        }
+
         self.utxo_set.add_transaction(&transaction.mtx);
         self.all_transactions.insert(id, transaction);
         trace!("Added transaction {}", id);
@@ -120,6 +131,10 @@ impl TransactionsPool {
 
     /// Fully removes the transaction from all relational sets, as well as from the UTXO set
     pub(crate) fn remove_transaction(&mut self, transaction_id: &TransactionId) -> RuleResult<MempoolTransaction> {
+        // Collect the transactions whose package is affected by the removal before the relations are dropped
+        let ancestors = self.get_ancestor_ids_in_pool(transaction_id);
+        let descendants = self.get_redeemer_ids_in_pool(transaction_id);
+
         // Remove all bijective parent/chained relations
         if let Some(parents) = self.parent_transactions.get(transaction_id) {
             for parent in parents.iter() {
@@ -154,6 +169,13 @@ impl TransactionsPool {
         // Remove the transaction from the mempool UTXO set
         self.utxo_set.remove_transaction(&removed_tx.mtx, &parent_ids);
 
+        // Refresh the packages of the former ancestors and descendants. They are recomputed rather than decremented
+        // since the removed transaction may have been the only link between some descendant and ancestor.
+        // The ancestor and descendant count limits keep this bounded.
+        for id in ancestors.into_iter().chain(descendants) {
+            self.refresh_package(&id);
+        }
+
         Ok(removed_tx)
     }
 
@@ -163,15 +185,137 @@ impl TransactionsPool {
 
     /// all_ready_transactions returns all fully populated mempool transactions having no parents in the mempool.
     /// These transactions are ready for being inserted in a block template.
+    ///
+    /// Each transaction comes with the best paying package it is an ancestor of, so that block templates
+    /// evaluate it by the feerate of its descendants when higher (child-pays-for-parent).
     pub(crate) fn all_ready_transactions(&self) -> Vec<CandidateTransaction> {
         // The returned transactions are leaving the mempool so they are cloned
         self.ready_transactions
             .iter()
             .take(self.config.maximum_ready_transaction_count as usize)
-            .map(|id| CandidateTransaction::from_mutable(&self.all_transactions.get(id).unwrap().mtx))
+            .map(|id| {
+                let (package_fee, package_mass) = self.get_best_package(id);
+                CandidateTransaction::from_mutable(&self.all_transactions.get(id).unwrap().mtx).with_package(package_fee, package_mass)
+            })
             .collect()
     }
 
+    /// Returns the ids of all transactions `transaction_id` directly and indirectly spends from and existing in the pool.
+    ///
+    /// The transactions are traversed in BFS mode. The returned order is not guaranteed to be
+    /// topological.
+    pub(crate) fn get_ancestor_ids_in_pool(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        match self.parent_transactions.get(transaction_id) {
+            Some(parents) => self.get_ancestor_ids_from(parents.iter().copied()),
+            None => vec![],
+        }
+    }
+
+    /// Returns the ids of the `parents` existing in the pool along with all the transactions they directly and
+    /// indirectly spend from and existing in the pool, traversed in BFS mode.
+    fn get_ancestor_ids_from(&self, parents: impl Iterator<Item = TransactionId>) -> Vec<TransactionId> {
+        let mut visited = TransactionIdSet::new();
+        let mut ancestors = vec![];
+        let mut queue = VecDeque::new();
+        for parent_id in parents {
+            if self.has(&parent_id) && visited.insert(parent_id) {
+                ancestors.push(parent_id);
+                queue.push_back(parent_id);
+            }
+        }
+        while let Some(id) = queue.pop_front() {
+            if let Some(parents) = self.parent_transactions.get(&id) {
+                for parent_id in parents.iter() {
+                    if self.has(parent_id) && visited.insert(*parent_id) {
+                        ancestors.push(*parent_id);
+                        queue.push_back(*parent_id);
+                    }
+                }
+            }
+        }
+        ancestors
+    }
+
+    /// Returns the ancestors and descendants of the transaction in the pool, along with the aggregate
+    /// fee and mass of the package they form with the transaction.
+    pub(crate) fn get_ancestry(&self, transaction_id: &TransactionId) -> Option<TransactionAncestry> {
+        let transaction = self.get(transaction_id)?;
+        let ancestors = self.get_ancestor_ids_in_pool(transaction_id);
+        let descendants = self.get_redeemer_ids_in_pool(transaction_id);
+        let (package_fee, package_mass) = ancestors
+            .iter()
+            .chain(descendants.iter())
+            .filter_map(|id| self.get(id))
+            .chain(std::iter::once(transaction))
+            .fold((0, 0), |(fee, mass), x| (fee + x.mtx.calculated_fee.unwrap(), mass + x.mtx.tx.mass()));
+        Some(TransactionAncestry::new(ancestors, descendants, package_fee, package_mass))
+    }
+
+    /// Returns the fee and mass of the best paying package among the ones formed by a descendant of
+    /// the transaction, or the transaction itself, along with all the in-pool ancestors of the latter.
+    ///
+    /// The packages are aggregated on insertion, so this operation is linear in the number of descendants
+    fn get_best_package(&self, transaction_id: &TransactionId) -> (u64, u64) {
+        let transaction = self.get(transaction_id).unwrap();
+        let mut best = (transaction.package_fee, transaction.package_mass);
+        for descendant in self.get_redeemer_ids_in_pool(transaction_id).iter().filter_map(|id| self.get(id)) {
+            let package = (descendant.package_fee, descendant.package_mass);
+            // Compare the feerates without loss of precision
+            if package.0 as u128 * best.1 as u128 > best.0 as u128 * package.1 as u128 {
+                best = package;
+            }
+        }
+        best
+    }
+
+    /// Recomputes the package aggregates and the descendant count of a transaction from the current relations in the pool
+    fn refresh_package(&mut self, transaction_id: &TransactionId) {
+        let ancestors = self.get_ancestor_ids_in_pool(transaction_id);
+        let descendant_count = self.get_redeemer_ids_in_pool(transaction_id).len();
+        let (ancestors_fee, ancestors_mass) = ancestors
+            .iter()
+            .filter_map(|id| self.get(id))
+            .fold((0, 0), |(fee, mass), x| (fee + x.mtx.calculated_fee.unwrap(), mass + x.mtx.tx.mass()));
+        if let Some(transaction) = self.all_transactions.get_mut(transaction_id) {
+            transaction.package_fee = transaction.mtx.calculated_fee.unwrap() + ancestors_fee;
+            transaction.package_mass = transaction.mtx.tx.mass() + ancestors_mass;
+            transaction.descendant_count = descendant_count;
+        }
+    }
+
+    /// Validates that inserting `transaction` into the pool keeps it within the maximum count of ancestors and all
+    /// its ancestors within the maximum count of descendants, considering that the transactions in `evicted` are
+    /// about to be removed from the pool.
+    pub(crate) fn check_package_limits(&self, transaction: &MutableTransaction, evicted: &TransactionIdSet) -> RuleResult<()> {
+        let transaction_id = transaction.id();
+        let ancestors = self.get_ancestor_ids_from(self.get_parent_transaction_ids_in_pool(transaction).into_iter());
+        if ancestors.len() as u64 > self.config.maximum_ancestor_count {
+            return Err(RuleError::RejectTooManyAncestors(transaction_id, ancestors.len(), self.config.maximum_ancestor_count));
+        }
+
+        // Evicted transactions free a descendant slot in each of their ancestors
+        let mut evicted_descendant_counts: HashMap<TransactionId, usize> = HashMap::new();
+        for evicted_id in evicted.iter() {
+            for ancestor_id in self.get_ancestor_ids_in_pool(evicted_id) {
+                *evicted_descendant_counts.entry(ancestor_id).or_default() += 1;
+            }
+        }
+        for ancestor_id in ancestors.iter() {
+            let ancestor = self.get(ancestor_id).unwrap();
+            let descendant_count =
+                ancestor.descendant_count - evicted_descendant_counts.get(ancestor_id).copied().unwrap_or_default() + 1;
+            if descendant_count as u64 > self.config.maximum_descendant_count {
+                return Err(RuleError::RejectTooManyDescendants(
+                    transaction_id,
+                    *ancestor_id,
+                    descendant_count,
+                    self.config.maximum_descendant_count,
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Is the mempool transaction identified by `transaction_id` unchained, thus having no successor?
     pub(crate) fn transaction_is_unchained(&self, transaction_id: &TransactionId) -> bool {
         if self.all_transactions.contains_key(transaction_id) {
@@ -192,12 +336,17 @@ impl TransactionsPool {
         &self,
         free_slots: usize,
         transaction: &MutableTransaction,
+        evicted: &TransactionIdSet,
     ) -> RuleResult<Vec<TransactionId>> {
         assert!(free_slots > 0);
         // Returns a vector of transactions to be removed that the caller has to remove actually.
         // The caller is golang validateAndInsertTransaction equivalent.
         // This behavior differs from golang impl.
-        let trim_size = self.len() + free_slots - usize::min(self.len() + free_slots, self.config.maximum_transaction_count as usize);
+        //
+        // `evicted` holds the ids of pool transactions the caller is about to remove anyway, hence
+        // they free their slots and are no candidates for removal.
+        let len = self.len() - evicted.len();
+        let trim_size = len + free_slots - usize::min(len + free_slots, self.config.maximum_transaction_count as usize);
         let mut transactions_to_remove = Vec::with_capacity(trim_size);
         if trim_size > 0 {
             // TODO: consider introducing an index on all_transactions low-priority items instead.
@@ -205,10 +354,12 @@ impl TransactionsPool {
             // Sorting this vector here may be sub-optimal compared with maintaining a sorted
             // index of all_transactions low-priority items if the proportion of low-priority txs
             // in all_transactions is important.
-            let low_priority_txs = self
-                .all_transactions
-                .values()
-                .filter(|x| x.priority == Priority::Low && self.transaction_is_unchained(&x.id()) && !x.is_parent_of(transaction));
+            let low_priority_txs = self.all_transactions.values().filter(|x| {
+                x.priority == Priority::Low
+                    && !evicted.contains(&x.id())
+                    && self.transaction_is_unchained(&x.id())
+                    && !x.is_parent_of(transaction)
+            });
 
             if trim_size == 1 {
                 // This is the most likely case. Here we just search the minimum, thus avoiding the need to sort altogether.
@@ -227,7 +378,7 @@ impl TransactionsPool {
         }
 
         // An error is returned if the mempool is filled with high priority and other unremovable transactions.
-        let tx_count = self.len() + free_slots - transactions_to_remove.len();
+        let tx_count = len + free_slots - transactions_to_remove.len();
         if tx_count as u64 > self.config.maximum_transaction_count {
             let err = RuleError::RejectMempoolIsFull(tx_count - free_slots, self.config.maximum_transaction_count);
             warn!("{}", err.to_string());
@@ -245,6 +396,10 @@ impl TransactionsPool {
         self.utxo_set.get_outpoint_owner_id(outpoint)
     }
 
+    pub(crate) fn get_double_spend_owner_ids(&self, transaction: &MutableTransaction) -> TransactionIdSet {
+        self.utxo_set.get_double_spend_owner_ids(transaction)
+    }
+
     pub(crate) fn check_double_spends(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         self.utxo_set.check_double_spends(transaction)
     }
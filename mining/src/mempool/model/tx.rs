@@ -1,20 +1,28 @@
 use crate::mempool::tx::Priority;
-use kaspa_consensus_core::{tx::MutableTransaction, tx::TransactionId};
+use kaspa_consensus_core::{tx::MutableTransaction, tx::Transaction, tx::TransactionId};
 use std::{
     cmp::Ordering,
     fmt::{Display, Formatter},
+    sync::Arc,
 };
 
 pub(crate) struct MempoolTransaction {
     pub(crate) mtx: MutableTransaction,
     pub(crate) priority: Priority,
     pub(crate) added_at_daa_score: u64,
+    /// Aggregate fee of the package formed by the transaction and all its ancestors in the transaction pool
+    pub(crate) package_fee: u64,
+    /// Aggregate mass of the package formed by the transaction and all its ancestors in the transaction pool
+    pub(crate) package_mass: u64,
+    /// Number of descendants of the transaction in the transaction pool
+    pub(crate) descendant_count: usize,
 }
 
 impl MempoolTransaction {
     pub(crate) fn new(mtx: MutableTransaction, priority: Priority, added_at_daa_score: u64) -> Self {
         assert_eq!(mtx.tx.inputs.len(), mtx.entries.len());
-        Self { mtx, priority, added_at_daa_score }
+        let (package_fee, package_mass) = (mtx.calculated_fee.unwrap_or_default(), mtx.tx.mass());
+        Self { mtx, priority, added_at_daa_score, package_fee, package_mass, descendant_count: 0 }
     }
 
     pub(crate) fn id(&self) -> TransactionId {
@@ -53,6 +61,16 @@ impl PartialEq for MempoolTransaction {
     }
 }
 
+/// The outcome of the post-validation of a transaction followed by its insertion into the mempool
+#[derive(Default)]
+pub(crate) struct TransactionPostValidation {
+    /// Ids of the transactions evicted from the mempool because replaced by the inserted transaction, along with
+    /// their redeemers and the orphans redeeming them
+    pub(crate) removed: Vec<TransactionId>,
+    /// The transaction, if it was inserted into the transaction pool
+    pub(crate) accepted: Option<Arc<Transaction>>,
+}
+
 #[derive(PartialEq, Eq)]
 pub(crate) enum TxRemovalReason {
     Muted,
@@ -61,6 +79,7 @@ pub(crate) enum TxRemovalReason {
     Unorphaned,
     Expired,
     DoubleSpend,
+    ReplacedByFee,
     InvalidInBlockTemplate,
     RevalidationWithMissingOutpoints,
 }
@@ -74,6 +93,7 @@ impl TxRemovalReason {
             TxRemovalReason::Unorphaned => "unorphaned",
             TxRemovalReason::Expired => "expired",
             TxRemovalReason::DoubleSpend => "double spend",
+            TxRemovalReason::ReplacedByFee => "replaced by fee",
             TxRemovalReason::InvalidInBlockTemplate => "invalid in block template",
             TxRemovalReason::RevalidationWithMissingOutpoints => "revalidation with missing outpoints",
         }
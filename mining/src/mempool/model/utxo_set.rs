@@ -68,6 +68,19 @@ impl MempoolUtxoSet {
         self.outpoint_owner_id.get(outpoint)
     }
 
+    /// Returns the ids of the other transactions in the mempool spending an output which one of this transaction inputs spends
+    pub(crate) fn get_double_spend_owner_ids(&self, transaction: &MutableTransaction) -> TransactionIdSet {
+        let transaction_id = transaction.id();
+        transaction
+            .tx
+            .inputs
+            .iter()
+            .filter_map(|input| self.get_outpoint_owner_id(&input.previous_outpoint))
+            .filter(|existing_transaction_id| **existing_transaction_id != transaction_id)
+            .copied()
+            .collect()
+    }
+
     /// Make sure no other transaction in the mempool is already spending an output which one of this transaction inputs spends
     pub(crate) fn check_double_spends(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         let transaction_id = transaction.id();
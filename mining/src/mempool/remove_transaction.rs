@@ -8,19 +8,25 @@ use kaspa_core::{debug, warn};
 use kaspa_utils::iter::IterExtensions;
 
 impl Mempool {
+    /// Removes a transaction, and its redeemers if `remove_redeemers` is set, from the mempool.
+    ///
+    /// Returns the ids of all the removed transactions, orphans included.
     pub(crate) fn remove_transaction(
         &mut self,
         transaction_id: &TransactionId,
         remove_redeemers: bool,
         reason: TxRemovalReason,
         extra_info: &str,
-    ) -> RuleResult<()> {
+    ) -> RuleResult<Vec<TransactionId>> {
         if self.orphan_pool.has(transaction_id) {
-            return self.orphan_pool.remove_orphan(transaction_id, true, reason, extra_info).map(|_| ());
+            return self
+                .orphan_pool
+                .remove_orphan(transaction_id, true, reason, extra_info)
+                .map(|x| x.iter().map(|x| x.id()).collect());
         }
 
         if !self.transaction_pool.has(transaction_id) {
-            return Ok(());
+            return Ok(vec![]);
         }
 
         let mut removed_transactions = vec![*transaction_id];
@@ -65,6 +71,6 @@ impl Mempool {
             },
         }
 
-        Ok(())
+        Ok(removed_transactions)
     }
 }
@@ -1,11 +1,14 @@
-use crate::mempool::{
-    errors::{RuleError, RuleResult},
-    model::{
-        pool::Pool,
-        tx::{MempoolTransaction, TxRemovalReason},
+use crate::{
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TransactionPostValidation, TxRemovalReason},
+        },
+        tx::{Orphan, Priority, RbfPolicy},
+        Mempool,
     },
-    tx::{Orphan, Priority},
-    Mempool,
+    model::TransactionIdSet,
 };
 use kaspa_consensus_core::{
     api::ConsensusApi,
@@ -13,19 +16,21 @@ use kaspa_consensus_core::{
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use kaspa_core::{debug, info};
-use std::sync::Arc;
 
 impl Mempool {
     pub(crate) fn pre_validate_and_populate_transaction(
         &self,
         consensus: &dyn ConsensusApi,
         mut transaction: MutableTransaction,
+        rbf_policy: RbfPolicy,
     ) -> RuleResult<MutableTransaction> {
         self.validate_transaction_unacceptance(&transaction)?;
         // Populate mass in the beginning, it will be used in multiple places throughout the validation and insertion.
         transaction.calculated_compute_mass = Some(consensus.calculate_transaction_compute_mass(&transaction.tx));
         self.validate_transaction_in_isolation(&transaction)?;
-        self.transaction_pool.check_double_spends(&transaction)?;
+        if rbf_policy == RbfPolicy::Forbidden {
+            self.transaction_pool.check_double_spends(&transaction)?;
+        }
         self.populate_mempool_entries(&mut transaction);
         Ok(transaction)
     }
@@ -37,7 +42,8 @@ impl Mempool {
         transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> RuleResult<Option<Arc<Transaction>>> {
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<TransactionPostValidation> {
         let transaction_id = transaction.id();
 
         // First check if the transaction was not already added to the mempool.
@@ -46,13 +52,19 @@ impl Mempool {
         // concurrently.
         if self.transaction_pool.has(&transaction_id) {
             debug!("Transaction {0} is not post validated since already in the mempool", transaction_id);
-            return Ok(None);
+            return Ok(TransactionPostValidation::default());
         }
 
         self.validate_transaction_unacceptance(&transaction)?;
 
         // Re-check double spends since validate_and_insert_transaction is no longer atomic
-        self.transaction_pool.check_double_spends(&transaction)?;
+        let conflicts = match rbf_policy {
+            RbfPolicy::Forbidden => {
+                self.transaction_pool.check_double_spends(&transaction)?;
+                TransactionIdSet::new()
+            }
+            RbfPolicy::Allowed => self.transaction_pool.get_double_spend_owner_ids(&transaction),
+        };
 
         match validation_result {
             Ok(_) => {}
@@ -60,8 +72,10 @@ impl Mempool {
                 if orphan == Orphan::Forbidden {
                     return Err(RuleError::RejectDisallowedOrphan(transaction_id));
                 }
+                // An orphan has no known fee so it cannot replace mempool transactions
+                self.transaction_pool.check_double_spends(&transaction)?;
                 self.orphan_pool.try_add_orphan(consensus.get_virtual_daa_score(), transaction, priority)?;
-                return Ok(None);
+                return Ok(TransactionPostValidation::default());
             }
             Err(err) => {
                 return Err(err);
@@ -70,15 +84,81 @@ impl Mempool {
 
         self.validate_transaction_in_context(&transaction)?;
 
-        // Before adding the transaction, check if there is room in the pool
-        self.transaction_pool.limit_transaction_count(1, &transaction)?.iter().try_for_each(|x| {
-            self.remove_transaction(x, true, TxRemovalReason::MakingRoom, format!(" for {}", transaction_id).as_str())
+        // Fully validate the replacement of the double spent transactions, if any, the package limits and that there is
+        // room in the pool before evicting anything, so that a rejected transaction leaves the mempool untouched
+        let replaced = self.validate_replacement(&transaction, conflicts)?;
+        let replaced_set = replaced.iter().copied().collect::<TransactionIdSet>();
+        self.transaction_pool.check_package_limits(&transaction, &replaced_set)?;
+        let making_room = self.transaction_pool.limit_transaction_count(1, &transaction, &replaced_set)?;
+
+        // Evict the transactions replaced by this one along with the orphans redeeming them
+        let mut removed = Vec::with_capacity(replaced.len());
+        for replaced_id in replaced.iter() {
+            removed.extend(self.remove_transaction(
+                replaced_id,
+                true,
+                TxRemovalReason::ReplacedByFee,
+                format!(" by {}", transaction_id).as_str(),
+            )?);
+        }
+        making_room.iter().try_for_each(|x| {
+            self.remove_transaction(x, true, TxRemovalReason::MakingRoom, format!(" for {}", transaction_id).as_str()).map(|_| ())
         })?;
 
         // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
         let accepted_transaction =
             self.transaction_pool.add_transaction(transaction, consensus.get_virtual_daa_score(), priority)?.mtx.tx.clone();
-        Ok(Some(accepted_transaction))
+        Ok(TransactionPostValidation { removed, accepted: Some(accepted_transaction) })
+    }
+
+    /// Validates that the transaction can replace the mempool transactions it double spends, namely that:
+    ///
+    /// - its feerate is strictly higher than the feerate of every conflicting transaction,
+    /// - the total mass of the conflicting transactions and of their descendants does not exceed the mass of a block,
+    /// - its fee covers the fees of all these evicted transactions plus the minimum relay fee of its own mass,
+    /// - it does not spend an output of any of the evicted transactions.
+    ///
+    /// Returns the ids of all the transactions to evict, conflicting ones first.
+    fn validate_replacement(&self, transaction: &MutableTransaction, conflicts: TransactionIdSet) -> RuleResult<Vec<TransactionId>> {
+        if conflicts.is_empty() {
+            return Ok(vec![]);
+        }
+        let transaction_id = transaction.id();
+        let fee = transaction.calculated_fee.unwrap();
+        let mass = transaction.tx.mass();
+        let feerate = fee as f64 / mass as f64;
+
+        for conflict_id in conflicts.iter() {
+            let conflict = self.transaction_pool.get(conflict_id).unwrap();
+            if feerate <= conflict.fee_rate() {
+                return Err(RuleError::RejectReplacementFeerate(transaction_id, feerate, conflict.fee_rate(), *conflict_id));
+            }
+        }
+
+        let mut evicted_ids = conflicts.iter().copied().collect::<Vec<_>>();
+        let mut visited = conflicts;
+        for conflict_id in evicted_ids.clone().iter() {
+            evicted_ids
+                .extend(self.transaction_pool.get_redeemer_ids_in_pool(conflict_id).into_iter().filter(|id| visited.insert(*id)));
+        }
+
+        if let Some(input) = transaction.tx.inputs.iter().find(|x| visited.contains(&x.previous_outpoint.transaction_id)) {
+            return Err(RuleError::RejectReplacementSpendsEvicted(transaction_id, input.previous_outpoint.transaction_id));
+        }
+
+        let (evicted_mass, evicted_fee) = evicted_ids.iter().fold((0u64, 0u64), |(mass, fee), id| {
+            let evicted = self.transaction_pool.get(id).unwrap();
+            (mass + evicted.mtx.tx.mass(), fee + evicted.mtx.calculated_fee.unwrap())
+        });
+        if evicted_mass > self.config.maximum_mass_per_block {
+            return Err(RuleError::RejectReplacementMass(transaction_id, evicted_mass, self.config.maximum_mass_per_block));
+        }
+        let required_fee = evicted_fee + self.minimum_required_transaction_relay_fee(mass);
+        if fee < required_fee {
+            return Err(RuleError::RejectReplacementFee(transaction_id, fee, required_fee));
+        }
+
+        Ok(evicted_ids)
     }
 
     /// Validates that the transaction wasn't already accepted into the DAG
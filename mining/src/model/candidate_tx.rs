@@ -11,12 +11,32 @@ pub(crate) struct CandidateTransaction {
     pub calculated_fee: u64,
     /// Populated mass
     pub calculated_mass: u64,
+    /// Fee of the best paying package the transaction opens the way to, see [`CandidateTransaction::with_package`]
+    pub package_fee: u64,
+    /// Mass of the best paying package the transaction opens the way to
+    pub package_mass: u64,
 }
 
 impl CandidateTransaction {
     pub(crate) fn from_mutable(tx: &MutableTransaction) -> Self {
         let mass = tx.tx.mass();
         assert_ne!(mass, 0, "mass field is expected to be set when inserting to the mempool");
-        Self { tx: tx.tx.clone(), calculated_fee: tx.calculated_fee.expect("fee is expected to be populated"), calculated_mass: mass }
+        let fee = tx.calculated_fee.expect("fee is expected to be populated");
+        Self { tx: tx.tx.clone(), calculated_fee: fee, calculated_mass: mass, package_fee: fee, package_mass: mass }
+    }
+
+    /// Sets the package of the candidate to a set of mempool transactions made of a descendant of the candidate
+    /// along with all its in-mempool ancestors, the candidate included.
+    ///
+    /// Chained transactions cannot be included in the same block, so a descendant paying a high fee can only get
+    /// mined once its ancestors are. Selecting the candidate by the feerate of such a package lets a child pay
+    /// for its parent (CPFP).
+    pub(crate) fn with_package(self, package_fee: u64, package_mass: u64) -> Self {
+        Self { package_fee, package_mass, ..self }
+    }
+
+    /// The feerate the candidate is selected by, being the highest of its own feerate and of its package feerate
+    pub(crate) fn selection_feerate(&self) -> f64 {
+        f64::max(self.calculated_fee as f64 / self.calculated_mass as f64, self.package_fee as f64 / self.package_mass as f64)
     }
 }
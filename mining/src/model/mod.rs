@@ -5,6 +5,8 @@ pub(crate) mod candidate_tx;
 pub mod owner_txs;
 pub mod topological_index;
 pub mod topological_sort;
+pub mod tx_ancestry;
+pub mod tx_insert;
 pub mod tx_query;
 
 /// A set of unique transaction ids
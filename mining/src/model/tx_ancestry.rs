@@ -0,0 +1,25 @@
+use kaspa_consensus_core::tx::TransactionId;
+
+/// The in-mempool relatives of a transaction along with the aggregate fee and mass of the package they form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionAncestry {
+    /// Ids of the transactions the transaction directly or indirectly spends from
+    pub ancestors: Vec<TransactionId>,
+    /// Ids of the transactions directly or indirectly spending from the transaction
+    pub descendants: Vec<TransactionId>,
+    /// Total fee of the transaction, its ancestors and its descendants
+    pub package_fee: u64,
+    /// Total mass of the transaction, its ancestors and its descendants
+    pub package_mass: u64,
+}
+
+impl TransactionAncestry {
+    pub fn new(ancestors: Vec<TransactionId>, descendants: Vec<TransactionId>, package_fee: u64, package_mass: u64) -> Self {
+        Self { ancestors, descendants, package_fee, package_mass }
+    }
+
+    /// The aggregate feerate of the package, in sompi per gram of mass
+    pub fn package_feerate(&self) -> f64 {
+        self.package_fee as f64 / self.package_mass as f64
+    }
+}
@@ -0,0 +1,18 @@
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
+use std::sync::Arc;
+
+/// The outcome of the insertion of a transaction into the mempool
+#[derive(Debug, Default)]
+pub struct TransactionInsertion {
+    /// Ids of the transactions evicted from the mempool because replaced by the inserted transaction, along with
+    /// their redeemers and the orphans redeeming them
+    pub removed: Vec<TransactionId>,
+    /// The inserted transaction followed by the transactions it unorphaned, all being clones of objects owned by the mempool
+    pub accepted: Vec<Arc<Transaction>>,
+}
+
+impl TransactionInsertion {
+    pub fn new(removed: Vec<TransactionId>, accepted: Vec<Arc<Transaction>>) -> Self {
+        Self { removed, accepted }
+    }
+}
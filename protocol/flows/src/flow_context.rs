@@ -1,9 +1,10 @@
 use crate::flowcontext::{
+    dandelion::DandelionRouter,
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
     transactions::TransactionsSpread,
 };
-use crate::{v5, v6};
+use crate::{v5, v6, v7};
 use async_trait::async_trait;
 use futures::future::join_all;
 use kaspa_addressmanager::AddressManager;
@@ -21,28 +22,29 @@ use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusInstance, ConsensusM
 use kaspa_core::{
     debug, info,
     kaspad_env::{name, version},
-    task::tick::TickService,
+    task::tick::{TickReason, TickService},
 };
 use kaspa_core::{time::unix_now, warn};
 use kaspa_hashes::Hash;
 use kaspa_mining::manager::MiningManagerProxy;
-use kaspa_mining::mempool::tx::{Orphan, Priority};
+use kaspa_mining::mempool::tx::{Orphan, Priority, RbfPolicy};
 use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
     common::ProtocolError,
-    convert::model::version::Version,
+    convert::model::version::{Version, SERVICE_COMPACT_BLOCKS},
     make_message,
-    pb::{rustweaved_message::Payload, InvRelayBlockMessage},
-    ConnectionInitializer, Hub, RustweavedHandshake, PeerKey, PeerProperties, Router,
+    pb::{rustweaved_message::Payload, InvRelayBlockMessage, StemTransactionMessage},
+    ConnectionInitializer, Hub, Misbehavior, PeerKey, PeerProperties, Router, RustweavedHandshake,
 };
 use kaspa_utils::iter::IterExtensions;
 use kaspa_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use std::{collections::hash_map::Entry, fmt::Display};
 use std::{
     iter::once,
+    net::IpAddr,
     ops::Deref,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -57,8 +59,14 @@ use tokio::sync::{
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use uuid::Uuid;
 
-/// The P2P protocol version. Currently the only one supported.
-const PROTOCOL_VERSION: u32 = 6;
+/// The P2P protocol version. Peers with older supported versions are served by the matching flows.
+const PROTOCOL_VERSION: u32 = 7;
+
+/// The minimal protocol version supporting Dandelion stem transactions
+const STEM_TRANSACTIONS_PROTOCOL_VERSION: u32 = 7;
+
+/// Interval between checks for stem transactions whose embargo expired
+const EMBARGO_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -217,6 +225,8 @@ pub struct FlowContextInner {
     shared_block_requests: Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>,
     transactions_spread: AsyncRwLock<TransactionsSpread>,
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
+    /// Dandelion stem routing state, set only if Dandelion relay is enabled
+    dandelion: Option<Mutex<DandelionRouter>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
     pub address_manager: Arc<Mutex<AddressManager>>,
@@ -319,6 +329,7 @@ impl FlowContext {
                 shared_block_requests: Arc::new(Mutex::new(HashMap::new())),
                 transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
+                dandelion: if config.enable_dandelion { Some(Mutex::new(DandelionRouter::new())) } else { None },
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
                 hub,
@@ -351,6 +362,14 @@ impl FlowContext {
         if let Some(logger) = self.block_event_logger.as_ref() {
             logger.start();
         }
+        if self.dandelion.is_some() {
+            let ctx = self.clone();
+            tokio::spawn(async move {
+                while let TickReason::Wakeup = ctx.tick_service.tick(EMBARGO_CHECK_INTERVAL).await {
+                    ctx.fluff_expired_embargoes().await;
+                }
+            });
+        }
     }
 
     pub fn set_connection_manager(&self, connection_manager: Arc<ConnectionManager>) {
@@ -365,6 +384,29 @@ impl FlowContext {
         self.connection_manager.read().clone()
     }
 
+    /// Returns whether the given IP belongs to a network whitelisted via `--whitelist`
+    pub fn is_whitelisted(&self, ip: IpAddr) -> bool {
+        self.connection_manager().is_some_and(|connection_manager| connection_manager.is_whitelisted(ip))
+    }
+
+    /// Adds the misbehavior to the score of the peer and bans the peer IP once the score reaches the ban threshold.
+    /// Whitelisted peers are never banned. Returns whether the peer was banned
+    pub async fn report_misbehavior(&self, router: &Router, misbehavior: Misbehavior, reason: &str) -> bool {
+        if !router.report_misbehavior(misbehavior, reason) {
+            return false;
+        }
+        let reason = router.misbehavior().last_reason.unwrap_or_default();
+        if self.is_whitelisted(router.net_address().ip()) {
+            warn!("Whitelisted peer {} reached the misbehavior ban threshold ({}), not banning", router, reason);
+            return false;
+        }
+        warn!("Banning peer {} for misbehavior ({})", router, reason);
+        if let Some(connection_manager) = self.connection_manager() {
+            connection_manager.ban_for(router.net_address().ip(), connection_manager.ban_duration(), reason).await;
+        }
+        true
+    }
+
     pub fn consensus(&self) -> ConsensusInstance {
         self.consensus_manager.consensus()
     }
@@ -581,6 +623,8 @@ impl FlowContext {
                         mining_manager.revalidate_high_priority_transactions(&consensus_clone, tx).await;
                     });
                     while let Some(transactions) = rx.recv().await {
+                        // Transactions still in their stem phase must not be announced to all peers
+                        let transactions = context.filter_embargoed_transactions(transactions);
                         let _ = context
                             .broadcast_transactions(
                                 transactions,
@@ -612,20 +656,24 @@ impl FlowContext {
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
     /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
     /// peers
+    ///
+    /// If Dandelion relay is enabled, the transaction is first stemmed to a single peer in order to hide its origin.
+    ///
+    /// Returns the ids of the mempool transactions replaced by the submitted transaction, if any.
     pub async fn submit_rpc_transaction(
         &self,
         consensus: &ConsensusProxy,
         transaction: Transaction,
         orphan: Orphan,
-    ) -> Result<(), ProtocolError> {
-        let accepted_transactions =
-            self.mining_manager().clone().validate_and_insert_transaction(consensus, transaction, Priority::High, orphan).await?;
-        self.broadcast_transactions(
-            accepted_transactions.iter().map(|x| x.id()),
-            false, // RPC transactions are considered high priority, so we don't want to throttle them
-        )
-        .await;
-        Ok(())
+        rbf_policy: RbfPolicy,
+    ) -> Result<Vec<TransactionId>, ProtocolError> {
+        let insertion = self
+            .mining_manager()
+            .clone()
+            .validate_and_insert_transaction(consensus, transaction, Priority::High, orphan, rbf_policy)
+            .await?;
+        self.stem_transactions(None, insertion.accepted).await;
+        Ok(insertion.removed)
     }
 
     /// Returns true if the time has come for running the task cleaning mempool transactions.
@@ -654,6 +702,93 @@ impl FlowContext {
     pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&self, transaction_ids: I, should_throttle: bool) {
         self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await
     }
+
+    /// Relays new transactions received from `source` (`None` for locally submitted transactions) through the Dandelion
+    /// stem phase, that is, sends them to the single stem relay routed to `source` and embargoes them.
+    ///
+    /// Transactions are broadcasted right away if Dandelion relay is disabled, if the node is in fluff mode for the
+    /// current epoch or if no peer can serve as a stem relay.
+    pub async fn stem_transactions(&self, source: Option<PeerKey>, transactions: Vec<Arc<Transaction>>) {
+        let Some(dandelion) = self.dandelion.as_ref() else {
+            // RPC transactions are considered high priority, so we don't want to throttle them (nor the rare stem transactions)
+            self.broadcast_transactions(transactions.iter().map(|tx| tx.id()), false).await;
+            return;
+        };
+
+        let candidates = self
+            .hub
+            .active_peers()
+            .into_iter()
+            .filter(|peer| {
+                let properties = peer.properties();
+                peer.is_outbound() && properties.protocol_version >= STEM_TRANSACTIONS_PROTOCOL_VERSION && !properties.disable_relay_tx
+            })
+            .map(|peer| peer.key())
+            .collect::<Vec<_>>();
+        let relay = dandelion.lock().stem_relay(source, &candidates);
+        let Some(relay) = relay else {
+            self.broadcast_transactions(transactions.iter().map(|tx| tx.id()), false).await;
+            return;
+        };
+
+        let mut fluffed = Vec::new();
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            let msg = make_message!(Payload::StemTransaction, StemTransactionMessage { transaction: Some((&*transaction).into()) });
+            match self.hub.send(relay, msg).await {
+                Ok(true) => dandelion.lock().embargo(transaction_id),
+                // The relay disconnected meanwhile, so we fall back to a broadcast
+                _ => fluffed.push(transaction_id),
+            }
+        }
+        self.broadcast_transactions(fluffed, false).await;
+    }
+
+    /// Lifts the Dandelion embargo of transactions received from peers through regular relay, since their stem phase is over.
+    ///
+    /// Callers must make sure the received transactions are identical to the embargoed ones, since a mere announcement
+    /// or a copy with different signatures does not prove that the stem phase is over.
+    pub fn lift_embargoes(&self, transaction_ids: &[TransactionId]) {
+        if let Some(dandelion) = self.dandelion.as_ref() {
+            dandelion.lock().lift_embargoes(transaction_ids);
+        }
+    }
+
+    /// Returns true if the transaction is still in its Dandelion stem phase, in which case it must be served to no one
+    pub fn is_embargoed(&self, transaction_id: &TransactionId) -> bool {
+        self.dandelion.as_ref().is_some_and(|dandelion| dandelion.lock().is_embargoed(transaction_id))
+    }
+
+    /// Returns the transactions still in their Dandelion stem phase
+    pub fn embargoed_transactions(&self) -> HashSet<TransactionId> {
+        self.dandelion.as_ref().map(|dandelion| dandelion.lock().embargoed_transactions()).unwrap_or_default()
+    }
+
+    /// Filters out the transactions which are still in their Dandelion stem phase
+    pub fn filter_embargoed_transactions(&self, transaction_ids: Vec<TransactionId>) -> Vec<TransactionId> {
+        match self.dandelion.as_ref() {
+            Some(dandelion) => {
+                let dandelion = dandelion.lock();
+                transaction_ids.into_iter().filter(|id| !dandelion.is_embargoed(id)).collect()
+            }
+            None => transaction_ids,
+        }
+    }
+
+    /// Broadcasts the stem transactions whose embargo expired without them being announced by the network
+    async fn fluff_expired_embargoes(&self) {
+        let Some(expired) = self.dandelion.as_ref().map(|dandelion| dandelion.lock().take_expired_embargoes()) else {
+            return;
+        };
+        if expired.is_empty() {
+            return;
+        }
+        // Transactions which meanwhile left the mempool (e.g. got mined) are not broadcasted
+        let unknown = self.mining_manager().clone().unknown_transactions(expired.clone()).await;
+        let expired = expired.into_iter().filter(|id| !unknown.contains(id)).collect::<Vec<_>>();
+        debug!("Dandelion: embargo expired for {} stem transactions, broadcasting", expired.len());
+        self.broadcast_transactions(expired, false).await;
+    }
 }
 
 #[async_trait]
@@ -673,8 +808,12 @@ impl ConnectionInitializer for FlowContext {
         // Subnets are not currently supported
         let mut self_version_message = Version::new(local_address, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
-        // TODO: get number of live services
-        // TODO: disable_relay_tx from config/cmd
+        // Light nodes have neither block bodies nor a mempool to serve and reconstruct compact blocks from
+        if !self.config.is_light {
+            self_version_message.services |= SERVICE_COMPACT_BLOCKS;
+        }
+        // Light nodes have no UTXO set to validate transactions against, so they ask peers not to relay them any
+        self_version_message.disable_relay_tx = self.config.is_light;
 
         // Perform the handshake
         let peer_version_message = handshake.handshake(self_version_message.into()).await?;
@@ -700,7 +839,12 @@ impl ConnectionInitializer for FlowContext {
 
         // Register all flows according to version
         let (flows, applied_protocol_version) = match peer_version.protocol_version {
-            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+            v if v >= PROTOCOL_VERSION => {
+                // Compact blocks are used only if both sides advertise them
+                let compact_blocks = !self.config.is_light && peer_version.has_service(SERVICE_COMPACT_BLOCKS);
+                (v7::register(self.clone(), router.clone(), compact_blocks), PROTOCOL_VERSION)
+            }
+            6 => (v6::register(self.clone(), router.clone()), 6),
             5 => (v5::register(self.clone(), router.clone()), 5),
             v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
         };
@@ -708,6 +852,7 @@ impl ConnectionInitializer for FlowContext {
         // Build and register the peer properties
         let peer_properties = Arc::new(PeerProperties {
             user_agent: peer_version.user_agent.to_owned(),
+            services: peer_version.services,
             advertised_protocol_version: peer_version.protocol_version,
             protocol_version: applied_protocol_version,
             disable_relay_tx: peer_version.disable_relay_tx,
@@ -723,7 +868,7 @@ impl ConnectionInitializer for FlowContext {
 
         // Launch all flows. Note we launch only after the ready signal was exchanged
         for flow in flows {
-            flow.launch();
+            flow.launch(self.clone());
         }
 
         if router.is_outbound() || peer_version.address.is_some() {
@@ -1,3 +1,4 @@
+use crate::flow_context::FlowContext;
 use kaspa_core::warn;
 use kaspa_p2p_lib::{common::ProtocolError, Router};
 use kaspa_utils::any::type_name_short;
@@ -16,11 +17,14 @@ where
 
     async fn start(&mut self) -> Result<(), ProtocolError>;
 
-    fn launch(mut self: Box<Self>) {
+    fn launch(mut self: Box<Self>, ctx: FlowContext) {
         tokio::spawn(async move {
             let res = self.start().await;
             if let Err(err) = res {
                 if let Some(router) = self.router() {
+                    if let Some(misbehavior) = err.misbehavior() {
+                        ctx.report_misbehavior(&router, misbehavior, &err.to_string()).await;
+                    }
                     router.try_sending_reject_message(&err).await;
                     if router.close().await || !err.is_connection_closed_error() {
                         warn!("{} flow error: {}, disconnecting from peer {}.", self.name(), err, router);
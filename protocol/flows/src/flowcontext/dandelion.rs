@@ -0,0 +1,184 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_p2p_lib::PeerKey;
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+/// Duration of a Dandelion epoch, during which the stem relays, the fluff mode and the stem routes are kept fixed
+const EPOCH_DURATION: Duration = Duration::from_secs(600);
+
+/// Number of outbound peers selected as stem relays for an epoch
+const STEM_RELAYS_PER_EPOCH: usize = 2;
+
+/// Probability (in percents) for the node to be in fluff mode during an epoch
+const FLUFF_PROBABILITY_PERCENT: u32 = 10;
+
+/// Minimal time a stem transaction is embargoed before falling back to a broadcast
+const EMBARGO_BASE: Duration = Duration::from_secs(10);
+
+/// Upper bound of the random delay added to each embargo, so that nodes along a stem do not time out together
+const EMBARGO_JITTER: Duration = Duration::from_secs(20);
+
+/// Dandelion++ routing state used for hiding the origin of transactions.
+///
+/// During the stem phase, a new transaction is forwarded to a single relay peer instead of being announced
+/// to all peers. Relays are selected among outbound peers at the beginning of every epoch, and each source
+/// peer (or the node itself for local transactions) is routed to the same relay for the whole epoch. A node
+/// in fluff mode ends the stem of transactions received from peers by broadcasting them.
+///
+/// Every stemmed transaction is embargoed: if it was not seen fluffed by the network before its embargo
+/// expires, it gets broadcasted by the node itself.
+pub struct DandelionRouter {
+    epoch_start: Option<Instant>,
+    relays: Vec<PeerKey>,
+    fluff_mode: bool,
+    /// Stem route of each source peer for the current epoch, `None` standing for locally submitted transactions
+    routes: HashMap<Option<PeerKey>, PeerKey>,
+    embargoes: HashMap<TransactionId, Instant>,
+}
+
+impl DandelionRouter {
+    pub fn new() -> Self {
+        Self { epoch_start: None, relays: Vec::new(), fluff_mode: false, routes: HashMap::new(), embargoes: HashMap::new() }
+    }
+
+    /// Returns the peer a transaction received from `source` should be stemmed to, or `None` if the transaction
+    /// should be fluffed. `source` is `None` for locally submitted transactions, which are always stemmed when a relay
+    /// is available. `candidates` are the peers currently eligible as stem relays.
+    ///
+    /// A new epoch starts when the current one is over or when one of its relays is no longer a candidate.
+    pub fn stem_relay(&mut self, source: Option<PeerKey>, candidates: &[PeerKey]) -> Option<PeerKey> {
+        let now = Instant::now();
+        let epoch_is_over = self.epoch_start.map_or(true, |start| now >= start + EPOCH_DURATION);
+        if epoch_is_over || self.relays.iter().any(|relay| !candidates.contains(relay)) {
+            self.start_epoch(now, candidates);
+        }
+
+        if source.is_some() && self.fluff_mode {
+            return None;
+        }
+
+        if let Some(&relay) = self.routes.get(&source) {
+            return Some(relay);
+        }
+        // Avoid routing a stem transaction back to the peer it was received from
+        let eligible = self.relays.iter().copied().filter(|&relay| Some(relay) != source).collect::<Vec<_>>();
+        let relay = *eligible.choose(&mut rand::thread_rng())?;
+        self.routes.insert(source, relay);
+        Some(relay)
+    }
+
+    fn start_epoch(&mut self, now: Instant, candidates: &[PeerKey]) {
+        let rng = &mut rand::thread_rng();
+        self.epoch_start = Some(now);
+        self.relays = candidates.choose_multiple(rng, STEM_RELAYS_PER_EPOCH).copied().collect();
+        self.fluff_mode = rng.gen_range(0..100) < FLUFF_PROBABILITY_PERCENT;
+        self.routes.clear();
+    }
+
+    /// Embargoes a transaction which was just stemmed
+    pub fn embargo(&mut self, transaction_id: TransactionId) {
+        let jitter = rand::thread_rng().gen_range(Duration::ZERO..EMBARGO_JITTER);
+        self.embargoes.insert(transaction_id, Instant::now() + EMBARGO_BASE + jitter);
+    }
+
+    pub fn is_embargoed(&self, transaction_id: &TransactionId) -> bool {
+        self.embargoes.contains_key(transaction_id)
+    }
+
+    pub fn embargoed_transactions(&self) -> HashSet<TransactionId> {
+        self.embargoes.keys().copied().collect()
+    }
+
+    /// Lifts the embargo of transactions received back from the network, meaning their stem phase is over
+    pub fn lift_embargoes<'a>(&mut self, transaction_ids: impl IntoIterator<Item = &'a TransactionId>) {
+        if self.embargoes.is_empty() {
+            return;
+        }
+        for transaction_id in transaction_ids {
+            self.embargoes.remove(transaction_id);
+        }
+    }
+
+    /// Removes and returns the transactions whose embargo expired
+    pub fn take_expired_embargoes(&mut self) -> Vec<TransactionId> {
+        let now = Instant::now();
+        let expired = self.embargoes.iter().filter(|(_, &deadline)| deadline <= now).map(|(&id, _)| id).collect::<Vec<_>>();
+        for transaction_id in expired.iter() {
+            self.embargoes.remove(transaction_id);
+        }
+        expired
+    }
+}
+
+impl Default for DandelionRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+    use uuid::Uuid;
+
+    fn peer_key(i: u8) -> PeerKey {
+        PeerKey::new(Uuid::new_v4().into(), IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)).into())
+    }
+
+    #[test]
+    fn test_stem_routes() {
+        let candidates = (0..8).map(peer_key).collect::<Vec<_>>();
+        let mut router = DandelionRouter::new();
+
+        // Without candidates, transactions are fluffed
+        assert_eq!(router.stem_relay(None, &[]), None);
+
+        // Local transactions are always routed to the same relay within an epoch
+        let local_relay = router.stem_relay(None, &candidates).unwrap();
+        assert!(candidates.contains(&local_relay));
+        for _ in 0..10 {
+            assert_eq!(router.stem_relay(None, &candidates), Some(local_relay));
+        }
+        assert_eq!(router.relays.len(), STEM_RELAYS_PER_EPOCH);
+
+        // Each source peer is consistently routed to one of the epoch relays, other than itself
+        for source in candidates.iter().copied() {
+            let relay = router.stem_relay(Some(source), &candidates);
+            if router.fluff_mode {
+                assert_eq!(relay, None);
+                continue;
+            }
+            let relay = relay.unwrap();
+            assert!(router.relays.contains(&relay));
+            assert_ne!(relay, source);
+            assert_eq!(router.stem_relay(Some(source), &candidates), Some(relay));
+        }
+
+        // Losing a relay starts a new epoch with relays selected among the remaining candidates
+        let remaining = candidates.iter().copied().filter(|&c| c != local_relay).collect::<Vec<_>>();
+        let new_relay = router.stem_relay(None, &remaining).unwrap();
+        assert_ne!(new_relay, local_relay);
+        assert!(router.relays.iter().all(|relay| remaining.contains(relay)));
+    }
+
+    #[test]
+    fn test_embargoes() {
+        let mut router = DandelionRouter::new();
+        let (a, b) = (TransactionId::from_u64_word(1), TransactionId::from_u64_word(2));
+        router.embargo(a);
+        router.embargo(b);
+        assert!(router.is_embargoed(&a) && router.is_embargoed(&b));
+        assert!(router.take_expired_embargoes().is_empty());
+
+        router.lift_embargoes([&a]);
+        assert!(!router.is_embargoed(&a));
+
+        router.embargoes.insert(b, Instant::now());
+        assert_eq!(router.take_expired_embargoes(), vec![b]);
+        assert!(!router.is_embargoed(&b));
+    }
+}
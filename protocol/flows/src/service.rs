@@ -1,14 +1,16 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
+use ipnet::IpNet;
 use kaspa_addressmanager::NetAddress;
 use kaspa_connectionmanager::ConnectionManager;
+use kaspa_consensusmanager::{snapshot::import_pruning_snapshot, spawn_blocking};
 use kaspa_core::{
     task::service::{AsyncService, AsyncServiceFuture},
-    trace,
+    trace, warn,
 };
-use kaspa_p2p_lib::Adaptor;
+use kaspa_p2p_lib::{encryption::EncryptionConfig, Adaptor};
 use kaspa_utils::triggers::SingleTrigger;
-use kaspa_utils_tower::counters::TowerConnectionCounters;
+use kaspa_utils_tower::{counters::TowerConnectionCounters, socks::Socks5Proxy};
 
 use crate::flow_context::FlowContext;
 
@@ -21,13 +23,19 @@ pub struct P2pService {
     listen: NetAddress,
     outbound_target: usize,
     inbound_limit: usize,
+    whitelist: Vec<IpNet>,
+    ban_duration: Duration,
     dns_seeders: &'static [&'static str],
     default_port: u16,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    encryption: Option<Arc<EncryptionConfig>>,
+    proxy: Option<Socks5Proxy>,
+    pruning_snapshot: Option<PathBuf>,
 }
 
 impl P2pService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         flow_context: Arc<FlowContext>,
         connect_peers: Vec<NetAddress>,
@@ -35,9 +43,14 @@ impl P2pService {
         listen: NetAddress,
         outbound_target: usize,
         inbound_limit: usize,
+        whitelist: Vec<IpNet>,
+        ban_duration: Duration,
         dns_seeders: &'static [&'static str],
         default_port: u16,
         counters: Arc<TowerConnectionCounters>,
+        encryption: Option<Arc<EncryptionConfig>>,
+        proxy: Option<Socks5Proxy>,
+        pruning_snapshot: Option<PathBuf>,
     ) -> Self {
         Self {
             flow_context,
@@ -47,9 +60,14 @@ impl P2pService {
             listen,
             outbound_target,
             inbound_limit,
+            whitelist,
+            ban_duration,
             dns_seeders,
             default_port,
             counters,
+            encryption,
+            proxy,
+            pruning_snapshot,
         }
     }
 }
@@ -65,25 +83,43 @@ impl AsyncService for P2pService {
         // Prepare a shutdown signal receiver
         let shutdown_signal = self.shutdown.listener.clone();
 
-        let p2p_adaptor =
-            Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-                .unwrap();
-        let connection_manager = ConnectionManager::new(
-            p2p_adaptor.clone(),
-            self.outbound_target,
-            self.inbound_limit,
-            self.dns_seeders,
-            self.default_port,
-            self.flow_context.address_manager.clone(),
-        );
-
-        self.flow_context.set_connection_manager(connection_manager.clone());
-        self.flow_context.start_async_services();
-
         // Launch the service and wait for a shutdown signal
         Box::pin(async move {
+            // Bootstrap consensus from the local snapshot before any peer is connected and might trigger an IBD
+            if let Some(path) = self.pruning_snapshot.clone() {
+                let (consensus_manager, config) = (self.flow_context.consensus_manager.clone(), self.flow_context.config.clone());
+                match spawn_blocking(move || import_pruning_snapshot(&consensus_manager, &config, &path)).await.unwrap() {
+                    Ok(true) => self.flow_context.on_pruning_point_utxoset_override(),
+                    Ok(false) => {}
+                    Err(err) => warn!("Failed importing the pruning point snapshot: {}", err),
+                }
+            }
+
+            let p2p_adaptor = Adaptor::bidirectional(
+                self.listen,
+                self.flow_context.hub().clone(),
+                self.flow_context.clone(),
+                self.counters.clone(),
+                self.encryption.clone(),
+                self.proxy,
+            )
+            .unwrap();
+            let connection_manager = ConnectionManager::new(
+                p2p_adaptor.clone(),
+                self.outbound_target,
+                self.inbound_limit,
+                self.whitelist.clone(),
+                self.ban_duration,
+                self.dns_seeders,
+                self.default_port,
+                self.flow_context.address_manager.clone(),
+            );
+
+            self.flow_context.set_connection_manager(connection_manager.clone());
+            self.flow_context.start_async_services();
+
             for peer_address in self.connect_peers.iter().cloned().chain(self.add_peers.iter().cloned()) {
-                connection_manager.add_connection_request(peer_address.into(), true).await;
+                connection_manager.add_connection_request(peer_address, true).await;
             }
 
             // Keep the P2P server running until a service shutdown signal is received
@@ -7,7 +7,6 @@ use kaspa_p2p_lib::{
     pb::{rustweaved_message::Payload, AddressesMessage, RequestAddressesMessage},
     IncomingRoute, Router,
 };
-use kaspa_utils::networking::IpAddress;
 use rand::seq::SliceRandom;
 use std::sync::Arc;
 
@@ -18,6 +17,10 @@ const MAX_ADDRESSES_SEND: usize = 1000;
 /// If a peer exceeds this value we consider it a protocol error.
 const MAX_ADDRESSES_RECEIVE: usize = 2500;
 
+/// The minimal protocol version of peers which are sent onion addresses. Older peers are not aware
+/// of the onion field and would interpret such addresses as their virtual IPs
+const ONION_ADDRESSES_MIN_PROTOCOL_VERSION: u32 = 7;
+
 pub struct ReceiveAddressesFlow {
     ctx: FlowContext,
     router: Arc<Router>,
@@ -49,13 +52,13 @@ impl ReceiveAddressesFlow {
             .await?;
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::Addresses)?;
-        let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
+        let address_list: Vec<NetAddress> = msg.try_into()?;
         if address_list.len() > MAX_ADDRESSES_RECEIVE {
             return Err(ProtocolError::OtherOwned(format!("address count {} exceeded {}", address_list.len(), MAX_ADDRESSES_RECEIVE)));
         }
         let mut amgr_lock = self.ctx.address_manager.lock();
-        for (ip, port) in address_list {
-            amgr_lock.add_address(NetAddress::new(ip, port))
+        for address in address_list {
+            amgr_lock.add_address(address)
         }
 
         Ok(())
@@ -87,11 +90,11 @@ impl SendAddressesFlow {
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         loop {
             dequeue!(self.incoming_route, Payload::RequestAddresses)?;
-            let addresses = self.ctx.address_manager.lock().iterate_addresses().collect_vec();
-            let address_list = addresses
-                .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND)
-                .map(|addr| (addr.ip, addr.port).into())
-                .collect();
+            let include_onion = self.router.properties().protocol_version >= ONION_ADDRESSES_MIN_PROTOCOL_VERSION;
+            let addresses =
+                self.ctx.address_manager.lock().iterate_addresses().filter(|addr| include_onion || !addr.is_onion()).collect_vec();
+            let address_list =
+                addresses.choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND).map(|&addr| addr.into()).collect();
             self.router.enqueue(make_message!(Payload::Addresses, AddressesMessage { address_list })).await?;
         }
     }
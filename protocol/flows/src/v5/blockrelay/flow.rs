@@ -3,18 +3,28 @@ use crate::{
     flow_trait::Flow,
     flowcontext::orphans::OrphanOutput,
 };
-use kaspa_consensus_core::{api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError};
+use kaspa_consensus_core::{
+    api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError, tx::Transaction,
+};
 use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use kaspa_core::debug;
 use kaspa_hashes::Hash;
+use kaspa_mining::model::tx_query::TransactionQuery;
 use kaspa_p2p_lib::{
     common::ProtocolError,
+    convert::model::compact::{short_transaction_id, CompactBlock},
     dequeue, dequeue_with_timeout, make_message, make_request,
-    pb::{rustweaved_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    pb::{
+        rustweaved_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestBlockTransactionsMessage,
+        RequestCompactBlockMessage, RequestRelayBlocksMessage,
+    },
     IncomingRoute, Router, SharedIncomingRoute,
 };
 use kaspa_utils::channel::{JobSender, JobTrySendError as TrySendError};
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
 
 pub struct RelayInvMessage {
     hash: Hash,
@@ -63,6 +73,8 @@ pub struct HandleRelayInvsFlow {
     msg_route: IncomingRoute,
     /// A channel sender for sending blocks to be handled by the IBD flow (of this peer)
     ibd_sender: JobSender<Block>,
+    /// Indicates whether relay blocks should be requested as compact blocks (protocol v7 peers advertising the service)
+    compact_blocks: bool,
 }
 
 #[async_trait::async_trait]
@@ -84,7 +96,13 @@ impl HandleRelayInvsFlow {
         msg_route: IncomingRoute,
         ibd_sender: JobSender<Block>,
     ) -> Self {
-        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender }
+        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender, compact_blocks: false }
+    }
+
+    /// Requests relay blocks as compact blocks, falling back to full blocks if reconstruction fails
+    pub fn with_compact_blocks(mut self) -> Self {
+        self.compact_blocks = true;
+        self
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
@@ -134,6 +152,11 @@ impl HandleRelayInvsFlow {
                 return Err(ProtocolError::OtherOwned(format!("sent header of {} where expected block with body", block.hash())));
             }
 
+            if self.ctx.config.is_light {
+                self.process_light_relay_block(&session, inv, block).await?;
+                continue;
+            }
+
             let blue_work_threshold = session.async_get_virtual_merge_depth_blue_work_threshold().await;
             // Since `blue_work` respects topology, the negation of this condition means that the relay
             // block is not in the future of virtual's merge depth root, and thus cannot be merged unless
@@ -215,6 +238,35 @@ impl HandleRelayInvsFlow {
         }
     }
 
+    /// Processes the header of a relay block on a light node. The body is dropped, and the block is
+    /// not relayed further since light nodes cannot serve block bodies to peers
+    async fn process_light_relay_block(
+        &mut self,
+        session: &ConsensusProxy,
+        inv: RelayInvMessage,
+        block: Block,
+    ) -> Result<(), ProtocolError> {
+        let block = Block::from_header_arc(block.header);
+        match session.validate_and_insert_block(block.clone()).block_task.await {
+            Ok(_) => {}
+            Err(RuleError::MissingParents(missing_parents)) => {
+                debug!("Block {} is orphan and has missing parents: {:?}", block.hash(), missing_parents);
+                let Some(ancestor_batch) = self.process_orphan(session, block.clone(), inv.known_within_range).await? else {
+                    return Ok(());
+                };
+                for block_task in ancestor_batch.block_tasks.into_iter().flatten() {
+                    block_task.await?;
+                }
+                session.validate_and_insert_block(block).block_task.await?;
+            }
+            Err(rule_error) => return Err(rule_error.into()),
+        }
+        // Unorphaning is logged internally and there is no further post-processing for header-only blocks
+        self.ctx.unorphan_blocks(session, inv.hash).await;
+        self.ctx.log_block_event(BlockLogEvent::Relay(inv.hash));
+        Ok(())
+    }
+
     fn enqueue_orphan_roots(&mut self, _orphan: Hash, roots: Vec<Hash>, known_within_range: bool) {
         self.invs_route.enqueue_indirect_invs(roots, known_within_range)
     }
@@ -228,6 +280,12 @@ impl HandleRelayInvsFlow {
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else {
             return Ok(None);
         };
+        if self.compact_blocks {
+            if let Some(block) = self.request_compact_block(requested_hash, request_id).await? {
+                return Ok(Some((block, request_scope)));
+            }
+            debug!("Could not reconstruct compact block {}, requesting the full block", requested_hash);
+        }
         self.router
             .enqueue(make_request!(
                 Payload::RequestRelayBlocks,
@@ -244,6 +302,63 @@ impl HandleRelayInvsFlow {
         }
     }
 
+    /// Requests a compact block and reconstructs it from the mempool, fetching the transactions missing locally
+    /// with a single additional round trip. Returns `None` if the reconstructed block does not match the header
+    /// merkle root, in which case the full block should be requested instead.
+    async fn request_compact_block(&mut self, requested_hash: Hash, request_id: u32) -> Result<Option<Block>, ProtocolError> {
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestCompactBlock,
+                RequestCompactBlockMessage { hash: Some(requested_hash.into()) },
+                request_id
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::CompactBlock)?;
+        let compact_block: CompactBlock = msg.try_into()?;
+        if compact_block.hash() != requested_hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested compact block {} but got block {}",
+                requested_hash,
+                compact_block.hash()
+            )));
+        }
+
+        let include_mass_field = compact_block.header.daa_score > self.ctx.config.storage_mass_activation_daa_score;
+        // Only mempool transactions matching a short id of the block are collected
+        let (nonce, short_ids) = (compact_block.nonce, compact_block.short_ids.iter().copied().collect::<HashSet<_>>());
+        let candidates = self
+            .ctx
+            .mining_manager()
+            .clone()
+            .get_shared_transactions_by_filter(TransactionQuery::All, move |id| {
+                short_ids.contains(&short_transaction_id(requested_hash, nonce, *id))
+            })
+            .await;
+        let mut partial_block = compact_block.reconstruct(candidates)?;
+
+        let missing_indexes = partial_block.missing_indexes();
+        if !missing_indexes.is_empty() {
+            self.router
+                .enqueue(make_request!(
+                    Payload::RequestBlockTransactions,
+                    RequestBlockTransactionsMessage { block_hash: Some(requested_hash.into()), indexes: missing_indexes },
+                    request_id
+                ))
+                .await?;
+            let msg = dequeue_with_timeout!(self.msg_route, Payload::BlockTransactions)?;
+            let (hash, transactions): (Hash, Vec<Transaction>) = msg.try_into()?;
+            if hash != requested_hash {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "requested transactions of block {} but got transactions of block {}",
+                    requested_hash, hash
+                )));
+            }
+            partial_block.fill_missing(transactions)?;
+        }
+
+        Ok(partial_block.into_block(include_mass_field))
+    }
+
     /// Process the orphan block. Returns `Some(BlockProcessingBatch)` if the block has no missing roots, where
     /// the batch includes ancestor blocks and their consensus processing batch. This indicates a retry is recommended.
     async fn process_orphan(
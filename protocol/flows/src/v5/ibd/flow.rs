@@ -25,7 +25,7 @@ use kaspa_p2p_lib::{
         rustweaved_message::Payload, RequestAntipastMessage, RequestHeadersMessage, RequestIbdBlocksMessage,
         RequestPruningPointAndItsAnticoneMessage, RequestPruningPointProofMessage, RequestPruningPointUtxoSetMessage,
     },
-    IncomingRoute, Router,
+    IncomingRoute, Misbehavior, Router,
 };
 use kaspa_utils::channel::JobReceiver;
 use std::{
@@ -68,7 +68,6 @@ struct QueueChunkOutput {
     daa_score: u64,
     timestamp: u64,
 }
-// TODO: define a peer banning strategy
 
 impl IbdFlow {
     pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute, relay_receiver: JobReceiver<Block>) -> Self {
@@ -84,6 +83,9 @@ impl IbdFlow {
                     Ok(_) => info!("IBD with peer {} completed successfully", self.router),
                     Err(e) => {
                         info!("IBD with peer {} completed with error: {}", self.router, e);
+                        if let ProtocolError::Timeout(_) = e {
+                            self.ctx.report_misbehavior(&self.router, Misbehavior::IbdStall, &e.to_string()).await;
+                        }
                         return Err(e);
                     }
                 }
@@ -135,12 +137,15 @@ impl IbdFlow {
             }
         }
 
-        // Sync missing bodies in the past of syncer sink (virtual selected parent)
-        self.sync_missing_block_bodies(&session, negotiation_output.syncer_virtual_selected_parent).await?;
+        // Light nodes keep block headers only
+        if !self.ctx.config.is_light {
+            // Sync missing bodies in the past of syncer sink (virtual selected parent)
+            self.sync_missing_block_bodies(&session, negotiation_output.syncer_virtual_selected_parent).await?;
 
-        // Relay block might be in the antipast of syncer sink, thus
-        // check its past for missing bodies as well.
-        self.sync_missing_block_bodies(&session, relay_block.hash()).await?;
+            // Relay block might be in the antipast of syncer sink, thus
+            // check its past for missing bodies as well.
+            self.sync_missing_block_bodies(&session, relay_block.hash()).await?;
+        }
 
         // Following IBD we revalidate orphans since many of them might have been processed during the IBD
         // or are now processable
@@ -222,7 +227,10 @@ impl IbdFlow {
         self.sync_headers(&staging_session, syncer_virtual_selected_parent, pruning_point, relay_block).await?;
         staging_session.async_validate_pruning_points().await?;
         self.validate_staging_timestamps(&self.ctx.consensus().session().await, &staging_session).await?;
-        self.sync_pruning_point_utxoset(&staging_session, pruning_point).await?;
+        // Light nodes rely on the validated pruning proof and header chain alone and keep no UTXO set
+        if !self.ctx.config.is_light {
+            self.sync_pruning_point_utxoset(&staging_session, pruning_point).await?;
+        }
         Ok(())
     }
 
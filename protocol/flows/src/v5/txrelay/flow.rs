@@ -10,7 +10,7 @@ use kaspa_mining::{
     errors::MiningManagerError,
     mempool::{
         errors::RuleError,
-        tx::{Orphan, Priority},
+        tx::{Orphan, Priority, RbfPolicy},
     },
     model::tx_query::TransactionQuery,
     P2pTxCountSample,
@@ -19,7 +19,7 @@ use kaspa_p2p_lib::{
     common::{ProtocolError, DEFAULT_TIMEOUT},
     dequeue, make_message,
     pb::{rustweaved_message::Payload, RequestTransactionsMessage, TransactionNotFoundMessage},
-    IncomingRoute, Router,
+    IncomingRoute, Misbehavior, Router,
 };
 use std::sync::Arc;
 use tokio::time::timeout;
@@ -52,6 +52,10 @@ pub struct RelayTransactionsFlow {
 
     /// Track the number of spam txs coming from this peer
     spam_counter: u64,
+
+    /// Transactions of whitelisted peers are inserted with high priority and are never throttled,
+    /// so that they get relayed even when the mempool is under pressure
+    is_whitelisted: bool,
 }
 
 /// Holds the state information for whether we will throttle tx relay or not
@@ -74,7 +78,8 @@ impl Flow for RelayTransactionsFlow {
 
 impl RelayTransactionsFlow {
     pub fn new(ctx: FlowContext, router: Arc<Router>, invs_route: IncomingRoute, msg_route: IncomingRoute) -> Self {
-        Self { ctx, router, invs_route, msg_route, spam_counter: 0 }
+        let is_whitelisted = ctx.is_whitelisted(router.net_address().ip());
+        Self { ctx, router, invs_route, msg_route, spam_counter: 0, is_whitelisted }
     }
 
     pub fn invs_channel_size() -> usize {
@@ -115,13 +120,15 @@ impl RelayTransactionsFlow {
 
             let session = self.ctx.consensus().unguarded_session();
 
-            // Transaction relay is disabled if the node is out of sync and thus not mining
-            if !session.async_is_nearly_synced().await {
+            // Transaction relay is disabled if the node is out of sync and thus not mining, or if
+            // it is a light node and thus has no UTXO set to validate transactions against
+            if self.ctx.config.is_light || !session.async_is_nearly_synced().await {
                 continue;
             }
 
-            let requests = self.request_transactions(inv, throttling_state.should_throttle, &throttling_state.curr_snapshot).await?;
-            self.receive_transactions(session, requests, throttling_state.should_throttle).await?;
+            let should_throttle = throttling_state.should_throttle && !self.is_whitelisted;
+            let requests = self.request_transactions(inv, should_throttle, &throttling_state.curr_snapshot).await?;
+            self.receive_transactions(session, requests, should_throttle).await?;
         }
     }
 
@@ -131,9 +138,14 @@ impl RelayTransactionsFlow {
         should_throttle: bool,
         curr_snapshot: &P2pTxCountSample,
     ) -> Result<Vec<RequestScope<TransactionId>>, ProtocolError> {
+        // Transactions embargoed by this node are requested as well, since receiving them back through regular
+        // relay is what ends their Dandelion stem phase
+        let embargoed = transaction_ids.iter().copied().filter(|id| self.ctx.is_embargoed(id)).collect::<Vec<_>>();
+
         // Build a vector with the transaction ids unknown in the mempool and not already requested
         // by another peer
-        let transaction_ids = self.ctx.mining_manager().clone().unknown_transactions(transaction_ids).await;
+        let mut transaction_ids = self.ctx.mining_manager().clone().unknown_transactions(transaction_ids).await;
+        transaction_ids.extend(embargoed);
         let mut requests = Vec::new();
         let snapshot_delta = curr_snapshot - &self.ctx.mining_manager().clone().p2p_tx_count_sample();
 
@@ -215,19 +227,40 @@ impl RelayTransactionsFlow {
                 transactions.push(transaction);
             }
         }
+
+        // Embargoed transactions are already in the mempool, so they are only checked for being identical to the
+        // validated local copy, which proves the stem phase is over and lifts their embargo. Since transaction ids
+        // do not commit to signature scripts, those have to be compared explicitly
+        let (embargoed, transactions): (Vec<_>, Vec<_>) =
+            transactions.into_iter().partition(|transaction| self.ctx.is_embargoed(&transaction.id()));
+        for transaction in embargoed {
+            let transaction_id = transaction.id();
+            if let Some(local) =
+                self.ctx.mining_manager().clone().get_transaction(transaction_id, TransactionQuery::TransactionsOnly).await
+            {
+                if local.tx.inputs.iter().zip(transaction.inputs.iter()).all(|(x, y)| x.signature_script == y.signature_script) {
+                    self.ctx.lift_embargoes(&[transaction_id]);
+                }
+            }
+        }
+
+        let priority = if self.is_whitelisted { Priority::High } else { Priority::Low };
+        // Relayed transactions may replace the mempool transactions they double spend, provided they satisfy the fee rules
         let insert_results = self
             .ctx
             .mining_manager()
             .clone()
-            .validate_and_insert_transaction_batch(&consensus, transactions, Priority::Low, Orphan::Allowed)
+            .validate_and_insert_transaction_batch(&consensus, transactions, priority, Orphan::Allowed, RbfPolicy::Allowed)
             .await;
 
         for res in insert_results.iter() {
             match res {
                 Ok(_) => {}
                 Err(MiningManagerError::MempoolError(RuleError::RejectInvalid(transaction_id))) => {
-                    // TODO: discuss a banning process
-                    return Err(ProtocolError::MisbehavingPeer(format!("rejected invalid transaction {}", transaction_id)));
+                    let reason = format!("rejected invalid transaction {}", transaction_id);
+                    if self.ctx.report_misbehavior(&self.router, Misbehavior::InvalidTransaction, &reason).await {
+                        return Err(ProtocolError::MisbehavingPeer(reason));
+                    }
                 }
                 Err(MiningManagerError::MempoolError(RuleError::RejectSpamTransaction(_)))
                 | Err(MiningManagerError::MempoolError(RuleError::RejectNonStandard(..))) => {
@@ -284,9 +317,14 @@ impl RequestTransactionsFlow {
             let msg = dequeue!(self.incoming_route, Payload::RequestTransactions)?;
             let tx_ids: Vec<_> = msg.try_into()?;
             for transaction_id in tx_ids {
-                if let Some(mutable_tx) =
-                    self.ctx.mining_manager().clone().get_transaction(transaction_id, TransactionQuery::TransactionsOnly).await
-                {
+                // Transactions still in their Dandelion stem phase are served to no one
+                let transaction = match self.ctx.is_embargoed(&transaction_id) {
+                    true => None,
+                    false => {
+                        self.ctx.mining_manager().clone().get_transaction(transaction_id, TransactionQuery::TransactionsOnly).await
+                    }
+                };
+                if let Some(mutable_tx) = transaction {
                     // trace!("Send transaction {} to {}", mutable_tx.id(), self.router.identity());
                     self.router.enqueue(make_message!(Payload::Transaction, (&*mutable_tx.tx).into())).await?;
                 } else {
@@ -0,0 +1,174 @@
+use crate::v5::{
+    address::{ReceiveAddressesFlow, SendAddressesFlow},
+    blockrelay::{flow::HandleRelayInvsFlow, handle_requests::HandleRelayBlockRequests},
+    ibd::IbdFlow,
+    ping::{ReceivePingsFlow, SendPingsFlow},
+    request_antipast::HandleAntipastRequests,
+    request_block_locator::RequestBlockLocatorFlow,
+    request_headers::RequestHeadersFlow,
+    request_ibd_blocks::HandleIbdBlockRequests,
+    request_ibd_chain_block_locator::RequestIbdChainBlockLocatorFlow,
+    request_pp_proof::RequestPruningPointProofFlow,
+    request_pruning_point_utxo_set::RequestPruningPointUtxoSetFlow,
+    txrelay::flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+};
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+
+use kaspa_p2p_lib::{Router, RustweavedMessagePayloadType, SharedIncomingRoute};
+use kaspa_utils::channel;
+use std::sync::Arc;
+
+use crate::{
+    v6::request_pruning_point_and_anticone::PruningPointAndItsAnticoneRequestsFlow,
+    v7::{relay_stem_transactions::RelayStemTransactionsFlow, request_compact_blocks::HandleCompactBlockRequests},
+};
+
+pub(crate) mod relay_stem_transactions;
+pub(crate) mod request_compact_blocks;
+
+pub fn register(ctx: FlowContext, router: Arc<Router>, compact_blocks: bool) -> Vec<Box<dyn Flow>> {
+    // IBD flow <-> invs flow communication uses a job channel in order to always
+    // maintain at most a single pending job which can be updated
+    let (ibd_sender, relay_receiver) = channel::job();
+
+    let mut flows: Vec<Box<dyn Flow>> = vec![
+        Box::new(IbdFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![
+                RustweavedMessagePayloadType::BlockHeaders,
+                RustweavedMessagePayloadType::DoneHeaders,
+                RustweavedMessagePayloadType::IbdBlockLocatorHighestHash,
+                RustweavedMessagePayloadType::IbdBlockLocatorHighestHashNotFound,
+                RustweavedMessagePayloadType::BlockWithTrustedDataV4,
+                RustweavedMessagePayloadType::DoneBlocksWithTrustedData,
+                RustweavedMessagePayloadType::IbdChainBlockLocator,
+                RustweavedMessagePayloadType::IbdBlock,
+                RustweavedMessagePayloadType::TrustedData,
+                RustweavedMessagePayloadType::PruningPoints,
+                RustweavedMessagePayloadType::PruningPointProof,
+                RustweavedMessagePayloadType::UnexpectedPruningPoint,
+                RustweavedMessagePayloadType::PruningPointUtxoSetChunk,
+                RustweavedMessagePayloadType::DonePruningPointUtxoSetChunks,
+            ]),
+            relay_receiver,
+        )),
+        Box::new(HandleRelayBlockRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestRelayBlocks]),
+        )),
+        Box::new(HandleCompactBlockRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![
+                RustweavedMessagePayloadType::RequestCompactBlock,
+                RustweavedMessagePayloadType::RequestBlockTransactions,
+            ]),
+        )),
+        Box::new(ReceivePingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![RustweavedMessagePayloadType::Ping]))),
+        Box::new(SendPingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![RustweavedMessagePayloadType::Pong]))),
+        Box::new(RequestHeadersFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestHeaders, RustweavedMessagePayloadType::RequestNextHeaders]),
+        )),
+        Box::new(RequestPruningPointProofFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestPruningPointProof]),
+        )),
+        Box::new(RequestIbdChainBlockLocatorFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestIbdChainBlockLocator]),
+        )),
+        Box::new(PruningPointAndItsAnticoneRequestsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![
+                RustweavedMessagePayloadType::RequestPruningPointAndItsAnticone,
+                RustweavedMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            ]),
+        )),
+        Box::new(RequestPruningPointUtxoSetFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![
+                RustweavedMessagePayloadType::RequestPruningPointUtxoSet,
+                RustweavedMessagePayloadType::RequestNextPruningPointUtxoSetChunk,
+            ]),
+        )),
+        Box::new(HandleIbdBlockRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestIbdBlocks]),
+        )),
+        Box::new(HandleAntipastRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestAntipast]),
+        )),
+        Box::new(RelayTransactionsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe_with_capacity(
+                vec![RustweavedMessagePayloadType::InvTransactions],
+                RelayTransactionsFlow::invs_channel_size(),
+            ),
+            router.subscribe_with_capacity(
+                vec![RustweavedMessagePayloadType::Transaction, RustweavedMessagePayloadType::TransactionNotFound],
+                RelayTransactionsFlow::txs_channel_size(),
+            ),
+        )),
+        Box::new(RequestTransactionsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestTransactions]),
+        )),
+        Box::new(RelayStemTransactionsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::StemTransaction]),
+        )),
+        Box::new(ReceiveAddressesFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::Addresses]),
+        )),
+        Box::new(SendAddressesFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestAddresses]),
+        )),
+        Box::new(RequestBlockLocatorFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![RustweavedMessagePayloadType::RequestBlockLocator]),
+        )),
+    ];
+
+    let invs_route = router.subscribe_with_capacity(vec![RustweavedMessagePayloadType::InvRelayBlock], ctx.block_invs_channel_size());
+    let shared_invs_route = SharedIncomingRoute::new(invs_route);
+
+    let num_relay_flows = (ctx.config.bps() as usize / 2).max(1);
+    flows.extend((0..num_relay_flows).map(|_| {
+        let flow = HandleRelayInvsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            shared_invs_route.clone(),
+            router.subscribe(vec![]),
+            ibd_sender.clone(),
+        );
+        Box::new(if compact_blocks { flow.with_compact_blocks() } else { flow }) as Box<dyn Flow>
+    }));
+
+    // The reject message is handled as a special case by the router
+    // RustweavedMessagePayloadType::Reject,
+
+    // We do not register the below two messages since they are deprecated also in go-kaspa
+    // RustweavedMessagePayloadType::BlockWithTrustedData,
+    // RustweavedMessagePayloadType::IbdBlockLocator,
+
+    flows
+}
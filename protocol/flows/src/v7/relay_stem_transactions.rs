@@ -0,0 +1,70 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_mining::{
+    errors::MiningManagerError,
+    mempool::{
+        errors::RuleError,
+        tx::{Orphan, Priority, RbfPolicy},
+    },
+};
+use kaspa_p2p_lib::{common::ProtocolError, dequeue, pb::rustweaved_message::Payload, IncomingRoute, Misbehavior, Router};
+use std::sync::Arc;
+
+/// Flow receiving transactions in their Dandelion stem phase, adding them to the mempool and
+/// either forwarding them to the next stem relay or fluffing them to the rest of the network.
+pub struct RelayStemTransactionsFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for RelayStemTransactionsFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl RelayStemTransactionsFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let transaction: Transaction = dequeue!(self.incoming_route, Payload::StemTransaction)?.try_into()?;
+
+            let session = self.ctx.consensus().unguarded_session();
+
+            // Transaction relay is disabled if the node is out of sync and thus not mining, or if
+            // it is a light node and thus has no UTXO set to validate transactions against
+            if self.ctx.config.is_light || !session.async_is_nearly_synced().await {
+                continue;
+            }
+
+            let transaction_id = transaction.id();
+            // Transactions of whitelisted peers are relayed even when the mempool is under pressure
+            let priority = if self.ctx.is_whitelisted(self.router.net_address().ip()) { Priority::High } else { Priority::Low };
+            match self
+                .ctx
+                .mining_manager()
+                .clone()
+                .validate_and_insert_transaction(&session, transaction, priority, Orphan::Forbidden, RbfPolicy::Allowed)
+                .await
+            {
+                Ok(insertion) => self.ctx.stem_transactions(Some(self.router.key()), insertion.accepted).await,
+                Err(MiningManagerError::MempoolError(RuleError::RejectInvalid(_))) => {
+                    let reason = format!("rejected invalid stem transaction {}", transaction_id);
+                    if self.ctx.report_misbehavior(&self.router, Misbehavior::InvalidTransaction, &reason).await {
+                        return Err(ProtocolError::MisbehavingPeer(reason));
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_core::debug;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::compact::CompactBlock,
+    make_response,
+    pb::{rustweaved_message::Payload, BlockTransactionsMessage},
+    IncomingRoute, Router,
+};
+use std::sync::Arc;
+
+/// Serves compact blocks and the transactions a peer failed to recover while reconstructing them
+pub struct HandleCompactBlockRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleCompactBlockRequests {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleCompactBlockRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let Some(msg) = self.incoming_route.recv().await else {
+                return Err(ProtocolError::ConnectionClosed);
+            };
+            let request_id = msg.request_id;
+            match msg.payload {
+                Some(Payload::RequestCompactBlock(request)) => self.send_compact_block(request.try_into()?, request_id).await?,
+                Some(Payload::RequestBlockTransactions(request)) => {
+                    let (hash, indexes) = request.try_into()?;
+                    self.send_block_transactions(hash, indexes, request_id).await?
+                }
+                payload => {
+                    return Err(ProtocolError::UnexpectedMessage(
+                        "Payload::RequestCompactBlock | Payload::RequestBlockTransactions",
+                        payload.as_ref().map(|v| v.into()),
+                    ))
+                }
+            }
+        }
+    }
+
+    async fn send_compact_block(&mut self, hash: Hash, request_id: u32) -> Result<(), ProtocolError> {
+        let block = self.ctx.consensus().unguarded_session().async_get_block(hash).await?;
+        let compact_block = CompactBlock::from_block(&block, rand::random());
+        self.router.enqueue(make_response!(Payload::CompactBlock, (&compact_block).into(), request_id)).await?;
+        debug!("relayed compact block with hash {} to peer {}", hash, self.router);
+        Ok(())
+    }
+
+    async fn send_block_transactions(&mut self, hash: Hash, indexes: Vec<u32>, request_id: u32) -> Result<(), ProtocolError> {
+        let block = self.ctx.consensus().unguarded_session().async_get_block(hash).await?;
+        let transactions = indexes
+            .into_iter()
+            .map(|i| {
+                block.transactions.get(i as usize).map(|tx| tx.into()).ok_or_else(|| {
+                    ProtocolError::OtherOwned(format!("requested transaction index {} is out of range for block {}", i, hash))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.router
+            .enqueue(make_response!(
+                Payload::BlockTransactions,
+                BlockTransactionsMessage { block_hash: Some(hash.into()), transactions },
+                request_id
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+//!
+//! Scripted adversarial peer used for exercising the P2P protocol handling of a target node.
+//!
+//! The peer performs a regular handshake and then replays a [`Script`] of crafted messages and expectations.
+//! Once the script is over, a [`ScriptOutcome`] is reported, allowing to assert how the target reacted.
+//!
+
+use crate::{
+    common::ProtocolError,
+    convert::model::version::Version,
+    core::adaptor::ConnectionInitializer,
+    handshake::RustweavedHandshake,
+    make_message, make_response,
+    pb::{rustweaved_message::Payload, AddressesMessage, PongMessage, RustweavedMessage},
+    IncomingRoute, Router, RustweavedMessagePayloadType,
+};
+use kaspa_core::{debug, trace};
+use parking_lot::Mutex;
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::oneshot::{channel as oneshot_channel, Receiver as OneshotReceiver, Sender as OneshotSender},
+    time::{timeout_at, Instant},
+};
+use tonic::async_trait;
+use uuid::Uuid;
+
+pub mod scripts;
+
+/// Protocol version advertised by default by adversarial peers. This is the latest version by which the target relays
+/// full blocks rather than compact ones, which keeps scripts simple
+pub const DEFAULT_ADVERSARY_PROTOCOL_VERSION: u32 = 6;
+
+/// Default time allowed for the target to send a message expected by a script step
+const DEFAULT_STEP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Builds the messages sent in response to a request received during a [`Step::Respond`] step
+pub type Responder = Box<dyn FnOnce(&RustweavedMessage) -> Vec<RustweavedMessage> + Send>;
+
+/// A single step of a [`Script`]
+pub enum Step {
+    /// Sends a message to the target
+    Send(RustweavedMessage),
+    /// Waits for the target to send a message of the given type, leaving it unanswered
+    Expect(RustweavedMessagePayloadType),
+    /// Waits for the target to send a message of the given type and answers it with the messages built by the responder
+    Respond(RustweavedMessagePayloadType, Responder),
+    /// Keeps the connection idle for the given duration, leaving all requests of the target unanswered
+    Stall(Duration),
+    /// Waits for the target to close the connection within the given duration
+    ExpectDisconnect(Duration),
+}
+
+/// A sequence of steps replayed by an [`AdversarialPeer`] once the handshake is over.
+///
+/// While waiting for the target, pings and address requests are answered so that the connection is
+/// not dropped for unrelated reasons. Any other message which is not awaited by the current step is ignored.
+pub struct Script {
+    steps: Vec<Step>,
+    step_timeout: Duration,
+}
+
+impl Script {
+    pub fn new() -> Self {
+        Self { steps: Vec::new(), step_timeout: DEFAULT_STEP_TIMEOUT }
+    }
+
+    /// Sets the time allowed for the target to send the message awaited by `Expect` and `Respond` steps
+    pub fn with_step_timeout(mut self, step_timeout: Duration) -> Self {
+        self.step_timeout = step_timeout;
+        self
+    }
+
+    pub fn send(mut self, msg: RustweavedMessage) -> Self {
+        self.steps.push(Step::Send(msg));
+        self
+    }
+
+    pub fn expect(mut self, msg_type: RustweavedMessagePayloadType) -> Self {
+        self.steps.push(Step::Expect(msg_type));
+        self
+    }
+
+    pub fn respond(
+        mut self,
+        msg_type: RustweavedMessagePayloadType,
+        responder: impl FnOnce(&RustweavedMessage) -> Vec<RustweavedMessage> + Send + 'static,
+    ) -> Self {
+        self.steps.push(Step::Respond(msg_type, Box::new(responder)));
+        self
+    }
+
+    pub fn stall(mut self, duration: Duration) -> Self {
+        self.steps.push(Step::Stall(duration));
+        self
+    }
+
+    pub fn expect_disconnect(mut self, duration: Duration) -> Self {
+        self.steps.push(Step::ExpectDisconnect(duration));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl Default for Script {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The result of replaying a [`Script`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptOutcome {
+    /// All steps were replayed and the connection is still open
+    Completed,
+    /// The target closed the connection during the step at the given index
+    Disconnected(usize),
+    /// The step at the given index failed, for instance because the target did not send an expected message in time
+    Failed(usize, String),
+}
+
+impl ScriptOutcome {
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, Self::Disconnected(_))
+    }
+}
+
+/// A connection initializer performing the handshake with the target and then replaying a script over the connection.
+/// The script is consumed by the first connection, so each adversarial peer is meant to be used for a single connection.
+pub struct AdversarialPeer {
+    network: String,
+    protocol_version: u32,
+    script: Mutex<Option<(Script, OneshotSender<ScriptOutcome>)>>,
+}
+
+impl AdversarialPeer {
+    /// Creates an adversarial peer for the given network name (see `Params::network_name`), along with a
+    /// receiver of the outcome of its script
+    pub fn new(network: String, script: Script) -> (Arc<Self>, OneshotReceiver<ScriptOutcome>) {
+        Self::with_protocol_version(network, DEFAULT_ADVERSARY_PROTOCOL_VERSION, script)
+    }
+
+    pub fn with_protocol_version(
+        network: String,
+        protocol_version: u32,
+        script: Script,
+    ) -> (Arc<Self>, OneshotReceiver<ScriptOutcome>) {
+        let (outcome_sender, outcome_receiver) = oneshot_channel();
+        (Arc::new(Self { network, protocol_version, script: Mutex::new(Some((script, outcome_sender))) }), outcome_receiver)
+    }
+}
+
+#[async_trait]
+impl ConnectionInitializer for AdversarialPeer {
+    async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+        let Some((script, outcome_sender)) = self.script.lock().take() else {
+            return Err(ProtocolError::Other("the script of this adversarial peer was already replayed"));
+        };
+
+        let mut handshake = RustweavedHandshake::new(&router);
+        router.start();
+        let version = Version::new(None, Uuid::new_v4().into(), self.network.clone(), None, self.protocol_version);
+        handshake.handshake(version.into()).await?;
+
+        // Subscribe to all messages before signaling ready, since the target launches its flows right after
+        let incoming_route = router.subscribe(scripted_message_types());
+        handshake.exchange_ready_messages().await?;
+
+        let runner = ScriptRunner { router, incoming_route, step_timeout: script.step_timeout };
+        tokio::spawn(async move {
+            let outcome = runner.run(script.steps).await;
+            debug!("Adversarial script completed with outcome {:?}", outcome);
+            let _ = outcome_sender.send(outcome);
+        });
+        Ok(())
+    }
+}
+
+/// All message types but the handshake ones and `Reject`, which is handled by the router by closing the connection
+fn scripted_message_types() -> Vec<RustweavedMessagePayloadType> {
+    RustweavedMessagePayloadType::ALL
+        .iter()
+        .copied()
+        .filter(|msg_type| {
+            !matches!(
+                msg_type,
+                RustweavedMessagePayloadType::Version
+                    | RustweavedMessagePayloadType::Verack
+                    | RustweavedMessagePayloadType::Ready
+                    | RustweavedMessagePayloadType::Reject
+            )
+        })
+        .collect()
+}
+
+struct ScriptRunner {
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+    step_timeout: Duration,
+}
+
+impl ScriptRunner {
+    async fn run(mut self, steps: Vec<Step>) -> ScriptOutcome {
+        for (i, step) in steps.into_iter().enumerate() {
+            match self.run_step(step).await {
+                Ok(()) => {}
+                Err(ProtocolError::ConnectionClosed) => return ScriptOutcome::Disconnected(i),
+                Err(err) => {
+                    self.router.close().await;
+                    return ScriptOutcome::Failed(i, err.to_string());
+                }
+            }
+        }
+        ScriptOutcome::Completed
+    }
+
+    async fn run_step(&mut self, step: Step) -> Result<(), ProtocolError> {
+        match step {
+            Step::Send(msg) => self.router.enqueue(msg).await,
+            Step::Expect(msg_type) => self.receive(msg_type).await.map(drop),
+            Step::Respond(msg_type, responder) => {
+                let request = self.receive(msg_type).await?;
+                for msg in responder(&request) {
+                    self.router.enqueue(msg).await?;
+                }
+                Ok(())
+            }
+            Step::Stall(duration) => self.idle(duration).await,
+            Step::ExpectDisconnect(duration) => {
+                self.idle(duration).await?;
+                Err(ProtocolError::OtherOwned(format!("the target did not disconnect within {:?}", duration)))
+            }
+        }
+    }
+
+    /// Waits for a message of the given type, answering or ignoring any other message received meanwhile
+    async fn receive(&mut self, msg_type: RustweavedMessagePayloadType) -> Result<RustweavedMessage, ProtocolError> {
+        let deadline = Instant::now() + self.step_timeout;
+        loop {
+            let msg = match timeout_at(deadline, self.incoming_route.recv()).await {
+                Ok(Some(msg)) => msg,
+                Ok(None) => return Err(ProtocolError::ConnectionClosed),
+                Err(_) => return Err(ProtocolError::Timeout(self.step_timeout)),
+            };
+            if RustweavedMessagePayloadType::from(msg.payload.as_ref().expect("routed messages have a payload")) == msg_type {
+                return Ok(msg);
+            }
+            self.handle_unscripted(msg).await?;
+        }
+    }
+
+    /// Keeps the connection open for `duration`, returning an error if it gets closed meanwhile
+    async fn idle(&mut self, duration: Duration) -> Result<(), ProtocolError> {
+        let deadline = Instant::now() + duration;
+        loop {
+            match timeout_at(deadline, self.incoming_route.recv()).await {
+                Ok(Some(msg)) => self.handle_unscripted(msg).await?,
+                Ok(None) => return Err(ProtocolError::ConnectionClosed),
+                Err(_) => return Ok(()),
+            }
+        }
+    }
+
+    async fn handle_unscripted(&self, msg: RustweavedMessage) -> Result<(), ProtocolError> {
+        match msg.payload {
+            Some(Payload::Ping(ping)) => self.router.enqueue(make_message!(Payload::Pong, PongMessage { nonce: ping.nonce })).await,
+            Some(Payload::RequestAddresses(_)) => {
+                self.router
+                    .enqueue(make_response!(Payload::Addresses, AddressesMessage { address_list: vec![] }, msg.request_id))
+                    .await
+            }
+            payload => {
+                trace!("Adversarial peer ignoring unscripted message {:?}", payload.as_ref().map(RustweavedMessagePayloadType::from));
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//!
+//! Ready-made adversarial scripts covering common attack patterns against the block relay, IBD and transaction relay flows.
+//!
+//! The IBD scripts take a valid `chain` of blocks (ordered from the child of `genesis` up to the tip) which is
+//! unknown to the target. The tip is relayed to the target which, finding it orphan and out of orphan resolution range,
+//! starts an IBD with the adversary as its syncer.
+//!
+
+use super::Script;
+use crate::{
+    make_message, make_response,
+    pb::{
+        rustweaved_message::Payload, BlockHeadersMessage, BlockLocatorMessage, BlockMessage, DoneHeadersMessage,
+        IbdChainBlockLocatorMessage, InvRelayBlockMessage, InvTransactionsMessage, PruningPointUtxoSetChunkMessage,
+        RequestTransactionsMessage, RustweavedMessage,
+    },
+    RustweavedMessagePayloadType,
+};
+use kaspa_consensus_core::{
+    block::Block,
+    tx::{ScriptPublicKey, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
+};
+use kaspa_hashes::Hash;
+use std::{collections::HashMap, time::Duration};
+
+/// The time allowed for the target to drop the connection after receiving the offending messages
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The time allowed for the target to process a relayed transaction before it gets requested back
+const TRANSACTION_PROCESSING_DELAY: Duration = Duration::from_secs(1);
+
+/// Relays a copy of `block` with an unsupported header version. The target is expected to reject the block
+/// and to ban the adversary for relaying an invalid block.
+pub fn malformed_relay_block(block: &Block) -> Script {
+    let mut header = (*block.header).clone();
+    header.version += 1;
+    header.finalize();
+    let malformed = Block::new(header, block.transactions.to_vec());
+
+    Script::new()
+        .send(inv_relay_block(malformed.hash()))
+        .respond(RustweavedMessagePayloadType::RequestRelayBlocks, move |request| {
+            vec![make_response!(Payload::Block, BlockMessage::from(&malformed), request.request_id)]
+        })
+        .expect_disconnect(DISCONNECT_TIMEOUT)
+}
+
+/// Leads the target into an IBD of `chain` and then sends the requested block bodies in reverse order.
+/// The target is expected to abort the IBD and disconnect.
+pub fn out_of_order_ibd_blocks(chain: Vec<Block>, genesis: Hash) -> Script {
+    let headers = BlockHeadersMessage { block_headers: chain.iter().map(|block| block.header.as_ref().into()).collect() };
+    let blocks: HashMap<Hash, Block> = chain.iter().map(|block| (block.hash(), block.clone())).collect();
+
+    relay_until_ibd(&chain, genesis)
+        .respond(RustweavedMessagePayloadType::RequestIbdChainBlockLocator, chain_locator_responder(tip_hash(&chain), genesis))
+        .respond(RustweavedMessagePayloadType::RequestHeaders, move |_| {
+            vec![make_message!(Payload::BlockHeaders, headers), make_message!(Payload::DoneHeaders, DoneHeadersMessage {})]
+        })
+        .respond(RustweavedMessagePayloadType::RequestIbdBlocks, move |request| {
+            let Some(Payload::RequestIbdBlocks(request)) = &request.payload else { unreachable!() };
+            request
+                .hashes
+                .iter()
+                .rev()
+                .filter_map(|hash| Hash::try_from(hash.clone()).ok())
+                .filter_map(|hash| blocks.get(&hash))
+                .map(|block| make_message!(Payload::IbdBlock, block.into()))
+                .collect()
+        })
+        .expect_disconnect(DISCONNECT_TIMEOUT)
+}
+
+/// Leads the target into an IBD of `chain` and then leaves its chain negotiation zoom-in request unanswered.
+/// The target is expected to time out, report the stall and disconnect.
+pub fn stalling_ibd_negotiation(chain: Vec<Block>, genesis: Hash) -> Script {
+    relay_until_ibd(&chain, genesis)
+        .expect(RustweavedMessagePayloadType::RequestIbdChainBlockLocator)
+        .expect_disconnect(DISCONNECT_TIMEOUT)
+}
+
+/// Floods the target with `chunks` unsolicited pruning point UTXO set chunks of `entries_per_chunk` entries each.
+/// Once the incoming route of the IBD flow is full, the target is expected to disconnect.
+pub fn utxo_chunk_flood(chunks: usize, entries_per_chunk: usize) -> Script {
+    let entry = UtxoEntry::new(1, ScriptPublicKey::from_vec(0, vec![0; 34]), 0, false);
+    let mut script = Script::new();
+    for i in 0..chunks {
+        let outpoint_and_utxo_entry_pairs = (0..entries_per_chunk)
+            .map(|j| (&TransactionOutpoint::new(Hash::from_u64_word(i as u64), j as u32), &entry).into())
+            .collect();
+        script = script
+            .send(make_message!(Payload::PruningPointUtxoSetChunk, PruningPointUtxoSetChunkMessage { outpoint_and_utxo_entry_pairs }));
+    }
+    script.expect_disconnect(DISCONNECT_TIMEOUT)
+}
+
+/// Acts as the Dandelion stem relay of the target, which is expected to stem `transaction` to the adversary once
+/// submitted to it. The adversary then stalls for `stall` so that the caller can inspect the target meanwhile.
+///
+/// The target is expected not to serve the embargoed transaction, to keep the embargo when the transaction is only
+/// announced or when a copy with tampered signatures is relayed back, and to lift it once an identical copy is relayed.
+pub fn stem_transaction_embargo(transaction: Transaction, stall: Duration) -> Script {
+    let transaction_id = transaction.id();
+    // Transaction ids do not commit to signature scripts, so the tampered copy has the same id
+    let mut tampered = transaction.clone();
+    tampered.inputs[0].signature_script.push(0);
+
+    Script::new()
+        .expect(RustweavedMessagePayloadType::StemTransaction)
+        .stall(stall)
+        .send(request_transactions(transaction_id))
+        .expect(RustweavedMessagePayloadType::TransactionNotFound)
+        .send(inv_transactions(transaction_id))
+        .respond(RustweavedMessagePayloadType::RequestTransactions, move |_| {
+            vec![make_message!(Payload::Transaction, (&tampered).into())]
+        })
+        .stall(TRANSACTION_PROCESSING_DELAY)
+        .send(request_transactions(transaction_id))
+        .expect(RustweavedMessagePayloadType::TransactionNotFound)
+        .send(inv_transactions(transaction_id))
+        .respond(RustweavedMessagePayloadType::RequestTransactions, move |_| {
+            vec![make_message!(Payload::Transaction, (&transaction).into())]
+        })
+        .stall(TRANSACTION_PROCESSING_DELAY)
+        .send(request_transactions(transaction_id))
+        .expect(RustweavedMessagePayloadType::Transaction)
+}
+
+/// Relays the tip of `chain` and answers the requests of the target up to the first chain locator request of its IBD.
+/// The following zoom-in request is left for the caller to script.
+fn relay_until_ibd(chain: &[Block], genesis: Hash) -> Script {
+    let tip = chain.last().expect("chain is expected to be non empty").clone();
+    let tip_hash = tip.hash();
+
+    Script::new()
+        .send(inv_relay_block(tip_hash))
+        .respond(RustweavedMessagePayloadType::RequestRelayBlocks, move |request| {
+            vec![make_response!(Payload::Block, BlockMessage::from(&tip), request.request_id)]
+        })
+        // Claim the tip to be out of the orphan resolution range of the target by locating it with its own hash only
+        .respond(RustweavedMessagePayloadType::RequestBlockLocator, move |request| {
+            vec![make_response!(Payload::BlockLocator, BlockLocatorMessage { hashes: vec![tip_hash.into()] }, request.request_id)]
+        })
+        .respond(RustweavedMessagePayloadType::RequestIbdChainBlockLocator, chain_locator_responder(tip_hash, genesis))
+}
+
+/// Answers a chain locator request with the locator `[tip, genesis]`, which is a valid response for both the
+/// initial request and the zoom-in request of the chain negotiation
+fn chain_locator_responder(tip_hash: Hash, genesis: Hash) -> impl FnOnce(&RustweavedMessage) -> Vec<RustweavedMessage> + Send {
+    move |_| {
+        vec![make_message!(
+            Payload::IbdChainBlockLocator,
+            IbdChainBlockLocatorMessage { block_locator_hashes: vec![tip_hash.into(), genesis.into()] }
+        )]
+    }
+}
+
+fn tip_hash(chain: &[Block]) -> Hash {
+    chain.last().expect("chain is expected to be non empty").hash()
+}
+
+fn inv_relay_block(hash: Hash) -> RustweavedMessage {
+    make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(hash.into()) })
+}
+
+fn inv_transactions(transaction_id: TransactionId) -> RustweavedMessage {
+    make_message!(Payload::InvTransactions, InvTransactionsMessage { ids: vec![transaction_id.into()] })
+}
+
+fn request_transactions(transaction_id: TransactionId) -> RustweavedMessage {
+    make_message!(Payload::RequestTransactions, RequestTransactionsMessage { ids: vec![transaction_id.into()] })
+}
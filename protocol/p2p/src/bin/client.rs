@@ -8,7 +8,7 @@ async fn main() {
     kaspa_core::log::init_logger(None, "debug");
     // [0] - init p2p-adaptor
     let initializer = Arc::new(EchoFlowInitializer::new());
-    let adaptor = kaspa_p2p_lib::Adaptor::client_only(kaspa_p2p_lib::Hub::new(), initializer, Default::default());
+    let adaptor = kaspa_p2p_lib::Adaptor::client_only(kaspa_p2p_lib::Hub::new(), initializer, Default::default(), None, None);
     // [1] - connect 128 peers + flows
     let ip_port = String::from("[::1]:50051");
     for i in 0..1 {
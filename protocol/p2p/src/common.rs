@@ -1,4 +1,8 @@
-use crate::{convert::error::ConversionError, core::peer::PeerKey, RustweavedMessagePayloadType};
+use crate::{
+    convert::error::ConversionError,
+    core::peer::{Misbehavior, PeerKey},
+    RustweavedMessagePayloadType,
+};
 use kaspa_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
 use kaspa_mining_errors::manager::MiningManagerError;
 use std::time::Duration;
@@ -89,6 +93,51 @@ impl ProtocolError {
         !matches!(self, Self::ConnectionClosed | Self::OutgoingRouteCapacityReached(_))
     }
 
+    /// The misbehavior of the peer indicated by this error, if any
+    pub fn misbehavior(&self) -> Option<Misbehavior> {
+        match self {
+            // Only rule errors proving the block is invalid by consensus are accounted for. Others, such as missing
+            // parents or a timestamp too far into the future, might result from the local state (or clock)
+            Self::RuleError(
+                RuleError::WrongBlockVersion(_)
+                | RuleError::NoParents
+                | RuleError::TooManyParents(..)
+                | RuleError::OriginParent
+                | RuleError::InvalidParentsRelation(..)
+                | RuleError::InvalidParent(_)
+                | RuleError::UnexpectedHeaderDaaScore(..)
+                | RuleError::UnexpectedHeaderBlueScore(..)
+                | RuleError::UnexpectedHeaderBlueWork(..)
+                | RuleError::UnexpectedDifficulty(..)
+                | RuleError::TimeTooOld(..)
+                | RuleError::KnownInvalid
+                | RuleError::MergeSetTooBig(..)
+                | RuleError::ViolatingBoundedMergeDepth
+                | RuleError::NoTransactions
+                | RuleError::FirstTxNotCoinbase
+                | RuleError::MultipleCoinbases(_)
+                | RuleError::BadCoinbasePayload(_)
+                | RuleError::BadCoinbasePayloadBlueScore(..)
+                | RuleError::TxInIsolationValidationFailed(..)
+                | RuleError::ExceedsMassLimit(_)
+                | RuleError::MassFieldTooLow(..)
+                | RuleError::DoubleSpendInSameBlock(_)
+                | RuleError::ChainedTransaction(_)
+                | RuleError::TxInContextFailed(..)
+                | RuleError::WrongSubsidy(..)
+                | RuleError::DuplicateTransactions(_)
+                | RuleError::InvalidPoW
+                | RuleError::WrongHeaderPruningPoint(..)
+                | RuleError::UnexpectedIndirectParents(..)
+                | RuleError::BadUTXOCommitment(..)
+                | RuleError::BadAcceptedIDMerkleRoot(..)
+                | RuleError::BadCoinbaseTransaction,
+            ) => Some(Misbehavior::InvalidBlock),
+            Self::UnexpectedMessage(..) => Some(Misbehavior::UnrequestedMessage),
+            _ => None,
+        }
+    }
+
     pub fn to_reject_message(&self) -> String {
         match self {
             Self::LoopbackConnection(_) => LOOPBACK_CONNECTION_MESSAGE.to_owned(),
@@ -1,6 +1,11 @@
-use super::{error::ConversionError, option::TryIntoOptionEx};
+use super::{
+    error::ConversionError,
+    model::compact::{CompactBlock, PrefilledTransaction},
+    option::TryIntoOptionEx,
+};
 use crate::pb as protowire;
 use kaspa_consensus_core::{block::Block, tx::Transaction};
+use std::sync::Arc;
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -12,6 +17,23 @@ impl From<&Block> for protowire::BlockMessage {
     }
 }
 
+impl From<&PrefilledTransaction> for protowire::PrefilledTransaction {
+    fn from(item: &PrefilledTransaction) -> Self {
+        Self { index: item.index, transaction: Some((&item.transaction).into()) }
+    }
+}
+
+impl From<&CompactBlock> for protowire::CompactBlockMessage {
+    fn from(block: &CompactBlock) -> Self {
+        Self {
+            header: Some(block.header.as_ref().into()),
+            short_id_nonce: block.nonce,
+            short_ids: block.short_ids.clone(),
+            prefilled_transactions: block.prefilled.iter().map(|tx| tx.into()).collect(),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // protowire to consensus_core
 // ----------------------------------------------------------------------------
@@ -26,3 +48,24 @@ impl TryFrom<protowire::BlockMessage> for Block {
         ))
     }
 }
+
+impl TryFrom<protowire::PrefilledTransaction> for PrefilledTransaction {
+    type Error = ConversionError;
+
+    fn try_from(item: protowire::PrefilledTransaction) -> Result<Self, Self::Error> {
+        Ok(Self::new(item.index, item.transaction.try_into_ex()?))
+    }
+}
+
+impl TryFrom<protowire::CompactBlockMessage> for CompactBlock {
+    type Error = ConversionError;
+
+    fn try_from(block: protowire::CompactBlockMessage) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            Arc::new(block.header.try_into_ex()?),
+            block.short_id_nonce,
+            block.short_ids,
+            block.prefilled_transactions.into_iter().map(|i| i.try_into()).collect::<Result<Vec<_>, Self::Error>>()?,
+        ))
+    }
+}
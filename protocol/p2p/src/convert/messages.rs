@@ -10,10 +10,10 @@ use crate::pb as protowire;
 use kaspa_consensus_core::{
     header::Header,
     pruning::{PruningPointProof, PruningPointsList},
-    tx::{TransactionId, TransactionOutpoint, UtxoEntry},
+    tx::{Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use kaspa_hashes::Hash;
-use kaspa_utils::networking::{IpAddress, PeerId};
+use kaspa_utils::networking::{NetAddress, PeerId};
 
 use std::sync::Arc;
 
@@ -159,6 +159,30 @@ impl TryFrom<protowire::RequestRelayBlocksMessage> for Vec<Hash> {
     }
 }
 
+impl TryFrom<protowire::RequestCompactBlockMessage> for Hash {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestCompactBlockMessage) -> Result<Self, Self::Error> {
+        msg.hash.try_into_ex()
+    }
+}
+
+impl TryFrom<protowire::RequestBlockTransactionsMessage> for (Hash, Vec<u32>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestBlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.indexes))
+    }
+}
+
+impl TryFrom<protowire::BlockTransactionsMessage> for (Hash, Vec<Transaction>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::BlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?))
+    }
+}
+
 impl TryFrom<protowire::RequestIbdBlocksMessage> for Vec<Hash> {
     type Error = ConversionError;
 
@@ -175,7 +199,7 @@ impl TryFrom<protowire::BlockLocatorMessage> for Vec<Hash> {
     }
 }
 
-impl TryFrom<protowire::AddressesMessage> for Vec<(IpAddress, u16)> {
+impl TryFrom<protowire::AddressesMessage> for Vec<NetAddress> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::AddressesMessage) -> Result<Self, Self::Error> {
@@ -207,6 +231,14 @@ impl TryFrom<protowire::TransactionNotFoundMessage> for TransactionId {
     }
 }
 
+impl TryFrom<protowire::StemTransactionMessage> for Transaction {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::StemTransactionMessage) -> Result<Self, Self::Error> {
+        msg.transaction.try_into_ex()
+    }
+}
+
 impl TryFrom<protowire::RequestBlockLocatorMessage> for (Hash, u32) {
     type Error = ConversionError;
     fn try_from(msg: protowire::RequestBlockLocatorMessage) -> Result<Self, Self::Error> {
@@ -0,0 +1,240 @@
+//!
+//! Model structures which are related to compact block relay. A compact block carries the block header,
+//! a short id for each transaction and a few prefilled transactions, letting the receiving node rebuild
+//! the block from its own mempool and fetch only the transactions it is missing.
+//!
+
+use kaspa_consensus_core::{
+    block::Block,
+    header::Header,
+    merkle::calc_hash_merkle_root_with_options,
+    tx::{Transaction, TransactionId},
+};
+use kaspa_hashes::{Hash, HasherBase, ShortTransactionIdHash};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+
+use crate::common::ProtocolError;
+
+/// Short transaction ids are truncated to 48 bits
+pub const SHORT_ID_MASK: u64 = (1 << 48) - 1;
+
+/// Computes the short id of a transaction within a compact block. The id is salted with the block
+/// hash and a sender-chosen nonce so that collisions cannot be crafted ahead of time by third parties.
+pub fn short_transaction_id(block_hash: Hash, nonce: u64, transaction_id: TransactionId) -> u64 {
+    let mut hasher = ShortTransactionIdHash::new();
+    hasher.update(block_hash).update(nonce.to_le_bytes()).update(transaction_id);
+    hasher.finalize().to_le_u64()[0] & SHORT_ID_MASK
+}
+
+pub struct PrefilledTransaction {
+    pub index: u32,
+    pub transaction: Transaction,
+}
+
+impl PrefilledTransaction {
+    pub fn new(index: u32, transaction: Transaction) -> Self {
+        Self { index, transaction }
+    }
+}
+
+pub struct CompactBlock {
+    pub header: Arc<Header>,
+    pub nonce: u64,
+    /// Short ids of all non-prefilled transactions, in block order
+    pub short_ids: Vec<u64>,
+    /// Prefilled transactions, ordered by strictly increasing block index
+    pub prefilled: Vec<PrefilledTransaction>,
+}
+
+impl CompactBlock {
+    pub fn new(header: Arc<Header>, nonce: u64, short_ids: Vec<u64>, prefilled: Vec<PrefilledTransaction>) -> Self {
+        Self { header, nonce, short_ids, prefilled }
+    }
+
+    /// Builds a compact block out of a full block, prefilling only the coinbase transaction
+    pub fn from_block(block: &Block, nonce: u64) -> Self {
+        let hash = block.hash();
+        let (prefilled, short_ids) = match block.transactions.split_first() {
+            Some((coinbase, transactions)) => (
+                vec![PrefilledTransaction::new(0, coinbase.clone())],
+                transactions.iter().map(|tx| short_transaction_id(hash, nonce, tx.id())).collect(),
+            ),
+            None => (vec![], vec![]),
+        };
+        Self::new(block.header.clone(), nonce, short_ids, prefilled)
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    /// Fills the block transaction slots with the prefilled transactions and with `candidates` matching
+    /// the short ids. Candidates whose short ids collide with each other are ignored and their slots are
+    /// left empty, to be fetched from the peer.
+    pub fn reconstruct(self, candidates: impl IntoIterator<Item = Arc<Transaction>>) -> Result<PartialBlock, ProtocolError> {
+        let hash = self.hash();
+        let total = self.short_ids.len() + self.prefilled.len();
+        let mut slots: Vec<Option<Transaction>> = vec![None; total];
+
+        let mut last_index = None;
+        for PrefilledTransaction { index, transaction } in self.prefilled {
+            let index = index as usize;
+            if index >= total || last_index.is_some_and(|last| index <= last) {
+                return Err(ProtocolError::OtherOwned(format!("compact block {} has an invalid prefilled index {}", hash, index)));
+            }
+            slots[index] = Some(transaction);
+            last_index = Some(index);
+        }
+
+        let mut pool: HashMap<u64, Option<Arc<Transaction>>> = HashMap::new();
+        for tx in candidates {
+            match pool.entry(short_transaction_id(hash, self.nonce, tx.id())) {
+                Entry::Occupied(mut e) => {
+                    e.insert(None);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(Some(tx));
+                }
+            }
+        }
+
+        let mut short_ids = self.short_ids.into_iter();
+        for slot in slots.iter_mut().filter(|slot| slot.is_none()) {
+            // The number of empty slots equals the number of short ids by construction
+            let short_id = short_ids.next().unwrap();
+            *slot = pool.get(&short_id).and_then(|tx| tx.as_deref().cloned());
+        }
+
+        Ok(PartialBlock { header: self.header, slots })
+    }
+}
+
+/// A block whose transactions were partially recovered from a compact block
+pub struct PartialBlock {
+    header: Arc<Header>,
+    slots: Vec<Option<Transaction>>,
+}
+
+impl PartialBlock {
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    /// Returns the block indexes of the transactions which could not be recovered locally
+    pub fn missing_indexes(&self) -> Vec<u32> {
+        self.slots.iter().enumerate().filter(|(_, slot)| slot.is_none()).map(|(i, _)| i as u32).collect()
+    }
+
+    /// Fills the missing slots with `transactions`, which are expected in the order of [`Self::missing_indexes`]
+    pub fn fill_missing(&mut self, transactions: Vec<Transaction>) -> Result<(), ProtocolError> {
+        let missing = self.slots.iter().filter(|slot| slot.is_none()).count();
+        if transactions.len() != missing {
+            return Err(ProtocolError::OtherOwned(format!(
+                "expected {} missing transactions for block {} but got {}",
+                missing,
+                self.hash(),
+                transactions.len()
+            )));
+        }
+        for (slot, tx) in self.slots.iter_mut().filter(|slot| slot.is_none()).zip(transactions) {
+            *slot = Some(tx);
+        }
+        Ok(())
+    }
+
+    /// Returns the full block if all transactions were recovered and they match the header merkle root.
+    ///
+    /// Note: short ids commit to transaction ids only, which do not cover signature scripts, hence a local
+    /// transaction might differ from the one included in the block even when the short ids match
+    pub fn into_block(self, include_mass_field: bool) -> Option<Block> {
+        let transactions = self.slots.into_iter().collect::<Option<Vec<_>>>()?;
+        if calc_hash_merkle_root_with_options(transactions.iter(), include_mass_field) != self.header.hash_merkle_root {
+            return None;
+        }
+        Some(Block::from_arcs(self.header, Arc::new(transactions)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        merkle::calc_hash_merkle_root,
+        subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+        tx::{ScriptPublicKey, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+
+    fn transaction(seed: u8, signature_script: Vec<u8>) -> Transaction {
+        let subnetwork_id = if seed == 0 { SUBNETWORK_ID_COINBASE } else { SUBNETWORK_ID_NATIVE };
+        let inputs = if seed == 0 {
+            vec![]
+        } else {
+            vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(seed as u64), 0), signature_script, 0, 1)]
+        };
+        Transaction::new(
+            0,
+            inputs,
+            vec![TransactionOutput::new(seed as u64 * 100, ScriptPublicKey::from_vec(0, vec![seed]))],
+            0,
+            subnetwork_id,
+            0,
+            vec![],
+        )
+    }
+
+    fn block(transactions: Vec<Transaction>) -> Block {
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(1), vec![]);
+        header.hash_merkle_root = calc_hash_merkle_root(transactions.iter());
+        Block::new(header, transactions)
+    }
+
+    #[test]
+    fn test_compact_block_reconstruction() {
+        let transactions: Vec<_> = (0..5).map(|i| transaction(i, vec![i])).collect();
+        let block = block(transactions.clone());
+
+        let compact = CompactBlock::from_block(&block, 7);
+        assert_eq!(compact.prefilled.len(), 1);
+        assert_eq!(compact.short_ids.len(), 4);
+
+        // The mempool holds transactions 1 and 3 along with an unrelated transaction
+        let pool = vec![Arc::new(transactions[1].clone()), Arc::new(transactions[3].clone()), Arc::new(transaction(9, vec![]))];
+        let mut partial = compact.reconstruct(pool).unwrap();
+        assert_eq!(partial.missing_indexes(), vec![2, 4]);
+
+        assert!(partial.fill_missing(vec![transactions[2].clone()]).is_err());
+        partial.fill_missing(vec![transactions[2].clone(), transactions[4].clone()]).unwrap();
+        assert!(partial.missing_indexes().is_empty());
+
+        let reconstructed = partial.into_block(false).unwrap();
+        assert_eq!(reconstructed.hash(), block.hash());
+        assert_eq!(*reconstructed.transactions, transactions);
+    }
+
+    #[test]
+    fn test_compact_block_merkle_mismatch() {
+        let transactions: Vec<_> = (0..3).map(|i| transaction(i, vec![i])).collect();
+        let block = block(transactions.clone());
+
+        // A local version of transaction 1 with the same id but a different signature script
+        let malleated = transaction(1, vec![42]);
+        assert_eq!(malleated.id(), transactions[1].id());
+
+        let compact = CompactBlock::from_block(&block, 0);
+        let pool = vec![Arc::new(malleated), Arc::new(transactions[2].clone())];
+        let partial = compact.reconstruct(pool).unwrap();
+        assert!(partial.missing_indexes().is_empty());
+        assert!(partial.into_block(false).is_none());
+    }
+
+    #[test]
+    fn test_compact_block_invalid_prefilled_index() {
+        let block = block((0..2).map(|i| transaction(i, vec![])).collect());
+        let mut compact = CompactBlock::from_block(&block, 0);
+        compact.prefilled[0].index = 2;
+        assert!(compact.reconstruct(vec![]).is_err());
+    }
+}
@@ -8,10 +8,13 @@ use kaspa_utils::networking::{NetAddress, PeerId};
 /// Maximum allowed length for the user agent field in a version message `VersionMessage`.
 pub const MAX_USER_AGENT_LEN: usize = 256;
 
+/// Service flag advertising that the node serves compact blocks and wishes to receive relay blocks as such
+pub const SERVICE_COMPACT_BLOCKS: u64 = 1 << 0;
+
 pub struct Version {
     pub protocol_version: u32,
     pub network: String,
-    pub services: u64, // TODO
+    pub services: u64,
     pub timestamp: u64,
     pub address: Option<NetAddress>,
     pub id: PeerId,
@@ -31,7 +34,7 @@ impl Version {
         Self {
             protocol_version,
             network,
-            services: 0, // TODO: get number of live services
+            services: 0,
             timestamp: unix_now(),
             address,
             id,
@@ -41,6 +44,10 @@ impl Version {
         }
     }
 
+    pub fn has_service(&self, service: u64) -> bool {
+        self.services & service == service
+    }
+
     pub fn add_user_agent(&mut self, name: &str, version: &str, comments: &[String]) {
         let comments = if !comments.is_empty() { format!("({})", comments.join("; ")) } else { "".to_string() };
         let new_user_agent = format!("{}:{}{}", name, version, comments);
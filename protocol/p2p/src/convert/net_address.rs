@@ -7,7 +7,7 @@ use super::error::ConversionError;
 use crate::pb as protowire;
 
 use itertools::Itertools;
-use kaspa_utils::networking::{IpAddress, NetAddress};
+use kaspa_utils::networking::{IpAddress, NetAddress, OnionAddress};
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -23,13 +23,18 @@ impl From<(IpAddress, u16)> for protowire::NetAddress {
                 IpAddr::V6(ip) => ip.octets().to_vec(),
             },
             port: port as u32,
+            onion: vec![],
         }
     }
 }
 
 impl From<NetAddress> for protowire::NetAddress {
     fn from(item: NetAddress) -> Self {
-        (item.ip, item.port).into()
+        let mut address: Self = (item.ip, item.port).into();
+        if let Some(onion) = item.onion {
+            address.onion = onion.public_key().to_vec();
+        }
+        address
     }
 }
 
@@ -63,6 +68,12 @@ impl TryFrom<protowire::NetAddress> for NetAddress {
     type Error = ConversionError;
 
     fn try_from(item: protowire::NetAddress) -> Result<Self, Self::Error> {
+        if !item.onion.is_empty() {
+            // The IP of onion addresses is derived from the public key
+            let public_key =
+                <[u8; 32]>::try_from(item.onion.as_slice()).map_err(|_| ConversionError::IllegalOnionKeyLength(item.onion.len()))?;
+            return Ok(NetAddress::new_onion(OnionAddress::new(public_key), item.port.try_into()?));
+        }
         let (ip, port) = item.try_into()?;
         Ok(NetAddress::new(ip, port))
     }
@@ -70,7 +81,7 @@ impl TryFrom<protowire::NetAddress> for NetAddress {
 
 #[cfg(test)]
 mod tests {
-    use kaspa_utils::networking::IpAddress;
+    use kaspa_utils::networking::{IpAddress, NetAddress};
 
     use crate::pb;
     use std::{
@@ -80,14 +91,21 @@ mod tests {
 
     #[test]
     fn test_netaddress() {
-        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123 };
+        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123, onion: vec![] };
         let ipv4 = Ipv4Addr::from_str("106.10.138.240").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv4.clone()).unwrap(), (ipv4, 123u16));
         assert_eq!(pb::NetAddress::from((ipv4, 123u16)), net_addr_ipv4);
 
-        let net_addr_ipv6 = pb::NetAddress { timestamp: 0, ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(), port: 456 };
+        let net_addr_ipv6 =
+            pb::NetAddress { timestamp: 0, ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(), port: 456, onion: vec![] };
         let ipv6 = Ipv6Addr::from_str("2001:0db8:85a3:0000:0000:8a2e:0370:7334").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv6.clone()).unwrap(), (ipv6, 456u16));
         assert_eq!(pb::NetAddress::from((ipv6, 456u16)), net_addr_ipv6);
+
+        let onion = NetAddress::from_str("pg6mmjiyjmcrsslvykfwnntlaru7p5svn6y2ymmju6nubxndf4pscryd.onion:16111").unwrap();
+        let net_addr_onion = pb::NetAddress::from(onion);
+        assert_eq!(net_addr_onion.onion, onion.onion.unwrap().public_key().to_vec());
+        assert_eq!(NetAddress::try_from(net_addr_onion.clone()).unwrap(), onion);
+        assert!(NetAddress::try_from(pb::NetAddress { onion: vec![1; 31], ..net_addr_onion }).is_err());
     }
 }
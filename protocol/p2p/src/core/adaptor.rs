@@ -1,9 +1,10 @@
 use crate::common::ProtocolError;
+use crate::core::encryption::EncryptionConfig;
 use crate::core::hub::Hub;
 use crate::ConnectionError;
 use crate::{core::connection_handler::ConnectionHandler, Router};
 use kaspa_utils::networking::NetAddress;
-use kaspa_utils_tower::counters::TowerConnectionCounters;
+use kaspa_utils_tower::{counters::TowerConnectionCounters, socks::Socks5Proxy};
 use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
@@ -46,29 +47,45 @@ impl Adaptor {
     }
 
     /// Creates a P2P adaptor with only client-side support. Typical Rustweave nodes should use `Adaptor::bidirectional`
-    pub fn client_only(hub: Hub, initializer: Arc<dyn ConnectionInitializer>, counters: Arc<TowerConnectionCounters>) -> Arc<Self> {
+    pub fn client_only(
+        hub: Hub,
+        initializer: Arc<dyn ConnectionInitializer>,
+        counters: Arc<TowerConnectionCounters>,
+        encryption: Option<Arc<EncryptionConfig>>,
+        proxy: Option<Socks5Proxy>,
+    ) -> Arc<Self> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, encryption, proxy);
         let adaptor = Arc::new(Adaptor::new(None, connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
         adaptor
     }
 
-    /// Creates a bidirectional P2P adaptor with a server serving at `serve_address` and with client support
+    /// Creates a bidirectional P2P adaptor with a server serving at `serve_address` and with client support.
+    /// If `encryption` is set, the encrypted transport is negotiated with peers supporting it.
+    /// If `proxy` is set, outbound connections are made through it
     pub fn bidirectional(
         serve_address: NetAddress,
         hub: Hub,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        encryption: Option<Arc<EncryptionConfig>>,
+        proxy: Option<Socks5Proxy>,
     ) -> Result<Arc<Self>, ConnectionError> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, encryption, proxy);
         let server_termination = connection_handler.serve(serve_address)?;
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
         Ok(adaptor)
     }
 
+    /// Indicates whether outbound connections to `address` can be made, i.e. whether it is not an onion
+    /// address or a proxy is set
+    pub fn can_connect(&self, address: &NetAddress) -> bool {
+        self.connection_handler.can_connect(address)
+    }
+
     /// Connect to a new peer (no retries)
     pub async fn connect_peer(&self, peer_address: String) -> Result<PeerKey, ConnectionError> {
         self.connection_handler.connect_with_retry(peer_address, 1, Default::default()).await.map(|r| r.key())
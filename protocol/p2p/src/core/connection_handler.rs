@@ -1,4 +1,5 @@
 use crate::common::ProtocolError;
+use crate::core::encryption::{self, EncryptionConfig, PeerEncryption, ENCRYPTION_MAGIC, HANDSHAKE_TIMEOUT};
 use crate::core::hub::HubEvent;
 use crate::pb::{
     p2p_client::P2pClient as ProtoP2pClient, p2p_server::P2p as ProtoP2p, p2p_server::P2pServer as ProtoP2pServer, RustweavedMessage,
@@ -10,18 +11,23 @@ use kaspa_utils::networking::NetAddress;
 use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{measure_request_body_size_layer, CountBytesBody, MapResponseBodyLayer, ServiceBuilder},
+    socks::Socks5Proxy,
 };
-use std::net::ToSocketAddrs;
+use std::net::{Ipv6Addr, SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Duration;
 use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::mpsc::{channel as mpsc_channel, Sender as MpscSender};
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
 use tonic::codegen::Body;
-use tonic::transport::{Error as TonicError, Server as TonicServer};
+use tonic::transport::{server::Connected, Error as TonicError, Server as TonicServer, Uri};
 use tonic::{Request, Response, Status as TonicStatus, Streaming};
 
 #[derive(Error, Debug)]
@@ -40,6 +46,12 @@ pub enum ConnectionError {
 
     #[error("{0}")]
     ProtocolError(#[from] ProtocolError),
+
+    #[error("plaintext connection to {0} refused since only allowlisted encrypted peers are accepted")]
+    EncryptionRequired(String),
+
+    #[error("cannot connect to onion address {0} without a proxy")]
+    ProxyRequired(String),
 }
 
 /// Maximum P2P decoded gRPC message size to send and receive
@@ -52,6 +64,10 @@ pub struct ConnectionHandler {
     hub_sender: MpscSender<HubEvent>,
     initializer: Arc<dyn ConnectionInitializer>,
     counters: Arc<TowerConnectionCounters>,
+    /// If set, outbound connections are encrypted when supported by the peer and encrypted inbound connections are accepted
+    encryption: Option<Arc<EncryptionConfig>>,
+    /// If set, all outbound connections go through this SOCKS5 proxy. Required for connecting to onion addresses
+    proxy: Option<Socks5Proxy>,
 }
 
 impl ConnectionHandler {
@@ -59,14 +75,22 @@ impl ConnectionHandler {
         hub_sender: MpscSender<HubEvent>,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        encryption: Option<Arc<EncryptionConfig>>,
+        proxy: Option<Socks5Proxy>,
     ) -> Self {
-        Self { hub_sender, initializer, counters }
+        Self { hub_sender, initializer, counters, encryption, proxy }
+    }
+
+    /// Indicates whether outbound connections to `address` can be made
+    pub(crate) fn can_connect(&self, address: &NetAddress) -> bool {
+        !address.is_onion() || self.proxy.is_some()
     }
 
     /// Launches a P2P server listener loop
     pub(crate) fn serve(&self, serve_address: NetAddress) -> Result<OneshotSender<()>, ConnectionError> {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
         let connection_handler = self.clone();
+        let encryption = self.encryption.clone();
         info!("P2P Server starting on: {}", serve_address);
 
         let bytes_tx = self.counters.bytes_tx.clone();
@@ -79,12 +103,21 @@ impl ConnectionHandler {
                 .max_decoding_message_size(P2P_MAX_MESSAGE_SIZE);
 
             // TODO: check whether we should set tcp_keepalive
-            let serve_result = TonicServer::builder()
+            let router = TonicServer::builder()
                 .layer(measure_request_body_size_layer(bytes_rx, |b| b))
                 .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, bytes_tx.clone())))
-                .add_service(proto_server)
-                .serve_with_shutdown(serve_address.into(), termination_receiver.map(drop))
-                .await;
+                .add_service(proto_server);
+            let serve_result = match encryption {
+                None => router.serve_with_shutdown(serve_address.into(), termination_receiver.map(drop)).await,
+                Some(encryption) => {
+                    let listener = TcpListener::bind(SocketAddr::from(serve_address))
+                        .await
+                        .unwrap_or_else(|err| panic!("P2P, Server failed binding to {serve_address}: {err}"));
+                    router
+                        .serve_with_incoming_shutdown(Self::negotiated_incoming(listener, encryption), termination_receiver.map(drop))
+                        .await
+                }
+            };
 
             match serve_result {
                 Ok(_) => info!("P2P Server stopped: {}", serve_address),
@@ -94,19 +127,101 @@ impl ConnectionHandler {
         Ok(termination_sender)
     }
 
+    /// Accepts inbound TCP connections and negotiates encryption for each of them. Connections starting with
+    /// [`ENCRYPTION_MAGIC`] are encrypted while others are served as plaintext, unless the config is restricted
+    fn negotiated_incoming(
+        listener: TcpListener,
+        encryption: Arc<EncryptionConfig>,
+    ) -> ReceiverStream<Result<NegotiatedStream, std::io::Error>> {
+        let (incoming_sender, incoming_receiver) = mpsc_channel(Self::incoming_channel_size());
+        tokio::spawn(async move {
+            loop {
+                let (stream, remote_address) = tokio::select! {
+                    _ = incoming_sender.closed() => break,
+                    res = listener.accept() => match res {
+                        Ok(accepted) => accepted,
+                        Err(err) => {
+                            debug!("P2P, failed accepting an inbound connection: {}", err);
+                            continue;
+                        }
+                    },
+                };
+                let encryption = encryption.clone();
+                let incoming_sender = incoming_sender.clone();
+                // Negotiate on a dedicated task so that slow peers do not delay accepting further connections
+                tokio::spawn(async move {
+                    match Self::negotiate_inbound(stream, remote_address, &encryption).await {
+                        Ok(stream) => {
+                            let _ = incoming_sender.send(Ok(stream)).await;
+                        }
+                        Err(err) => debug!("P2P, encryption negotiation failed for inbound peer {}: {}", remote_address, err),
+                    }
+                });
+            }
+        });
+        ReceiverStream::new(incoming_receiver)
+    }
+
+    async fn negotiate_inbound(
+        stream: TcpStream,
+        remote_address: SocketAddr,
+        encryption: &EncryptionConfig,
+    ) -> Result<NegotiatedStream, ConnectionError> {
+        let mut first_byte = [0u8; 1];
+        match tokio::time::timeout(HANDSHAKE_TIMEOUT, stream.peek(&mut first_byte)).await {
+            Ok(Ok(1)) => {}
+            Ok(Ok(_)) => return Err(ConnectionError::IoError(std::io::ErrorKind::UnexpectedEof.into())),
+            Ok(Err(err)) => return Err(err.into()),
+            Err(_) => return Err(ConnectionError::IoError(std::io::ErrorKind::TimedOut.into())),
+        }
+        if first_byte[0] == ENCRYPTION_MAGIC[0] {
+            let (stream, peer_encryption) = encryption::respond(stream, encryption).await?;
+            Ok(NegotiatedStream::encrypted(stream, remote_address, peer_encryption))
+        } else if encryption.is_restricted() {
+            Err(ConnectionError::EncryptionRequired(remote_address.to_string()))
+        } else {
+            Ok(NegotiatedStream::plaintext(stream, remote_address))
+        }
+    }
+
     /// Connect to a new peer
     pub(crate) async fn connect(&self, peer_address: String) -> Result<Arc<Router>, ConnectionError> {
-        let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
-            return Err(ConnectionError::NoAddress);
+        // Onion peers are identified by the virtual IP of their onion address
+        let onion_address = NetAddress::from_str(&peer_address).ok().filter(NetAddress::is_onion);
+        let socket_address = match onion_address {
+            Some(_) if self.proxy.is_none() => return Err(ConnectionError::ProxyRequired(peer_address)),
+            Some(address) => address.into(),
+            None => match (peer_address.parse::<SocketAddr>(), self.proxy) {
+                (Ok(address), _) => address,
+                // Host names are resolved by the proxy so that no DNS query leaks out of it, hence the actual
+                // address of such peers is unknown and they are identified by the unspecified IP
+                (Err(_), Some(_)) => SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), Self::parse_host_port(&peer_address)?.1),
+                (Err(_), None) => peer_address.to_socket_addrs()?.next().ok_or(ConnectionError::NoAddress)?,
+            },
         };
+        let host = Self::parse_host_port(&peer_address)?.0;
         let peer_address = format!("http://{}", peer_address); // Add scheme prefix as required by Tonic
 
-        let channel = tonic::transport::Endpoint::new(peer_address)?
+        let endpoint = tonic::transport::Endpoint::new(peer_address.clone())?
             .timeout(Duration::from_millis(Self::communication_timeout()))
             .connect_timeout(Duration::from_millis(Self::connect_timeout()))
-            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())))
-            .connect()
-            .await?;
+            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())));
+
+        let (channel, peer_encryption) = match self.encryption.clone() {
+            None if self.proxy.is_none() => (endpoint.connect().await?, None),
+            None => self.connect_with_connector(&endpoint, socket_address, host, None).await?,
+            Some(encryption) => {
+                match self.connect_with_connector(&endpoint, socket_address, host, Some(encryption.clone())).await {
+                    Ok(connected) => connected,
+                    Err(err) if encryption.is_restricted() => return Err(err),
+                    Err(err) => {
+                        // The peer might not support encryption, hence we fall back to a plaintext connection
+                        debug!("P2P, encrypted connection to {} failed: {}, falling back to plaintext", peer_address, err);
+                        self.connect_with_connector(&endpoint, socket_address, host, None).await?
+                    }
+                }
+            }
+        };
 
         let channel = ServiceBuilder::new()
             .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, self.counters.bytes_rx.clone())))
@@ -123,7 +238,8 @@ impl ConnectionHandler {
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_network_channel_size());
         let incoming_stream = client.message_stream(ReceiverStream::new(outgoing_receiver)).await?.into_inner();
 
-        let router = Router::new(socket_address, true, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router =
+            Router::new(socket_address, true, peer_encryption, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
 
         // For outbound peers, we perform the initialization as part of the connect logic
         match self.initializer.initialize_connection(router.clone()).await {
@@ -144,6 +260,53 @@ impl ConnectionHandler {
         Ok(router)
     }
 
+    /// Connects through a custom connector which dials through the proxy, if set, and then negotiates
+    /// encryption, if `encryption` is set
+    async fn connect_with_connector(
+        &self,
+        endpoint: &tonic::transport::Endpoint,
+        socket_address: SocketAddr,
+        host: &str,
+        encryption: Option<Arc<EncryptionConfig>>,
+    ) -> Result<(tonic::transport::Channel, Option<PeerEncryption>), ConnectionError> {
+        let proxy = self.proxy;
+        let host: Arc<str> = host.into();
+        // The connector is reused by tonic for reconnecting, so the status of the latest handshake is kept aside
+        let status = Arc::new(parking_lot::Mutex::new(None));
+        let status_clone = status.clone();
+        let connector = tower::service_fn(move |_: Uri| {
+            let encryption = encryption.clone();
+            let status = status_clone.clone();
+            let host = host.clone();
+            async move {
+                let stream = match proxy {
+                    // Host names, onion addresses included, are resolved by the proxy
+                    Some(proxy) => proxy.connect(&host, socket_address.port()).await?,
+                    None => TcpStream::connect(socket_address).await?,
+                };
+                stream.set_nodelay(true)?;
+                match encryption {
+                    Some(encryption) => {
+                        let (stream, peer_encryption) = encryption::initiate(stream, &encryption).await?;
+                        *status.lock() = Some(peer_encryption);
+                        Ok::<_, std::io::Error>(NegotiatedStream::encrypted(stream, socket_address, peer_encryption))
+                    }
+                    None => Ok(NegotiatedStream::plaintext(stream, socket_address)),
+                }
+            }
+        });
+        let channel = endpoint.connect_with_connector(connector).await?;
+        let peer_encryption = *status.lock();
+        Ok((channel, peer_encryption))
+    }
+
+    /// Splits a `<host>:<port>` peer address, where an IPv6 host is enclosed in brackets
+    fn parse_host_port(peer_address: &str) -> Result<(&str, u16), ConnectionError> {
+        let (host, port) = peer_address.rsplit_once(':').ok_or(ConnectionError::NoAddress)?;
+        let port = port.parse().map_err(|_| ConnectionError::NoAddress)?;
+        Ok((host.trim_start_matches('[').trim_end_matches(']'), port))
+    }
+
     /// Connect to a new peer with `retry_attempts` retries and `retry_interval` duration between each attempt
     pub(crate) async fn connect_with_retry(
         &self,
@@ -179,6 +342,10 @@ impl ConnectionHandler {
     }
 
     // TODO: revisit the below constants
+    fn incoming_channel_size() -> usize {
+        128
+    }
+
     fn outgoing_network_channel_size() -> usize {
         // TODO: this number is taken from go-kaspad and should be re-evaluated
         (1 << 17) + 256
@@ -206,7 +373,12 @@ impl ProtoP2p for ConnectionHandler {
         &self,
         request: Request<Streaming<RustweavedMessage>>,
     ) -> Result<Response<Self::MessageStreamStream>, TonicStatus> {
-        let Some(remote_address) = request.remote_addr() else {
+        // Connections accepted through encryption negotiation carry their own connection info
+        let (remote_address, peer_encryption) = match request.extensions().get::<NegotiatedConnectInfo>() {
+            Some(info) => (Some(info.remote_address), info.encryption),
+            None => (request.remote_addr(), None),
+        };
+        let Some(remote_address) = remote_address else {
             return Err(TonicStatus::new(tonic::Code::InvalidArgument, "Incoming connection opening request has no remote address"));
         };
 
@@ -215,7 +387,8 @@ impl ProtoP2p for ConnectionHandler {
         let incoming_stream = request.into_inner();
 
         // Build the router object
-        let router = Router::new(remote_address, false, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router =
+            Router::new(remote_address, false, peer_encryption, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
 
         // Notify the central Hub about the new peer
         self.hub_sender.send(HubEvent::NewPeer(router)).await.expect("hub receiver should never drop before senders");
@@ -224,3 +397,74 @@ impl ProtoP2p for ConnectionHandler {
         Ok(Response::new(Box::pin(ReceiverStream::new(outgoing_receiver).map(Ok)) as Self::MessageStreamStream))
     }
 }
+
+/// Connection info of inbound streams which went through encryption negotiation
+#[derive(Clone)]
+pub struct NegotiatedConnectInfo {
+    remote_address: SocketAddr,
+    encryption: Option<PeerEncryption>,
+}
+
+enum NegotiatedInner {
+    Plaintext(TcpStream),
+    Encrypted(DuplexStream),
+}
+
+/// A stream which is either plaintext or the plaintext side of an encrypted connection
+pub struct NegotiatedStream {
+    inner: NegotiatedInner,
+    info: NegotiatedConnectInfo,
+}
+
+impl NegotiatedStream {
+    fn plaintext(stream: TcpStream, remote_address: SocketAddr) -> Self {
+        Self { inner: NegotiatedInner::Plaintext(stream), info: NegotiatedConnectInfo { remote_address, encryption: None } }
+    }
+
+    fn encrypted(stream: DuplexStream, remote_address: SocketAddr, encryption: PeerEncryption) -> Self {
+        Self {
+            inner: NegotiatedInner::Encrypted(stream),
+            info: NegotiatedConnectInfo { remote_address, encryption: Some(encryption) },
+        }
+    }
+}
+
+impl Connected for NegotiatedStream {
+    type ConnectInfo = NegotiatedConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        self.info.clone()
+    }
+}
+
+impl AsyncRead for NegotiatedStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        match &mut self.get_mut().inner {
+            NegotiatedInner::Plaintext(stream) => Pin::new(stream).poll_read(cx, buf),
+            NegotiatedInner::Encrypted(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for NegotiatedStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        match &mut self.get_mut().inner {
+            NegotiatedInner::Plaintext(stream) => Pin::new(stream).poll_write(cx, buf),
+            NegotiatedInner::Encrypted(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match &mut self.get_mut().inner {
+            NegotiatedInner::Plaintext(stream) => Pin::new(stream).poll_flush(cx),
+            NegotiatedInner::Encrypted(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match &mut self.get_mut().inner {
+            NegotiatedInner::Plaintext(stream) => Pin::new(stream).poll_shutdown(cx),
+            NegotiatedInner::Encrypted(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
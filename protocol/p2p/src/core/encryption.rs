@@ -0,0 +1,386 @@
+//!
+//! An optional encrypted and authenticated transport for P2P connections.
+//!
+//! Both sides exchange ephemeral secp256k1 keys and derive directional ChaCha20-Poly1305 session keys from
+//! their ECDH secret. Each side then proves ownership of its static node key by signing the handshake
+//! transcript, which lets nodes pin the static keys of trusted peers (see [`EncryptionConfig::with_allowlist`]).
+//! The static keys are exchanged encrypted, so passive observers learn neither the traffic nor the node identities.
+//! The responder reveals its static key only after authenticating and allowing the initiator, so probing a listener
+//! does not disclose its identity.
+//!
+//! An encrypted connection is recognized by [`ENCRYPTION_MAGIC`], which can never prefix a plaintext HTTP/2
+//! connection, so a single listener serves both encrypted and plaintext peers.
+//!
+
+use chacha20poly1305::{aead::Aead, ChaCha20Poly1305, KeyInit, Nonce};
+use rand::thread_rng;
+use secp256k1::{ecdh::SharedSecret, schnorr::Signature, Keypair, Message, PublicKey, SECP256K1};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashSet,
+    fs::{self, OpenOptions},
+    io::{Error, ErrorKind, Result, Write},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+use tokio::io::{duplex, split, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, DuplexStream};
+
+pub use secp256k1::{SecretKey, XOnlyPublicKey};
+
+/// Prefix sent by the initiator (and echoed by the responder) of an encrypted connection. The leading zero
+/// byte distinguishes it from the HTTP/2 connection preface sent by plaintext peers
+pub const ENCRYPTION_MAGIC: [u8; 8] = *b"\0RWENC01";
+
+/// Maximum time for completing the handshake of an encrypted connection
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum plaintext size of a single encrypted frame
+const MAX_FRAME_SIZE: usize = 1 << 16;
+
+/// Size of the ChaCha20-Poly1305 authentication tag appended to each frame
+const TAG_SIZE: usize = 16;
+
+const HELLO_SIZE: usize = ENCRYPTION_MAGIC.len() + secp256k1::constants::PUBLIC_KEY_SIZE;
+
+/// Local settings of the encrypted transport
+#[derive(Clone)]
+pub struct EncryptionConfig {
+    keypair: Keypair,
+    allowlist: Option<HashSet<XOnlyPublicKey>>,
+    required: bool,
+}
+
+impl EncryptionConfig {
+    pub fn new(secret_key: SecretKey) -> Self {
+        Self { keypair: Keypair::from_secret_key(SECP256K1, &secret_key), allowlist: None, required: false }
+    }
+
+    /// Refuses plaintext peers, inbound and outbound, so that connections cannot be downgraded to plaintext
+    pub fn with_required_encryption(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Restricts all connections, inbound and outbound, to encrypted peers holding one of the given static keys
+    pub fn with_allowlist(mut self, allowlist: impl IntoIterator<Item = XOnlyPublicKey>) -> Self {
+        self.allowlist = Some(allowlist.into_iter().collect());
+        self
+    }
+
+    /// The static public key identifying this node to encrypted peers
+    pub fn public_key(&self) -> XOnlyPublicKey {
+        self.keypair.x_only_public_key().0
+    }
+
+    /// Indicates whether plaintext peers are refused
+    pub fn is_restricted(&self) -> bool {
+        self.required || self.allowlist.is_some()
+    }
+
+    fn is_allowed(&self, public_key: &XOnlyPublicKey) -> bool {
+        self.allowlist.as_ref().map_or(true, |allowlist| allowlist.contains(public_key))
+    }
+}
+
+/// The encryption status of an established peer connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerEncryption {
+    /// The authenticated static public key of the peer
+    pub public_key: XOnlyPublicKey,
+}
+
+/// Loads the node secret key from `path`, generating and persisting a new one if the file does not exist
+pub fn load_or_create_secret_key(path: &Path) -> Result<SecretKey> {
+    if path.exists() {
+        let hex = fs::read_to_string(path)?;
+        return SecretKey::from_str(hex.trim()).map_err(|err| Error::new(ErrorKind::InvalidData, err));
+    }
+    let secret_key = SecretKey::new(&mut thread_rng());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    // The key file is readable by its owner only
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(secret_key.display_secret().to_string().as_bytes())?;
+    Ok(secret_key)
+}
+
+/// Performs the handshake as the connecting side and returns a plaintext stream backed by the encrypted `stream`
+pub(crate) async fn initiate<S>(stream: S, config: &EncryptionConfig) -> Result<(DuplexStream, PeerEncryption)>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    with_timeout(handshake(stream, config, true)).await
+}
+
+/// Performs the handshake as the accepting side. Expects [`ENCRYPTION_MAGIC`] to not have been consumed from `stream`
+pub(crate) async fn respond<S>(stream: S, config: &EncryptionConfig) -> Result<(DuplexStream, PeerEncryption)>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    with_timeout(handshake(stream, config, false)).await
+}
+
+async fn with_timeout<T>(future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, future)
+        .await
+        .unwrap_or_else(|_| Err(Error::new(ErrorKind::TimedOut, "encryption handshake timed out")))
+}
+
+async fn handshake<S>(mut stream: S, config: &EncryptionConfig, is_initiator: bool) -> Result<(DuplexStream, PeerEncryption)>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    // Exchange ephemeral keys
+    let ephemeral = Keypair::new_global(&mut thread_rng());
+    let mut local_hello = [0u8; HELLO_SIZE];
+    local_hello[..ENCRYPTION_MAGIC.len()].copy_from_slice(&ENCRYPTION_MAGIC);
+    local_hello[ENCRYPTION_MAGIC.len()..].copy_from_slice(&ephemeral.public_key().serialize());
+    stream.write_all(&local_hello).await?;
+
+    let mut remote_hello = [0u8; HELLO_SIZE];
+    stream.read_exact(&mut remote_hello).await?;
+    if remote_hello[..ENCRYPTION_MAGIC.len()] != ENCRYPTION_MAGIC {
+        return Err(Error::new(ErrorKind::InvalidData, "peer does not support encrypted connections"));
+    }
+    let remote_ephemeral =
+        PublicKey::from_slice(&remote_hello[ENCRYPTION_MAGIC.len()..]).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+    // Derive the session keys from the ephemeral secret and the transcript
+    let (initiator_hello, responder_hello) = if is_initiator { (local_hello, remote_hello) } else { (remote_hello, local_hello) };
+    let transcript: [u8; 32] = Sha256::new().chain_update(initiator_hello).chain_update(responder_hello).finalize().into();
+    let shared_secret = SharedSecret::new(&remote_ephemeral, &ephemeral.secret_key());
+    let initiator_key = derive_key(b"initiator", &shared_secret, &transcript);
+    let responder_key = derive_key(b"responder", &shared_secret, &transcript);
+    let (mut sealer, mut opener) = if is_initiator {
+        (FrameCipher::new(&initiator_key), FrameCipher::new(&responder_key))
+    } else {
+        (FrameCipher::new(&responder_key), FrameCipher::new(&initiator_key))
+    };
+
+    // Authenticate the static keys by signing the transcript. The initiator, which chose whom to connect to, goes first
+    // while the responder reveals its static key only once the initiator is authenticated and allowed
+    if is_initiator {
+        write_auth(&mut stream, &mut sealer, config, &transcript, is_initiator).await?;
+    }
+    let public_key = read_auth(&mut stream, &mut opener, config, &transcript, !is_initiator).await?;
+    if !is_initiator {
+        write_auth(&mut stream, &mut sealer, config, &transcript, is_initiator).await?;
+    }
+
+    Ok((spawn_transport(stream, sealer, opener), PeerEncryption { public_key }))
+}
+
+/// Sends the local static public key along with its signature of the transcript
+async fn write_auth<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    sealer: &mut FrameCipher,
+    config: &EncryptionConfig,
+    transcript: &[u8; 32],
+    is_initiator: bool,
+) -> Result<()> {
+    let signature = SECP256K1.sign_schnorr(&auth_message(transcript, is_initiator), &config.keypair);
+    let mut local_auth = config.public_key().serialize().to_vec();
+    local_auth.extend_from_slice(signature.as_ref());
+    write_frame(writer, sealer, &local_auth).await
+}
+
+/// Receives the static public key of the peer, verifies its signature of the transcript and that it is allowed
+async fn read_auth<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    opener: &mut FrameCipher,
+    config: &EncryptionConfig,
+    transcript: &[u8; 32],
+    is_remote_initiator: bool,
+) -> Result<XOnlyPublicKey> {
+    let remote_auth = read_frame(reader, opener).await?;
+    if remote_auth.len() != 96 {
+        return Err(Error::new(ErrorKind::InvalidData, "malformed encryption handshake"));
+    }
+    let invalid_data = |err| Error::new(ErrorKind::InvalidData, err);
+    let public_key = XOnlyPublicKey::from_slice(&remote_auth[..32]).map_err(invalid_data)?;
+    let signature = Signature::from_slice(&remote_auth[32..]).map_err(invalid_data)?;
+    SECP256K1.verify_schnorr(&signature, &auth_message(transcript, is_remote_initiator), &public_key).map_err(invalid_data)?;
+    if !config.is_allowed(&public_key) {
+        return Err(Error::new(ErrorKind::PermissionDenied, format!("peer public key {} is not allowlisted", public_key)));
+    }
+    Ok(public_key)
+}
+
+fn derive_key(label: &[u8], shared_secret: &SharedSecret, transcript: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(b"rustweave-p2p-")
+        .chain_update(label)
+        .chain_update(shared_secret)
+        .chain_update(transcript)
+        .finalize()
+        .into()
+}
+
+fn auth_message(transcript: &[u8; 32], is_initiator: bool) -> Message {
+    let role: &[u8] = if is_initiator { b"initiator" } else { b"responder" };
+    Message::from_digest(Sha256::new().chain_update(transcript).chain_update(role).finalize().into())
+}
+
+/// Runs the encryption of `stream` in the background and returns the plaintext side of it
+fn spawn_transport<S>(stream: S, mut sealer: FrameCipher, mut opener: FrameCipher) -> DuplexStream
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (plaintext, transport) = duplex(MAX_FRAME_SIZE * 4);
+    let (mut network_reader, mut network_writer) = split(stream);
+    let (mut transport_reader, mut transport_writer) = split(transport);
+
+    tokio::spawn(async move {
+        while let Ok(frame) = read_frame(&mut network_reader, &mut opener).await {
+            if transport_writer.write_all(&frame).await.is_err() {
+                break;
+            }
+        }
+        let _ = transport_writer.shutdown().await;
+    });
+
+    tokio::spawn(async move {
+        let mut buffer = vec![0u8; MAX_FRAME_SIZE];
+        loop {
+            match transport_reader.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if write_frame(&mut network_writer, &mut sealer, &buffer[..n]).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        let _ = network_writer.shutdown().await;
+    });
+
+    plaintext
+}
+
+/// A directional AEAD cipher using a frame counter as nonce
+struct FrameCipher {
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl FrameCipher {
+    fn new(key: &[u8; 32]) -> Self {
+        Self { cipher: ChaCha20Poly1305::new(key.into()), counter: 0 }
+    }
+
+    fn next_nonce(&mut self) -> Nonce {
+        let mut nonce = Nonce::default();
+        nonce[4..].copy_from_slice(&self.counter.to_le_bytes());
+        self.counter += 1;
+        nonce
+    }
+
+    fn seal(&mut self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.next_nonce();
+        self.cipher.encrypt(&nonce, plaintext).map_err(|_| Error::new(ErrorKind::InvalidInput, "frame encryption failed"))
+    }
+
+    fn open(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.next_nonce();
+        self.cipher.decrypt(&nonce, ciphertext).map_err(|_| Error::new(ErrorKind::InvalidData, "frame authentication failed"))
+    }
+}
+
+async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, cipher: &mut FrameCipher, plaintext: &[u8]) -> Result<()> {
+    let ciphertext = cipher.seal(plaintext)?;
+    writer.write_all(&(ciphertext.len() as u32).to_be_bytes()).await?;
+    writer.write_all(&ciphertext).await
+}
+
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R, cipher: &mut FrameCipher) -> Result<Vec<u8>> {
+    let len = reader.read_u32().await? as usize;
+    if !(TAG_SIZE..=MAX_FRAME_SIZE + TAG_SIZE).contains(&len) {
+        return Err(Error::new(ErrorKind::InvalidData, format!("invalid encrypted frame length {}", len)));
+    }
+    let mut ciphertext = vec![0u8; len];
+    reader.read_exact(&mut ciphertext).await?;
+    cipher.open(&ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn connect(initiator: EncryptionConfig, responder: EncryptionConfig) -> (Result<DuplexStream>, Result<DuplexStream>) {
+        let (a, b) = duplex(1024);
+        let (initiated, responded) = tokio::join!(initiate(a, &initiator), respond(b, &responder));
+        (initiated.map(|(s, _)| s), responded.map(|(s, _)| s))
+    }
+
+    fn random_config() -> EncryptionConfig {
+        EncryptionConfig::new(SecretKey::new(&mut thread_rng()))
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_roundtrip() {
+        let (initiator, responder) = (random_config(), random_config());
+        let (a, b) = duplex(1024);
+        let (initiated, responded) = tokio::join!(initiate(a, &initiator), respond(b, &responder));
+        let (mut a, a_status) = initiated.unwrap();
+        let (mut b, b_status) = responded.unwrap();
+        assert_eq!(a_status.public_key, responder.public_key());
+        assert_eq!(b_status.public_key, initiator.public_key());
+
+        // Exceed a single frame in order to test chunking
+        let payload: Vec<u8> = (0..MAX_FRAME_SIZE * 3 + 5).map(|i| i as u8).collect();
+        let expected = payload.clone();
+        let writer = tokio::spawn(async move {
+            a.write_all(&payload).await.unwrap();
+            a
+        });
+        let mut received = vec![0u8; expected.len()];
+        b.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, expected);
+
+        b.write_all(b"pong").await.unwrap();
+        let mut a = writer.await.unwrap();
+        let mut pong = [0u8; 4];
+        a.read_exact(&mut pong).await.unwrap();
+        assert_eq!(&pong, b"pong");
+    }
+
+    #[tokio::test]
+    async fn test_allowlist() {
+        let (initiator, responder) = (random_config(), random_config());
+
+        let restricted = responder.clone().with_allowlist([initiator.public_key()]);
+        let (initiated, responded) = connect(initiator.clone(), restricted).await;
+        assert!(initiated.is_ok() && responded.is_ok());
+
+        // A rejected initiator never gets the static key of the responder
+        let restricted = responder.clone().with_allowlist([random_config().public_key()]);
+        let (initiated, responded) = connect(initiator, restricted).await;
+        assert_eq!(responded.unwrap_err().kind(), ErrorKind::PermissionDenied);
+        assert_eq!(initiated.unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_secret_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("p2p.key");
+        let secret_key = load_or_create_secret_key(&path).unwrap();
+        assert_eq!(load_or_create_secret_key(&path).unwrap(), secret_key);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_peer_is_detected() {
+        let (mut a, b) = duplex(1024);
+        a.write_all(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n0123456789abcdef0").await.unwrap();
+        assert_eq!(respond(b, &random_config()).await.unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+}
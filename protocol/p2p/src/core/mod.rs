@@ -1,5 +1,6 @@
 pub mod adaptor;
 pub mod connection_handler;
+pub mod encryption;
 pub mod hub;
 pub mod payload_type;
 pub mod peer;
@@ -1,51 +1,74 @@
 use crate::pb::rustweaved_message::Payload as RustweavedMessagePayload;
 
-#[repr(u8)]
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-pub enum RustweavedMessagePayloadType {
-    Addresses = 0,
-    Block,
-    Transaction,
-    BlockLocator,
-    RequestAddresses,
-    RequestRelayBlocks,
-    RequestTransactions,
-    IbdBlock,
-    InvRelayBlock,
-    InvTransactions,
-    Ping,
-    Pong,
-    Verack,
-    Version,
-    TransactionNotFound,
-    Reject,
-    PruningPointUtxoSetChunk,
-    RequestIbdBlocks,
-    UnexpectedPruningPoint,
-    IbdBlockLocator,
-    IbdBlockLocatorHighestHash,
-    RequestNextPruningPointUtxoSetChunk,
-    DonePruningPointUtxoSetChunks,
-    IbdBlockLocatorHighestHashNotFound,
-    BlockWithTrustedData,
-    DoneBlocksWithTrustedData,
-    RequestPruningPointAndItsAnticone,
-    BlockHeaders,
-    RequestNextHeaders,
-    DoneHeaders,
-    RequestPruningPointUtxoSet,
-    RequestHeaders,
-    RequestBlockLocator,
-    PruningPoints,
-    RequestPruningPointProof,
-    PruningPointProof,
-    Ready,
-    BlockWithTrustedDataV4,
-    TrustedData,
-    RequestIbdChainBlockLocator,
-    IbdChainBlockLocator,
-    RequestAntipast,
-    RequestNextPruningPointAndItsAnticoneBlocks,
+/// Declares the payload type enum along with the list of all its variants
+macro_rules! payload_types {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $first:ident = 0, $($variant:ident,)* }) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $first = 0,
+            $($variant,)*
+        }
+
+        impl $name {
+            /// All the message payload types, in declaration order
+            pub const ALL: &'static [$name] = &[$name::$first, $($name::$variant,)*];
+        }
+    };
+}
+
+payload_types! {
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
+    pub enum RustweavedMessagePayloadType {
+        Addresses = 0,
+        Block,
+        Transaction,
+        BlockLocator,
+        RequestAddresses,
+        RequestRelayBlocks,
+        RequestTransactions,
+        IbdBlock,
+        InvRelayBlock,
+        InvTransactions,
+        Ping,
+        Pong,
+        Verack,
+        Version,
+        TransactionNotFound,
+        Reject,
+        PruningPointUtxoSetChunk,
+        RequestIbdBlocks,
+        UnexpectedPruningPoint,
+        IbdBlockLocator,
+        IbdBlockLocatorHighestHash,
+        RequestNextPruningPointUtxoSetChunk,
+        DonePruningPointUtxoSetChunks,
+        IbdBlockLocatorHighestHashNotFound,
+        BlockWithTrustedData,
+        DoneBlocksWithTrustedData,
+        RequestPruningPointAndItsAnticone,
+        BlockHeaders,
+        RequestNextHeaders,
+        DoneHeaders,
+        RequestPruningPointUtxoSet,
+        RequestHeaders,
+        RequestBlockLocator,
+        PruningPoints,
+        RequestPruningPointProof,
+        PruningPointProof,
+        Ready,
+        BlockWithTrustedDataV4,
+        TrustedData,
+        RequestIbdChainBlockLocator,
+        IbdChainBlockLocator,
+        RequestAntipast,
+        RequestNextPruningPointAndItsAnticoneBlocks,
+        RequestCompactBlock,
+        CompactBlock,
+        RequestBlockTransactions,
+        BlockTransactions,
+        StemTransaction,
+    }
 }
 
 impl From<&RustweavedMessagePayload> for RustweavedMessagePayloadType {
@@ -81,7 +104,9 @@ impl From<&RustweavedMessagePayload> for RustweavedMessagePayloadType {
             }
             RustweavedMessagePayload::BlockWithTrustedData(_) => RustweavedMessagePayloadType::BlockWithTrustedData,
             RustweavedMessagePayload::DoneBlocksWithTrustedData(_) => RustweavedMessagePayloadType::DoneBlocksWithTrustedData,
-            RustweavedMessagePayload::RequestPruningPointAndItsAnticone(_) => RustweavedMessagePayloadType::RequestPruningPointAndItsAnticone,
+            RustweavedMessagePayload::RequestPruningPointAndItsAnticone(_) => {
+                RustweavedMessagePayloadType::RequestPruningPointAndItsAnticone
+            }
             RustweavedMessagePayload::BlockHeaders(_) => RustweavedMessagePayloadType::BlockHeaders,
             RustweavedMessagePayload::RequestNextHeaders(_) => RustweavedMessagePayloadType::RequestNextHeaders,
             RustweavedMessagePayload::DoneHeaders(_) => RustweavedMessagePayloadType::DoneHeaders,
@@ -100,6 +125,11 @@ impl From<&RustweavedMessagePayload> for RustweavedMessagePayloadType {
             RustweavedMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 RustweavedMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            RustweavedMessagePayload::RequestCompactBlock(_) => RustweavedMessagePayloadType::RequestCompactBlock,
+            RustweavedMessagePayload::CompactBlock(_) => RustweavedMessagePayloadType::CompactBlock,
+            RustweavedMessagePayload::RequestBlockTransactions(_) => RustweavedMessagePayloadType::RequestBlockTransactions,
+            RustweavedMessagePayload::BlockTransactions(_) => RustweavedMessagePayloadType::BlockTransactions,
+            RustweavedMessagePayload::StemTransaction(_) => RustweavedMessagePayloadType::StemTransaction,
         }
     }
 }
@@ -1,11 +1,66 @@
+use crate::core::encryption::PeerEncryption;
 use kaspa_consensus_core::subnets::SubnetworkId;
 use kaspa_utils::networking::{IpAddress, PeerId};
 use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
 
+/// The misbehavior score at which a peer gets banned
+pub const MISBEHAVIOR_BAN_THRESHOLD: u32 = 100;
+
+/// Kinds of peer misbehavior which are reported by flows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehavior {
+    InvalidBlock,
+    InvalidTransaction,
+    UnrequestedMessage,
+    IbdStall,
+}
+
+impl Misbehavior {
+    /// The amount each occurrence adds to the misbehavior score of the peer
+    pub fn score(self) -> u32 {
+        match self {
+            Misbehavior::InvalidBlock => MISBEHAVIOR_BAN_THRESHOLD,
+            // A stall might be due to network conditions rather than to the peer, hence it is only lightly accounted for
+            Misbehavior::IbdStall => 10,
+            Misbehavior::InvalidTransaction | Misbehavior::UnrequestedMessage => 20,
+        }
+    }
+}
+
+impl Display for Misbehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Misbehavior::InvalidBlock => "invalid block",
+            Misbehavior::InvalidTransaction => "invalid transaction",
+            Misbehavior::UnrequestedMessage => "unrequested message",
+            Misbehavior::IbdStall => "IBD stall",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The misbehavior accumulated by a peer throughout its connection
+#[derive(Debug, Clone, Default)]
+pub struct PeerMisbehavior {
+    pub score: u32,
+    /// Description of the latest reported misbehavior
+    pub last_reason: Option<String>,
+}
+
+impl PeerMisbehavior {
+    /// Adds `misbehavior` to the score. Returns true if this addition made the score reach the ban threshold
+    pub fn add(&mut self, misbehavior: Misbehavior, reason: &str) -> bool {
+        let was_below_threshold = self.score < MISBEHAVIOR_BAN_THRESHOLD;
+        self.score = self.score.saturating_add(misbehavior.score());
+        self.last_reason = Some(format!("{misbehavior}: {reason}"));
+        was_below_threshold && self.score >= MISBEHAVIOR_BAN_THRESHOLD
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct PeerProperties {
     pub user_agent: String,
-    // TODO: add services
+    pub services: u64,
     pub advertised_protocol_version: u32,
     pub protocol_version: u32,
     pub disable_relay_tx: bool,
@@ -18,9 +73,11 @@ pub struct Peer {
     identity: PeerId,
     net_address: SocketAddr,
     is_outbound: bool,
+    encryption: Option<PeerEncryption>,
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    misbehavior: PeerMisbehavior,
 }
 
 impl Peer {
@@ -28,11 +85,13 @@ impl Peer {
         identity: PeerId,
         net_address: SocketAddr,
         is_outbound: bool,
+        encryption: Option<PeerEncryption>,
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        misbehavior: PeerMisbehavior,
     ) -> Self {
-        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration }
+        Self { identity, net_address, is_outbound, encryption, connection_started, properties, last_ping_duration, misbehavior }
     }
 
     /// Internal identity of this peer
@@ -54,6 +113,11 @@ impl Peer {
         self.is_outbound
     }
 
+    /// The encryption status of this connection, `None` for plaintext connections
+    pub fn encryption(&self) -> Option<PeerEncryption> {
+        self.encryption
+    }
+
     pub fn time_connected(&self) -> u64 {
         Instant::now().duration_since(self.connection_started).as_millis() as u64
     }
@@ -65,6 +129,10 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    pub fn misbehavior(&self) -> &PeerMisbehavior {
+        &self.misbehavior
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -90,3 +158,24 @@ impl Display for PeerKey {
         write!(f, "{}+{}", self.identity, self.ip)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_misbehavior_threshold() {
+        let mut misbehavior = PeerMisbehavior::default();
+        for _ in 0..4 {
+            assert!(!misbehavior.add(Misbehavior::InvalidTransaction, "rejected transaction"));
+        }
+        assert_eq!(misbehavior.score, 80);
+        assert!(misbehavior.add(Misbehavior::UnrequestedMessage, "unexpected block"));
+        assert_eq!(misbehavior.last_reason.as_deref(), Some("unrequested message: unexpected block"));
+        // The threshold is reported as crossed only once
+        assert!(!misbehavior.add(Misbehavior::InvalidBlock, "bad merkle root"));
+        assert_eq!(misbehavior.score, 200);
+
+        assert!(PeerMisbehavior::default().add(Misbehavior::InvalidBlock, "bad merkle root"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::encryption::PeerEncryption;
 use crate::core::hub::HubEvent;
 use crate::pb::RejectMessage;
 use crate::pb::{rustweaved_message::Payload as RustweavedMessagePayload, RustweavedMessage};
@@ -19,7 +20,7 @@ use tokio::sync::mpsc::{channel as mpsc_channel, Receiver as MpscReceiver, Sende
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tonic::Streaming;
 
-use super::peer::{PeerKey, PeerProperties};
+use super::peer::{Misbehavior, PeerKey, PeerMisbehavior, PeerProperties};
 
 pub struct IncomingRoute {
     rx: MpscReceiver<RustweavedMessage>,
@@ -83,7 +84,9 @@ impl From<RustweavedMessagePayloadType> for IncomingRouteOverflowPolicy {
     fn from(msg_type: RustweavedMessagePayloadType) -> Self {
         match msg_type {
             // Inv messages are unique in the sense that no harm is done if some of them are dropped
-            RustweavedMessagePayloadType::InvTransactions | RustweavedMessagePayloadType::InvRelayBlock => IncomingRouteOverflowPolicy::Drop,
+            RustweavedMessagePayloadType::InvTransactions | RustweavedMessagePayloadType::InvRelayBlock => {
+                IncomingRouteOverflowPolicy::Drop
+            }
             _ => IncomingRouteOverflowPolicy::Disconnect,
         }
     }
@@ -102,6 +105,9 @@ struct RouterMutableState {
 
     /// Duration of the last ping to this peer
     last_ping_duration: u64,
+
+    /// Misbehavior reported for this peer
+    misbehavior: PeerMisbehavior,
 }
 
 impl RouterMutableState {
@@ -123,6 +129,9 @@ pub struct Router {
     /// Indicates whether this connection is an outbound connection
     is_outbound: bool,
 
+    /// The encryption status of this connection, `None` for plaintext connections
+    encryption: Option<PeerEncryption>,
+
     /// Time of creation of this object and the connection it holds
     connection_started: Instant,
 
@@ -159,9 +168,11 @@ impl From<&Router> for Peer {
             router.identity(),
             router.net_address,
             router.is_outbound,
+            router.encryption,
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.misbehavior(),
         )
     }
 }
@@ -176,6 +187,7 @@ impl Router {
     pub(crate) async fn new(
         net_address: SocketAddr,
         is_outbound: bool,
+        encryption: Option<PeerEncryption>,
         hub_sender: MpscSender<HubEvent>,
         mut incoming_stream: Streaming<RustweavedMessage>,
         outgoing_route: MpscSender<RustweavedMessage>,
@@ -187,6 +199,7 @@ impl Router {
             identity: Default::default(),
             net_address,
             is_outbound,
+            encryption,
             connection_started: Instant::now(),
             routing_map_by_type: RwLock::new(HashMap::new()),
             routing_map_by_id: RwLock::new(HashMap::new()),
@@ -269,6 +282,11 @@ impl Router {
         self.is_outbound
     }
 
+    /// The encryption status of this connection, `None` for plaintext connections
+    pub fn encryption(&self) -> Option<PeerEncryption> {
+        self.encryption
+    }
+
     pub fn connection_started(&self) -> Instant {
         self.connection_started
     }
@@ -294,6 +312,16 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    pub fn misbehavior(&self) -> PeerMisbehavior {
+        self.mutable_state.lock().misbehavior.clone()
+    }
+
+    /// Adds to the misbehavior score of this peer. Returns true if the score has just reached the ban threshold
+    pub fn report_misbehavior(&self, misbehavior: Misbehavior, reason: &str) -> bool {
+        debug!("P2P, peer {} misbehaved ({}): {}", self, misbehavior, reason);
+        self.mutable_state.lock().misbehavior.add(misbehavior, reason)
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -414,7 +442,8 @@ impl Router {
         if err.can_send_outgoing_message() {
             // Send an explicit reject message for easier tracing of logical bugs causing protocol errors.
             // No need to handle errors since we are closing anyway
-            let _ = self.enqueue(make_message!(RustweavedMessagePayload::Reject, RejectMessage { reason: err.to_reject_message() })).await;
+            let _ =
+                self.enqueue(make_message!(RustweavedMessagePayload::Reject, RejectMessage { reason: err.to_reject_message() })).await;
         }
     }
 
@@ -3,7 +3,7 @@ use crate::{
     core::adaptor::ConnectionInitializer,
     handshake::RustweavedHandshake,
     pb::{self, VersionMessage},
-    IncomingRoute, RustweavedMessagePayloadType, Router,
+    IncomingRoute, Router, RustweavedMessagePayloadType,
 };
 use kaspa_core::{debug, time::unix_now, trace, warn};
 use std::sync::Arc;
@@ -64,6 +64,11 @@ impl EchoFlow {
             RustweavedMessagePayloadType::IbdChainBlockLocator,
             RustweavedMessagePayloadType::RequestAntipast,
             RustweavedMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            RustweavedMessagePayloadType::RequestCompactBlock,
+            RustweavedMessagePayloadType::CompactBlock,
+            RustweavedMessagePayloadType::RequestBlockTransactions,
+            RustweavedMessagePayloadType::BlockTransactions,
+            RustweavedMessagePayloadType::StemTransaction,
         ]);
         let mut echo_flow = EchoFlow { router, receiver };
         debug!("EchoFlow, start app-layer receiving loop");
@@ -157,10 +162,14 @@ mod tests {
         kaspa_core::log::try_init_logger("debug");
 
         let address1 = NetAddress::from_str("[::1]:50053").unwrap();
-        let adaptor1 = Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default()).unwrap();
+        let adaptor1 =
+            Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), None, None)
+                .unwrap();
 
         let address2 = NetAddress::from_str("[::1]:50054").unwrap();
-        let adaptor2 = Adaptor::bidirectional(address2, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default()).unwrap();
+        let adaptor2 =
+            Adaptor::bidirectional(address2, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), None, None)
+                .unwrap();
 
         // Initiate the connection from `adaptor1` (outbound) to `adaptor2` (inbound)
         let peer2_id = adaptor1
@@ -3,6 +3,8 @@ pub mod pb {
     tonic::include_proto!("protowire");
 }
 
+#[cfg(feature = "adversary")]
+pub mod adversary;
 pub mod common;
 pub mod convert;
 pub mod echo;
@@ -12,8 +14,9 @@ mod handshake;
 
 pub use crate::core::adaptor::{Adaptor, ConnectionInitializer};
 pub use crate::core::connection_handler::ConnectionError;
+pub use crate::core::encryption;
 pub use crate::core::hub::Hub;
 pub use crate::core::payload_type::RustweavedMessagePayloadType;
-pub use crate::core::peer::{Peer, PeerKey, PeerProperties};
+pub use crate::core::peer::{Misbehavior, Peer, PeerKey, PeerMisbehavior, PeerProperties, MISBEHAVIOR_BAN_THRESHOLD};
 pub use crate::core::router::{IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
 pub use handshake::RustweavedHandshake;
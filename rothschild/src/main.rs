@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
 
 use clap::{Arg, ArgAction, Command};
 use itertools::Itertools;
@@ -15,6 +15,7 @@ use kaspa_grpc_client::{ClientPool, GrpcClient};
 use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_rpc_core::{api::rpc::RpcApi, notify::mode::NotificationMode};
 use kaspa_txscript::pay_to_address_script;
+use kaspa_utils_tower::socks::Socks5Proxy;
 use parking_lot::Mutex;
 use rayon::prelude::*;
 use secp256k1::{rand::thread_rng, Keypair};
@@ -38,6 +39,7 @@ pub struct Args {
     pub private_key: Option<String>,
     pub tps: u64,
     pub rpc_server: String,
+    pub proxy: Option<Socks5Proxy>,
     pub threads: u8,
     pub unleashed: bool,
 }
@@ -49,6 +51,7 @@ impl Args {
             private_key: m.get_one::<String>("private-key").cloned(),
             tps: m.get_one::<u64>("tps").cloned().unwrap(),
             rpc_server: m.get_one::<String>("rpcserver").cloned().unwrap_or("localhost:16210".to_owned()),
+            proxy: m.get_one::<SocketAddr>("proxy").copied().map(Socks5Proxy::new),
             threads: m.get_one::<u8>("threads").cloned().unwrap(),
             unleashed: m.get_one::<bool>("unleashed").cloned().unwrap_or(false),
         }
@@ -77,6 +80,13 @@ pub fn cli() -> Command {
                 .default_value("localhost:16210")
                 .help("RPC server"),
         )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("proxy")
+                .value_parser(clap::value_parser!(SocketAddr))
+                .help("Connect to the RPC server via a SOCKS5 proxy (e.g. 127.0.0.1:9050)"),
+        )
         .arg(
             Arg::new("threads")
                 .long("threads")
@@ -87,7 +97,7 @@ pub fn cli() -> Command {
         .arg(Arg::new("unleashed").long("unleashed").action(ArgAction::SetTrue).hide(true).help("Allow higher TPS"))
 }
 
-async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &str) -> GrpcClient {
+async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &str, proxy: Option<Socks5Proxy>) -> GrpcClient {
     GrpcClient::connect_with_args(
         NotificationMode::Direct,
         format!("grpc://{}", address),
@@ -97,6 +107,7 @@ async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &st
         false,
         Some(500_000),
         Default::default(),
+        proxy,
     )
     .await
     .unwrap()
@@ -126,6 +137,7 @@ async fn main() {
         false,
         Some(500_000),
         Default::default(),
+        args.proxy,
     )
     .await
     .unwrap();
@@ -177,7 +189,7 @@ async fn main() {
     const CLIENT_POOL_SIZE: usize = 8;
     let mut rpc_clients = Vec::with_capacity(CLIENT_POOL_SIZE);
     for _ in 0..CLIENT_POOL_SIZE {
-        rpc_clients.push(Arc::new(new_rpc_client(&subscription_context, &args.rpc_server).await));
+        rpc_clients.push(Arc::new(new_rpc_client(&subscription_context, &args.rpc_server, args.proxy).await));
     }
 
     let submit_tx_pool = ClientPool::new(rpc_clients, 1000);
@@ -44,6 +44,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "ReorgAlert notification: {} removed chain blocks", "_0.reorg.depth")]
+    ReorgAlert(ReorgAlertNotification),
 }
 }
 
@@ -60,6 +63,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::ReorgAlert(v) => to_value(&v),
         }
     }
 }
@@ -86,6 +86,24 @@ pub enum RpcApiOps {
     GetCoinSupply,
     /// Get DAA Score timestamp estimate
     GetDaaScoreTimestampEstimate,
+    /// Get the accepting chain blocks of a list of transactions (requires the transaction index)
+    GetTransactionAcceptance,
+    /// Get feerate estimations based on the mempool state
+    GetFeeEstimate,
+    /// Get the accepted and rejected transactions of the blocks merged by a list of chain blocks
+    GetBlocksAcceptanceData,
+    /// Get the transactions which spent from or paid to a list of addresses (requires the address index)
+    GetTransactionsByAddresses,
+    /// Get the in-mempool ancestors and descendants of a mempool transaction along with their package feerate
+    GetMempoolEntryAncestry,
+    /// Get the currently banned peer IPs along with their ban reasons and expiries
+    GetBannedPeers,
+    /// Get the merkle proofs of the inclusion of a transaction in a block and of its acceptance by a chain block
+    GetTransactionInclusionProof,
+    /// Get the most recent reorgs of the virtual selected chain
+    GetReorgHistory,
+    /// Extracts a transaction out of the request message and attempts to add it to the mempool, replacing by fee the mempool transactions it double spends
+    SubmitTransactionReplacement,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -97,6 +115,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
     NotifySinkBlueScoreChanged,
+    NotifyReorgAlert,
 
     // ~
     Subscribe,
@@ -114,6 +133,7 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification,
     PruningPointUtxoSetOverrideNotification,
     NewBlockTemplateNotification,
+    ReorgAlertNotification,
 }
 
 impl RpcApiOps {
@@ -129,6 +149,7 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyReorgAlert
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -155,6 +176,7 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::ReorgAlert => RpcApiOps::ReorgAlertNotification,
         }
     }
 }
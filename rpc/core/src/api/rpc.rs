@@ -132,6 +132,15 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse>;
 
+    /// Submits a transaction to the mempool, allowing it to replace by fee the mempool transactions it double spends.
+    async fn submit_transaction_replacement(&self, transaction: RpcTransaction) -> RpcResult<SubmitTransactionReplacementResponse> {
+        self.submit_transaction_replacement_call(SubmitTransactionReplacementRequest::new(transaction)).await
+    }
+    async fn submit_transaction_replacement_call(
+        &self,
+        request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse>;
+
     /// Requests information about a specific block.
     async fn get_block(&self, hash: RpcHash, include_transactions: bool) -> RpcResult<RpcBlock> {
         Ok(self.get_block_call(GetBlockRequest::new(hash, include_transactions)).await?.block)
@@ -304,6 +313,88 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetDaaScoreTimestampEstimateRequest,
     ) -> RpcResult<GetDaaScoreTimestampEstimateResponse>;
 
+    /// Retrieves the acceptance of the given transactions by the selected chain.
+    /// Requires the node to run with the transaction index enabled.
+    async fn get_transaction_acceptance(&self, transaction_ids: Vec<RpcTransactionId>) -> RpcResult<Vec<RpcTransactionAcceptance>> {
+        Ok(self.get_transaction_acceptance_call(GetTransactionAcceptanceRequest { transaction_ids }).await?.acceptances)
+    }
+    async fn get_transaction_acceptance_call(
+        &self,
+        request: GetTransactionAcceptanceRequest,
+    ) -> RpcResult<GetTransactionAcceptanceResponse>;
+
+    /// Retrieves the feerates expected to get a transaction included in a block at the
+    /// priority, normal and low levels, based on the current mempool state.
+    async fn get_fee_estimate(&self) -> RpcResult<RpcFeeEstimate> {
+        Ok(self.get_fee_estimate_call(GetFeeEstimateRequest {}).await?.estimate)
+    }
+    async fn get_fee_estimate_call(&self, request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse>;
+
+    /// Retrieves, for each of the given chain blocks, the transactions of its mergeset blocks
+    /// which were accepted or rejected by it, along with the reason of each rejection.
+    async fn get_blocks_acceptance_data(&self, chain_block_hashes: Vec<RpcHash>) -> RpcResult<Vec<RpcChainBlockAcceptanceData>> {
+        Ok(self.get_blocks_acceptance_data_call(GetBlocksAcceptanceDataRequest { chain_block_hashes }).await?.acceptance_data)
+    }
+    async fn get_blocks_acceptance_data_call(
+        &self,
+        request: GetBlocksAcceptanceDataRequest,
+    ) -> RpcResult<GetBlocksAcceptanceDataResponse>;
+
+    /// Retrieves, for each of the given addresses, the transactions accepted by the selected chain which spent
+    /// from or paid to it, ordered by accepting DAA score and starting at `start_daa_score`.
+    /// Requires the node to run with the address index enabled.
+    async fn get_transactions_by_addresses(
+        &self,
+        addresses: Vec<RpcAddress>,
+        start_daa_score: u64,
+        limit: u32,
+    ) -> RpcResult<Vec<RpcTransactionsByAddressesEntry>> {
+        Ok(self
+            .get_transactions_by_addresses_call(GetTransactionsByAddressesRequest { addresses, start_daa_score, limit })
+            .await?
+            .entries)
+    }
+    async fn get_transactions_by_addresses_call(
+        &self,
+        request: GetTransactionsByAddressesRequest,
+    ) -> RpcResult<GetTransactionsByAddressesResponse>;
+
+    /// Retrieves the in-mempool ancestors and descendants of a transaction of the mempool transaction pool,
+    /// along with the aggregate fee, mass and feerate of the package they form with it.
+    async fn get_mempool_entry_ancestry(&self, transaction_id: RpcTransactionId) -> RpcResult<GetMempoolEntryAncestryResponse> {
+        self.get_mempool_entry_ancestry_call(GetMempoolEntryAncestryRequest { transaction_id }).await
+    }
+    async fn get_mempool_entry_ancestry_call(
+        &self,
+        request: GetMempoolEntryAncestryRequest,
+    ) -> RpcResult<GetMempoolEntryAncestryResponse>;
+
+    /// Requests the list of currently banned peer IPs, along with the reasons and expiries of their bans.
+    async fn get_banned_peers(&self) -> RpcResult<GetBannedPeersResponse> {
+        self.get_banned_peers_call(GetBannedPeersRequest {}).await
+    }
+    async fn get_banned_peers_call(&self, request: GetBannedPeersRequest) -> RpcResult<GetBannedPeersResponse>;
+
+    /// Requests the merkle proofs that a transaction is included in a block and accepted by a chain block.
+    ///
+    /// This call is only available when the node was started with `--txindex`.
+    async fn get_transaction_inclusion_proof(
+        &self,
+        transaction_id: RpcTransactionId,
+    ) -> RpcResult<GetTransactionInclusionProofResponse> {
+        self.get_transaction_inclusion_proof_call(GetTransactionInclusionProofRequest::new(transaction_id)).await
+    }
+    async fn get_transaction_inclusion_proof_call(
+        &self,
+        request: GetTransactionInclusionProofRequest,
+    ) -> RpcResult<GetTransactionInclusionProofResponse>;
+
+    /// Requests the most recent reorgs of the virtual selected chain which removed at least `min_depth` chain blocks.
+    async fn get_reorg_history(&self, min_depth: u64) -> RpcResult<GetReorgHistoryResponse> {
+        self.get_reorg_history_call(GetReorgHistoryRequest::new(min_depth)).await
+    }
+    async fn get_reorg_history_call(&self, request: GetReorgHistoryRequest) -> RpcResult<GetReorgHistoryResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -0,0 +1,18 @@
+use crate::RpcReorgInfo;
+use kaspa_consensus_core::reorg::ReorgRecord;
+
+// ----------------------------------------------------------------------------
+// consensus_core to rpc_core
+// ----------------------------------------------------------------------------
+
+impl From<&ReorgRecord> for RpcReorgInfo {
+    fn from(item: &ReorgRecord) -> Self {
+        Self {
+            timestamp: item.timestamp,
+            depth: item.depth,
+            removed_chain_block_hashes: item.removed_chain_block_hashes.clone(),
+            added_chain_block_count: item.added_chain_block_count,
+            lost_accepted_transaction_count: item.lost_accepted_transaction_count,
+        }
+    }
+}
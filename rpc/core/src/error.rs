@@ -50,6 +50,15 @@ pub enum RpcError {
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
+    #[error("Block {0} is not a chain block.")]
+    NotChainBlock(RpcHash),
+
+    #[error("Requested {0} chain blocks, which is more than max {1} allowed.")]
+    ChainBlockCountExceedingMaximum(usize, usize),
+
+    #[error("Requested {0} addresses, which is more than max {1} allowed.")]
+    AddressCountExceedingMaximum(usize, usize),
+
     #[error("If includeTransactions is set, then includeBlockVerboseData must be set as well.")]
     InvalidGetBlocksRequest,
 
@@ -59,6 +68,12 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Method unavailable. Run the node with the --addressindex argument.")]
+    NoAddressIndex,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -71,9 +86,15 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method unavailable on a light node. Run the node without the --light argument.")]
+    UnavailableInLightMode,
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
+    #[error("Cannot ban IP {0} because it belongs to a whitelisted network.")]
+    IpIsWhitelisted(IpAddress),
+
     #[error("IP {0} is not registered as banned.")]
     IpIsNotBanned(IpAddress),
 
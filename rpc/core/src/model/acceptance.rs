@@ -0,0 +1,69 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{RpcHash, RpcTransactionId};
+
+/// Represents the reason why a transaction of a merged block was not accepted by the chain block merging it
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcTransactionRejectionReason {
+    /// The coinbase transaction of a merged block other than the selected parent. The rewards
+    /// of the merged block are paid by the coinbase transaction of the accepting chain block instead.
+    MergedCoinbase,
+    /// A transaction with the same id was already accepted from a preceding block of the mergeset
+    DuplicateInMergeset,
+    /// The transaction failed validation in the UTXO context of the accepting chain block
+    /// (missing or already spent inputs, immature coinbase inputs, lock time, signatures...)
+    InvalidInUtxoContext,
+}
+
+impl RpcTransactionRejectionReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RpcTransactionRejectionReason::MergedCoinbase => "coinbase of a merged block",
+            RpcTransactionRejectionReason::DuplicateInMergeset => "already accepted from a preceding merged block",
+            RpcTransactionRejectionReason::InvalidInUtxoContext => "invalid in the UTXO context of the accepting block",
+        }
+    }
+}
+
+impl std::fmt::Display for RpcTransactionRejectionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents a transaction of a merged block accepted by a chain block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAcceptedTransaction {
+    pub transaction_id: RpcTransactionId,
+    pub index_within_block: u32,
+}
+
+/// Represents a transaction of a merged block rejected by a chain block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRejectedTransaction {
+    pub transaction_id: RpcTransactionId,
+    pub index_within_block: u32,
+    pub reason: RpcTransactionRejectionReason,
+}
+
+/// Represents the acceptance of the transactions of a block merged by a chain block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMergesetBlockAcceptanceData {
+    pub merged_block_hash: RpcHash,
+    pub accepted_transactions: Vec<RpcAcceptedTransaction>,
+    pub rejected_transactions: Vec<RpcRejectedTransaction>,
+}
+
+/// Represents the acceptance data of a chain block, listing its mergeset blocks in consensus order
+/// (starting with the selected parent)
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcChainBlockAcceptanceData {
+    pub accepting_block_hash: RpcHash,
+    pub mergeset_block_acceptance_data: Vec<RpcMergesetBlockAcceptanceData>,
+}
@@ -1,4 +1,4 @@
-use crate::{RpcTransactionOutpoint, RpcUtxoEntry};
+use crate::{RpcHash, RpcTransaction, RpcTransactionId, RpcTransactionOutpoint, RpcUtxoEntry};
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
@@ -22,3 +22,42 @@ pub struct RpcBalancesByAddressesEntry {
     /// Balance of `address` if available
     pub balance: Option<u64>,
 }
+
+/// Represents an output of a transaction paying to an address, as returned by the `GetTransactionsByAddresses` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressTransactionOutput {
+    pub index: u32,
+    pub amount: u64,
+}
+
+/// Represents an accepted transaction which spent from or paid to an address, as returned by the `GetTransactionsByAddresses` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressTransaction {
+    pub transaction_id: RpcTransactionId,
+    pub accepting_block_hash: RpcHash,
+    pub accepting_daa_score: u64,
+    pub including_block_hash: RpcHash,
+    pub is_coinbase: bool,
+
+    /// Outputs of the transaction paying to the address
+    pub received_outputs: Vec<RpcAddressTransactionOutput>,
+
+    /// Total amount of the address UTXOs spent by the transaction
+    pub spent_amount: u64,
+
+    /// The full transaction, returned for transactions spending from the address
+    /// unless the body of the including block was pruned
+    pub transaction: Option<RpcTransaction>,
+}
+
+/// Represents the transaction history of an address returned by the `GetTransactionsByAddresses` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionsByAddressesEntry {
+    pub address: RpcAddress,
+
+    /// Transactions of `address` ordered by accepting DAA score
+    pub transactions: Vec<RpcAddressTransaction>,
+}
@@ -29,6 +29,26 @@ impl RpcMempoolEntryByAddress {
     }
 }
 
+/// A feerate (in sompi per gram of transaction mass) along with the estimated
+/// time it takes for a transaction paying it to be included in a block
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeerateBucket {
+    pub feerate: f64,
+    pub estimated_seconds: f64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeEstimate {
+    /// A feerate expected to get a transaction included in the next block
+    pub priority_bucket: RpcFeerateBucket,
+    /// A feerate expected to get a transaction included within about a minute
+    pub normal_bucket: RpcFeerateBucket,
+    /// The lowest feerate accepted by the mempool
+    pub low_bucket: RpcFeerateBucket,
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -299,6 +299,33 @@ impl SubmitTransactionResponse {
     }
 }
 
+/// Submits a transaction allowed to replace by fee the mempool transactions it double spends
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionReplacementRequest {
+    pub transaction: RpcTransaction,
+}
+
+impl SubmitTransactionReplacementRequest {
+    pub fn new(transaction: RpcTransaction) -> Self {
+        Self { transaction }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionReplacementResponse {
+    pub transaction_id: RpcTransactionId,
+    /// Ids of the mempool transactions replaced by the submitted transaction
+    pub replaced_transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl SubmitTransactionReplacementResponse {
+    pub fn new(transaction_id: RpcTransactionId, replaced_transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_id, replaced_transaction_ids }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSubnetworkRequest {
@@ -825,6 +852,219 @@ impl GetDaaScoreTimestampEstimateResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionAcceptanceRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl GetTransactionAcceptanceRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+}
+
+/// Transactions which are not accepted by the current selected chain are omitted from `acceptances`.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionAcceptanceResponse {
+    pub acceptances: Vec<RpcTransactionAcceptance>,
+}
+
+impl GetTransactionAcceptanceResponse {
+    pub fn new(acceptances: Vec<RpcTransactionAcceptance>) -> Self {
+        Self { acceptances }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateResponse {
+    pub estimate: RpcFeeEstimate,
+}
+
+impl GetFeeEstimateResponse {
+    pub fn new(estimate: RpcFeeEstimate) -> Self {
+        Self { estimate }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlocksAcceptanceDataRequest {
+    pub chain_block_hashes: Vec<RpcHash>,
+}
+
+impl GetBlocksAcceptanceDataRequest {
+    pub fn new(chain_block_hashes: Vec<RpcHash>) -> Self {
+        Self { chain_block_hashes }
+    }
+}
+
+/// `acceptance_data` matches the order of the requested chain block hashes.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlocksAcceptanceDataResponse {
+    pub acceptance_data: Vec<RpcChainBlockAcceptanceData>,
+}
+
+impl GetBlocksAcceptanceDataResponse {
+    pub fn new(acceptance_data: Vec<RpcChainBlockAcceptanceData>) -> Self {
+        Self { acceptance_data }
+    }
+}
+
+/// Requests at least `limit` transactions per address, starting at `start_daa_score`.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByAddressesRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub start_daa_score: u64,
+    pub limit: u32,
+}
+
+impl GetTransactionsByAddressesRequest {
+    pub fn new(addresses: Vec<RpcAddress>, start_daa_score: u64, limit: u32) -> Self {
+        Self { addresses, start_daa_score, limit }
+    }
+}
+
+/// `entries` matches the order of the requested addresses. The transactions sharing the accepting DAA score
+/// of the last transaction of an entry are always returned in full, so the next page of an address can be
+/// requested starting right above that DAA score.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByAddressesResponse {
+    pub entries: Vec<RpcTransactionsByAddressesEntry>,
+}
+
+impl GetTransactionsByAddressesResponse {
+    pub fn new(entries: Vec<RpcTransactionsByAddressesEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolEntryAncestryRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl GetMempoolEntryAncestryRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+/// The package is made of the requested transaction along with all its in-mempool ancestors and descendants.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolEntryAncestryResponse {
+    /// Transactions the requested transaction directly or indirectly spends from
+    pub ancestors: Vec<RpcTransactionId>,
+    /// Transactions directly or indirectly spending from the requested transaction
+    pub descendants: Vec<RpcTransactionId>,
+    pub package_fee: u64,
+    pub package_mass: u64,
+    /// Aggregate feerate of the package, in sompi per gram of mass
+    pub package_feerate: f64,
+}
+
+impl GetMempoolEntryAncestryResponse {
+    pub fn new(
+        ancestors: Vec<RpcTransactionId>,
+        descendants: Vec<RpcTransactionId>,
+        package_fee: u64,
+        package_mass: u64,
+        package_feerate: f64,
+    ) -> Self {
+        Self { ancestors, descendants, package_fee, package_mass, package_feerate }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBannedPeersRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBannedPeersResponse {
+    pub banned_peers: Vec<RpcBannedPeerInfo>,
+}
+
+impl GetBannedPeersResponse {
+    pub fn new(banned_peers: Vec<RpcBannedPeerInfo>) -> Self {
+        Self { banned_peers }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionInclusionProofRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl GetTransactionInclusionProofRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+/// The transaction is proven to be included in `including_block_header` through its `hash_merkle_root`, and
+/// accepted by the chain block `accepting_block_header` through its `accepted_id_merkle_root`.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionInclusionProofResponse {
+    pub including_block_header: RpcHeader,
+    /// Proof of the transaction hash within the `hash_merkle_root` of the including block
+    pub hash_merkle_witness: RpcMerkleWitness,
+    pub accepting_block_header: RpcHeader,
+    /// Proof of the transaction id within the `accepted_id_merkle_root` of the accepting block
+    pub accepted_id_merkle_witness: RpcMerkleWitness,
+}
+
+impl GetTransactionInclusionProofResponse {
+    pub fn new(
+        including_block_header: RpcHeader,
+        hash_merkle_witness: RpcMerkleWitness,
+        accepting_block_header: RpcHeader,
+        accepted_id_merkle_witness: RpcMerkleWitness,
+    ) -> Self {
+        Self { including_block_header, hash_merkle_witness, accepting_block_header, accepted_id_merkle_witness }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReorgHistoryRequest {
+    /// Only reorgs removing at least this number of chain blocks are returned
+    pub min_depth: u64,
+}
+
+impl GetReorgHistoryRequest {
+    pub fn new(min_depth: u64) -> Self {
+        Self { min_depth }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReorgHistoryResponse {
+    /// The most recent reorgs, most recent first. Reorgs removing less than 5 chain blocks are not tracked,
+    /// unless the node alerts on shallower ones
+    pub reorgs: Vec<RpcReorgInfo>,
+}
+
+impl GetReorgHistoryResponse {
+    pub fn new(reorgs: Vec<RpcReorgInfo>) -> Self {
+        Self { reorgs }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -1133,6 +1373,38 @@ pub struct NotifyNewBlockTemplateResponse {}
 #[serde(rename_all = "camelCase")]
 pub struct NewBlockTemplateNotification {}
 
+// ~~~~~~~~~~~~~~~~~~~~~~
+// ReorgAlertNotification
+
+/// NotifyReorgAlertRequest registers this connection for reorgAlert notifications.
+///
+/// See: ReorgAlertNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyReorgAlertRequest {
+    pub command: Command,
+}
+
+impl NotifyReorgAlertRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyReorgAlertResponse {}
+
+/// ReorgAlertNotification is sent whenever a reorg of the virtual selected chain removes
+/// at least the number of chain blocks configured with `--reorg-alert-depth`.
+///
+/// See: NotifyReorgAlertRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgAlertNotification {
+    pub reorg: RpcReorgInfo,
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -1,3 +1,4 @@
+pub mod acceptance;
 pub mod address;
 pub mod block;
 pub mod blue_work;
@@ -8,10 +9,12 @@ pub mod mempool;
 pub mod message;
 pub mod network;
 pub mod peer;
+pub mod reorg;
 pub mod script_class;
 pub mod subnets;
 pub mod tx;
 
+pub use acceptance::*;
 pub use address::*;
 pub use block::*;
 pub use blue_work::*;
@@ -22,5 +25,6 @@ pub use mempool::*;
 pub use message::*;
 pub use network::*;
 pub use peer::*;
+pub use reorg::*;
 pub use subnets::*;
 pub use tx::*;
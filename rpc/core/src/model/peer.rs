@@ -20,4 +20,26 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+
+    pub is_encrypted: bool,
+    /// The authenticated static public key of the peer, only set for encrypted connections
+    pub encryption_public_key: Option<String>,
+
+    /// Accumulated misbehavior score, the peer is banned once it reaches the ban threshold
+    pub misbehavior_score: u32,
+    /// Description of the latest misbehavior of the peer
+    pub misbehavior_reason: Option<String>,
+
+    /// Whether the peer belongs to a whitelisted network, exempting it from the inbound limit and from bans
+    pub is_whitelisted: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct RpcBannedPeerInfo {
+    pub ip: RpcIpAddress,
+    /// Unix time in milliseconds at which the ban was applied
+    pub banned_at: u64,
+    /// Unix time in milliseconds at which the ban expires
+    pub expires_at: u64,
+    pub reason: String,
 }
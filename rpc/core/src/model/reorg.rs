@@ -0,0 +1,20 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::RpcHash;
+
+/// Represents a reorg of the virtual selected chain, i.e. a chain change which removed chain blocks
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcReorgInfo {
+    /// Unix time in milliseconds at which the reorg was processed
+    pub timestamp: u64,
+    /// The number of chain blocks removed from the selected chain
+    pub depth: u64,
+    /// The removed chain blocks, ordered from the previous sink downwards
+    pub removed_chain_block_hashes: Vec<RpcHash>,
+    /// The number of chain blocks added to the selected chain
+    pub added_chain_block_count: u64,
+    /// The number of transactions which were accepted by the removed chain blocks and are not accepted by the added ones
+    pub lost_accepted_transaction_count: u64,
+}
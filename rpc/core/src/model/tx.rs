@@ -1,5 +1,6 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_addresses::Address;
+use kaspa_consensus_core::merkle::MerkleWitness;
 use kaspa_consensus_core::tx::{
     ScriptPublicKey, ScriptVec, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry,
 };
@@ -115,3 +116,35 @@ pub struct RpcAcceptedTransactionIds {
     pub accepting_block_hash: RpcHash,
     pub accepted_transaction_ids: Vec<RpcTransactionId>,
 }
+
+/// Represents the acceptance of a transaction by a chain block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionAcceptance {
+    pub transaction_id: RpcTransactionId,
+    pub accepting_block_hash: RpcHash,
+    pub accepting_daa_score: u64,
+    pub including_block_hash: RpcHash,
+    pub index_within_block: u32,
+}
+
+/// Represents the inclusion proof of a leaf within a merkle tree, see [`MerkleWitness`]
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMerkleWitness {
+    pub index: u32,
+    /// Sibling hashes along the path from the leaf to the root, ordered from the leaf level upwards
+    pub siblings: Vec<RpcHash>,
+}
+
+impl From<MerkleWitness> for RpcMerkleWitness {
+    fn from(item: MerkleWitness) -> Self {
+        Self { index: item.index, siblings: item.siblings }
+    }
+}
+
+impl From<RpcMerkleWitness> for MerkleWitness {
+    fn from(item: RpcMerkleWitness) -> Self {
+        Self { index: item.index, siblings: item.siblings }
+    }
+}
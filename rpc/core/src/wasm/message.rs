@@ -40,6 +40,99 @@ const TS_ACCEPTED_TRANSACTION_IDS: &'static str = r#"
     }
 "#;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TRANSACTION_ACCEPTANCE: &'static str = r#"
+    /**
+     * Acceptance of a transaction by a chain block.
+     * 
+     * @category Node RPC
+     */
+    export interface ITransactionAcceptance {
+        transactionId : HexString;
+        acceptingBlockHash : HexString;
+        acceptingDaaScore : bigint;
+        includingBlockHash : HexString;
+        indexWithinBlock : number;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_FEE_ESTIMATE: &'static str = r#"
+    /**
+     * A feerate (in sompi per gram of transaction mass) along with the estimated
+     * time it takes for a transaction paying it to be included in a block.
+     * 
+     * @category Node RPC
+     */
+    export interface IFeerateBucket {
+        feerate : number;
+        estimatedSeconds : number;
+    }
+
+    /**
+     * Feerate estimations at the priority, normal and low levels.
+     * 
+     * @category Node RPC
+     */
+    export interface IFeeEstimate {
+        priorityBucket : IFeerateBucket;
+        normalBucket : IFeerateBucket;
+        lowBucket : IFeerateBucket;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_BLOCKS_ACCEPTANCE_DATA: &'static str = r#"
+    /**
+     * Reason why a transaction of a merged block was not accepted.
+     * 
+     * @category Node RPC
+     */
+    export type TransactionRejectionReason = "mergedCoinbase" | "duplicateInMergeset" | "invalidInUtxoContext";
+
+    /**
+     * Transaction of a merged block accepted by a chain block.
+     * 
+     * @category Node RPC
+     */
+    export interface IAcceptedTransaction {
+        transactionId : HexString;
+        indexWithinBlock : number;
+    }
+
+    /**
+     * Transaction of a merged block rejected by a chain block.
+     * 
+     * @category Node RPC
+     */
+    export interface IRejectedTransaction {
+        transactionId : HexString;
+        indexWithinBlock : number;
+        reason : TransactionRejectionReason;
+    }
+
+    /**
+     * Acceptance of the transactions of a block merged by a chain block.
+     * 
+     * @category Node RPC
+     */
+    export interface IMergesetBlockAcceptanceData {
+        mergedBlockHash : HexString;
+        acceptedTransactions : IAcceptedTransaction[];
+        rejectedTransactions : IRejectedTransaction[];
+    }
+
+    /**
+     * Acceptance data of a chain block.
+     * 
+     * @category Node RPC
+     */
+    export interface IChainBlockAcceptanceData {
+        acceptingBlockHash : HexString;
+        mergesetBlockAcceptanceData : IMergesetBlockAcceptanceData[];
+    }
+"#;
+
 // ---
 
 declare! {
@@ -862,6 +955,333 @@ try_from! ( args: GetDaaScoreTimestampEstimateResponse, IGetDaaScoreTimestampEst
 
 // ---
 
+declare! {
+    IGetTransactionAcceptanceRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionAcceptanceRequest {
+        transactionIds : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionAcceptanceRequest, GetTransactionAcceptanceRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionAcceptanceResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionAcceptanceResponse {
+        acceptances : ITransactionAcceptance[];
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionAcceptanceResponse, IGetTransactionAcceptanceResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetFeeEstimateRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateRequest { }
+    "#,
+}
+
+try_from! ( args: IGetFeeEstimateRequest, GetFeeEstimateRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetFeeEstimateResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateResponse {
+        estimate : IFeeEstimate;
+    }
+    "#,
+}
+
+try_from! ( args: GetFeeEstimateResponse, IGetFeeEstimateResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBlocksAcceptanceDataRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlocksAcceptanceDataRequest {
+        chainBlockHashes : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: IGetBlocksAcceptanceDataRequest, GetBlocksAcceptanceDataRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBlocksAcceptanceDataResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlocksAcceptanceDataResponse {
+        acceptanceData : IChainBlockAcceptanceData[];
+    }
+    "#,
+}
+
+try_from! ( args: GetBlocksAcceptanceDataResponse, IGetBlocksAcceptanceDataResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionsByAddressesRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByAddressesRequest {
+        addresses : Address[] | string[];
+        startDaaScore : bigint;
+        limit : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionsByAddressesRequest, GetTransactionsByAddressesRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionsByAddressesResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IAddressTransactionOutput {
+        index : number;
+        amount : bigint;
+    }
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IAddressTransaction {
+        transactionId : HexString;
+        acceptingBlockHash : HexString;
+        acceptingDaaScore : bigint;
+        includingBlockHash : HexString;
+        isCoinbase : boolean;
+        receivedOutputs : IAddressTransactionOutput[];
+        spentAmount : bigint;
+        transaction? : ITransaction;
+    }
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ITransactionsByAddressesEntry {
+        address : Address;
+        transactions : IAddressTransaction[];
+    }
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByAddressesResponse {
+        entries : ITransactionsByAddressesEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionsByAddressesResponse, IGetTransactionsByAddressesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetMempoolEntryAncestryRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetMempoolEntryAncestryRequest {
+        transactionId : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetMempoolEntryAncestryRequest, GetMempoolEntryAncestryRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetMempoolEntryAncestryResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetMempoolEntryAncestryResponse {
+        ancestors : HexString[];
+        descendants : HexString[];
+        packageFee : bigint;
+        packageMass : bigint;
+        packageFeerate : number;
+    }
+    "#,
+}
+
+try_from! ( args: GetMempoolEntryAncestryResponse, IGetMempoolEntryAncestryResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBannedPeersRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetBannedPeersRequest { }
+    "#,
+}
+
+try_from! ( args: IGetBannedPeersRequest, GetBannedPeersRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBannedPeersResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetBannedPeersResponse {
+        [key: string]: any
+    }
+    "#,
+}
+
+try_from! ( args: GetBannedPeersResponse, IGetBannedPeersResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionInclusionProofRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionInclusionProofRequest {
+        transactionId : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionInclusionProofRequest, GetTransactionInclusionProofRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionInclusionProofResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionInclusionProofResponse {
+        [key: string]: any
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionInclusionProofResponse, IGetTransactionInclusionProofResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetReorgHistoryRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetReorgHistoryRequest {
+        minDepth : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetReorgHistoryRequest, GetReorgHistoryRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetReorgHistoryResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetReorgHistoryResponse {
+        [key: string]: any
+    }
+    "#,
+}
+
+try_from! ( args: GetReorgHistoryResponse, IGetReorgHistoryResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetCurrentNetworkRequest,
     r#"
@@ -1347,6 +1767,62 @@ try_from! ( args: SubmitTransactionResponse, ISubmitTransactionResponse, {
 
 // ---
 
+declare! {
+    ISubmitTransactionReplacementRequest,
+    // "ISubmitTransactionReplacementRequest | Transaction",
+    r#"
+    /**
+     * Submit a transaction allowed to replace by fee the mempool transactions it double spends.
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionReplacementRequest {
+        transaction : Transaction
+    }
+    "#,
+}
+
+try_from! ( args: ISubmitTransactionReplacementRequest, SubmitTransactionReplacementRequest, {
+    let transaction = if let Some(transaction) = args.try_get_value("transaction")? {
+        transaction
+    } else {
+        args.into()
+    };
+
+    let request = if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
+        SubmitTransactionReplacementRequest {
+            transaction : transaction.into(),
+        }
+    } else {
+        from_value(transaction)?
+    };
+    Ok(request)
+});
+
+declare! {
+    ISubmitTransactionReplacementResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionReplacementResponse {
+        transactionId : HexString;
+        /**
+         * Ids of the mempool transactions replaced by the submitted transaction
+         */
+        replacedTransactionIds : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: SubmitTransactionReplacementResponse, ISubmitTransactionReplacementResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IUnbanRequest,
     r#"
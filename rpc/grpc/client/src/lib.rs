@@ -39,6 +39,7 @@ use kaspa_utils::{channel::Channel, triggers::DuplexTrigger};
 use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{measure_request_body_size_layer, CountBytesBody, MapResponseBodyLayer, ServiceBuilder},
+    socks::Socks5Proxy,
 };
 use regex::Regex;
 use std::{
@@ -86,7 +87,7 @@ impl GrpcClient {
     pub const DIRECT_MODE_LISTENER_ID: ListenerId = 0;
 
     pub async fn connect(url: String) -> Result<GrpcClient> {
-        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, Default::default()).await
+        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, Default::default(), None).await
     }
 
     /// Connects to a gRPC server.
@@ -113,6 +114,8 @@ impl GrpcClient {
     /// `timeout_duration`: request timeout duration
     ///
     /// `counters`: collects some bandwidth metrics
+    ///
+    /// `proxy`: when provided, the server is reached through this SOCKS5 proxy, which also resolves its host name
     pub async fn connect_with_args(
         notification_mode: NotificationMode,
         url: String,
@@ -122,6 +125,7 @@ impl GrpcClient {
         override_handle_stop_notify: bool,
         timeout_duration: Option<u64>,
         counters: Arc<TowerConnectionCounters>,
+        proxy: Option<Socks5Proxy>,
     ) -> Result<GrpcClient> {
         let schema = Regex::new(r"^grpc://").unwrap();
         if !schema.is_match(&url) {
@@ -133,6 +137,7 @@ impl GrpcClient {
             override_handle_stop_notify,
             timeout_duration.unwrap_or(REQUEST_TIMEOUT_DURATION),
             counters,
+            proxy,
         )
         .await?;
         let converter = Arc::new(RpcCoreConverter::new());
@@ -271,6 +276,15 @@ impl RpcApi for GrpcClient {
     route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
     route!(get_coin_supply_call, GetCoinSupply);
     route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+    route!(get_transaction_acceptance_call, GetTransactionAcceptance);
+    route!(get_fee_estimate_call, GetFeeEstimate);
+    route!(get_blocks_acceptance_data_call, GetBlocksAcceptanceData);
+    route!(get_transactions_by_addresses_call, GetTransactionsByAddresses);
+    route!(get_mempool_entry_ancestry_call, GetMempoolEntryAncestry);
+    route!(get_banned_peers_call, GetBannedPeers);
+    route!(get_transaction_inclusion_proof_call, GetTransactionInclusionProof);
+    route!(get_reorg_history_call, GetReorgHistory);
+    route!(submit_transaction_replacement_call, SubmitTransactionReplacement);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -433,6 +447,9 @@ struct Inner {
 
     // bandwidth counters
     counters: Arc<TowerConnectionCounters>,
+
+    // Optional SOCKS5 proxy the server is reached through
+    proxy: Option<Socks5Proxy>,
 }
 
 impl Inner {
@@ -445,6 +462,7 @@ impl Inner {
         override_handle_stop_notify: bool,
         timeout_duration: u64,
         counters: Arc<TowerConnectionCounters>,
+        proxy: Option<Socks5Proxy>,
     ) -> Self {
         let resolver: DynResolver = match server_features.handle_message_id {
             true => Arc::new(IdResolver::new()),
@@ -470,6 +488,7 @@ impl Inner {
             connection_event_sender,
             override_handle_stop_notify,
             counters,
+            proxy,
         }
     }
 
@@ -480,14 +499,21 @@ impl Inner {
         override_handle_stop_notify: bool,
         timeout_duration: u64,
         counters: Arc<TowerConnectionCounters>,
+        proxy: Option<Socks5Proxy>,
     ) -> Result<Arc<Self>> {
         // Request channel
         let (request_sender, request_receiver) = async_channel::unbounded();
 
         // Try to connect to the server
-        let (stream, server_features) =
-            Inner::try_connect(url.clone(), request_sender.clone(), request_receiver.clone(), timeout_duration, counters.clone())
-                .await?;
+        let (stream, server_features) = Inner::try_connect(
+            url.clone(),
+            request_sender.clone(),
+            request_receiver.clone(),
+            timeout_duration,
+            counters.clone(),
+            proxy,
+        )
+        .await?;
 
         // create the inner object
         let inner = Arc::new(Inner::new(
@@ -499,6 +525,7 @@ impl Inner {
             override_handle_stop_notify,
             timeout_duration,
             counters,
+            proxy,
         ));
 
         // Start the request timeout cleaner
@@ -518,21 +545,28 @@ impl Inner {
         request_receiver: RustweavedRequestReceiver,
         request_timeout: u64,
         counters: Arc<TowerConnectionCounters>,
+        proxy: Option<Socks5Proxy>,
     ) -> Result<(Streaming<RustweavedResponse>, ServerFeatures)> {
         // gRPC endpoint
-        #[cfg(not(feature = "heap"))]
-        let channel =
-            tonic::transport::Channel::builder(url.parse::<tonic::transport::Uri>().map_err(|e| Error::String(e.to_string()))?)
-                .timeout(tokio::time::Duration::from_millis(request_timeout))
-                .connect_timeout(tokio::time::Duration::from_millis(CONNECT_TIMEOUT_DURATION))
-                .connect()
-                .await?;
+        let endpoint =
+            tonic::transport::Channel::builder(url.parse::<tonic::transport::Uri>().map_err(|e| Error::String(e.to_string()))?);
 
-        #[cfg(feature = "heap")]
-        let channel =
-            tonic::transport::Channel::builder(url.parse::<tonic::transport::Uri>().map_err(|e| Error::String(e.to_string()))?)
-                .connect()
-                .await?;
+        #[cfg(not(feature = "heap"))]
+        let endpoint = endpoint
+            .timeout(tokio::time::Duration::from_millis(request_timeout))
+            .connect_timeout(tokio::time::Duration::from_millis(CONNECT_TIMEOUT_DURATION));
+
+        let channel = match proxy {
+            None => endpoint.connect().await?,
+            Some(proxy) => {
+                let connector = tower::service_fn(move |uri: tonic::transport::Uri| async move {
+                    let host = uri.host().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+                    let port = uri.port_u16().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "missing port"))?;
+                    proxy.connect(host, port).await
+                });
+                endpoint.connect_with_connector(connector).await?
+            }
+        };
 
         let bytes_rx = &counters.bytes_rx;
         let bytes_tx = &counters.bytes_tx;
@@ -612,6 +646,7 @@ impl Inner {
             self.request_receiver.clone(),
             self.timeout_duration,
             self.counters.clone(),
+            self.proxy,
         )
         .await?;
 
@@ -0,0 +1,158 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash, RpcResult, RpcTransactionRejectionReason};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &RpcTransactionRejectionReason, protowire::RpcTransactionRejectionReason, {
+    match item {
+        RpcTransactionRejectionReason::MergedCoinbase => Self::MergedCoinbase,
+        RpcTransactionRejectionReason::DuplicateInMergeset => Self::DuplicateInMergeset,
+        RpcTransactionRejectionReason::InvalidInUtxoContext => Self::InvalidInUtxoContext,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcAcceptedTransaction, protowire::RpcAcceptedTransaction, {
+    Self { transaction_id: item.transaction_id.to_string(), index_within_block: item.index_within_block }
+});
+
+from!(item: &kaspa_rpc_core::RpcRejectedTransaction, protowire::RpcRejectedTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        index_within_block: item.index_within_block,
+        reason: protowire::RpcTransactionRejectionReason::from(&item.reason) as i32,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcMergesetBlockAcceptanceData, protowire::RpcMergesetBlockAcceptanceData, {
+    Self {
+        merged_block_hash: item.merged_block_hash.to_string(),
+        accepted_transactions: item.accepted_transactions.iter().map(|x| x.into()).collect(),
+        rejected_transactions: item.rejected_transactions.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcChainBlockAcceptanceData, protowire::RpcChainBlockAcceptanceData, {
+    Self {
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        mergeset_block_acceptance_data: item.mergeset_block_acceptance_data.iter().map(|x| x.into()).collect(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+from!(item: protowire::RpcTransactionRejectionReason, RpcTransactionRejectionReason, {
+    match item {
+        protowire::RpcTransactionRejectionReason::MergedCoinbase => Self::MergedCoinbase,
+        protowire::RpcTransactionRejectionReason::DuplicateInMergeset => Self::DuplicateInMergeset,
+        protowire::RpcTransactionRejectionReason::InvalidInUtxoContext => Self::InvalidInUtxoContext,
+    }
+});
+
+try_from!(item: &protowire::RpcAcceptedTransaction, kaspa_rpc_core::RpcAcceptedTransaction, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, index_within_block: item.index_within_block }
+});
+
+try_from!(item: &protowire::RpcRejectedTransaction, kaspa_rpc_core::RpcRejectedTransaction, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        index_within_block: item.index_within_block,
+        reason: protowire::RpcTransactionRejectionReason::try_from(item.reason)
+            .map_err(|_| RpcError::PrimitiveToEnumConversionError)?
+            .into(),
+    }
+});
+
+try_from!(item: &protowire::RpcMergesetBlockAcceptanceData, kaspa_rpc_core::RpcMergesetBlockAcceptanceData, {
+    Self {
+        merged_block_hash: RpcHash::from_str(&item.merged_block_hash)?,
+        accepted_transactions: item.accepted_transactions.iter().map(|x| x.try_into()).collect::<RpcResult<Vec<_>>>()?,
+        rejected_transactions: item.rejected_transactions.iter().map(|x| x.try_into()).collect::<RpcResult<Vec<_>>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcChainBlockAcceptanceData, kaspa_rpc_core::RpcChainBlockAcceptanceData, {
+    Self {
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        mergeset_block_acceptance_data: item
+            .mergeset_block_acceptance_data
+            .iter()
+            .map(|x| x.try_into())
+            .collect::<RpcResult<Vec<_>>>()?,
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use crate::protowire;
+    use kaspa_rpc_core::{
+        RpcAcceptedTransaction, RpcChainBlockAcceptanceData, RpcHash, RpcMergesetBlockAcceptanceData, RpcRejectedTransaction,
+        RpcTransactionRejectionReason,
+    };
+
+    #[test]
+    fn test_rpc_chain_block_acceptance_data() {
+        let r = RpcChainBlockAcceptanceData {
+            accepting_block_hash: RpcHash::from_u64_word(1),
+            mergeset_block_acceptance_data: vec![
+                RpcMergesetBlockAcceptanceData {
+                    merged_block_hash: RpcHash::from_u64_word(2),
+                    accepted_transactions: vec![RpcAcceptedTransaction {
+                        transaction_id: RpcHash::from_u64_word(3),
+                        index_within_block: 0,
+                    }],
+                    rejected_transactions: vec![],
+                },
+                RpcMergesetBlockAcceptanceData {
+                    merged_block_hash: RpcHash::from_u64_word(4),
+                    accepted_transactions: vec![],
+                    rejected_transactions: vec![
+                        RpcRejectedTransaction {
+                            transaction_id: RpcHash::from_u64_word(5),
+                            index_within_block: 0,
+                            reason: RpcTransactionRejectionReason::MergedCoinbase,
+                        },
+                        RpcRejectedTransaction {
+                            transaction_id: RpcHash::from_u64_word(6),
+                            index_within_block: 1,
+                            reason: RpcTransactionRejectionReason::DuplicateInMergeset,
+                        },
+                        RpcRejectedTransaction {
+                            transaction_id: RpcHash::from_u64_word(7),
+                            index_within_block: 2,
+                            reason: RpcTransactionRejectionReason::InvalidInUtxoContext,
+                        },
+                    ],
+                },
+            ],
+        };
+        let p: protowire::RpcChainBlockAcceptanceData = (&r).into();
+        let r2: RpcChainBlockAcceptanceData = (&p).try_into().unwrap();
+        let p2: protowire::RpcChainBlockAcceptanceData = (&r2).into();
+        assert_eq!(p, p2);
+
+        assert_eq!(r.accepting_block_hash, r2.accepting_block_hash);
+        for (m, m2) in r.mergeset_block_acceptance_data.iter().zip(r2.mergeset_block_acceptance_data.iter()) {
+            assert_eq!(m.merged_block_hash, m2.merged_block_hash);
+            for (tx, tx2) in m.accepted_transactions.iter().zip(m2.accepted_transactions.iter()) {
+                assert_eq!(tx.transaction_id, tx2.transaction_id);
+                assert_eq!(tx.index_within_block, tx2.index_within_block);
+            }
+            for (tx, tx2) in m.rejected_transactions.iter().zip(m2.rejected_transactions.iter()) {
+                assert_eq!(tx.transaction_id, tx2.transaction_id);
+                assert_eq!(tx.index_within_block, tx2.index_within_block);
+                assert_eq!(tx.reason, tx2.reason);
+            }
+        }
+
+        // An unknown rejection reason fails the conversion
+        let mut p3 = p.clone();
+        p3.mergeset_block_acceptance_data[1].rejected_transactions[0].reason = 100;
+        assert!(RpcChainBlockAcceptanceData::try_from(&p3).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -10,6 +11,27 @@ from!(item: &kaspa_rpc_core::RpcBalancesByAddressesEntry, protowire::RpcBalances
     Self { address: (&item.address).into(), balance: item.balance.unwrap_or_default(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::RpcAddressTransactionOutput, protowire::RpcAddressTransactionOutput, {
+    Self { index: item.index, amount: item.amount }
+});
+
+from!(item: &kaspa_rpc_core::RpcAddressTransaction, protowire::RpcAddressTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_daa_score: item.accepting_daa_score,
+        including_block_hash: item.including_block_hash.to_string(),
+        is_coinbase: item.is_coinbase,
+        received_outputs: item.received_outputs.iter().map(|x| x.into()).collect(),
+        spent_amount: item.spent_amount,
+        transaction: item.transaction.as_ref().map(|x| x.into()),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcTransactionsByAddressesEntry, protowire::RpcTransactionsByAddressesEntry, {
+    Self { address: (&item.address).into(), transactions: item.transactions.iter().map(|x| x.into()).collect() }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -18,3 +40,27 @@ try_from!(item: &protowire::RpcBalancesByAddressesEntry, kaspa_rpc_core::RpcBala
     let balance = if item.error.is_some() { None } else { Some(item.balance) };
     Self { address: item.address.as_str().try_into()?, balance }
 });
+
+try_from!(item: &protowire::RpcAddressTransactionOutput, kaspa_rpc_core::RpcAddressTransactionOutput, {
+    Self { index: item.index, amount: item.amount }
+});
+
+try_from!(item: &protowire::RpcAddressTransaction, kaspa_rpc_core::RpcAddressTransaction, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_daa_score: item.accepting_daa_score,
+        including_block_hash: RpcHash::from_str(&item.including_block_hash)?,
+        is_coinbase: item.is_coinbase,
+        received_outputs: item.received_outputs.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        spent_amount: item.spent_amount,
+        transaction: item.transaction.as_ref().map(kaspa_rpc_core::RpcTransaction::try_from).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::RpcTransactionsByAddressesEntry, kaspa_rpc_core::RpcTransactionsByAddressesEntry, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
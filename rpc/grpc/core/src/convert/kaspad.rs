@@ -57,6 +57,15 @@ pub mod rustweaved_request_convert {
     impl_into_rustweaved_request!(GetServerInfo);
     impl_into_rustweaved_request!(GetSyncStatus);
     impl_into_rustweaved_request!(GetDaaScoreTimestampEstimate);
+    impl_into_rustweaved_request!(GetTransactionAcceptance);
+    impl_into_rustweaved_request!(GetFeeEstimate);
+    impl_into_rustweaved_request!(GetBlocksAcceptanceData);
+    impl_into_rustweaved_request!(GetTransactionsByAddresses);
+    impl_into_rustweaved_request!(GetMempoolEntryAncestry);
+    impl_into_rustweaved_request!(GetBannedPeers);
+    impl_into_rustweaved_request!(GetTransactionInclusionProof);
+    impl_into_rustweaved_request!(GetReorgHistory);
+    impl_into_rustweaved_request!(SubmitTransactionReplacement);
 
     impl_into_rustweaved_request!(NotifyBlockAdded);
     impl_into_rustweaved_request!(NotifyNewBlockTemplate);
@@ -66,6 +75,7 @@ pub mod rustweaved_request_convert {
     impl_into_rustweaved_request!(NotifyVirtualDaaScoreChanged);
     impl_into_rustweaved_request!(NotifyVirtualChainChanged);
     impl_into_rustweaved_request!(NotifySinkBlueScoreChanged);
+    impl_into_rustweaved_request!(NotifyReorgAlert);
 
     macro_rules! impl_into_rustweaved_request {
         ($name:tt) => {
@@ -188,6 +198,15 @@ pub mod rustweaved_response_convert {
     impl_into_rustweaved_response!(GetServerInfo);
     impl_into_rustweaved_response!(GetSyncStatus);
     impl_into_rustweaved_response!(GetDaaScoreTimestampEstimate);
+    impl_into_rustweaved_response!(GetTransactionAcceptance);
+    impl_into_rustweaved_response!(GetFeeEstimate);
+    impl_into_rustweaved_response!(GetBlocksAcceptanceData);
+    impl_into_rustweaved_response!(GetTransactionsByAddresses);
+    impl_into_rustweaved_response!(GetMempoolEntryAncestry);
+    impl_into_rustweaved_response!(GetBannedPeers);
+    impl_into_rustweaved_response!(GetTransactionInclusionProof);
+    impl_into_rustweaved_response!(GetReorgHistory);
+    impl_into_rustweaved_response!(SubmitTransactionReplacement);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -197,6 +216,7 @@ pub mod rustweaved_response_convert {
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_notify_response!(NotifyReorgAlert);
 
     impl_into_kaspad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_kaspad_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
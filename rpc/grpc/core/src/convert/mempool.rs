@@ -18,6 +18,18 @@ from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcFeerateBucket, protowire::RpcFeerateBucket, {
+    Self { feerate: item.feerate, estimated_seconds: item.estimated_seconds }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeeEstimate, protowire::RpcFeeEstimate, {
+    Self {
+        priority_bucket: Some((&item.priority_bucket).into()),
+        normal_bucket: Some((&item.normal_bucket).into()),
+        low_bucket: Some((&item.low_bucket).into()),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -40,3 +52,21 @@ try_from!(item: &protowire::RpcMempoolEntryByAddress, kaspa_rpc_core::RpcMempool
         item.receiving.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     )
 });
+
+try_from!(item: &protowire::RpcFeerateBucket, kaspa_rpc_core::RpcFeerateBucket, {
+    Self { feerate: item.feerate, estimated_seconds: item.estimated_seconds }
+});
+
+try_from!(item: &protowire::RpcFeeEstimate, kaspa_rpc_core::RpcFeeEstimate, {
+    let bucket = |bucket: &Option<protowire::RpcFeerateBucket>, field: &str| {
+        bucket
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcFeeEstimate".to_string(), field.to_string()))?
+            .try_into()
+    };
+    Self {
+        priority_bucket: bucket(&item.priority_bucket, "priority_bucket")?,
+        normal_bucket: bucket(&item.normal_bucket, "normal_bucket")?,
+        low_bucket: bucket(&item.low_bucket, "low_bucket")?,
+    }
+});
@@ -248,6 +248,17 @@ from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionResponse>, protowire::Su
     Self { transaction_id: item.transaction_id.to_string(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::SubmitTransactionReplacementRequest, protowire::SubmitTransactionReplacementRequestMessage, {
+    Self { transaction: Some((&item.transaction).into()) }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionReplacementResponse>, protowire::SubmitTransactionReplacementResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        replaced_transaction_ids: item.replaced_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        error: None,
+    }
+});
+
 from!(item: &kaspa_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
     Self { subnetwork_id: item.subnetwork_id.to_string() }
 });
@@ -394,6 +405,75 @@ from!(item: RpcResult<&kaspa_rpc_core::GetDaaScoreTimestampEstimateResponse>, pr
     Self { timestamps: item.timestamps.clone(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::GetTransactionAcceptanceRequest, protowire::GetTransactionAcceptanceRequestMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionAcceptanceResponse>, protowire::GetTransactionAcceptanceResponseMessage, {
+    Self { acceptances: item.acceptances.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetFeeEstimateRequest, protowire::GetFeeEstimateRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateResponse>, protowire::GetFeeEstimateResponseMessage, {
+    Self { estimate: Some((&item.estimate).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetBlocksAcceptanceDataRequest, protowire::GetBlocksAcceptanceDataRequestMessage, {
+    Self { chain_block_hashes: item.chain_block_hashes.iter().map(|x| x.to_string()).collect() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetBlocksAcceptanceDataResponse>, protowire::GetBlocksAcceptanceDataResponseMessage, {
+    Self { acceptance_data: item.acceptance_data.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionsByAddressesRequest, protowire::GetTransactionsByAddressesRequestMessage, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        start_daa_score: item.start_daa_score,
+        limit: item.limit,
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionsByAddressesResponse>, protowire::GetTransactionsByAddressesResponseMessage, {
+    Self { entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetMempoolEntryAncestryRequest, protowire::GetMempoolEntryAncestryRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetMempoolEntryAncestryResponse>, protowire::GetMempoolEntryAncestryResponseMessage, {
+    Self {
+        ancestors: item.ancestors.iter().map(|x| x.to_string()).collect(),
+        descendants: item.descendants.iter().map(|x| x.to_string()).collect(),
+        package_fee: item.package_fee,
+        package_mass: item.package_mass,
+        package_feerate: item.package_feerate,
+        error: None,
+    }
+});
+
+from!(&kaspa_rpc_core::GetBannedPeersRequest, protowire::GetBannedPeersRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetBannedPeersResponse>, protowire::GetBannedPeersResponseMessage, {
+    Self { banned_peers: item.banned_peers.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionInclusionProofRequest, protowire::GetTransactionInclusionProofRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionInclusionProofResponse>, protowire::GetTransactionInclusionProofResponseMessage, {
+    Self {
+        including_block_header: Some((&item.including_block_header).into()),
+        hash_merkle_witness: Some((&item.hash_merkle_witness).into()),
+        accepting_block_header: Some((&item.accepting_block_header).into()),
+        accepted_id_merkle_witness: Some((&item.accepted_id_merkle_witness).into()),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GetReorgHistoryRequest, protowire::GetReorgHistoryRequestMessage, {
+    Self { min_depth: item.min_depth }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetReorgHistoryResponse>, protowire::GetReorgHistoryResponseMessage, {
+    Self { reorgs: item.reorgs.iter().map(|x| x.into()).collect(), error: None }
+});
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -468,6 +548,11 @@ from!(item: &kaspa_rpc_core::NotifyVirtualDaaScoreChangedRequest, protowire::Not
 });
 from!(RpcResult<&kaspa_rpc_core::NotifyVirtualDaaScoreChangedResponse>, protowire::NotifyVirtualDaaScoreChangedResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyReorgAlertRequest, protowire::NotifyReorgAlertRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyReorgAlertResponse>, protowire::NotifyReorgAlertResponseMessage);
+
 from!(item: &kaspa_rpc_core::NotifyVirtualChainChangedRequest, protowire::NotifyVirtualChainChangedRequestMessage, {
     Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
 });
@@ -647,6 +732,24 @@ try_from!(item: &protowire::SubmitTransactionResponseMessage, RpcResult<kaspa_rp
     Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
 });
 
+try_from!(item: &protowire::SubmitTransactionReplacementRequestMessage, kaspa_rpc_core::SubmitTransactionReplacementRequest, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("SubmitTransactionReplacementRequestMessage".to_string(), "transaction".to_string())
+            })?
+            .try_into()?,
+    }
+});
+try_from!(item: &protowire::SubmitTransactionReplacementResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionReplacementResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        replaced_transaction_ids: item.replaced_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
 try_from!(item: &protowire::GetSubnetworkRequestMessage, kaspa_rpc_core::GetSubnetworkRequest, {
     Self { subnetwork_id: kaspa_rpc_core::RpcSubnetworkId::from_str(&item.subnetwork_id)? }
 });
@@ -791,6 +894,85 @@ try_from!(item: &protowire::GetDaaScoreTimestampEstimateResponseMessage, RpcResu
     Self { timestamps: item.timestamps.clone() }
 });
 
+try_from!(item: &protowire::GetTransactionAcceptanceRequestMessage, kaspa_rpc_core::GetTransactionAcceptanceRequest, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::GetTransactionAcceptanceResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionAcceptanceResponse>, {
+    Self { acceptances: item.acceptances.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::GetFeeEstimateRequestMessage, kaspa_rpc_core::GetFeeEstimateRequest);
+try_from!(item: &protowire::GetFeeEstimateResponseMessage, RpcResult<kaspa_rpc_core::GetFeeEstimateResponse>, {
+    Self {
+        estimate: item
+            .estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateResponse".to_string(), "estimate".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetBlocksAcceptanceDataRequestMessage, kaspa_rpc_core::GetBlocksAcceptanceDataRequest, {
+    Self { chain_block_hashes: item.chain_block_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::GetBlocksAcceptanceDataResponseMessage, RpcResult<kaspa_rpc_core::GetBlocksAcceptanceDataResponse>, {
+    Self { acceptance_data: item.acceptance_data.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetTransactionsByAddressesRequestMessage, kaspa_rpc_core::GetTransactionsByAddressesRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        start_daa_score: item.start_daa_score,
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetTransactionsByAddressesResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionsByAddressesResponse>, {
+    Self { entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetMempoolEntryAncestryRequestMessage, kaspa_rpc_core::GetMempoolEntryAncestryRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetMempoolEntryAncestryResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolEntryAncestryResponse>, {
+    Self {
+        ancestors: item.ancestors.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        descendants: item.descendants.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        package_fee: item.package_fee,
+        package_mass: item.package_mass,
+        package_feerate: item.package_feerate,
+    }
+});
+
+try_from!(&protowire::GetBannedPeersRequestMessage, kaspa_rpc_core::GetBannedPeersRequest);
+try_from!(item: &protowire::GetBannedPeersResponseMessage, RpcResult<kaspa_rpc_core::GetBannedPeersResponse>, {
+    Self { banned_peers: item.banned_peers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetTransactionInclusionProofRequestMessage, kaspa_rpc_core::GetTransactionInclusionProofRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetTransactionInclusionProofResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionInclusionProofResponse>, {
+    let missing_field =
+        |field: &str| RpcError::MissingRpcFieldError("GetTransactionInclusionProofResponse".to_string(), field.to_string());
+    Self {
+        including_block_header: item.including_block_header.as_ref().ok_or_else(|| missing_field("includingBlockHeader"))?.try_into()?,
+        hash_merkle_witness: item.hash_merkle_witness.as_ref().ok_or_else(|| missing_field("hashMerkleWitness"))?.try_into()?,
+        accepting_block_header: item.accepting_block_header.as_ref().ok_or_else(|| missing_field("acceptingBlockHeader"))?.try_into()?,
+        accepted_id_merkle_witness: item
+            .accepted_id_merkle_witness
+            .as_ref()
+            .ok_or_else(|| missing_field("acceptedIdMerkleWitness"))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetReorgHistoryRequestMessage, kaspa_rpc_core::GetReorgHistoryRequest, {
+    Self { min_depth: item.min_depth }
+});
+try_from!(item: &protowire::GetReorgHistoryResponseMessage, RpcResult<kaspa_rpc_core::GetReorgHistoryResponse>, {
+    Self { reorgs: item.reorgs.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
@@ -870,6 +1052,11 @@ try_from!(item: &protowire::NotifyVirtualDaaScoreChangedRequestMessage, kaspa_rp
 });
 try_from!(&protowire::NotifyVirtualDaaScoreChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifyVirtualDaaScoreChangedResponse>);
 
+try_from!(item: &protowire::NotifyReorgAlertRequestMessage, kaspa_rpc_core::NotifyReorgAlertRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyReorgAlertResponseMessage, RpcResult<kaspa_rpc_core::NotifyReorgAlertResponse>);
+
 try_from!(item: &protowire::NotifyVirtualChainChangedRequestMessage, kaspa_rpc_core::NotifyVirtualChainChangedRequest, {
     Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
 });
@@ -1,3 +1,4 @@
+pub mod acceptance;
 pub mod address;
 pub mod block;
 pub mod error;
@@ -8,4 +9,5 @@ pub mod message;
 pub mod metrics;
 pub mod notification;
 pub mod peer;
+pub mod reorg;
 pub mod tx;
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::{RpcError, RpcNodeId, RpcPeerAddress};
+use kaspa_rpc_core::{RpcError, RpcIpAddress, RpcNodeId, RpcPeerAddress};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -19,9 +19,18 @@ from!(item: &kaspa_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as i64,
         is_ibd_peer: item.is_ibd_peer,
+        is_encrypted: item.is_encrypted,
+        encryption_public_key: item.encryption_public_key.clone().unwrap_or_default(),
+        misbehavior_score: item.misbehavior_score,
+        misbehavior_reason: item.misbehavior_reason.clone().unwrap_or_default(),
+        is_whitelisted: item.is_whitelisted,
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcBannedPeerInfo, protowire::RpcBannedPeerInfo, {
+    Self { ip: item.ip.to_string(), banned_at: item.banned_at, expires_at: item.expires_at, reason: item.reason.clone() }
+});
+
 from!(item: &kaspa_rpc_core::RpcPeerAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 from!(item: &kaspa_rpc_core::RpcIpAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 
@@ -40,6 +49,20 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, kaspa_rpc_core::RpcPeer
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as u64,
         is_ibd_peer: item.is_ibd_peer,
+        is_encrypted: item.is_encrypted,
+        encryption_public_key: (!item.encryption_public_key.is_empty()).then(|| item.encryption_public_key.clone()),
+        misbehavior_score: item.misbehavior_score,
+        misbehavior_reason: (!item.misbehavior_reason.is_empty()).then(|| item.misbehavior_reason.clone()),
+        is_whitelisted: item.is_whitelisted,
+    }
+});
+
+try_from!(item: &protowire::RpcBannedPeerInfo, kaspa_rpc_core::RpcBannedPeerInfo, {
+    Self {
+        ip: RpcIpAddress::from_str(&item.ip)?,
+        banned_at: item.banned_at,
+        expires_at: item.expires_at,
+        reason: item.reason.clone(),
     }
 });
 
@@ -0,0 +1,32 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcReorgInfo, protowire::RpcReorgInfo, {
+    Self {
+        timestamp: item.timestamp,
+        depth: item.depth,
+        removed_chain_block_hashes: item.removed_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
+        added_chain_block_count: item.added_chain_block_count,
+        lost_accepted_transaction_count: item.lost_accepted_transaction_count,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcReorgInfo, kaspa_rpc_core::RpcReorgInfo, {
+    Self {
+        timestamp: item.timestamp,
+        depth: item.depth,
+        removed_chain_block_hashes: item.removed_chain_block_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        added_chain_block_count: item.added_chain_block_count,
+        lost_accepted_transaction_count: item.lost_accepted_transaction_count,
+    }
+});
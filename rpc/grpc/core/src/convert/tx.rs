@@ -82,6 +82,20 @@ from!(item: &kaspa_rpc_core::RpcAcceptedTransactionIds, protowire::RpcAcceptedTr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcTransactionAcceptance, protowire::RpcTransactionAcceptance, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_daa_score: item.accepting_daa_score,
+        including_block_hash: item.including_block_hash.to_string(),
+        index_within_block: item.index_within_block,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcMerkleWitness, protowire::RpcMerkleWitness, {
+    Self { index: item.index, siblings: item.siblings.iter().map(|x| x.to_string()).collect() }
+});
+
 from!(item: &kaspa_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddressesEntry, {
     Self {
         address: item.address.as_ref().map_or("".to_string(), |x| x.into()),
@@ -189,6 +203,20 @@ try_from!(item: &protowire::RpcAcceptedTransactionIds, kaspa_rpc_core::RpcAccept
     }
 });
 
+try_from!(item: &protowire::RpcTransactionAcceptance, kaspa_rpc_core::RpcTransactionAcceptance, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_daa_score: item.accepting_daa_score,
+        including_block_hash: RpcHash::from_str(&item.including_block_hash)?,
+        index_within_block: item.index_within_block,
+    }
+});
+
+try_from!(item: &protowire::RpcMerkleWitness, kaspa_rpc_core::RpcMerkleWitness, {
+    Self { index: item.index, siblings: item.siblings.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(item: &protowire::RpcUtxosByAddressesEntry, kaspa_rpc_core::RpcUtxosByAddressesEntry, {
     let address = if item.address.is_empty() { None } else { Some(item.address.as_str().try_into()?) };
     Self {
@@ -81,6 +81,15 @@ pub enum RustweavedPayloadOps {
     GetServerInfo,
     GetSyncStatus,
     GetDaaScoreTimestampEstimate,
+    GetTransactionAcceptance,
+    GetFeeEstimate,
+    GetBlocksAcceptanceData,
+    GetTransactionsByAddresses,
+    GetMempoolEntryAncestry,
+    GetBannedPeers,
+    GetTransactionInclusionProof,
+    GetReorgHistory,
+    SubmitTransactionReplacement,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -91,6 +100,7 @@ pub enum RustweavedPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyReorgAlert,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
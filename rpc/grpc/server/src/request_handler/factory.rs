@@ -75,6 +75,15 @@ impl Factory {
                 GetServerInfo,
                 GetSyncStatus,
                 GetDaaScoreTimestampEstimate,
+                GetTransactionAcceptance,
+                GetFeeEstimate,
+                GetBlocksAcceptanceData,
+                GetTransactionsByAddresses,
+                GetMempoolEntryAncestry,
+                GetBannedPeers,
+                GetTransactionInclusionProof,
+                GetReorgHistory,
+                SubmitTransactionReplacement,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -83,6 +92,7 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyReorgAlert,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -228,6 +228,60 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_acceptance_call(
+        &self,
+        _request: GetTransactionAcceptanceRequest,
+    ) -> RpcResult<GetTransactionAcceptanceResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_blocks_acceptance_data_call(
+        &self,
+        _request: GetBlocksAcceptanceDataRequest,
+    ) -> RpcResult<GetBlocksAcceptanceDataResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transactions_by_addresses_call(
+        &self,
+        _request: GetTransactionsByAddressesRequest,
+    ) -> RpcResult<GetTransactionsByAddressesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mempool_entry_ancestry_call(
+        &self,
+        _request: GetMempoolEntryAncestryRequest,
+    ) -> RpcResult<GetMempoolEntryAncestryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_banned_peers_call(&self, _request: GetBannedPeersRequest) -> RpcResult<GetBannedPeersResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_inclusion_proof_call(
+        &self,
+        _request: GetTransactionInclusionProofRequest,
+    ) -> RpcResult<GetTransactionInclusionProofResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_reorg_history_call(&self, _request: GetReorgHistoryRequest) -> RpcResult<GetReorgHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn submit_transaction_replacement_call(
+        &self,
+        _request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use kaspa_addresses::Address;
 use kaspa_consensus_core::{
+    acceptance_data::AcceptanceData,
     block::Block,
     config::Config,
     hashing::tx::hash,
@@ -11,15 +12,23 @@ use kaspa_consensus_core::{
 use kaspa_consensus_notify::notification::{self as consensus_notify, Notification as ConsensusNotification};
 use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
 use kaspa_math::Uint256;
-use kaspa_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
+use kaspa_mining::{
+    feerate::{FeerateBucket, FeerateEstimations},
+    model::{owner_txs::OwnerTransactions, TransactionIdSet},
+};
 use kaspa_notify::converter::Converter;
 use kaspa_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry,
-    RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
-    RpcTransactionVerboseData,
+    BlockAddedNotification, Notification, RpcAcceptedTransaction, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData,
+    RpcChainBlockAcceptanceData, RpcFeeEstimate, RpcFeerateBucket, RpcHash, RpcMempoolEntry, RpcMempoolEntryByAddress,
+    RpcMergesetBlockAcceptanceData, RpcRejectedTransaction, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput,
+    RpcTransactionOutputVerboseData, RpcTransactionRejectionReason, RpcTransactionVerboseData,
 };
 use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
-use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+};
 
 /// Conversion of consensus_core to rpc_core structures
 pub struct ConsensusConverter {
@@ -102,6 +111,15 @@ impl ConsensusConverter {
         RpcMempoolEntryByAddress::new(address, sending, receiving)
     }
 
+    pub fn get_fee_estimate(&self, estimations: &FeerateEstimations) -> RpcFeeEstimate {
+        let bucket = |x: &FeerateBucket| RpcFeerateBucket { feerate: x.feerate, estimated_seconds: x.estimated_seconds };
+        RpcFeeEstimate {
+            priority_bucket: bucket(&estimations.priority_bucket),
+            normal_bucket: bucket(&estimations.normal_bucket),
+            low_bucket: bucket(&estimations.low_bucket),
+        }
+    }
+
     pub fn get_owner_entries(
         &self,
         consensus: &ConsensusProxy,
@@ -177,6 +195,57 @@ impl ConsensusConverter {
             })
             .collect())
     }
+
+    /// Converts the acceptance data of a chain block, completing it with the transactions of each mergeset block
+    /// which were rejected by the chain block.
+    ///
+    /// Consensus does not store the rule a rejected transaction failed, so the reason is derived from the acceptance
+    /// data itself: merged coinbase transactions and transactions already accepted from a preceding mergeset block
+    /// are recognized as such, any other rejection being due to the transaction UTXO context validation.
+    ///
+    /// Only the transaction ids of the mergeset blocks are read from consensus.
+    pub async fn get_chain_block_acceptance_data(
+        &self,
+        consensus: &ConsensusProxy,
+        accepting_block_hash: RpcHash,
+        acceptance_data: &AcceptanceData,
+    ) -> RpcResult<RpcChainBlockAcceptanceData> {
+        let mergeset_transaction_ids =
+            consensus.async_get_blocks_transaction_ids(acceptance_data.iter().map(|x| x.block_hash).collect()).await?;
+        let mut mergeset_accepted_ids = HashSet::new();
+        let mut mergeset_block_acceptance_data = Vec::with_capacity(acceptance_data.len());
+        for (merged_block_data, transaction_ids) in acceptance_data.iter().zip(mergeset_transaction_ids) {
+            let accepted_indexes =
+                merged_block_data.accepted_transactions.iter().map(|x| x.index_within_block).collect::<HashSet<_>>();
+            let rejected_transactions = transaction_ids
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| !accepted_indexes.contains(&(*index as u32)))
+                .map(|(index, transaction_id)| {
+                    // The coinbase transaction is always the first transaction of a block
+                    let reason = if index == 0 {
+                        RpcTransactionRejectionReason::MergedCoinbase
+                    } else if mergeset_accepted_ids.contains(&transaction_id) {
+                        RpcTransactionRejectionReason::DuplicateInMergeset
+                    } else {
+                        RpcTransactionRejectionReason::InvalidInUtxoContext
+                    };
+                    RpcRejectedTransaction { transaction_id, index_within_block: index as u32, reason }
+                })
+                .collect();
+            mergeset_accepted_ids.extend(merged_block_data.accepted_transactions.iter().map(|x| x.transaction_id));
+            mergeset_block_acceptance_data.push(RpcMergesetBlockAcceptanceData {
+                merged_block_hash: merged_block_data.block_hash,
+                accepted_transactions: merged_block_data
+                    .accepted_transactions
+                    .iter()
+                    .map(|x| RpcAcceptedTransaction { transaction_id: x.transaction_id, index_within_block: x.index_within_block })
+                    .collect(),
+                rejected_transactions,
+            });
+        }
+        Ok(RpcChainBlockAcceptanceData { accepting_block_hash, mergeset_block_acceptance_data })
+    }
 }
 
 #[async_trait]
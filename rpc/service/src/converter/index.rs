@@ -1,10 +1,16 @@
 use async_trait::async_trait;
+use kaspa_addressindex::model::AddressTransactionsByScriptPublicKey;
 use kaspa_consensus_core::config::Config;
 use kaspa_index_core::indexed_utxos::UtxoSetByScriptPublicKey;
 use kaspa_index_core::notification::{self as index_notify, Notification as IndexNotification};
 use kaspa_notify::converter::Converter;
-use kaspa_rpc_core::{utxo_set_into_rpc, Notification, RpcUtxosByAddressesEntry, UtxosChangedNotification};
-use std::sync::Arc;
+use kaspa_rpc_core::{
+    utxo_set_into_rpc, Notification, RpcAddress, RpcAddressTransaction, RpcAddressTransactionOutput, RpcTransaction,
+    RpcTransactionAcceptance, RpcTransactionId, RpcTransactionsByAddressesEntry, RpcUtxosByAddressesEntry, UtxosChangedNotification,
+};
+use kaspa_txindex::model::TxAcceptanceByTransactionId;
+use kaspa_txscript::pay_to_address_script;
+use std::{collections::HashMap, sync::Arc};
 
 /// Conversion of consensus_core to rpc_core structures
 #[derive(Debug)]
@@ -27,6 +33,68 @@ impl IndexConverter {
     pub fn get_utxos_by_addresses_entries(&self, item: &UtxoSetByScriptPublicKey) -> Vec<RpcUtxosByAddressesEntry> {
         utxo_set_into_rpc(item, Some(self.config.prefix()))
     }
+
+    /// Converts the indexed acceptances into rpc acceptances, following the order of `transaction_ids`
+    /// and omitting transactions which are not indexed.
+    pub fn get_transaction_acceptances(
+        &self,
+        transaction_ids: &[RpcTransactionId],
+        acceptances: &TxAcceptanceByTransactionId,
+    ) -> Vec<RpcTransactionAcceptance> {
+        transaction_ids
+            .iter()
+            .filter_map(|transaction_id| {
+                acceptances.get(transaction_id).map(|acceptance| RpcTransactionAcceptance {
+                    transaction_id: *transaction_id,
+                    accepting_block_hash: acceptance.accepting_block_hash,
+                    accepting_daa_score: acceptance.accepting_daa_score,
+                    including_block_hash: acceptance.including_block_hash,
+                    index_within_block: acceptance.index_within_block,
+                })
+            })
+            .collect()
+    }
+
+    /// Converts the indexed address transactions into rpc entries, following the order of `addresses`.
+    pub fn get_transactions_by_addresses_entries(
+        &self,
+        addresses: &[RpcAddress],
+        transactions: &AddressTransactionsByScriptPublicKey,
+        spending_transactions: &HashMap<RpcTransactionId, RpcTransaction>,
+    ) -> Vec<RpcTransactionsByAddressesEntry> {
+        addresses
+            .iter()
+            .map(|address| {
+                let transactions = transactions
+                    .get(&pay_to_address_script(address))
+                    .map(|transactions| {
+                        transactions
+                            .iter()
+                            .map(|transaction| RpcAddressTransaction {
+                                transaction_id: transaction.transaction_id,
+                                accepting_block_hash: transaction.accepting_block_hash,
+                                accepting_daa_score: transaction.accepting_daa_score,
+                                including_block_hash: transaction.including_block_hash,
+                                is_coinbase: transaction.is_coinbase,
+                                received_outputs: transaction
+                                    .received_outputs
+                                    .iter()
+                                    .map(|&(index, amount)| RpcAddressTransactionOutput { index, amount })
+                                    .collect(),
+                                spent_amount: transaction.spent_amount,
+                                transaction: if transaction.spent_amount > 0 {
+                                    spending_transactions.get(&transaction.transaction_id).cloned()
+                                } else {
+                                    None
+                                },
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                RpcTransactionsByAddressesEntry { address: address.clone(), transactions }
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
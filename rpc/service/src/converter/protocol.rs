@@ -25,6 +25,11 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            is_encrypted: peer.encryption().is_some(),
+            encryption_public_key: peer.encryption().map(|encryption| encryption.public_key.to_string()),
+            misbehavior_score: peer.misbehavior().score,
+            misbehavior_reason: peer.misbehavior().last_reason.clone(),
+            is_whitelisted: self.flow_context.is_whitelisted(peer.net_address().ip()),
         }
     }
 
@@ -4,13 +4,16 @@ use super::collector::{CollectorFromConsensus, CollectorFromIndex};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use crate::service::NetworkType::{Mainnet, Testnet};
 use async_trait::async_trait;
+use kaspa_addressindex::api::AddressIndexProxy;
 use kaspa_consensus_core::api::counters::ProcessingCounters;
 use kaspa_consensus_core::errors::block::RuleError;
+use kaspa_consensus_core::errors::consensus::ConsensusError;
 use kaspa_consensus_core::{
     block::Block,
     coinbase::MinerData,
     config::Config,
     constants::MAX_SOMPI,
+    merkle::{create_hash_merkle_witness, create_merkle_witness},
     network::NetworkType,
     tx::{Transaction, COINBASE_TRANSACTION_INDEX},
 };
@@ -35,7 +38,10 @@ use kaspa_index_core::{
     notifier::IndexNotifier,
 };
 use kaspa_mining::model::tx_query::TransactionQuery;
-use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
+use kaspa_mining::{
+    manager::MiningManagerProxy,
+    mempool::tx::{Orphan, RbfPolicy},
+};
 use kaspa_notify::listener::ListenerLifespan;
 use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
@@ -60,12 +66,13 @@ use kaspa_rpc_core::{
     notify::connection::ChannelConnection,
     Notification, RpcError, RpcResult,
 };
+use kaspa_txindex::api::TxIndexProxy;
 use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use kaspa_utxoindex::api::UtxoIndexProxy;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     iter::once,
     sync::{atomic::Ordering, Arc},
     vec,
@@ -96,6 +103,8 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
+    addressindex: Option<AddressIndexProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -113,6 +122,18 @@ pub struct RpcCoreService {
 
 const RPC_CORE: &str = "rpc-core";
 
+/// The maximum number of transactions returned per address by a single `GetTransactionsByAddresses` call
+const MAX_ADDRESS_TRANSACTIONS_LIMIT: usize = 1_000;
+
+/// The maximum number of addresses queried by a single `GetTransactionsByAddresses` call
+const MAX_ADDRESS_TRANSACTIONS_ADDRESSES: usize = 100;
+
+/// The maximum number of headers returned by a single `GetHeaders` call
+const MAX_HEADERS_LIMIT: u64 = 2_000;
+
+/// The maximum number of chain blocks whose acceptance data is requested by a single `GetBlocksAcceptanceData` call
+const MAX_BLOCKS_ACCEPTANCE_DATA_LIMIT: usize = 100;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -125,6 +146,8 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addressindex: Option<AddressIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -150,7 +173,8 @@ impl RpcCoreService {
         // Prepare the rpc-core notifier objects
         let mut consensus_events: EventSwitches = EVENT_TYPE_ARRAY[..].into();
         consensus_events[EventType::UtxosChanged] = false;
-        consensus_events[EventType::PruningPointUtxoSetOverride] = index_notifier.is_none();
+        // Pruning point UTXO set overrides are relayed by the index processor only when the utxoindex is enabled
+        consensus_events[EventType::PruningPointUtxoSetOverride] = utxoindex.is_none();
         let consensus_converter = Arc::new(ConsensusConverter::new(consensus_manager.clone(), config.clone()));
         let consensus_collector = Arc::new(CollectorFromConsensus::new(
             "rpc-core <= consensus",
@@ -195,6 +219,8 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
+            addressindex,
             config,
             consensus_converter,
             index_converter,
@@ -276,6 +302,9 @@ impl RpcCoreService {
 #[async_trait]
 impl RpcApi for RpcCoreService {
     async fn submit_block_call(&self, request: SubmitBlockRequest) -> RpcResult<SubmitBlockResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
         let session = self.consensus_manager.consensus().unguarded_session();
 
         // TODO: consider adding an error field to SubmitBlockReport to document both the report and error fields
@@ -336,6 +365,10 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     async fn get_block_template_call(&self, request: GetBlockTemplateRequest) -> RpcResult<GetBlockTemplateResponse> {
         trace!("incoming GetBlockTemplate request");
 
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+
         if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
             return Err(RpcError::General("Mining on mainnet is not supported for initial Rust versions".to_owned()));
         }
@@ -441,6 +474,10 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
     async fn get_mempool_entry_call(&self, request: GetMempoolEntryRequest) -> RpcResult<GetMempoolEntryResponse> {
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
+        // Transactions still in their Dandelion stem phase are not revealed
+        if self.flow_context.is_embargoed(&request.transaction_id) {
+            return Err(RpcError::TransactionNotFound(request.transaction_id));
+        }
         let Some(transaction) = self.mining_manager.clone().get_transaction(request.transaction_id, query).await else {
             return Err(RpcError::TransactionNotFound(request.transaction_id));
         };
@@ -448,13 +485,36 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetMempoolEntryResponse::new(self.consensus_converter.get_mempool_entry(&session, &transaction)))
     }
 
+    async fn get_mempool_entry_ancestry_call(
+        &self,
+        request: GetMempoolEntryAncestryRequest,
+    ) -> RpcResult<GetMempoolEntryAncestryResponse> {
+        let embargoed = self.flow_context.embargoed_transactions();
+        if embargoed.contains(&request.transaction_id) {
+            return Err(RpcError::TransactionNotFound(request.transaction_id));
+        }
+        let Some(ancestry) = self.mining_manager.clone().get_transaction_ancestry(request.transaction_id).await else {
+            return Err(RpcError::TransactionNotFound(request.transaction_id));
+        };
+        let package_feerate = ancestry.package_feerate();
+        Ok(GetMempoolEntryAncestryResponse::new(
+            ancestry.ancestors.into_iter().filter(|id| !embargoed.contains(id)).collect(),
+            ancestry.descendants.into_iter().filter(|id| !embargoed.contains(id)).collect(),
+            ancestry.package_fee,
+            ancestry.package_mass,
+            package_feerate,
+        ))
+    }
+
     async fn get_mempool_entries_call(&self, request: GetMempoolEntriesRequest) -> RpcResult<GetMempoolEntriesResponse> {
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let session = self.consensus_manager.consensus().unguarded_session();
         let (transactions, orphans) = self.mining_manager.clone().get_all_transactions(query).await;
+        let embargoed = self.flow_context.embargoed_transactions();
         let mempool_entries = transactions
             .iter()
             .chain(orphans.iter())
+            .filter(|transaction| !embargoed.contains(&transaction.id()))
             .map(|transaction| self.consensus_converter.get_mempool_entry(&session, transaction))
             .collect();
         Ok(GetMempoolEntriesResponse::new(mempool_entries))
@@ -467,7 +527,15 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let session = self.consensus_manager.consensus().unguarded_session();
         let script_public_keys = request.addresses.iter().map(pay_to_address_script).collect();
-        let grouped_txs = self.mining_manager.clone().get_transactions_by_addresses(script_public_keys, query).await;
+        let mut grouped_txs = self.mining_manager.clone().get_transactions_by_addresses(script_public_keys, query).await;
+        let embargoed = self.flow_context.embargoed_transactions();
+        if !embargoed.is_empty() {
+            grouped_txs.transactions.retain(|id, _| !embargoed.contains(id));
+            grouped_txs.owners.values_mut().for_each(|owner_transactions| {
+                owner_transactions.sending_txs.retain(|id| !embargoed.contains(id));
+                owner_transactions.receiving_txs.retain(|id| !embargoed.contains(id));
+            });
+        }
         let mempool_entries = grouped_txs
             .owners
             .iter()
@@ -486,6 +554,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     }
 
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
         let allow_orphan = self.config.unsafe_rpc && request.allow_orphan;
         if !self.config.unsafe_rpc && request.allow_orphan {
             warn!("SubmitTransaction RPC command called with AllowOrphan enabled while node in safe RPC mode -- switching to ForbidOrphan.");
@@ -498,7 +569,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             true => Orphan::Allowed,
             false => Orphan::Forbidden,
         };
-        self.flow_context.submit_rpc_transaction(&session, transaction, orphan).await.map_err(|err| {
+        self.flow_context.submit_rpc_transaction(&session, transaction, orphan, RbfPolicy::Forbidden).await.map_err(|err| {
             let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
             debug!("{err}");
             err
@@ -506,6 +577,27 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(SubmitTransactionResponse::new(transaction_id))
     }
 
+    async fn submit_transaction_replacement_call(
+        &self,
+        request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        let transaction_id = transaction.id();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let replaced_transaction_ids =
+            self.flow_context.submit_rpc_transaction(&session, transaction, Orphan::Forbidden, RbfPolicy::Allowed).await.map_err(
+                |err| {
+                    let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+                    debug!("{err}");
+                    err
+                },
+            )?;
+        Ok(SubmitTransactionReplacementResponse::new(transaction_id, replaced_transaction_ids))
+    }
+
     async fn get_current_network_call(&self, _: GetCurrentNetworkRequest) -> RpcResult<GetCurrentNetworkResponse> {
         Ok(GetCurrentNetworkResponse::new(*self.config.net))
     }
@@ -527,6 +619,10 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         &self,
         request: GetVirtualChainFromBlockRequest,
     ) -> RpcResult<GetVirtualChainFromBlockResponse> {
+        // Light nodes keep no acceptance data and follow the headers selected chain, hence serve chain hashes only
+        if self.config.is_light && request.include_accepted_transaction_ids {
+            return Err(RpcError::UnavailableInLightMode);
+        }
         let session = self.consensus_manager.consensus().session().await;
         let virtual_chain = session.async_get_virtual_chain_from_block(request.start_hash).await?;
         let accepted_transaction_ids = if request.include_accepted_transaction_ids {
@@ -647,18 +743,153 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetDaaScoreTimestampEstimateResponse::new(timestamps))
     }
 
+    async fn get_transaction_acceptance_call(
+        &self,
+        request: GetTransactionAcceptanceRequest,
+    ) -> RpcResult<GetTransactionAcceptanceResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let acceptances = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_acceptances(request.transaction_ids.clone())
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?;
+        Ok(GetTransactionAcceptanceResponse::new(
+            self.index_converter.get_transaction_acceptances(&request.transaction_ids, &acceptances),
+        ))
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        let estimations = self.mining_manager.clone().get_feerate_estimations().await;
+        Ok(GetFeeEstimateResponse::new(self.consensus_converter.get_fee_estimate(&estimations)))
+    }
+
+    async fn get_blocks_acceptance_data_call(
+        &self,
+        request: GetBlocksAcceptanceDataRequest,
+    ) -> RpcResult<GetBlocksAcceptanceDataResponse> {
+        if request.chain_block_hashes.len() > MAX_BLOCKS_ACCEPTANCE_DATA_LIMIT {
+            return Err(RpcError::ChainBlockCountExceedingMaximum(request.chain_block_hashes.len(), MAX_BLOCKS_ACCEPTANCE_DATA_LIMIT));
+        }
+        let session = self.consensus_manager.consensus().session().await;
+        for hash in request.chain_block_hashes.iter().copied() {
+            if !session.async_is_chain_block(hash).await? {
+                return Err(RpcError::NotChainBlock(hash));
+            }
+        }
+        let blocks_acceptance_data = session.async_get_blocks_acceptance_data(request.chain_block_hashes.clone()).await?;
+        let mut acceptance_data = Vec::with_capacity(blocks_acceptance_data.len());
+        for (hash, block_acceptance_data) in request.chain_block_hashes.iter().copied().zip(blocks_acceptance_data.iter()) {
+            acceptance_data
+                .push(self.consensus_converter.get_chain_block_acceptance_data(&session, hash, block_acceptance_data).await?);
+        }
+        Ok(GetBlocksAcceptanceDataResponse::new(acceptance_data))
+    }
+
+    async fn get_transactions_by_addresses_call(
+        &self,
+        request: GetTransactionsByAddressesRequest,
+    ) -> RpcResult<GetTransactionsByAddressesResponse> {
+        if !self.config.addressindex {
+            return Err(RpcError::NoAddressIndex);
+        }
+        if request.addresses.len() > MAX_ADDRESS_TRANSACTIONS_ADDRESSES {
+            return Err(RpcError::AddressCountExceedingMaximum(request.addresses.len(), MAX_ADDRESS_TRANSACTIONS_ADDRESSES));
+        }
+        let limit = (request.limit as usize).min(MAX_ADDRESS_TRANSACTIONS_LIMIT);
+        let script_public_keys = request.addresses.iter().map(pay_to_address_script).collect::<Vec<_>>();
+        let transactions = self
+            .addressindex
+            .clone()
+            .unwrap()
+            .get_address_transactions(script_public_keys, request.start_daa_score, limit)
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?;
+
+        // The transactions spending from the addresses are returned in full, fetching each including block once
+        let mut spending_transaction_ids = HashMap::<RpcHash, HashSet<RpcTransactionId>>::new();
+        for transaction in transactions.values().flatten().filter(|transaction| transaction.spent_amount > 0) {
+            spending_transaction_ids.entry(transaction.including_block_hash).or_default().insert(transaction.transaction_id);
+        }
+        let session = self.consensus_manager.consensus().session().await;
+        let mut spending_transactions = HashMap::new();
+        for (block_hash, transaction_ids) in spending_transaction_ids {
+            // The body of the block may have been pruned, in which case the transactions are returned without their data
+            let Ok(block) = session.async_get_block(block_hash).await else {
+                continue;
+            };
+            spending_transactions.extend(
+                block
+                    .transactions
+                    .iter()
+                    .filter(|transaction| transaction_ids.contains(&transaction.id()))
+                    .map(|transaction| (transaction.id(), RpcTransaction::from(transaction))),
+            );
+        }
+
+        Ok(GetTransactionsByAddressesResponse::new(self.index_converter.get_transactions_by_addresses_entries(
+            &request.addresses,
+            &transactions,
+            &spending_transactions,
+        )))
+    }
+
     async fn ping_call(&self, _: PingRequest) -> RpcResult<PingResponse> {
         Ok(PingResponse {})
     }
 
-    async fn get_headers_call(&self, _request: GetHeadersRequest) -> RpcResult<GetHeadersResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_headers_call(&self, request: GetHeadersRequest) -> RpcResult<GetHeadersResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let limit = request.limit.min(MAX_HEADERS_LIMIT) as usize;
+        let mut headers = Vec::with_capacity(limit);
+        if request.is_ascending {
+            // Walk up the selected chain, which is the headers selected chain on light nodes
+            let chain = session.async_get_chain_from_block(request.start_hash, limit).await.map_err(|err| match err {
+                ConsensusError::NotChainBlock(hash) => RpcError::NotChainBlock(hash),
+                err => err.into(),
+            })?;
+            for hash in chain {
+                headers.push(session.async_get_header(hash).await?);
+            }
+        } else {
+            // Walk down the selected parents until reaching the pruning point
+            let start_header = session.async_get_header(request.start_hash).await?;
+            let pruning_point = session.async_pruning_point().await;
+            let mut current = request.start_hash;
+            headers.push(start_header);
+            while headers.len() < limit && current != pruning_point {
+                current = session.async_get_ghostdag_data(current).await?.selected_parent;
+                headers.push(session.async_get_header(current).await?);
+            }
+        }
+        headers.truncate(limit);
+        Ok(GetHeadersResponse::new(headers.into_iter().map(|header| (*header).clone()).collect()))
     }
 
     async fn get_block_dag_info_call(&self, _: GetBlockDagInfoRequest) -> RpcResult<GetBlockDagInfoResponse> {
         let session = self.consensus_manager.consensus().unguarded_session();
         let (consensus_stats, tips, pruning_point, sink) =
             join!(session.async_get_stats(), session.async_get_tips(), session.async_pruning_point(), session.async_get_sink());
+        if self.config.is_light {
+            // Light nodes never resolve virtual, so the DAG state is reported from the headers selected tip
+            let headers_selected_tip = session.async_get_headers_selected_tip().await;
+            let header = session.async_get_header(headers_selected_tip).await?;
+            return Ok(GetBlockDagInfoResponse::new(
+                self.config.net,
+                consensus_stats.block_counts.block_count,
+                consensus_stats.block_counts.header_count,
+                tips,
+                self.consensus_converter.get_difficulty_ratio(header.bits),
+                header.timestamp,
+                vec![headers_selected_tip],
+                pruning_point,
+                header.daa_score,
+                headers_selected_tip,
+            ));
+        }
         Ok(GetBlockDagInfoResponse::new(
             self.config.net,
             consensus_stats.block_counts.block_count,
@@ -711,7 +942,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
         let peer_address = request.peer_address.normalize(self.config.net.default_p2p_port());
         if let Some(connection_manager) = self.flow_context.connection_manager() {
-            connection_manager.add_connection_request(peer_address.into(), request.is_permanent).await;
+            connection_manager.add_connection_request(peer_address, request.is_permanent).await;
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -730,6 +961,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
         if let Some(connection_manager) = self.flow_context.connection_manager() {
             let ip = request.ip.into();
+            if connection_manager.is_whitelisted(ip) {
+                return Err(RpcError::IpIsWhitelisted(request.ip));
+            }
             if connection_manager.ip_has_permanent_connection(ip).await {
                 return Err(RpcError::IpHasPermanentConnection(request.ip));
             }
@@ -754,6 +988,73 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(UnbanResponse {})
     }
 
+    async fn get_banned_peers_call(&self, _: GetBannedPeersRequest) -> RpcResult<GetBannedPeersResponse> {
+        let bans = self.flow_context.address_manager.lock().get_all_bans();
+        let banned_peers = bans
+            .into_iter()
+            .map(|(ip, ban)| RpcBannedPeerInfo { ip, banned_at: ban.timestamp, expires_at: ban.expiry, reason: ban.reason })
+            .collect();
+        Ok(GetBannedPeersResponse::new(banned_peers))
+    }
+
+    async fn get_transaction_inclusion_proof_call(
+        &self,
+        request: GetTransactionInclusionProofRequest,
+    ) -> RpcResult<GetTransactionInclusionProofResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let acceptances = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_acceptances(vec![request.transaction_id])
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?;
+        let acceptance = acceptances.get(&request.transaction_id).ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+
+        let session = self.consensus_manager.consensus().session().await;
+
+        // Prove the inclusion of the transaction through the hash merkle root of the including block,
+        // hashing transactions according to the storage mass activation state of that block
+        let including_block = session.async_get_block(acceptance.including_block_hash).await?;
+        let include_mass_field = including_block.header.daa_score > self.config.storage_mass_activation_daa_score;
+        let hash_merkle_witness = create_hash_merkle_witness(
+            including_block.transactions.iter(),
+            acceptance.index_within_block as usize,
+            include_mass_field,
+        )
+        .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+
+        // Prove the acceptance of the transaction through the accepted id merkle root of the accepting chain block,
+        // which commits to the sorted ids of all transactions accepted by its mergeset
+        let acceptance_data = session.async_get_block_acceptance_data(acceptance.accepting_block_hash).await?;
+        let mut accepted_tx_ids = acceptance_data
+            .iter()
+            .flat_map(|merged_block| merged_block.accepted_transactions.iter().map(|entry| entry.transaction_id))
+            .collect::<Vec<_>>();
+        accepted_tx_ids.sort();
+        let accepted_id_merkle_witness = accepted_tx_ids
+            .binary_search(&request.transaction_id)
+            .ok()
+            .and_then(|index| create_merkle_witness(accepted_tx_ids.iter().copied(), index))
+            .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+        let accepting_block_header = session.async_get_header(acceptance.accepting_block_hash).await?;
+
+        Ok(GetTransactionInclusionProofResponse::new(
+            (*including_block.header).clone(),
+            hash_merkle_witness.into(),
+            (*accepting_block_header).clone(),
+            accepted_id_merkle_witness.into(),
+        ))
+    }
+
+    async fn get_reorg_history_call(&self, request: GetReorgHistoryRequest) -> RpcResult<GetReorgHistoryResponse> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let reorgs = session.get_reorg_history(request.min_depth).iter().map(RpcReorgInfo::from).collect();
+        Ok(GetReorgHistoryResponse::new(reorgs))
+    }
+
     async fn get_connected_peer_info_call(&self, _: GetConnectedPeerInfoRequest) -> RpcResult<GetConnectedPeerInfoResponse> {
         let peers = self.flow_context.hub().active_peers();
         let peer_info = self.protocol_converter.get_peers_info(&peers);
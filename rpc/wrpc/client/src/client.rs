@@ -67,6 +67,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::ReorgAlertNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -597,6 +598,14 @@ impl RpcApi for RustweaveRpcClient {
             GetCoinSupply,
             GetConnectedPeerInfo,
             GetDaaScoreTimestampEstimate,
+            GetTransactionAcceptance,
+            GetFeeEstimate,
+            GetBlocksAcceptanceData,
+            GetTransactionsByAddresses,
+            GetMempoolEntryAncestry,
+            GetBannedPeers,
+            GetTransactionInclusionProof,
+            GetReorgHistory,
             GetServerInfo,
             GetCurrentNetwork,
             GetHeaders,
@@ -617,6 +626,7 @@ impl RpcApi for RustweaveRpcClient {
             Shutdown,
             SubmitBlock,
             SubmitTransaction,
+            SubmitTransactionReplacement,
             Unban,
         ]
     );
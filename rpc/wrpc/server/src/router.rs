@@ -45,6 +45,14 @@ impl Router {
                 GetCoinSupply,
                 GetConnectedPeerInfo,
                 GetDaaScoreTimestampEstimate,
+                GetTransactionAcceptance,
+                GetFeeEstimate,
+                GetBlocksAcceptanceData,
+                GetTransactionsByAddresses,
+                GetMempoolEntryAncestry,
+                GetBannedPeers,
+                GetTransactionInclusionProof,
+                GetReorgHistory,
                 GetServerInfo,
                 GetCurrentNetwork,
                 GetHeaders,
@@ -66,6 +74,7 @@ impl Router {
                 Shutdown,
                 SubmitBlock,
                 SubmitTransaction,
+                SubmitTransactionReplacement,
                 Unban,
             ]
         );
@@ -126,6 +126,7 @@ impl Server {
                 true,
                 None,
                 Default::default(),
+                None,
             )
             .await
             .map_err(|e| WebSocketError::Other(e.to_string()))?;
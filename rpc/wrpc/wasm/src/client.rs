@@ -912,6 +912,10 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Rustweave BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a reorg alert notification event.
+    /// Reorg alert notification event is produced when a reorg of the virtual
+    /// selected chain reaches the alert depth configured on the Rustweave node.
+    ReorgAlert,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -943,6 +947,21 @@ build_wrpc_wasm_bindgen_interface!(
         /// Returned information: Peer ID, IP address and port, connection
         /// status, protocol version.
         GetConnectedPeerInfo,
+        /// Retrieves the peer IPs currently banned by the Rustweave node.
+        /// Returned information: Banned IP, ban and expiry times, ban reason.
+        GetBannedPeers,
+        /// Retrieves the merkle proofs that a transaction is included in a block
+        /// and accepted by a chain block. Requires the node to run with `--txindex`.
+        /// Returned information: Including and accepting block headers, merkle witnesses.
+        GetTransactionInclusionProof,
+        /// Retrieves the most recent reorgs of the virtual selected chain
+        /// which removed at least the requested number of chain blocks.
+        /// Returned information: Reorg times, depths, removed chain blocks, lost transaction acceptances.
+        GetReorgHistory,
+        /// Retrieves the feerates (in sompi per gram of transaction mass) expected
+        /// to get a transaction included in a block at the priority, normal and low levels.
+        /// Returned information: Feerate estimations along with their estimated times to inclusion.
+        GetFeeEstimate,
         /// Retrieves general information about the Rustweave node.
         /// Returned information: Version of the Rustweave node, protocol
         /// version, network identifier.
@@ -1003,6 +1022,18 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves multiple blocks from the Rustweave BlockDAG.
         /// Returned information: List of block information.
         GetBlocks,
+        /// Retrieves the transactions of the blocks merged by the specified
+        /// chain blocks which were accepted or rejected by them.
+        /// Returned information: List of chain block acceptance data.
+        GetBlocksAcceptanceData,
+        /// Retrieves the transactions accepted by the selected chain which spent
+        /// from or paid to the specified addresses (requires the address index).
+        /// Returned information: List of transactions by address.
+        GetTransactionsByAddresses,
+        /// Retrieves the in-mempool ancestors and descendants of a mempool transaction.
+        /// Returned information: Ancestor and descendant transaction ids along with
+        /// the aggregate fee, mass and feerate of the package they form.
+        GetMempoolEntryAncestry,
         /// Generates a new block template for mining.
         /// Returned information: Block template information.
         GetBlockTemplate,
@@ -1028,6 +1059,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves information about a subnetwork in the Rustweave BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves the chain blocks accepting the specified transactions
+        /// (requires the node to run with the transaction index).
+        /// Returned information: List of transaction acceptances.
+        GetTransactionAcceptance,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
@@ -1044,6 +1079,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// Submits a transaction to the Rustweave network.
         /// Returned information: None.
         SubmitTransaction,
+        /// Submits a transaction to the Rustweave network, allowing it to replace
+        /// by fee the mempool transactions it double spends.
+        /// Returned information: Ids of the replaced transactions.
+        SubmitTransactionReplacement,
         /// Unbans a previously banned peer, allowing it to connect
         /// to the Rustweave node again.
         /// Returned information: None.
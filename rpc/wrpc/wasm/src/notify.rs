@@ -21,6 +21,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    ReorgAlert = "reorg-alert",
 }
 
 /**
@@ -36,7 +37,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IReorgAlert;
 
 /**
  * RPC notification event data map.
@@ -55,6 +57,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "reorg-alert" : IReorgAlert,
 }
 
 /**
@@ -80,6 +83,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeReorgAlert},
  * 
  * @category Node RPC
  */
@@ -237,3 +241,18 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IReorgAlert,
+    r#"
+    /**
+     * Reorg alert notification event is produced when a reorg of the virtual
+     * selected chain reaches the alert depth configured on the Rustweave node.
+     * 
+     * @category Node RPC
+     */
+    export interface IReorgAlert {
+        [key: string]: any;
+    }
+    "#,
+}
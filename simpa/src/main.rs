@@ -22,7 +22,7 @@ use kaspa_consensus_core::{
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_core::{info, task::service::AsyncService, task::tick::TickService, time::unix_now, trace, warn};
 use kaspa_database::prelude::ConnBuilder;
-use kaspa_database::{create_temp_db, load_existing_db};
+use kaspa_database::{create_memory_db, create_temp_db, load_existing_db};
 use kaspa_hashes::Hash;
 use kaspa_perf_monitor::{builder::Builder, counters::CountersSnapshot};
 use kaspa_utils::fd_budget;
@@ -117,6 +117,10 @@ struct Args {
     rocksdb_files_limit: Option<i32>,
     #[arg(long)]
     rocksdb_mem_budget: Option<usize>,
+
+    /// Keep the simulation databases in memory instead of temporary RocksDB directories (ignored for the output DB)
+    #[arg(long, default_value_t = false)]
+    in_memory_db: bool,
 }
 
 #[cfg(feature = "heap")]
@@ -234,6 +238,7 @@ fn main_impl(mut args: Args) {
                 args.rocksdb_stats_period_sec,
                 args.rocksdb_files_limit,
                 args.rocksdb_mem_budget,
+                args.in_memory_db,
             )
             .run(until);
         consensus.shutdown(handles);
@@ -246,7 +251,11 @@ fn main_impl(mut args: Args) {
     }
 
     // Benchmark the DAG validation time
-    let (_lifetime2, db2) = create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd));
+    let (_lifetime2, db2) = if args.in_memory_db {
+        create_memory_db!()
+    } else {
+        create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd))
+    };
     let (dummy_notification_sender, _) = unbounded();
     let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
     let consensus2 = Arc::new(Consensus::new(
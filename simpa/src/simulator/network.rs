@@ -11,7 +11,7 @@ use kaspa_consensus::consensus::Consensus;
 use kaspa_consensus_core::block::Block;
 use kaspa_database::prelude::ConnBuilder;
 use kaspa_database::utils::DbLifetime;
-use kaspa_database::{create_permanent_db, create_temp_db};
+use kaspa_database::{create_memory_db, create_permanent_db, create_temp_db};
 use kaspa_utils::fd_budget;
 use kaspa_utils::sim::Simulation;
 
@@ -50,6 +50,7 @@ impl RustweaveNetworkSimulator {
         rocksdb_stats_period_sec: Option<u32>,
         rocksdb_files_limit: Option<i32>,
         rocksdb_mem_budget: Option<usize>,
+        in_memory_db: bool,
     ) -> &mut Self {
         let secp = secp256k1::Secp256k1::new();
         let mut rng = rand::thread_rng();
@@ -68,6 +69,8 @@ impl RustweaveNetworkSimulator {
                 (true, Some(dir), true, None) => create_permanent_db!(dir, builder.enable_stats()),
                 (true, Some(dir), false, _) => create_permanent_db!(dir, builder),
 
+                _ if in_memory_db => create_memory_db!(),
+
                 (_, _, true, Some(rocksdb_stats_period_sec)) => {
                     create_temp_db!(builder.enable_stats().with_stats_period(rocksdb_stats_period_sec))
                 }
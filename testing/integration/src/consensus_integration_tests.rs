@@ -8,11 +8,13 @@ use kaspa_consensus::config::genesis::GENESIS;
 use kaspa_consensus::config::{Config, ConfigBuilder};
 use kaspa_consensus::consensus::factory::Factory as ConsensusFactory;
 use kaspa_consensus::consensus::test_consensus::{TestConsensus, TestConsensusFactory};
+use kaspa_consensus::model::stores::acceptance_data::AcceptanceDataStoreReader;
 use kaspa_consensus::model::stores::block_transactions::{
     BlockTransactionsStore, BlockTransactionsStoreReader, DbBlockTransactionsStore,
 };
 use kaspa_consensus::model::stores::ghostdag::{GhostdagStoreReader, KType as GhostdagKType};
 use kaspa_consensus::model::stores::headers::HeaderStoreReader;
+use kaspa_consensus::model::stores::pruning::PruningStoreReader;
 use kaspa_consensus::model::stores::reachability::DbReachabilityStore;
 use kaspa_consensus::model::stores::relations::DbRelationsStore;
 use kaspa_consensus::model::stores::selected_chain::SelectedChainStoreReader;
@@ -27,6 +29,7 @@ use kaspa_consensus_core::blockhash::new_unique;
 use kaspa_consensus_core::blockstatus::BlockStatus;
 use kaspa_consensus_core::constants::{BLOCK_VERSION, STORAGE_MASS_PARAMETER};
 use kaspa_consensus_core::errors::block::{BlockProcessResult, RuleError};
+use kaspa_consensus_core::errors::consensus::ConsensusError;
 use kaspa_consensus_core::header::Header;
 use kaspa_consensus_core::network::{NetworkId, NetworkType::Mainnet};
 use kaspa_consensus_core::subnets::SubnetworkId;
@@ -49,7 +52,7 @@ use kaspa_core::signals::Shutdown;
 use kaspa_core::task::runtime::AsyncRuntime;
 use kaspa_core::{assert_match, info};
 use kaspa_database::create_temp_db;
-use kaspa_database::prelude::{CachePolicy, ConnBuilder};
+use kaspa_database::prelude::{CachePolicy, ConnBuilder, StoreResultExtensions};
 use kaspa_index_processor::service::IndexService;
 use kaspa_math::Uint256;
 use kaspa_muhash::MuHash;
@@ -68,6 +71,7 @@ use std::{
     future::Future,
     io::{BufRead, BufReader},
     str::{from_utf8, FromStr},
+    time::Duration,
 };
 
 use crate::common;
@@ -831,6 +835,8 @@ impl RustweavedGoParams {
             max_block_mass: self.MaxBlockMass,
             storage_mass_parameter: STORAGE_MASS_PARAMETER,
             storage_mass_activation_daa_score: u64::MAX,
+            introspection_activation_daa_score: u64::MAX,
+            string_opcodes_activation_daa_score: u64::MAX,
             deflationary_phase_daa_score: self.DeflationaryPhaseDaaScore,
             pre_deflationary_phase_base_subsidy: self.PreDeflationaryPhaseBaseSubsidy,
             coinbase_maturity: MAINNET_PARAMS.coinbase_maturity,
@@ -954,6 +960,8 @@ async fn json_test(file_path: &str, concurrency: bool) {
         &notify_service.notifier(),
         subscription_context.clone(),
         Some(UtxoIndexProxy::new(utxoindex.clone())),
+        None,
+        None,
     ));
 
     let async_runtime = Arc::new(AsyncRuntime::new(2));
@@ -1755,3 +1763,163 @@ async fn staging_consensus_test() {
     core.shutdown();
     core.join(joins);
 }
+
+/// Returns a config builder with short finality, pruning and window depths, so that the pruning point
+/// moves after a few hundred chain blocks and data in its past is actually pruned
+fn short_pruning_depth_config_builder() -> ConfigBuilder {
+    ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().edit_consensus_params(|p| {
+        p.ghostdag_k = 2;
+        p.mergeset_size_limit = 10;
+        p.merge_depth = 10;
+        p.finality_depth = 20;
+        p.pruning_depth = 2 * p.finality_depth + 4 * p.mergeset_size_limit * p.ghostdag_k as u64 + 2 * p.ghostdag_k as u64 + 2;
+        p.legacy_difficulty_window_size = 32;
+        p.min_difficulty_window_len = 32;
+        p.pruning_proof_m = 10;
+    })
+}
+
+/// Waits for the pruning processor to reach a pruning point and history root satisfying `is_pruned`,
+/// and returns them
+async fn wait_for_pruned_history(consensus: &TestConsensus, is_pruned: impl Fn(Hash, Hash) -> bool) -> (Hash, Hash) {
+    for _ in 0..600 {
+        let pruning_point_read = consensus.pruning_point_store.read();
+        let (pruning_point, history_root) = (pruning_point_read.pruning_point().unwrap(), pruning_point_read.history_root().unwrap());
+        drop(pruning_point_read);
+        if is_pruned(pruning_point, history_root) {
+            return (pruning_point, history_root);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("pruning did not complete in time");
+}
+
+#[tokio::test]
+async fn light_node_pruning_test() {
+    init_allocator_with_default_settings();
+    let config = short_pruning_depth_config_builder().apply_args(|config| config.is_light = true).build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // Light nodes process headers only and never resolve virtual
+    let mut tip = config.genesis.hash;
+    for i in 1..=3 * config.pruning_depth {
+        let hash: Hash = i.into();
+        let header = consensus.build_header_with_parents(hash, vec![tip]);
+        consensus.validate_and_insert_block(Block::from_header(header)).block_task.await.unwrap();
+        tip = hash;
+    }
+    assert_eq!(consensus.get_sink(), config.genesis.hash);
+
+    // The pruning point is advanced following the headers selected tip, and headers in its past are pruned
+    let genesis = config.genesis.hash;
+    let (pruning_point, _) = wait_for_pruned_history(&consensus, |pp, root| pp != genesis && root == pp).await;
+    assert!(consensus.headers_store.get_header(pruning_point).is_ok());
+    assert!(consensus.headers_store.get_header(config.genesis.hash).is_ok());
+    assert!(consensus.headers_store.get_header(1.into()).unwrap_option().is_none());
+    assert!(consensus.headers_store.get_header(tip).is_ok());
+
+    consensus.shutdown(wait_handles);
+}
+
+/// Adds a chain of `count` UTXO-valid blocks on top of `tip`, hashed by consecutive indices starting at `first`,
+/// and returns the new tip
+async fn add_chain_blocks(consensus: &TestConsensus, mut tip: Hash, first: u64, count: u64) -> Hash {
+    for i in first..first + count {
+        let hash: Hash = i.into();
+        consensus.add_utxo_valid_block_with_parents(hash, vec![tip], vec![]).await.unwrap();
+        tip = hash;
+    }
+    tip
+}
+
+#[tokio::test]
+async fn retention_period_pruning_test() {
+    init_allocator_with_default_settings();
+    let retention_depth = 40;
+    let config = short_pruning_depth_config_builder().apply_args(|config| config.retention_depth = Some(retention_depth)).build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    let tip = add_chain_blocks(&consensus, config.genesis.hash, 1, 3 * config.pruning_depth).await;
+
+    // On a chain every block adds a single DAA score, so the retention root is the chain block exactly
+    // `retention_depth` DAA scores below the pruning point
+    let genesis = config.genesis.hash;
+    let daa_score = |hash: Hash| consensus.headers_store.get_daa_score(hash).unwrap();
+    let (pruning_point, history_root) =
+        wait_for_pruned_history(&consensus, |pp, root| root != genesis && daa_score(pp) - daa_score(root) == retention_depth).await;
+    assert_ne!(history_root, pruning_point);
+    assert!(consensus.selected_chain_store.read().get_by_hash(history_root).is_ok());
+
+    // Full block data from the history root onwards is retained, while older block data is pruned
+    assert!(consensus.block_transactions_store.get(history_root).is_ok());
+    assert!(consensus.acceptance_data_store.get(history_root).is_ok());
+    assert!(consensus.block_transactions_store.get(pruning_point).is_ok());
+    assert!(consensus.acceptance_data_store.get(pruning_point).is_ok());
+    assert!(consensus.block_transactions_store.get(1.into()).unwrap_option().is_none());
+    assert!(consensus.acceptance_data_store.get(1.into()).unwrap_option().is_none());
+    assert!(consensus.get_block(tip).is_ok());
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn retention_root_never_below_history_root_test() {
+    init_allocator_with_default_settings();
+    let (_db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+    let genesis = MAINNET_PARAMS.genesis.hash;
+
+    // Prune with a short retention period first
+    let config = short_pruning_depth_config_builder().apply_args(|config| config.retention_depth = Some(10)).build();
+    let (dummy_notification_sender, _) = unbounded();
+    let consensus = TestConsensus::with_db(db.clone(), &config, dummy_notification_sender);
+    let wait_handles = consensus.init();
+    let tip = add_chain_blocks(&consensus, genesis, 1, 3 * config.pruning_depth).await;
+    let (_, history_root) = wait_for_pruned_history(&consensus, |pp, root| root != genesis && root != pp).await;
+    consensus.shutdown(wait_handles);
+    drop(consensus);
+
+    // Restart with a retention period reaching far below the history root. Data in its past was already pruned,
+    // so the history root must be kept as is rather than moved back, also when the pruning point advances further
+    let config = short_pruning_depth_config_builder().apply_args(|config| config.retention_depth = Some(1_000_000)).build();
+    let (dummy_notification_sender, _) = unbounded();
+    let consensus = TestConsensus::with_db(db, &config, dummy_notification_sender);
+    let wait_handles = consensus.init();
+    let pruning_point_before = consensus.pruning_point_store.read().pruning_point().unwrap();
+    add_chain_blocks(&consensus, tip, 3 * config.pruning_depth + 1, config.pruning_depth).await;
+    let (_, root) = wait_for_pruned_history(&consensus, |pp, _| pp != pruning_point_before).await;
+    assert_eq!(root, history_root);
+    assert!(consensus.block_transactions_store.get(history_root).is_ok());
+    assert!(consensus.acceptance_data_store.get(history_root).is_ok());
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn get_chain_from_block_test() {
+    init_allocator_with_default_settings();
+    let config = short_pruning_depth_config_builder().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    let genesis = config.genesis.hash;
+    let tip = add_chain_blocks(&consensus, genesis, 1, 100).await;
+    let side_block: Hash = 1000.into();
+    consensus.add_utxo_valid_block_with_parents(side_block, vec![genesis], vec![]).await.unwrap();
+    assert_eq!(consensus.get_sink(), tip);
+
+    // Starting far below the tip, only `max_blocks` chain blocks are walked
+    let expected = std::iter::once(genesis).chain((1..10).map(Hash::from)).collect_vec();
+    assert_eq!(consensus.get_chain_from_block(genesis, 10).unwrap(), expected);
+    assert_eq!(consensus.get_chain_from_block(50.into(), 3).unwrap(), (50..53).map(Hash::from).collect_vec());
+
+    // Near the tip, the walk stops at the tip
+    assert_eq!(consensus.get_chain_from_block(98.into(), 10).unwrap(), (98..=100).map(Hash::from).collect_vec());
+    assert!(consensus.get_chain_from_block(tip, 0).unwrap().is_empty());
+
+    assert!(matches!(consensus.get_chain_from_block(side_block, 10), Err(ConsensusError::NotChainBlock(hash)) if hash == side_block));
+    assert!(matches!(consensus.get_chain_from_block(2000.into(), 10), Err(ConsensusError::HeaderNotFound(_))));
+
+    consensus.shutdown(wait_handles);
+}
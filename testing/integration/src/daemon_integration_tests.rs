@@ -322,6 +322,55 @@ async fn daemon_utxos_propagation_test() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_whitelist_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let mut kaspad1 = Daemon::new_random_with_args(args.clone(), total_fd_limit);
+    let mut kaspad2 = Daemon::new_random_with_args(Args { whitelist: vec!["127.0.0.0/8".to_owned()], ..args }, total_fd_limit);
+    let rpc_client1 = kaspad1.start().await;
+    let rpc_client2 = kaspad2.start().await;
+
+    // Connect kaspad1 to kaspad2, making kaspad1 an inbound whitelisted peer of kaspad2
+    rpc_client1.add_peer(format!("127.0.0.1:{}", kaspad2.p2p_port).try_into().unwrap(), false).await.unwrap();
+    let check_client = rpc_client2.clone();
+    wait_for(
+        50,
+        20,
+        move || {
+            async fn peer_connected(client: GrpcClient) -> bool {
+                client.get_connected_peer_info().await.unwrap().peer_info.len() == 1
+            }
+            Box::pin(peer_connected(check_client.clone()))
+        },
+        "the nodes did not connect to each other",
+    )
+    .await;
+
+    let peer_info = rpc_client2.get_connected_peer_info().await.unwrap().peer_info;
+    assert!(peer_info[0].is_whitelisted);
+    assert!(!rpc_client1.get_connected_peer_info().await.unwrap().peer_info[0].is_whitelisted);
+
+    // Whitelisted peers cannot be banned
+    assert!(rpc_client2.ban(peer_info[0].address.ip).await.is_err());
+    assert!(rpc_client2.get_banned_peers().await.unwrap().banned_peers.is_empty());
+    assert_eq!(rpc_client2.get_connected_peer_info().await.unwrap().peer_info.len(), 1);
+
+    rpc_client1.disconnect().await.unwrap();
+    rpc_client2.disconnect().await.unwrap();
+    kaspad1.shutdown();
+    kaspad2.shutdown();
+}
+
 // The following test runtime parameters are required for a graceful shutdown of the gRPC server
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_cleaning_test() {
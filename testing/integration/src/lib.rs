@@ -23,5 +23,8 @@ pub mod mempool_benchmarks;
 #[cfg(feature = "devnet-prealloc")]
 pub mod subscribe_benchmarks;
 
+#[cfg(test)]
+pub mod p2p_adversary_tests;
+
 #[cfg(test)]
 pub mod rpc_tests;
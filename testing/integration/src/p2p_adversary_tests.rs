@@ -0,0 +1,233 @@
+use crate::common::{
+    daemon::Daemon,
+    utils::{fetch_spendable_utxos, generate_tx, wait_for},
+};
+use kaspa_addresses::Address;
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_consensus::params::{SIMNET_GENESIS, SIMNET_PARAMS};
+use kaspa_consensus_core::{block::Block, network::NetworkId};
+use kaspa_grpc_client::GrpcClient;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    adversary::{scripts, AdversarialPeer, Script, ScriptOutcome},
+    Adaptor, Hub,
+};
+use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspad_lib::args::Args;
+use rand::thread_rng;
+use std::time::Duration;
+
+/// The first protocol version supporting Dandelion stem transactions
+const STEM_TRANSACTIONS_PROTOCOL_VERSION: u32 = 7;
+
+fn simnet_args() -> Args {
+    Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        ..Default::default()
+    }
+}
+
+/// Mines a chain of `count` blocks on top of the current sink and returns it in mining order
+async fn mine_chain(client: &GrpcClient, network: NetworkId, count: usize) -> Vec<Block> {
+    mine_chain_to(client, Address::new(network.into(), kaspa_addresses::Version::PubKey, &[0; 32]), count).await
+}
+
+/// Mines a chain of `count` blocks paying to `pay_address` on top of the current sink and returns it in mining order
+async fn mine_chain_to(client: &GrpcClient, pay_address: Address, count: usize) -> Vec<Block> {
+    let mut chain = Vec::with_capacity(count);
+    for _ in 0..count {
+        let template = client.get_block_template(pay_address.clone(), vec![]).await.unwrap();
+        let hash = template.block.header.hash;
+        client.submit_block(template.block, false).await.unwrap();
+        wait_for_sink(client, hash, "the mined block did not become the sink").await;
+        chain.push((&client.get_block(hash, true).await.unwrap()).try_into().unwrap());
+    }
+    chain
+}
+
+async fn wait_for_sink(client: &GrpcClient, sink: Hash, panic_message: &'static str) {
+    let client = client.clone();
+    wait_for(
+        100,
+        100,
+        move || {
+            async fn sink_reached(client: GrpcClient, sink: Hash) -> bool {
+                client.get_block_dag_info().await.unwrap().sink == sink
+            }
+            Box::pin(sink_reached(client.clone(), sink))
+        },
+        panic_message,
+    )
+    .await;
+}
+
+/// Connects an adversarial peer replaying `script` to the target daemon and returns the outcome of the script
+async fn replay(target: &Daemon, script: Script) -> ScriptOutcome {
+    let (peer, outcome_receiver) = AdversarialPeer::new(target.network.to_prefixed(), script);
+    let adaptor = Adaptor::client_only(Hub::new(), peer, Default::default(), None, None);
+    adaptor.connect_peer(format!("127.0.0.1:{}", target.p2p_port)).await.unwrap();
+    let outcome = tokio::time::timeout(Duration::from_secs(120), outcome_receiver)
+        .await
+        .expect("the adversarial script did not complete in time")
+        .expect("the adversarial peer dropped the outcome sender");
+    adaptor.terminate_all_peers().await;
+    outcome
+}
+
+/// Asserts that the target is still healthy by having it sync `source` up to `sink` through a regular IBD
+async fn assert_syncs_from(target_client: &GrpcClient, source: &Daemon, sink: Hash) {
+    target_client.add_peer(format!("127.0.0.1:{}", source.p2p_port).try_into().unwrap(), true).await.unwrap();
+    wait_for_sink(target_client, sink, "the target did not sync from the honest source").await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn malformed_relay_block_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let mut source = Daemon::new_random_with_args(simnet_args(), 10);
+    let mut target = Daemon::new_random_with_args(simnet_args(), 10);
+    let source_client = source.start().await;
+    let target_client = target.start().await;
+
+    let chain = mine_chain(&source_client, source.network, 1).await;
+    let outcome = replay(&target, scripts::malformed_relay_block(&chain[0])).await;
+    assert!(outcome.is_disconnected(), "expected the target to disconnect but got {:?}", outcome);
+
+    // Relaying an invalid block is expected to result in an immediate ban
+    let banned_peers = target_client.get_banned_peers().await.unwrap().banned_peers;
+    assert!(banned_peers.iter().any(|peer| peer.ip.is_loopback()), "expected the adversarial peer to be banned");
+    assert_eq!(target_client.get_block_dag_info().await.unwrap().sink, SIMNET_GENESIS.hash);
+
+    source.shutdown();
+    target.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn out_of_order_ibd_blocks_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let mut source = Daemon::new_random_with_args(simnet_args(), 10);
+    let mut target = Daemon::new_random_with_args(simnet_args(), 10);
+    let source_client = source.start().await;
+    let target_client = target.start().await;
+
+    let chain = mine_chain(&source_client, source.network, 10).await;
+    let sink = chain.last().unwrap().hash();
+    let outcome = replay(&target, scripts::out_of_order_ibd_blocks(chain, SIMNET_GENESIS.hash)).await;
+    assert!(outcome.is_disconnected(), "expected the target to disconnect but got {:?}", outcome);
+    assert!(target_client.get_banned_peers().await.unwrap().banned_peers.is_empty());
+
+    assert_syncs_from(&target_client, &source, sink).await;
+
+    source.shutdown();
+    target.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn stalling_ibd_negotiation_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let mut source = Daemon::new_random_with_args(simnet_args(), 10);
+    let mut target = Daemon::new_random_with_args(simnet_args(), 10);
+    let source_client = source.start().await;
+    let target_client = target.start().await;
+
+    let chain = mine_chain(&source_client, source.network, 10).await;
+    let sink = chain.last().unwrap().hash();
+    let outcome = replay(&target, scripts::stalling_ibd_negotiation(chain, SIMNET_GENESIS.hash)).await;
+    assert!(outcome.is_disconnected(), "expected the target to disconnect but got {:?}", outcome);
+
+    // A stalled IBD must not prevent the target from syncing with another peer
+    assert_syncs_from(&target_client, &source, sink).await;
+
+    source.shutdown();
+    target.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn utxo_chunk_flood_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let mut source = Daemon::new_random_with_args(simnet_args(), 10);
+    let mut target = Daemon::new_random_with_args(simnet_args(), 10);
+    let source_client = source.start().await;
+    let target_client = target.start().await;
+
+    // Send well above the capacity of the IBD flow incoming route
+    let outcome = replay(&target, scripts::utxo_chunk_flood(1024, 100)).await;
+    assert!(outcome.is_disconnected(), "expected the target to disconnect but got {:?}", outcome);
+
+    let chain = mine_chain(&source_client, source.network, 5).await;
+    assert_syncs_from(&target_client, &source, chain.last().unwrap().hash()).await;
+
+    source.shutdown();
+    target.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn stem_transaction_embargo_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let mut target = Daemon::new_random_with_args(Args { dandelion: true, utxoindex: true, ..simnet_args() }, 10);
+    let target_client = target.start().await;
+
+    // Mine until a coinbase output of the miner matures and build a transaction spending it
+    let (miner_sk, miner_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let miner_address =
+        Address::new(target.network.into(), kaspa_addresses::Version::PubKey, &miner_pk.x_only_public_key().0.serialize());
+    let miner_schnorr_key = secp256k1::Keypair::from_secret_key(secp256k1::SECP256K1, &miner_sk);
+    mine_chain_to(&target_client, miner_address.clone(), SIMNET_PARAMS.coinbase_maturity as usize + 10).await;
+    let utxos = fetch_spendable_utxos(&target_client, miner_address.clone(), SIMNET_PARAMS.coinbase_maturity).await;
+    let transaction = generate_tx(miner_schnorr_key, &utxos[0..1], utxos[0].1.amount / 2, 1, &miner_address);
+    let transaction_id = transaction.id();
+
+    // Have the target connect to the adversary, which makes the latter its only candidate stem relay
+    let adversary_port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let adversary_address = format!("127.0.0.1:{}", adversary_port);
+    let (peer, outcome_receiver) = AdversarialPeer::with_protocol_version(
+        target.network.to_prefixed(),
+        STEM_TRANSACTIONS_PROTOCOL_VERSION,
+        scripts::stem_transaction_embargo(transaction.clone(), Duration::from_secs(3)),
+    );
+    let adaptor =
+        Adaptor::bidirectional(adversary_address.parse().unwrap(), Hub::new(), peer, Default::default(), None, None).unwrap();
+    target_client.add_peer(adversary_address.try_into().unwrap(), true).await.unwrap();
+    let check_client = target_client.clone();
+    wait_for(
+        50,
+        20,
+        move || {
+            async fn peer_connected(client: GrpcClient) -> bool {
+                client.get_connected_peer_info().await.unwrap().peer_info.len() == 1
+            }
+            Box::pin(peer_connected(check_client.clone()))
+        },
+        "the target did not connect to the adversary",
+    )
+    .await;
+
+    // The submitted transaction is stemmed to the adversary and, while embargoed, is not revealed through RPC
+    target_client.submit_transaction((&transaction).into(), false).await.unwrap();
+    assert!(target_client.get_mempool_entry(transaction_id, false, false).await.is_err());
+    assert!(target_client.get_mempool_entries(false, false).await.unwrap().is_empty());
+
+    let outcome = tokio::time::timeout(Duration::from_secs(120), outcome_receiver)
+        .await
+        .expect("the adversarial script did not complete in time")
+        .expect("the adversarial peer dropped the outcome sender");
+    assert_eq!(outcome, ScriptOutcome::Completed);
+
+    // Relaying back an identical copy lifted the embargo
+    assert!(target_client.get_mempool_entry(transaction_id, false, false).await.is_ok());
+
+    adaptor.terminate_all_peers().await;
+    target.shutdown();
+}
@@ -11,7 +11,7 @@ use kaspa_hashes::Hash;
 use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
+        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, ReorgAlertScope, Scope,
         SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
@@ -49,6 +49,8 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        txindex: true,
+        addressindex: true,
         unsafe_rpc: true,
         ..Default::default()
     };
@@ -279,6 +281,8 @@ async fn sanity_test() {
 
                     let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
                     assert!(response.banned_addresses.contains(&ip));
+                    let response = rpc_client.get_banned_peers().await.unwrap();
+                    assert!(response.banned_peers.iter().any(|peer| peer.ip == ip && peer.expires_at > peer.banned_at));
 
                     let _ = rpc_client.unban_call(UnbanRequest { ip }).await.unwrap();
                     let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
@@ -290,6 +294,10 @@ async fn sanity_test() {
                 tst!(op, "see Ban")
             }
 
+            RustweavedPayloadOps::GetBannedPeers => {
+                tst!(op, "see Ban")
+            }
+
             RustweavedPayloadOps::SubmitTransaction => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -301,6 +309,17 @@ async fn sanity_test() {
                 })
             }
 
+            RustweavedPayloadOps::SubmitTransactionReplacement => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Build an erroneous transaction...
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let result = rpc_client.submit_transaction_replacement((&transaction).into()).await;
+                    // ...that gets rejected by the consensus
+                    assert!(result.is_err());
+                })
+            }
+
             RustweavedPayloadOps::GetSubnetwork => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -345,12 +364,12 @@ async fn sanity_test() {
             RustweavedPayloadOps::GetHeaders => {
                 let rpc_client = client.clone();
                 tst!(op, {
-                    let response_result = rpc_client
+                    let response = rpc_client
                         .get_headers_call(GetHeadersRequest { start_hash: SIMNET_GENESIS.hash, limit: 1, is_ascending: true })
-                        .await;
-
-                    // Err because it's currently unimplemented
-                    assert!(response_result.is_err());
+                        .await
+                        .unwrap();
+                    assert_eq!(response.headers.len(), 1);
+                    assert_eq!(response.headers[0].hash, SIMNET_GENESIS.hash);
                 })
             }
 
@@ -546,6 +565,84 @@ async fn sanity_test() {
                 })
             }
 
+            RustweavedPayloadOps::GetTransactionAcceptance => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An unknown transaction is omitted from the response
+                    let acceptances = rpc_client.get_transaction_acceptance(vec![Hash::from_u64_word(127)]).await.unwrap();
+                    assert!(acceptances.is_empty());
+                })
+            }
+
+            RustweavedPayloadOps::GetTransactionInclusionProof => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No proof can be built for a transaction which is not accepted by the selected chain
+                    let result = rpc_client.get_transaction_inclusion_proof(Hash::from_u64_word(127)).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            RustweavedPayloadOps::GetReorgHistory => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The selected chain of a freshly started node did not go through any reorg
+                    let response = rpc_client.get_reorg_history(0).await.unwrap();
+                    assert!(response.reorgs.is_empty());
+                })
+            }
+
+            RustweavedPayloadOps::GetFeeEstimate => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let estimate = rpc_client.get_fee_estimate().await.unwrap();
+                    assert!(estimate.low_bucket.feerate <= estimate.normal_bucket.feerate);
+                    assert!(estimate.normal_bucket.feerate <= estimate.priority_bucket.feerate);
+                })
+            }
+
+            RustweavedPayloadOps::GetBlocksAcceptanceData => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let sink = rpc_client.get_sink().await.unwrap().sink;
+                    let acceptance_data = rpc_client.get_blocks_acceptance_data(vec![sink]).await.unwrap();
+                    assert_eq!(acceptance_data.len(), 1);
+                    assert_eq!(acceptance_data[0].accepting_block_hash, sink);
+
+                    // A block which is not on the selected chain is rejected
+                    let result = rpc_client.get_blocks_acceptance_data(vec![Hash::from_u64_word(127)]).await;
+                    assert!(result.is_err());
+
+                    // Requests exceeding the maximum number of chain blocks are rejected
+                    let result = rpc_client.get_blocks_acceptance_data(vec![sink; 101]).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            RustweavedPayloadOps::GetTransactionsByAddresses => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32])];
+                    let entries = rpc_client.get_transactions_by_addresses(addresses.clone(), 0, 100).await.unwrap();
+                    assert_eq!(entries.len(), 1);
+                    assert_eq!(entries[0].address, addresses[0]);
+                    assert!(entries[0].transactions.is_empty());
+
+                    // Requests exceeding the maximum number of addresses are rejected
+                    let result = rpc_client.get_transactions_by_addresses(vec![addresses[0].clone(); 101], 0, 100).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            RustweavedPayloadOps::GetMempoolEntryAncestry => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // A transaction missing from the mempool is rejected
+                    let result = rpc_client.get_mempool_entry_ancestry(Hash::from_u64_word(127)).await;
+                    assert!(result.is_err());
+                })
+            }
+
             RustweavedPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -607,6 +704,13 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            RustweavedPayloadOps::NotifyReorgAlert => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, ReorgAlertScope {}.into()).await.unwrap();
+                })
+            }
             RustweavedPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -9,6 +9,7 @@ pub mod mem_size;
 pub mod networking;
 pub mod option;
 pub mod refs;
+pub mod snapshot_file;
 
 pub mod as_slice;
 
@@ -1,13 +1,15 @@
 // #![allow(dead_code)]
 use borsh::{BorshDeserialize, BorshSerialize};
 use ipnet::IpNet;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Sha3_256};
 use std::{
     fmt::Display,
     net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     ops::Deref,
     str::FromStr,
 };
+use thiserror::Error;
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 
@@ -28,6 +30,11 @@ const TS_IP_ADDRESS: &'static str = r#"
          * Optional port number.
          */
         port?: number;
+        /**
+         * Optional Tor v3 onion service address (e.g. `<56 characters>.onion`),
+         * in which case `ip` holds its virtual IPv6 address.
+         */
+        onion?: string;
     }
 "#;
 
@@ -128,6 +135,14 @@ impl IpAddress {
     pub fn prefix_bucket(&self) -> PrefixBucket {
         PrefixBucket::from(self)
     }
+
+    /// Indicates whether this is the virtual IP of an onion address (see [`OnionAddress::virtual_ip`])
+    pub fn is_onion(&self) -> bool {
+        match self.0 {
+            IpAddr::V6(ip) => ip.octets().starts_with(&ONION_VIRTUAL_PREFIX),
+            IpAddr::V4(_) => false,
+        }
+    }
 }
 
 impl From<IpAddr> for IpAddress {
@@ -218,16 +233,134 @@ impl BorshDeserialize for IpAddress {
     }
 }
 
+/// The version byte of Tor v3 onion addresses
+const ONION_V3_VERSION: u8 = 3;
+
+/// The IPv6 prefix (the OnionCat range) onion addresses are mapped into, so that they can be
+/// keyed, bucketed and banned like any other IP address
+const ONION_VIRTUAL_PREFIX: [u8; 6] = [0xfd, 0x87, 0xd8, 0x7e, 0xeb, 0x43];
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid Tor v3 onion address")]
+pub struct OnionAddressError;
+
+/// A Tor v3 onion service address, identified by the ed25519 public key of the service
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct OnionAddress([u8; 32]);
+
+impl OnionAddress {
+    pub fn new(public_key: [u8; 32]) -> Self {
+        Self(public_key)
+    }
+
+    pub fn public_key(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns the IPv6 address this onion address is mapped to, made of [`ONION_VIRTUAL_PREFIX`]
+    /// followed by the first 10 bytes of the public key
+    pub fn virtual_ip(&self) -> IpAddress {
+        let mut octets = [0u8; 16];
+        octets[..ONION_VIRTUAL_PREFIX.len()].copy_from_slice(&ONION_VIRTUAL_PREFIX);
+        octets[ONION_VIRTUAL_PREFIX.len()..].copy_from_slice(&self.0[..16 - ONION_VIRTUAL_PREFIX.len()]);
+        Ipv6Addr::from(octets).into()
+    }
+
+    fn checksum(&self) -> [u8; 2] {
+        let digest = Sha3_256::new().chain_update(b".onion checksum").chain_update(self.0).chain_update([ONION_V3_VERSION]).finalize();
+        [digest[0], digest[1]]
+    }
+}
+
+impl FromStr for OnionAddress {
+    type Err = OnionAddressError;
+
+    /// Parses the `<base32(public key | checksum | version)>.onion` form of the address
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encoded = s.strip_suffix(".onion").ok_or(OnionAddressError)?;
+        if encoded.len() != 56 {
+            return Err(OnionAddressError);
+        }
+        let mut decoded = [0u8; 35];
+        let (mut buffer, mut bits, mut i) = (0u64, 0, 0);
+        for c in encoded.bytes() {
+            let value = BASE32_ALPHABET.iter().position(|&a| a == c.to_ascii_lowercase()).ok_or(OnionAddressError)?;
+            buffer = (buffer << 5) | value as u64;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                decoded[i] = (buffer >> bits) as u8;
+                i += 1;
+            }
+        }
+        let address = Self(decoded[..32].try_into().unwrap());
+        if decoded[34] != ONION_V3_VERSION || decoded[32..34] != address.checksum() {
+            return Err(OnionAddressError);
+        }
+        Ok(address)
+    }
+}
+
+impl Display for OnionAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut raw = [0u8; 35];
+        raw[..32].copy_from_slice(&self.0);
+        raw[32..34].copy_from_slice(&self.checksum());
+        raw[34] = ONION_V3_VERSION;
+        let (mut buffer, mut bits) = (0u64, 0);
+        let mut encoded = String::with_capacity(62);
+        for byte in raw {
+            buffer = (buffer << 8) | byte as u64;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                encoded.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+            }
+        }
+        write!(f, "{}.onion", encoded)
+    }
+}
+
+// Onion addresses are serialized in their textual form
+
+impl Serialize for OnionAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for OnionAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 /// A network address, equivalent of a [SocketAddr].
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
+///
+/// Tor v3 onion addresses are supported as well, in which case `ip` is the virtual IP of the onion
+/// address (see [`OnionAddress::virtual_ip`]) and `onion` holds the actual address to connect to.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug)]
 pub struct NetAddress {
     pub ip: IpAddress,
     pub port: u16,
+    #[serde(default)]
+    pub onion: Option<OnionAddress>,
 }
 
 impl NetAddress {
     pub fn new(ip: IpAddress, port: u16) -> Self {
-        Self { ip, port }
+        Self { ip, port, onion: None }
+    }
+
+    pub fn new_onion(onion: OnionAddress, port: u16) -> Self {
+        Self { ip: onion.virtual_ip(), port, onion: Some(onion) }
+    }
+
+    pub fn is_onion(&self) -> bool {
+        self.onion.is_some()
     }
 
     pub fn prefix_bucket(&self) -> PrefixBucket {
@@ -241,56 +374,97 @@ impl From<SocketAddr> for NetAddress {
     }
 }
 
+/// Note: onion addresses are converted to their virtual IP
 impl From<NetAddress> for SocketAddr {
     fn from(value: NetAddress) -> Self {
         Self::new(value.ip.0, value.port)
     }
 }
 
+/// Parses `<onion address>:<port>` if `s` is of this form
+fn parse_onion_with_port(s: &str) -> Option<(OnionAddress, Option<u16>)> {
+    match s.rsplit_once(':') {
+        Some((host, port)) => Some((host.parse().ok()?, Some(port.parse().ok()?))),
+        None => Some((s.parse().ok()?, None)),
+    }
+}
+
 impl FromStr for NetAddress {
     type Err = AddrParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((onion, Some(port))) = parse_onion_with_port(s) {
+            return Ok(Self::new_onion(onion, port));
+        }
         SocketAddr::from_str(s).map(NetAddress::from)
     }
 }
 
 impl Display for NetAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        SocketAddr::from(self.to_owned()).fmt(f)
+        match self.onion {
+            Some(onion) => write!(f, "{}:{}", onion, self.port),
+            None => SocketAddr::from(self.to_owned()).fmt(f),
+        }
+    }
+}
+
+//
+// The Borsh encoding of network addresses is part of the wRPC protocol, hence it is kept to the ip and
+// port only and onion addresses are conveyed by their virtual IP
+//
+
+impl BorshSerialize for NetAddress {
+    fn serialize<W: borsh::maybestd::io::Write>(&self, writer: &mut W) -> ::core::result::Result<(), borsh::maybestd::io::Error> {
+        borsh::BorshSerialize::serialize(&self.ip, writer)?;
+        borsh::BorshSerialize::serialize(&self.port, writer)
+    }
+}
+
+impl BorshDeserialize for NetAddress {
+    fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, borsh::maybestd::io::Error> {
+        let ip = BorshDeserialize::deserialize(buf)?;
+        let port = BorshDeserialize::deserialize(buf)?;
+        Ok(Self::new(ip, port))
     }
 }
 
 /// A network address possibly without explicit port.
 ///
 /// Use `normalize` to get a fully determined address.
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug)]
 pub struct ContextualNetAddress {
     ip: IpAddress,
     port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    onion: Option<OnionAddress>,
 }
 
 impl ContextualNetAddress {
     fn new(ip: IpAddress, port: Option<u16>) -> Self {
-        Self { ip, port }
+        Self { ip, port, onion: None }
     }
 
     pub fn normalize(&self, default_port: u16) -> NetAddress {
-        NetAddress::new(self.ip, self.port.unwrap_or(default_port))
+        let port = self.port.unwrap_or(default_port);
+        match self.onion {
+            Some(onion) => NetAddress::new_onion(onion, port),
+            None => NetAddress::new(self.ip, port),
+        }
     }
 
     pub fn unspecified() -> Self {
-        Self { ip: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)).into(), port: None }
+        Self::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)).into(), None)
     }
 
     pub fn loopback() -> Self {
-        Self { ip: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)).into(), port: None }
+        Self::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)).into(), None)
     }
 }
 
 impl From<NetAddress> for ContextualNetAddress {
     fn from(value: NetAddress) -> Self {
-        Self::new(value.ip, Some(value.port))
+        Self { ip: value.ip, port: Some(value.port), onion: value.onion }
     }
 }
 
@@ -298,6 +472,9 @@ impl FromStr for ContextualNetAddress {
     type Err = AddrParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((onion, port)) = parse_onion_with_port(s) {
+            return Ok(Self { ip: onion.virtual_ip(), port, onion: Some(onion) });
+        }
         match SocketAddr::from_str(s) {
             Ok(socket) => Ok(Self::new(socket.ip().into(), Some(socket.port()))),
             Err(_) => Ok(Self::new(IpAddress::from_str(s)?, None)),
@@ -313,6 +490,21 @@ impl TryFrom<&str> for ContextualNetAddress {
     }
 }
 
+impl BorshSerialize for ContextualNetAddress {
+    fn serialize<W: borsh::maybestd::io::Write>(&self, writer: &mut W) -> ::core::result::Result<(), borsh::maybestd::io::Error> {
+        borsh::BorshSerialize::serialize(&self.ip, writer)?;
+        borsh::BorshSerialize::serialize(&self.port, writer)
+    }
+}
+
+impl BorshDeserialize for ContextualNetAddress {
+    fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, borsh::maybestd::io::Error> {
+        let ip = BorshDeserialize::deserialize(buf)?;
+        let port = BorshDeserialize::deserialize(buf)?;
+        Ok(Self::new(ip, port))
+    }
+}
+
 impl TryFrom<String> for ContextualNetAddress {
     type Error = AddrParseError;
 
@@ -323,9 +515,11 @@ impl TryFrom<String> for ContextualNetAddress {
 
 impl Display for ContextualNetAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.port {
-            Some(port) => SocketAddr::new(self.ip.into(), port).fmt(f),
-            None => self.ip.fmt(f),
+        match (self.onion, self.port) {
+            (Some(onion), Some(port)) => write!(f, "{}:{}", onion, port),
+            (Some(onion), None) => onion.fmt(f),
+            (None, Some(port)) => SocketAddr::new(self.ip.into(), port).fmt(f),
+            (None, None) => self.ip.fmt(f),
         }
     }
 }
@@ -435,6 +629,38 @@ mod tests {
         assert!(addr_v6.is_ok());
     }
 
+    #[test]
+    fn test_onion_address() {
+        let s = "pg6mmjiyjmcrsslvykfwnntlaru7p5svn6y2ymmju6nubxndf4pscryd.onion";
+        let onion = OnionAddress::from_str(s).unwrap();
+        assert_eq!(onion.to_string(), s);
+        assert_eq!(OnionAddress::from_str(&s.to_uppercase().replace(".ONION", ".onion")).unwrap(), onion);
+        assert_eq!(onion.virtual_ip(), IpAddress::from_str("fd87:d87e:eb43:79bc:c625:184b:0519:4975").unwrap());
+        assert!(onion.virtual_ip().is_onion());
+        assert!(!onion.virtual_ip().is_publicly_routable());
+
+        // Invalid checksum, version and length
+        assert!(OnionAddress::from_str("qg6mmjiyjmcrsslvykfwnntlaru7p5svn6y2ymmju6nubxndf4pscryd.onion").is_err());
+        assert!(OnionAddress::from_str("pg6mmjiyjmcrsslvykfwnntlaru7p5svn6y2ymmju6nubxndf4pscryc.onion").is_err());
+        assert!(OnionAddress::from_str("pg6mmjiyjmcrsslvykfwnntlaru7p5svn6y2ymmju6nubxndf4pscry.onion").is_err());
+
+        let addr = NetAddress::from_str(&format!("{}:16111", s)).unwrap();
+        assert_eq!(addr, NetAddress::new_onion(onion, 16111));
+        assert_eq!(addr.to_string(), format!("{}:16111", s));
+        // The Borsh encoding keeps the legacy layout, hence only the virtual IP of the onion address is retained
+        let bin = addr.try_to_vec().unwrap();
+        assert_eq!(bin, NetAddress::new(onion.virtual_ip(), 16111).try_to_vec().unwrap());
+        assert_eq!(NetAddress::try_from_slice(&bin).unwrap(), NetAddress::new(onion.virtual_ip(), 16111));
+        let json = serde_json::to_string(&addr).unwrap();
+        assert_eq!(serde_json::from_str::<NetAddress>(&json).unwrap(), addr);
+        let legacy_json = r#"{"ip":"127.0.0.1","port":16111}"#;
+        assert_eq!(serde_json::from_str::<NetAddress>(legacy_json).unwrap(), NetAddress::from_str("127.0.0.1:16111").unwrap());
+
+        let contextual = ContextualNetAddress::from_str(s).unwrap();
+        assert_eq!(contextual.to_string(), s);
+        assert_eq!(contextual.normalize(16111), addr);
+    }
+
     #[test]
     fn test_prefix_bucket() {
         let prefix_bytes: [u8; 2] = [42u8, 43u8];
@@ -0,0 +1,266 @@
+//!
+//! Versioned and checksummed snapshot files, shared by all the snapshot kinds of the node.
+//!
+//! A snapshot file is laid out as follows:
+//! 1) magic bytes identifying the snapshot kind, followed by the bincode encoded format version
+//! 2) a sequence of values, each one bincode encoded into a length-prefixed blob. Large collections are written as a
+//!    sequence of non-empty chunks terminated by an empty chunk
+//! 3) a SHA-256 checksum of all the preceding bytes
+//!
+//! The layout of the values is up to each snapshot kind, which is expected to bump its version whenever it changes.
+//!
+
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Size of the magic bytes opening a snapshot file
+pub const MAGIC_SIZE: usize = 8;
+
+const CHECKSUM_SIZE: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum SnapshotFileError {
+    #[error("snapshot file access failed: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("snapshot (de)serialization failed: {0}")]
+    SerializationError(#[from] bincode::Error),
+
+    #[error("the file is not a snapshot of the expected kind")]
+    InvalidFormat,
+
+    #[error("snapshot version {0} is not supported (expected version {1})")]
+    UnsupportedVersion(u16, u16),
+
+    #[error("snapshot checksum mismatch, the file is corrupted")]
+    ChecksumMismatch,
+}
+
+pub type SnapshotFileResult<T> = Result<T, SnapshotFileError>;
+
+/// Wraps a writer, hashing all the bytes written through it
+struct ChecksumWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Wraps a reader, hashing all the bytes read through it
+struct ChecksumReader<R: Read> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+/// Sequential writer of a snapshot file.
+///
+/// The snapshot is written to a temporary file which is only renamed to its final path by [`SnapshotFileWriter::finish`],
+/// so an existing file at this path is only ever replaced by a complete snapshot.
+pub struct SnapshotFileWriter {
+    writer: ChecksumWriter<BufWriter<File>>,
+    path: PathBuf,
+    temp_path: PathBuf,
+}
+
+impl SnapshotFileWriter {
+    pub fn create(path: &Path, magic: &[u8; MAGIC_SIZE], version: u16) -> SnapshotFileResult<Self> {
+        let temp_path = path.with_extension("tmp");
+        let mut writer = ChecksumWriter { inner: BufWriter::new(File::create(&temp_path)?), hasher: Sha256::new() };
+        writer.write_all(magic)?;
+        bincode::serialize_into(&mut writer, &version)?;
+        Ok(Self { writer, path: path.to_owned(), temp_path })
+    }
+
+    /// Writes a single value
+    pub fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> SnapshotFileResult<()> {
+        // Values are written as whole byte blobs since some types (e.g. script public keys) can only be
+        // deserialized from borrowed bytes
+        let bytes = bincode::serialize(value)?;
+        bincode::serialize_into(&mut self.writer, &bytes)?;
+        Ok(())
+    }
+
+    /// Writes a chunk of a chunked collection. Empty chunks are skipped since an empty chunk marks the end of the collection.
+    pub fn write_chunk<T: Serialize>(&mut self, chunk: &[T]) -> SnapshotFileResult<()> {
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        self.write(chunk)
+    }
+
+    /// Ends the chunked collection written so far by [`SnapshotFileWriter::write_chunk`]
+    pub fn end_chunks(&mut self) -> SnapshotFileResult<()> {
+        self.write::<[u8]>(&[])
+    }
+
+    /// Writes the checksum and moves the snapshot to its final path
+    pub fn finish(self) -> SnapshotFileResult<()> {
+        let ChecksumWriter { inner: mut writer, hasher } = self.writer;
+        writer.write_all(&hasher.finalize())?;
+        writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        fs::rename(&self.temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Sequential reader of a snapshot file.
+///
+/// Note: the snapshot checksum is only verified by [`SnapshotFileReader::finish`], after all values were read.
+pub struct SnapshotFileReader<R: Read> {
+    reader: ChecksumReader<R>,
+}
+
+impl SnapshotFileReader<BufReader<File>> {
+    pub fn open(path: &Path, magic: &[u8; MAGIC_SIZE], version: u16) -> SnapshotFileResult<Self> {
+        Self::new(BufReader::new(File::open(path)?), magic, version)
+    }
+}
+
+impl<R: Read> SnapshotFileReader<R> {
+    pub fn new(reader: R, magic: &[u8; MAGIC_SIZE], version: u16) -> SnapshotFileResult<Self> {
+        let mut reader = ChecksumReader { inner: reader, hasher: Sha256::new() };
+
+        let mut file_magic = [0u8; MAGIC_SIZE];
+        reader.read_exact(&mut file_magic)?;
+        if file_magic != *magic {
+            return Err(SnapshotFileError::InvalidFormat);
+        }
+        let file_version: u16 = bincode::deserialize_from(&mut reader)?;
+        if file_version != version {
+            return Err(SnapshotFileError::UnsupportedVersion(file_version, version));
+        }
+        Ok(Self { reader })
+    }
+
+    /// Reads a single value
+    pub fn read<T: DeserializeOwned>(&mut self) -> SnapshotFileResult<T> {
+        let bytes: Vec<u8> = bincode::deserialize_from(&mut self.reader)?;
+        Ok(bincode::deserialize(&bytes)?)
+    }
+
+    /// Reads the next chunk of a chunked collection, returning `None` once all its chunks were read
+    pub fn next_chunk<T: DeserializeOwned>(&mut self) -> SnapshotFileResult<Option<Vec<T>>> {
+        let chunk: Vec<T> = self.read()?;
+        Ok((!chunk.is_empty()).then_some(chunk))
+    }
+
+    /// Verifies the snapshot checksum and that no data follows it
+    pub fn finish(self) -> SnapshotFileResult<()> {
+        let ChecksumReader { inner: mut reader, hasher } = self.reader;
+        let mut checksum = [0u8; CHECKSUM_SIZE];
+        reader.read_exact(&mut checksum)?;
+        if checksum[..] != hasher.finalize()[..] {
+            return Err(SnapshotFileError::ChecksumMismatch);
+        }
+        if reader.read(&mut [0u8; 1])? != 0 {
+            return Err(SnapshotFileError::InvalidFormat);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAGIC: [u8; MAGIC_SIZE] = *b"TESTSNAP";
+    const VERSION: u16 = 3;
+
+    fn write_test_snapshot(path: &Path, chunks: &[Vec<(u64, String)>]) {
+        let mut writer = SnapshotFileWriter::create(path, &MAGIC, VERSION).unwrap();
+        writer.write(&vec![1u64, 2, 3]).unwrap();
+        for chunk in chunks {
+            writer.write_chunk(chunk).unwrap();
+        }
+        writer.end_chunks().unwrap();
+        writer.write("tail").unwrap();
+        writer.finish().unwrap();
+    }
+
+    type TestSnapshot = (Vec<u64>, Vec<(u64, String)>, String);
+
+    fn read_test_snapshot<R: Read>(mut reader: SnapshotFileReader<R>) -> SnapshotFileResult<TestSnapshot> {
+        let head = reader.read()?;
+        let mut items = vec![];
+        while let Some(chunk) = reader.next_chunk()? {
+            items.extend(chunk);
+        }
+        let tail = reader.read()?;
+        reader.finish()?;
+        Ok((head, items, tail))
+    }
+
+    fn chunks(count: u64, chunk_size: u64) -> Vec<Vec<(u64, String)>> {
+        (0..count).map(|chunk| (0..chunk_size).map(|i| (chunk * chunk_size + i, i.to_string())).collect()).collect()
+    }
+
+    #[test]
+    fn test_snapshot_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.snapshot");
+        let mut chunks = chunks(3, 10);
+        // Empty chunks must not end the collection prematurely
+        chunks.insert(1, vec![]);
+
+        write_test_snapshot(&path, &chunks);
+        assert!(!path.with_extension("tmp").exists());
+
+        let reader = SnapshotFileReader::open(&path, &MAGIC, VERSION).unwrap();
+        assert_eq!(read_test_snapshot(reader).unwrap(), (vec![1, 2, 3], chunks.concat(), "tail".to_string()));
+    }
+
+    #[test]
+    fn test_snapshot_file_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.snapshot");
+        write_test_snapshot(&path, &chunks(1, 4));
+        let bytes = fs::read(&path).unwrap();
+
+        let read_all = |bytes: &[u8]| read_test_snapshot(SnapshotFileReader::new(bytes, &MAGIC, VERSION)?);
+        read_all(&bytes).unwrap();
+
+        // A flipped bit within the last chunk item, followed by the end of chunks and tail blobs, is detected by the checksum
+        let mut corrupted = bytes.clone();
+        let position = corrupted.len() - CHECKSUM_SIZE - 20 - 16 - 1;
+        corrupted[position] ^= 1;
+        assert!(matches!(read_all(&corrupted), Err(SnapshotFileError::ChecksumMismatch)));
+
+        // A wrong magic or an unknown version is rejected upfront
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'X';
+        assert!(matches!(read_all(&corrupted), Err(SnapshotFileError::InvalidFormat)));
+        let mut corrupted = bytes.clone();
+        corrupted[MAGIC_SIZE] = 2;
+        assert!(matches!(read_all(&corrupted), Err(SnapshotFileError::UnsupportedVersion(2, VERSION))));
+
+        // A truncated file or trailing data is rejected
+        assert!(read_all(&bytes[..bytes.len() - 1]).is_err());
+        let mut corrupted = bytes.clone();
+        corrupted.push(0);
+        assert!(matches!(read_all(&corrupted), Err(SnapshotFileError::InvalidFormat)));
+    }
+}
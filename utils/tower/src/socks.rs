@@ -0,0 +1,138 @@
+//!
+//! A minimal SOCKS5 client (RFC 1928), used for tunneling outbound connections through a proxy such as
+//! a local Tor daemon. Only the `CONNECT` command without authentication is supported.
+//!
+
+use std::{
+    fmt::Display,
+    io::{Error, Result},
+    net::{IpAddr, SocketAddr},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// The default SOCKS port of a local Tor daemon
+pub const DEFAULT_SOCKS5_PORT: u16 = 9050;
+
+const SOCKS_VERSION: u8 = 5;
+const METHOD_NO_AUTHENTICATION: u8 = 0;
+const COMMAND_CONNECT: u8 = 1;
+const REPLY_SUCCEEDED: u8 = 0;
+
+const ADDRESS_TYPE_IPV4: u8 = 1;
+const ADDRESS_TYPE_DOMAIN: u8 = 3;
+const ADDRESS_TYPE_IPV6: u8 = 4;
+
+fn protocol_error(message: impl Display) -> Error {
+    Error::other(format!("SOCKS5: {}", message))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Socks5Proxy {
+    address: SocketAddr,
+}
+
+impl Socks5Proxy {
+    pub fn new(address: SocketAddr) -> Self {
+        Self { address }
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Opens a TCP connection to `host:port` through the proxy. Host names, onion addresses included,
+    /// are resolved by the proxy, so that no DNS query leaks out of it
+    pub async fn connect(&self, host: &str, port: u16) -> Result<TcpStream> {
+        let mut stream = TcpStream::connect(self.address).await?;
+        stream.set_nodelay(true)?;
+
+        // Method negotiation
+        stream.write_all(&[SOCKS_VERSION, 1, METHOD_NO_AUTHENTICATION]).await?;
+        let mut reply = [0u8; 2];
+        stream.read_exact(&mut reply).await?;
+        if reply != [SOCKS_VERSION, METHOD_NO_AUTHENTICATION] {
+            return Err(protocol_error("the proxy requires an unsupported authentication method"));
+        }
+
+        // Connect request
+        let mut request = vec![SOCKS_VERSION, COMMAND_CONNECT, 0];
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                request.push(ADDRESS_TYPE_IPV4);
+                request.extend(ip.octets());
+            }
+            Ok(IpAddr::V6(ip)) => {
+                request.push(ADDRESS_TYPE_IPV6);
+                request.extend(ip.octets());
+            }
+            Err(_) => {
+                let len = u8::try_from(host.len()).map_err(|_| protocol_error(format!("host name {} is too long", host)))?;
+                request.extend([ADDRESS_TYPE_DOMAIN, len]);
+                request.extend(host.as_bytes());
+            }
+        }
+        request.extend(port.to_be_bytes());
+        stream.write_all(&request).await?;
+
+        let mut reply = [0u8; 4];
+        stream.read_exact(&mut reply).await?;
+        if reply[0] != SOCKS_VERSION {
+            return Err(protocol_error(format!("unexpected version {} in proxy reply", reply[0])));
+        }
+        if reply[1] != REPLY_SUCCEEDED {
+            return Err(protocol_error(format!("the proxy failed connecting to {}:{} with reply code {}", host, port, reply[1])));
+        }
+
+        // Skip the address the proxy bound for the connection, followed by its port
+        let bound_len = match reply[3] {
+            ADDRESS_TYPE_IPV4 => 4,
+            ADDRESS_TYPE_IPV6 => 16,
+            ADDRESS_TYPE_DOMAIN => stream.read_u8().await? as usize,
+            address_type => return Err(protocol_error(format!("unexpected address type {} in proxy reply", address_type))),
+        };
+        let mut bound = vec![0u8; bound_len + 2];
+        stream.read_exact(&mut bound).await?;
+
+        Ok(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_socks5_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Socks5Proxy::new(listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [SOCKS_VERSION, 1, METHOD_NO_AUTHENTICATION]);
+            stream.write_all(&[SOCKS_VERSION, METHOD_NO_AUTHENTICATION]).await.unwrap();
+
+            let mut header = [0u8; 5];
+            stream.read_exact(&mut header).await.unwrap();
+            assert_eq!(header[..4], [SOCKS_VERSION, COMMAND_CONNECT, 0, ADDRESS_TYPE_DOMAIN]);
+            let mut host = vec![0u8; header[4] as usize + 2];
+            stream.read_exact(&mut host).await.unwrap();
+            assert_eq!(&host[..host.len() - 2], b"example.onion");
+            assert_eq!(host[host.len() - 2..], 16111u16.to_be_bytes());
+
+            stream.write_all(&[SOCKS_VERSION, REPLY_SUCCEEDED, 0, ADDRESS_TYPE_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+            stream.write_all(b"ping").await.unwrap();
+        });
+
+        let mut stream = proxy.connect("example.onion", 16111).await.unwrap();
+        let mut payload = [0u8; 4];
+        stream.read_exact(&mut payload).await.unwrap();
+        assert_eq!(&payload, b"ping");
+        server.await.unwrap();
+    }
+}
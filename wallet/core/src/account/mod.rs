@@ -16,7 +16,7 @@ use crate::storage::account::AccountSettings;
 use crate::storage::AccountMetadata;
 use crate::storage::{PrvKeyData, PrvKeyDataId};
 use crate::tx::PaymentOutput;
-use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
+use crate::tx::{FeePriority, Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
 use crate::utxo::balance::{AtomicBalance, BalanceStrings};
 use crate::utxo::UtxoContextBinding;
 use kaspa_bip32::{ChildNumber, ExtendedPrivateKey, PrivateKey};
@@ -210,6 +210,13 @@ pub trait Account: AnySync + Send + Sync + 'static {
                     None => ScanExtent::EmptyWindow,
                 };
 
+                // rebuild the transaction history first, see [`HistoryScan`]
+                let history_scans = [
+                    HistoryScan::new_with_address_manager(derivation.receive_address_manager(), window_size, Some(extent)),
+                    HistoryScan::new_with_address_manager(derivation.change_address_manager(), window_size, Some(extent)),
+                ];
+                HistoryScan::scan(&history_scans, self.utxo_context()).await?;
+
                 let scans = [
                     Scan::new_with_address_manager(
                         derivation.receive_address_manager(),
@@ -236,6 +243,8 @@ pub trait Account: AnySync + Send + Sync + 'static {
                 address_set.insert(self.receive_address()?);
                 address_set.insert(self.change_address()?);
 
+                HistoryScan::scan(&[HistoryScan::new_with_address_set(address_set.clone())], self.utxo_context()).await?;
+
                 let scan = Scan::new_with_address_set(address_set, &balance, current_daa_score);
                 scan.scan(self.utxo_context()).await?;
             }
@@ -316,10 +325,13 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
     /// Send funds to a [`PaymentDestination`] comprised of one or multiple [`PaymentOutputs`](crate::tx::PaymentOutputs)
     /// or [`PaymentDestination::Change`] variant that will forward funds to the change address.
+    /// If supplied, `fee_priority` sets the feerate of the final transaction as currently estimated by the node.
+    #[allow(clippy::too_many_arguments)]
     async fn send(
         self: Arc<Self>,
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
+        fee_priority: Option<FeePriority>,
         payload: Option<Vec<u8>>,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
@@ -330,6 +342,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
 
         let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        let settings = apply_fee_priority(self.wallet(), settings, fee_priority).await?;
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -401,10 +414,13 @@ pub trait Account: AnySync + Send + Sync + 'static {
         self: Arc<Self>,
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
+        fee_priority: Option<FeePriority>,
         payload: Option<Vec<u8>>,
         abortable: &Abortable,
     ) -> Result<GeneratorSummary> {
+        let wallet = self.wallet().clone();
         let settings = GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        let settings = apply_fee_priority(&wallet, settings, fee_priority).await?;
 
         let generator = Generator::try_new(settings, None, Some(abortable))?;
 
@@ -427,6 +443,21 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
 downcast_sync!(dyn Account);
 
+/// Applies the feerate of `fee_priority`, if any, as currently estimated by the node, to the generator `settings`
+async fn apply_fee_priority(
+    wallet: &Arc<Wallet>,
+    settings: GeneratorSettings,
+    fee_priority: Option<FeePriority>,
+) -> Result<GeneratorSettings> {
+    match fee_priority {
+        Some(fee_priority) => {
+            let fee_estimate = wallet.rpc_api().get_fee_estimate().await?;
+            Ok(settings.fee_priority(fee_priority, &fee_estimate))
+        }
+        None => Ok(settings),
+    }
+}
+
 /// Account trait used by legacy account types (BIP32 account types with the `'972` derivation path).
 #[async_trait]
 pub trait AsLegacyAccount: Account {
@@ -6,7 +6,7 @@
 //!
 
 use crate::imports::*;
-use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
+use crate::tx::{FeePriority, Fees, GeneratorSummary, PaymentDestination};
 use kaspa_addresses::Address;
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -450,6 +450,9 @@ pub struct AccountsSendRequest {
     pub payment_secret: Option<Secret>,
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
+    /// Fee priority level setting the feerate of the final transaction, as estimated by the node
+    #[serde(default)]
+    pub fee_priority: Option<FeePriority>,
     pub payload: Option<Vec<u8>>,
 }
 
@@ -487,6 +490,9 @@ pub struct AccountsEstimateRequest {
     pub account_id: AccountId,
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
+    /// Fee priority level setting the feerate of the final transaction, as estimated by the node
+    #[serde(default)]
+    pub fee_priority: Option<FeePriority>,
     pub payload: Option<Vec<u8>>,
 }
 
@@ -19,7 +19,7 @@ pub use crate::serializer::*;
 pub use crate::storage::*;
 pub use crate::tx::MassCombinationStrategy;
 pub use crate::utxo::balance::Balance;
-pub use crate::utxo::scan::{Scan, ScanExtent};
+pub use crate::utxo::scan::{HistoryScan, Scan, ScanExtent};
 pub use crate::utxo::{Maturity, NetworkParams, OutgoingTransaction, UtxoContext, UtxoEntryReference, UtxoProcessor};
 pub use crate::wallet::*;
 pub use crate::{storage, utils};
@@ -9,6 +9,7 @@ use crate::tx::PendingTransactionInner;
 use workflow_core::time::{unixtime_as_millis_u64, unixtime_to_locale_string};
 use workflow_wasm::utils::try_get_js_value_prop;
 
+use kaspa_consensus_core::tx::Transaction;
 pub use kaspa_consensus_core::tx::TransactionId;
 use zeroize::Zeroize;
 
@@ -533,6 +534,79 @@ impl TransactionRecord {
         }
     }
 
+    /// Creates a record of a transaction found in the node address history which paid
+    /// to the account without spending from it.
+    ///
+    /// The record has no unixtime, which is estimated from its DAA score once discovered.
+    pub fn new_external_from_history(
+        utxo_context: &UtxoContext,
+        id: TransactionId,
+        block_daa_score: u64,
+        utxo_entries: Vec<UtxoRecord>,
+    ) -> Self {
+        let binding = Binding::from(utxo_context.binding());
+        let aggregate_input_value = utxo_entries.iter().map(|utxo| utxo.amount).sum::<u64>();
+
+        let transaction_data = TransactionData::External { utxo_entries, aggregate_input_value };
+
+        TransactionRecord {
+            id,
+            unixtime_msec: None,
+            value: aggregate_input_value,
+            binding,
+            transaction_data,
+            block_daa_score,
+            network_id: utxo_context.processor().network_id().expect("network expected for transaction record generation"),
+            metadata: None,
+            note: None,
+        }
+    }
+
+    /// Creates a record of a transaction found in the node address history which spent
+    /// `spent_value` from the account, returning the outputs in `utxo_entries` to it as change.
+    ///
+    /// The record has no unixtime, which is estimated from its DAA score once discovered.
+    pub fn new_outgoing_from_history(
+        utxo_context: &UtxoContext,
+        transaction: Transaction,
+        block_daa_score: u64,
+        spent_value: u64,
+        utxo_entries: &[UtxoRecord],
+    ) -> Self {
+        let binding = Binding::from(utxo_context.binding());
+        let aggregate_output_value = transaction.outputs.iter().map(|output| output.value).sum::<u64>();
+        let change_value = utxo_entries.iter().map(|utxo| utxo.amount).sum::<u64>();
+        let payment_value = aggregate_output_value.saturating_sub(change_value);
+
+        let id = transaction.id();
+
+        // the inputs spent from other wallets are unknown, so the
+        // fees assume all inputs of the transaction were spent
+        // from the account
+        let transaction_data = TransactionData::Outgoing {
+            fees: spent_value.saturating_sub(aggregate_output_value),
+            aggregate_input_value: spent_value,
+            aggregate_output_value,
+            transaction,
+            payment_value: Some(payment_value),
+            change_value,
+            accepted_daa_score: Some(block_daa_score),
+            utxo_entries: vec![],
+        };
+
+        TransactionRecord {
+            id,
+            unixtime_msec: None,
+            value: payment_value,
+            binding,
+            transaction_data,
+            block_daa_score,
+            network_id: utxo_context.processor().network_id().expect("network expected for transaction record generation"),
+            metadata: None,
+            note: None,
+        }
+    }
+
     pub fn new_outgoing(
         utxo_context: &UtxoContext,
         outgoing_tx: &OutgoingTransaction,
@@ -4,6 +4,7 @@
 
 use crate::result::Result;
 use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_rpc_core::RpcFeeEstimate;
 use serde::{Deserialize, Serialize};
 
 /// Transaction fees.  Fees are comprised of 2 values:
@@ -94,3 +95,43 @@ impl TryFrom<String> for Fees {
         Self::try_from(fee.as_str())
     }
 }
+
+/// Fee priority levels. A level is resolved into a feerate using the
+/// fee estimate provided by the node (see `RpcApi::get_fee_estimate()`),
+/// relieving the caller from supplying an explicit priority fee value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePriority {
+    /// the lowest feerate accepted by the node
+    Low,
+    /// a feerate expected to get the transaction included within about a minute
+    #[default]
+    Normal,
+    /// a feerate expected to get the transaction included in the next block
+    Priority,
+}
+
+impl FeePriority {
+    /// Returns the feerate (in sompi per gram of transaction mass) of this level
+    pub fn feerate(&self, estimate: &RpcFeeEstimate) -> f64 {
+        match self {
+            FeePriority::Low => estimate.low_bucket.feerate,
+            FeePriority::Normal => estimate.normal_bucket.feerate,
+            FeePriority::Priority => estimate.priority_bucket.feerate,
+        }
+    }
+}
+
+impl TryFrom<&str> for FeePriority {
+    type Error = crate::error::Error;
+    fn try_from(priority: &str) -> Result<Self> {
+        match priority.to_lowercase().as_str() {
+            "low" => Ok(FeePriority::Low),
+            "normal" => Ok(FeePriority::Normal),
+            "priority" => Ok(FeePriority::Priority),
+            _ => {
+                Err(crate::error::Error::custom(format!("invalid fee priority '{priority}', expected 'low', 'normal' or 'priority'")))
+            }
+        }
+    }
+}
@@ -284,6 +284,8 @@ struct Inner {
     final_transaction: Option<FinalTransaction>,
     // applies only to the final transaction
     final_transaction_priority_fee: Fees,
+    // feerate applied to the final transaction in place of the minimum relay feerate
+    final_transaction_feerate: Option<f64>,
     // issued only in the final transaction
     final_transaction_outputs: Vec<TransactionOutput>,
     // pre-calculated partial harmonic for user outputs (does not include change)
@@ -313,6 +315,7 @@ impl std::fmt::Debug for Inner {
             .field("signature_mass_per_input", &self.signature_mass_per_input)
             // .field("final_transaction", &self.final_transaction)
             .field("final_transaction_priority_fee", &self.final_transaction_priority_fee)
+            .field("final_transaction_feerate", &self.final_transaction_feerate)
             .field("final_transaction_outputs", &self.final_transaction_outputs)
             .field("final_transaction_outputs_harmonic", &self.final_transaction_outputs_harmonic)
             .field("final_transaction_outputs_compute_mass", &self.final_transaction_outputs_compute_mass)
@@ -343,6 +346,7 @@ impl Generator {
             minimum_signatures,
             change_address,
             final_transaction_priority_fee,
+            final_transaction_feerate,
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context,
@@ -352,6 +356,16 @@ impl Generator {
         let network_params = NetworkParams::from(network_id);
         let mass_calculator = MassCalculator::new(&network_id.into(), &network_params);
 
+        // a feerate determines the final transaction fees, making an explicit priority fee optional
+        let final_transaction_priority_fee = match final_transaction_priority_fee {
+            Fees::None
+                if final_transaction_feerate.is_some() && !matches!(final_transaction_destination, PaymentDestination::Change) =>
+            {
+                Fees::SenderPays(0)
+            }
+            fees => fees,
+        };
+
         let (final_transaction_outputs, final_transaction_amount) = match final_transaction_destination {
             PaymentDestination::Change => {
                 if !final_transaction_priority_fee.is_none() {
@@ -441,6 +455,7 @@ impl Generator {
             signature_mass_per_input,
             final_transaction,
             final_transaction_priority_fee,
+            final_transaction_feerate,
             final_transaction_outputs,
             final_transaction_outputs_harmonic,
             final_transaction_outputs_compute_mass,
@@ -548,6 +563,16 @@ impl Generator {
         self.inner.mass_calculator.calc_minimum_transaction_fee_from_mass(self.calc_relay_transaction_mass(data))
     }
 
+    /// Calculate final transaction fees for the given `transaction_mass`, applying
+    /// the final transaction feerate if one was supplied
+    fn calc_final_transaction_fees(&self, transaction_mass: u64) -> u64 {
+        let minimum_fees = self.inner.mass_calculator.calc_minimum_transaction_fee_from_mass(transaction_mass);
+        match self.inner.final_transaction_feerate {
+            Some(feerate) => minimum_fees.max((transaction_mass as f64 * feerate).ceil() as u64),
+            None => minimum_fees,
+        }
+    }
+
     /// Main UTXO entry processing loop. This function sources UTXOs from [`Generator::get_utxo_entry()`] and
     /// accumulates consumed UTXO entry data within the [`Context`], [`Stage`] and [`Data`] structures.
     ///
@@ -716,6 +741,11 @@ impl Generator {
         let MassDisposition { transaction_mass, storage_mass, transaction_fees, absorb_change_to_fees } =
             self.calculate_mass(stage, data, final_transaction.value_with_priority_fee)?;
 
+        // a transaction exceeding the standard mass or following other transactions of the stage becomes an edge
+        // transaction paying the minimum relay fees, only the actual final transaction pays the final feerate
+        let is_edge = transaction_mass > MAXIMUM_STANDARD_TRANSACTION_MASS || stage.number_of_transactions > 0;
+        let transaction_fees = if is_edge { transaction_fees } else { self.calc_final_transaction_fees(transaction_mass) };
+
         let total_stage_value_needed = if self.inner.final_transaction_priority_fee.sender_pays() {
             final_transaction.value_with_priority_fee + stage.aggregate_fees + transaction_fees
         } else {
@@ -731,7 +761,7 @@ impl Generator {
         if reject {
             // need more value, reject finalization (try adding more inputs)
             Ok(None)
-        } else if transaction_mass > MAXIMUM_STANDARD_TRANSACTION_MASS || stage.number_of_transactions > 0 {
+        } else if is_edge {
             self.generate_edge_transaction(context, stage, data)
         } else {
             // ---
@@ -6,9 +6,10 @@
 use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
-use crate::tx::{Fees, PaymentDestination};
+use crate::tx::{FeePriority, Fees, PaymentDestination};
 use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoIterator};
 use kaspa_addresses::Address;
+use kaspa_rpc_core::RpcFeeEstimate;
 use workflow_core::channel::Multiplexer;
 
 pub struct GeneratorSettings {
@@ -28,6 +29,8 @@ pub struct GeneratorSettings {
     pub change_address: Address,
     // applies only to the final transaction
     pub final_transaction_priority_fee: Fees,
+    // feerate (sompi per gram) applied to the final transaction in place of the minimum relay feerate
+    pub final_transaction_feerate: Option<f64>,
     // final transaction outputs
     pub final_transaction_destination: PaymentDestination,
     // payload
@@ -79,6 +82,7 @@ impl GeneratorSettings {
             source_utxo_context: Some(account.utxo_context().clone()),
 
             final_transaction_priority_fee: final_priority_fee,
+            final_transaction_feerate: None,
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
@@ -110,6 +114,7 @@ impl GeneratorSettings {
             source_utxo_context: Some(utxo_context),
 
             final_transaction_priority_fee: final_priority_fee,
+            final_transaction_feerate: None,
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
@@ -139,6 +144,7 @@ impl GeneratorSettings {
             source_utxo_context: None,
 
             final_transaction_priority_fee: final_priority_fee,
+            final_transaction_feerate: None,
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
@@ -147,6 +153,13 @@ impl GeneratorSettings {
         Ok(settings)
     }
 
+    /// Applies the feerate of the given [`FeePriority`] level, as found in the
+    /// fee estimate obtained from the node, to the final transaction.
+    pub fn fee_priority(mut self, fee_priority: FeePriority, fee_estimate: &RpcFeeEstimate) -> Self {
+        self.final_transaction_feerate = Some(fee_priority.feerate(fee_estimate));
+        self
+    }
+
     pub fn utxo_context_transfer(mut self, destination_utxo_context: &UtxoContext) -> Self {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
@@ -2,12 +2,13 @@
 
 use crate::error::Error;
 use crate::result::Result;
-use crate::tx::{Fees, MassCalculator, PaymentDestination};
+use crate::tx::{FeePriority, Fees, MassCalculator, PaymentDestination};
 use crate::utxo::UtxoEntryReference;
 use crate::{tx::PaymentOutputs, utils::kaspa_to_sompi};
-use kaspa_addresses::Address;
+use kaspa_addresses::{Address, Prefix, Version};
 use kaspa_consensus_core::network::{NetworkId, NetworkType};
 use kaspa_consensus_core::tx::Transaction;
+use kaspa_rpc_core::{RpcFeeEstimate, RpcFeerateBucket};
 use rand::prelude::*;
 use std::cell::RefCell;
 use std::fmt::Debug;
@@ -408,6 +409,7 @@ where
         source_utxo_context,
         destination_utxo_context,
         final_transaction_priority_fee: final_priority_fee,
+        final_transaction_feerate: None,
         final_transaction_destination,
         final_transaction_payload,
     };
@@ -536,6 +538,38 @@ fn test_generator_dust_1_1() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_generator_fee_priority() -> Result<()> {
+    let bucket = |feerate| RpcFeerateBucket { feerate, estimated_seconds: 1.0 };
+    let estimate = RpcFeeEstimate { priority_bucket: bucket(10.0), normal_bucket: bucket(2.0), low_bucket: bucket(1.0) };
+
+    for (fee_priority, feerate) in [(FeePriority::Low, 1.0), (FeePriority::Normal, 2.0), (FeePriority::Priority, 10.0)] {
+        let network_id = test_network_id();
+        let utxo_entries = [10.0, 10.0].into_iter().map(kaspa_to_sompi).map(UtxoEntryReference::simulated).collect::<Vec<_>>();
+        let change_address = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let outputs = PaymentOutputs::from((Address::new(Prefix::Testnet, Version::PubKey, &[2u8; 32]), kaspa_to_sompi(5.0)));
+        // no explicit priority fee is required when a fee priority level is selected
+        let settings = GeneratorSettings::try_new_with_iterator(
+            network_id,
+            Box::new(utxo_entries.into_iter()),
+            change_address,
+            1,
+            1,
+            outputs.into(),
+            Fees::None,
+            None,
+            None,
+        )?
+        .fee_priority(fee_priority, &estimate);
+
+        let pt = Generator::try_new(settings, None, None)?.generate_transaction()?.expect("final transaction").validate();
+        assert!(pt.is_final());
+        assert_eq!(pt.fees(), (pt.inner.mass as f64 * feerate).ceil() as u64, "fees of {fee_priority:?} level");
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_generator_inputs_2_outputs_2_fees_exclude() -> Result<()> {
     generator(
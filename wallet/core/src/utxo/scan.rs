@@ -6,12 +6,19 @@
 
 use crate::derivation::AddressManager;
 use crate::imports::*;
+use crate::storage::transaction::UtxoRecord;
 use crate::utxo::balance::AtomicBalance;
 use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoEntryReferenceExtension};
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_rpc_core::RpcTransactionsByAddressesEntry;
+use kaspa_txscript::pay_to_address_script;
 use std::cmp::max;
 
 pub const DEFAULT_WINDOW_SIZE: usize = 8;
 
+/// Number of transactions requested per address by a single `GetTransactionsByAddresses` call
+pub const HISTORY_PAGE_SIZE: u32 = 100;
+
 #[derive(Default, Clone, Copy)]
 pub enum ScanExtent {
     /// Scan until an empty range is found
@@ -170,3 +177,179 @@ impl Scan {
         Ok(())
     }
 }
+
+/// Transaction of the address history, aggregated over all the scanned addresses.
+struct HistoryTransaction {
+    accepting_daa_score: u64,
+    utxo_entries: Vec<UtxoRecord>,
+    spent_value: u64,
+    /// The full transaction, returned by the node for transactions spending from the account
+    transaction: Option<Transaction>,
+}
+
+/// Address history scanner, rebuilding the [`TransactionRecord`] history of
+/// an account from the address index of the node (`--addressindex`).
+///
+/// The history scan should run before the UTXO [`Scan`] so that the records
+/// of outgoing transactions take precedence over the external records the
+/// UTXO scan derives from their change outputs.
+pub struct HistoryScan {
+    provider: Provider,
+    window_size: Option<usize>,
+    extent: Option<ScanExtent>,
+}
+
+impl HistoryScan {
+    pub fn new_with_address_manager(
+        address_manager: Arc<AddressManager>,
+        window_size: Option<usize>,
+        extent: Option<ScanExtent>,
+    ) -> HistoryScan {
+        HistoryScan { provider: Provider::AddressManager(address_manager), window_size, extent }
+    }
+
+    pub fn new_with_address_set(addresses: HashSet<Address>) -> HistoryScan {
+        HistoryScan { provider: Provider::AddressSet(addresses), window_size: None, extent: None }
+    }
+
+    /// Scan the address history of all the given scanners as a whole, so that a transaction
+    /// touching addresses of multiple scanners results in a single record.
+    ///
+    /// Address managers are advanced to the last address found in the history. The scan is
+    /// skipped when the node does not maintain an address index.
+    pub async fn scan(scans: &[HistoryScan], utxo_context: &UtxoContext) -> Result<()> {
+        let mut history = HashMap::<TransactionId, HistoryTransaction>::new();
+        for scan in scans.iter() {
+            let result = match &scan.provider {
+                Provider::AddressManager(address_manager) => {
+                    scan.scan_with_address_manager(address_manager, utxo_context, &mut history).await
+                }
+                Provider::AddressSet(addresses) => {
+                    Self::fetch_history(addresses.iter().cloned().collect(), utxo_context, &mut history).await.map(|_| ())
+                }
+            };
+            if let Err(err) = result {
+                log_warn!("Unable to scan the address history: {err}");
+                return Ok(());
+            }
+        }
+
+        let mut history = history.into_iter().collect::<Vec<_>>();
+        history.sort_by_key(|(_, transaction)| transaction.accepting_daa_score);
+
+        for (id, transaction) in history.into_iter() {
+            let HistoryTransaction { accepting_daa_score, utxo_entries, spent_value, transaction } = transaction;
+            let record = if spent_value == 0 {
+                TransactionRecord::new_external_from_history(utxo_context, id, accepting_daa_score, utxo_entries)
+            } else {
+                // spending transactions are recorded along with their full transaction data,
+                // which is missing if the node pruned the body of the including block
+                let Some(transaction) = transaction else {
+                    log_warn!("Transaction data of {id} is unavailable, skipping its history record");
+                    continue;
+                };
+                TransactionRecord::new_outgoing_from_history(
+                    utxo_context,
+                    transaction,
+                    accepting_daa_score,
+                    spent_value,
+                    &utxo_entries,
+                )
+            };
+            utxo_context.processor().handle_discovery(record).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn scan_with_address_manager(
+        &self,
+        address_manager: &Arc<AddressManager>,
+        utxo_context: &UtxoContext,
+        history: &mut HashMap<TransactionId, HistoryTransaction>,
+    ) -> Result<()> {
+        let window_size = self.window_size.unwrap_or(DEFAULT_WINDOW_SIZE) as u32;
+        let extent = self.extent.expect("address manager requires an extent");
+
+        let mut cursor: u32 = 0;
+        let mut last_address_index = address_manager.index();
+
+        loop {
+            let first = cursor;
+            let last = if cursor == 0 { max(last_address_index + 1, window_size) } else { cursor + window_size };
+            cursor = last;
+
+            let addresses = address_manager.get_range(first..last)?;
+            let used = Self::fetch_history(addresses, utxo_context, history).await?;
+            for address in used.iter() {
+                if let Some(address_index) = address_manager.inner().address_to_index_map.get(address) {
+                    last_address_index = max(last_address_index, *address_index);
+                }
+            }
+
+            if used.is_empty() {
+                match &extent {
+                    ScanExtent::EmptyWindow if cursor > last_address_index + window_size => break,
+                    ScanExtent::Depth(depth) if &cursor > depth => break,
+                    _ => {}
+                }
+            }
+            yield_executor().await;
+        }
+
+        address_manager.set_index(last_address_index)?;
+
+        Ok(())
+    }
+
+    /// Fetches the complete history of `addresses` into `history`, returning the addresses having any.
+    async fn fetch_history(
+        addresses: Vec<Address>,
+        utxo_context: &UtxoContext,
+        history: &mut HashMap<TransactionId, HistoryTransaction>,
+    ) -> Result<Vec<Address>> {
+        let mut used = vec![];
+        let mut requests = vec![(addresses, 0)];
+        while let Some((addresses, start_daa_score)) = requests.pop() {
+            let entries = utxo_context
+                .processor()
+                .rpc_api()
+                .get_transactions_by_addresses(addresses, start_daa_score, HISTORY_PAGE_SIZE)
+                .await?;
+            for RpcTransactionsByAddressesEntry { address, transactions } in entries.into_iter() {
+                let Some(last) = transactions.last() else {
+                    continue;
+                };
+                // a full page may be followed by more transactions, which all have a higher DAA score
+                if transactions.len() >= HISTORY_PAGE_SIZE as usize {
+                    requests.push((vec![address.clone()], last.accepting_daa_score + 1));
+                }
+                if start_daa_score == 0 {
+                    used.push(address.clone());
+                }
+
+                let script_public_key = pay_to_address_script(&address);
+                for transaction in transactions.into_iter() {
+                    let entry = history.entry(transaction.transaction_id).or_insert_with(|| HistoryTransaction {
+                        accepting_daa_score: transaction.accepting_daa_score,
+                        utxo_entries: vec![],
+                        spent_value: 0,
+                        transaction: None,
+                    });
+                    entry.spent_value += transaction.spent_amount;
+                    if let (None, Some(rpc_transaction)) = (&entry.transaction, &transaction.transaction) {
+                        entry.transaction = Some(Transaction::try_from(rpc_transaction)?);
+                    }
+                    entry.utxo_entries.extend(transaction.received_outputs.iter().map(|output| UtxoRecord {
+                        address: Some(address.clone()),
+                        index: output.index,
+                        amount: output.amount,
+                        script_public_key: script_public_key.clone(),
+                        is_coinbase: transaction.is_coinbase,
+                    }));
+                }
+            }
+        }
+        Ok(used)
+    }
+}
@@ -4,7 +4,7 @@ use super::extensions::*;
 use crate::account::descriptor::IAccountDescriptor;
 use crate::api::message::*;
 use crate::imports::*;
-use crate::tx::{Fees, PaymentDestination, PaymentOutputs};
+use crate::tx::{FeePriority, Fees, PaymentDestination, PaymentOutputs};
 use crate::wasm::tx::fees::IFees;
 use crate::wasm::tx::GeneratorSummary;
 use js_sys::Array;
@@ -1365,6 +1365,10 @@ declare! {
          * Priority fee.
          */
         priorityFeeSompi? : IFees | bigint;
+        /**
+         * Fee priority level ("low", "normal" or "priority") setting the feerate of the final transaction.
+         */
+        feePriority? : string;
         /**
          * 
          */
@@ -1382,13 +1386,14 @@ try_from! ( args: IAccountsSendRequest, AccountsSendRequest, {
     let wallet_secret = args.get_secret("walletSecret")?;
     let payment_secret = args.try_get_secret("paymentSecret")?;
     let priority_fee_sompi = args.get::<IFees>("priorityFeeSompi")?.try_into()?;
+    let fee_priority = args.try_get_string("feePriority")?.map(|v| FeePriority::try_from(v.as_str())).transpose()?;
     let payload = args.try_get_value("payload")?.map(|v| v.try_as_vec_u8()).transpose()?;
 
     let outputs = args.get_value("destination")?;
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
 
-    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload })
+    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, fee_priority, destination, payload })
 });
 
 declare! {
@@ -1495,6 +1500,7 @@ declare! {
         accountId : HexString;
         destination : IPaymentOutput[];
         priorityFeeSompi : IFees | bigint;
+        feePriority? : string;
         payload? : Uint8Array | string;
     }
     "#,
@@ -1503,13 +1509,14 @@ declare! {
 try_from! ( args: IAccountsEstimateRequest, AccountsEstimateRequest, {
     let account_id = args.get_account_id("accountId")?;
     let priority_fee_sompi = args.get::<IFees>("priorityFeeSompi")?.try_into()?;
+    let fee_priority = args.try_get_string("feePriority")?.map(|v| FeePriority::try_from(v.as_str())).transpose()?;
     let payload = args.try_get_value("payload")?.map(|v| v.try_as_vec_u8()).transpose()?;
 
     let outputs = args.get_value("destination")?;
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
 
-    Ok(AccountsEstimateRequest { account_id, priority_fee_sompi, destination, payload })
+    Ok(AccountsEstimateRequest { account_id, priority_fee_sompi, fee_priority, destination, payload })
 });
 
 declare! {